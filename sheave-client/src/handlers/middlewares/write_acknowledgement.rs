@@ -1,19 +1,13 @@
 use std::{
     io::Result as IOResult,
-    future::Future,
-    pin::{
-        Pin,
-        pin
-    },
-    task::{
-        Context as FutureContext,
-        Poll
-    },
+    pin::Pin,
     time::Duration
 };
 use log::info;
-use futures::ready;
-use tokio::io::AsyncWrite;
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite
+};
 use sheave_core::{
     ByteBuffer,
     Encoder,
@@ -32,31 +26,33 @@ use sheave_core::{
 
 #[doc(hidden)]
 #[derive(Debug)]
-pub struct AcknowledgementWriter<'a, W: AsyncWrite + MeasureAcknowledgement + Unpin>(Pin<&'a mut W>);
+pub struct AcknowledgementWriter;
 
 #[doc(hidden)]
-impl<W: AsyncWrite + MeasureAcknowledgement + Unpin> Middleware for AcknowledgementWriter<'_, W> {
-    fn poll_handle_wrapped<H: AsyncHandler + Unpin>(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext, handler: Pin<&mut H>) -> Poll<IOResult<()>> {
-        self.0.begin_measuring();
-
-        ready!(handler.poll_handle(cx, rtmp_context))?;
-
-        let acknowledgement = self.0.as_acknowledgement();
-
-        self.0.finish_measuring();
-
-        if acknowledgement > (rtmp_context.get_peer_bandwidth() / 8) {
+impl Middleware for AcknowledgementWriter {
+    async fn handle_wrapped<R, W, H>(&mut self, mut reader: Pin<&mut R>, mut writer: Pin<&mut W>, rtmp_context: &mut RtmpContext, handler: &mut H) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        H: AsyncHandler + Send
+    {
+        handler.handle(reader.as_mut(), writer.as_mut(), rtmp_context).await?;
+
+        /* NOTE: Received bytes are counted by the read half itself, including the handshake. */
+        let window_acknowledgement_size = rtmp_context.get_window_acknowledgement_size().get_inner();
+        if reader.pass_window(window_acknowledgement_size) {
+            let acknowledgement = reader.as_acknowledgement();
             let mut buffer = ByteBuffer::default();
             buffer.encode(&acknowledgement);
-            ready!(pin!(write_chunk(self.0.as_mut(), rtmp_context, Acknowledgement::CHANNEL.into(), Duration::default(), Acknowledgement::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer))).poll(cx))?;
+            write_chunk(writer, rtmp_context, Acknowledgement::CHANNEL.into(), Duration::default(), Acknowledgement::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
             info!("Acknowledgement got sent.");
         }
 
-        Poll::Ready(Ok(()))
+        Ok(())
     }
 }
 
 #[doc(hidden)]
-pub fn write_acknowledgement<'a, W: AsyncWrite + MeasureAcknowledgement + Unpin>(writer: Pin<&'a mut W>) -> AcknowledgementWriter<'a, W> {
-    AcknowledgementWriter(writer)
+pub fn write_acknowledgement() -> AcknowledgementWriter {
+    AcknowledgementWriter
 }
@@ -1,5 +1,4 @@
 use std::{
-    future::Future,
     io::{
         Error as IOError,
         ErrorKind,
@@ -9,24 +8,19 @@ use std::{
         Pin,
         pin
     },
-    sync::Arc,
-    task::{
-        Context as FutureContext,
-        Poll
-    },
-    time::{
-        Duration,
-        Instant
-    }
+    time::Duration
 };
+use bytes::Bytes;
 use log::{
+    debug,
     error,
-    info
+    info,
+    warn
 };
-use futures::ready;
 use tokio::io::{
     AsyncRead,
-    AsyncWrite
+    AsyncWrite,
+    AsyncWriteExt
 };
 use sheave_core::{
     ByteBuffer,
@@ -37,21 +31,28 @@ use sheave_core::{
     handlers::{
         AsyncHandler,
         AsyncHandlerExt,
+        Capabilities,
         ClientType,
         ErrorHandler,
+        FlowControl,
         HandlerConstructor,
         LastChunk,
+        MeasureAcknowledgement,
+        Pacing,
         PublisherStatus,
         RtmpContext,
-        StreamWrapper,
         SubscriberStatus,
         inconsistent_sha,
         stream_got_exhausted
     },
     handshake::{
+        DiffieHellman,
+        DigestScheme,
         EncryptionAlgorithm,
         Handshake,
-        Version
+        Version,
+        elapsed_since,
+        epoch_timestamp
     },
     messages::{
         /* Used in common */
@@ -67,13 +68,17 @@ use sheave_core::{
         Audio,
         Video,
         SetDataFrame,
+        Aggregate,
         Acknowledgement,
         amf::v0::{
             Number,
             AmfString,
+            AmfValue,
             Object
         },
         headers::MessageType,
+        unknown_message,
+        unknown_event,
 
         /* Publisher-side */
         ReleaseStream,
@@ -90,39 +95,67 @@ use sheave_core::{
         FcSubscribe,
         SetBufferLength,
         Play,
-        amf::v0::Boolean
+        amf::v0::Boolean,
+        StreamIsRecorded,
+        StreamEof,
+        StreamDry,
+        BufferEmpty,
+        BufferReady,
+
+        /* Either side */
+        PeerBandwidth,
+        PingRequest,
+        PingResponse,
+        SwfVerifyRequest,
+        SwfVerifyResponse
     },
     net::RtmpReadExt,
     object,
     readers::*,
+    session::unknown_chunk_stream,
     writers::*
 };
 use super::{
     error_response,
-    middlewares::write_acknowledgement
+    middlewares::{
+        write_acknowledgement,
+        write_ping_request
+    }
 };
 
 #[doc(hidden)]
 #[derive(Debug)]
-struct HandshakeHandler<'a, RW: AsyncRead + AsyncWrite + Unpin>(Pin<&'a mut RW>);
+struct HandshakeHandler<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(Pin<&'a mut R>, Pin<&'a mut W>);
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> HandshakeHandler<'_, RW> {
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> HandshakeHandler<'_, R, W> {
     async fn handle_first_handshake(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
-        let encryption_algorithm = EncryptionAlgorithm::default();
+        /* NOTE: RTMPE requires the signed handshake because public keys are located via the digest scheme. */
+        let encryption_algorithm = if rtmp_context.is_signed() && rtmp_context.get_rtmpe_keys().is_some() {
+            EncryptionAlgorithm::DiffieHellman
+        } else {
+            EncryptionAlgorithm::default()
+        };
 
         let version = if rtmp_context.is_signed() {
             Version::LATEST_CLIENT
         } else {
             Version::UNSIGNED
         };
-        let mut client_request = Handshake::new(Instant::now().elapsed(), version);
+        let digest_scheme = DigestScheme::from(encryption_algorithm);
+        let mut client_request = Handshake::new(epoch_timestamp(), version);
+        if let EncryptionAlgorithm::DiffieHellman = encryption_algorithm {
+            let diffie_hellman = DiffieHellman::generate();
+            client_request.imprint_public_key(digest_scheme, diffie_hellman.get_public_key());
+            rtmp_context.set_diffie_hellman(diffie_hellman);
+        }
         if rtmp_context.is_signed() {
-            client_request.imprint_digest(encryption_algorithm, Handshake::CLIENT_KEY);
+            client_request.imprint_digest(digest_scheme, Handshake::CLIENT_KEY);
+            rtmp_context.set_digest_scheme(digest_scheme);
         }
 
-        write_encryption_algorithm(self.0.as_mut(), encryption_algorithm).await?;
-        write_handshake(self.0.as_mut(), &client_request).await?;
+        write_encryption_algorithm(self.1.as_mut(), encryption_algorithm).await?;
+        write_handshake(self.1.as_mut(), &client_request).await?;
 
         rtmp_context.set_encryption_algorithm(encryption_algorithm);
         rtmp_context.set_client_handshake(client_request);
@@ -135,34 +168,68 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> HandshakeHandler<'_, RW> {
         let encryption_algorithm = read_encryption_algorithm(pin!(self.0.await_until_receiving())).await?;
         let mut server_request = read_handshake(pin!(self.0.await_until_receiving())).await?;
         let server_response = read_handshake(pin!(self.0.await_until_receiving())).await?;
+        let rtmpe_keys = rtmp_context.get_rtmpe_keys().cloned();
+
+        /* NOTE: S2 is expected to echo the timestamp of C1, otherwise the round-trip time can't be measured. */
+        match rtmp_context.get_client_handshake().unwrap().measure_round_trip_time(&server_response) {
+            Some(round_trip_time) => {
+                debug!("Round-trip time: {round_trip_time:?}");
+                rtmp_context.set_round_trip_time(round_trip_time);
+            },
+            None => warn!("S2 didn't echo the timestamp of C1: {:?}", server_response.get_timestamp())
+        }
+        rtmp_context.seed_from_round_trip_time();
+
+        /* NOTE: The response depends on S1 as it has been sent, so it is computed before our signature overwrites it. */
+        if let Some(swf_verification) = rtmp_context.get_swf_verification() {
+            rtmp_context.set_swf_verify_response(swf_verification.respond(&server_request));
+        }
 
         if !rtmp_context.is_signed() {
-            write_handshake(self.0.as_mut(), &server_request).await?;
+            if let Some(rtmpe_keys) = rtmpe_keys {
+                rtmpe_keys.disable();
+            }
+            write_handshake(self.1.as_mut(), &server_request).await?;
 
             rtmp_context.set_server_handshake(server_request);
             rtmp_context.set_client_handshake(server_response);
 
-        } else if !server_request.did_digest_match(encryption_algorithm, Handshake::SERVER_KEY) {
-            error!("Invalid SHA digest/signature: {:x?}", server_request.get_digest(encryption_algorithm));
-            return Err(inconsistent_sha(server_response.get_digest(encryption_algorithm).to_vec()))
-        } else {
+        } else if let Some(server_digest_scheme) = server_request.find_digest_scheme(Handshake::SERVER_KEY) {
+            debug!("Digest scheme: {server_digest_scheme:?}");
             let mut server_response_key: Vec<u8> = Vec::new();
             server_response_key.extend_from_slice(Handshake::SERVER_KEY);
             server_response_key.extend_from_slice(Handshake::COMMON_KEY);
 
-            if !server_response.did_signature_match(encryption_algorithm, &server_response_key) {
+            /* NOTE: The server signs our request with the scheme we have chosen, even if it has responded with another scheme. */
+            let digest_scheme = rtmp_context.get_digest_scheme().unwrap_or_default();
+            let client_request = rtmp_context.get_client_handshake().unwrap();
+            if !server_response.did_signature_match_request(client_request, digest_scheme, &server_response_key) {
                 error!("Invalid SHA digest/signature: {:x?}", server_response.get_signature());
                 return Err(inconsistent_sha(server_response.get_signature().to_vec()))
             } else {
+                if let Some(rtmpe_keys) = rtmpe_keys {
+                    match (encryption_algorithm, rtmp_context.get_diffie_hellman()) {
+                        (EncryptionAlgorithm::DiffieHellman, Some(diffie_hellman)) => {
+                            let server_public_key = server_request.get_public_key(server_digest_scheme);
+                            let shared_secret = diffie_hellman.compute_shared_secret(server_public_key)?;
+                            rtmpe_keys.install(&shared_secret, diffie_hellman.get_public_key(), server_public_key);
+                        },
+                        _ => rtmpe_keys.disable()
+                    }
+                }
+
                 let mut client_response_key: Vec<u8> = Vec::new();
                 client_response_key.extend_from_slice(Handshake::CLIENT_KEY);
                 client_response_key.extend_from_slice(Handshake::COMMON_KEY);
-                server_request.imprint_signature(encryption_algorithm, &client_response_key);
-                write_handshake(self.0.as_mut(), &server_request).await?;
+                server_request.imprint_signature(server_digest_scheme, &client_response_key);
+                write_handshake(self.1.as_mut(), &server_request).await?;
 
                 rtmp_context.set_server_handshake(server_request);
                 rtmp_context.set_client_handshake(server_response);
             }
+        } else {
+            error!("Invalid SHA digest/signature: {:x?}", server_request.get_digest(DigestScheme::default()));
+            return Err(inconsistent_sha(server_request.get_digest(DigestScheme::default()).to_vec()))
         }
 
         info!("Second handshake got handled.");
@@ -171,24 +238,45 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> HandshakeHandler<'_, RW> {
 }
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for HandshakeHandler<'_, RW> {
-    fn poll_handle(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        ready!(pin!(self.handle_first_handshake(rtmp_context)).poll(cx))?;
-        pin!(self.handle_second_handshake(rtmp_context)).poll(cx)
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> HandshakeHandler<'_, R, W> {
+    async fn handle(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
+        self.handle_first_handshake(rtmp_context).await?;
+        self.1.as_mut().flush().await?;
+        self.handle_second_handshake(rtmp_context).await?;
+        self.1.as_mut().flush().await?;
+
+        /* NOTE: The server's acknowledgements count also C0-C2. */
+        rtmp_context.get_flow_control_mut().add_sent_amount(FlowControl::HANDSHAKE_SIZE);
+        Ok(())
     }
 }
 
 #[doc(hidden)]
-fn handle_handshake<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>) -> HandshakeHandler<'a, RW> {
-    HandshakeHandler(stream)
+fn handle_handshake<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(reader: Pin<&'a mut R>, writer: Pin<&'a mut W>) -> HandshakeHandler<'a, R, W> {
+    HandshakeHandler(reader, writer)
 }
 
 #[doc(hidden)]
 #[derive(Debug)]
-struct MessageHandler<'a, RW: AsyncRead + AsyncWrite + Unpin>(Pin<&'a mut RW>);
+struct HandshakeStep;
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
+impl AsyncHandler for HandshakeStep {
+    async fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        handle_handshake(reader, writer).handle(rtmp_context).await
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+struct MessageHandler<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(Pin<&'a mut R>, Pin<&'a mut W>);
+
+#[doc(hidden)]
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> MessageHandler<'_, R, W> {
     async fn write_connect_request(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         use ClientType::*;
 
@@ -196,7 +284,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
 
         rtmp_context.increase_transaction_id();
 
-        let command_object = match client_type {
+        let mut command_object = match client_type {
             Publisher => object!(
                 "app" => rtmp_context.get_app().unwrap().clone(),
                 "type" => AmfString::from("nonprivate"),
@@ -214,12 +302,15 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
                 "videoFunction" => Number::from(1u8)
             )
         };
+        if let Some(capabilities) = rtmp_context.get_capabilities() {
+            capabilities.write_into(command_object.get_properties_mut());
+        }
         let connect = Connect::new(command_object);
         let mut buffer = ByteBuffer::default();
         buffer.encode(&AmfString::from("connect"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&connect);
-        write_chunk(self.0.as_mut(), rtmp_context, Connect::CHANNEL.into(), Duration::default(), Connect::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, Connect::CHANNEL.into(), Duration::default(), Connect::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_command_object(connect.into());
 
@@ -230,7 +321,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
     async fn write_window_acknowledgement_size(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         let mut buffer = ByteBuffer::default();
         buffer.encode(&rtmp_context.get_window_acknowledgement_size());
-        write_chunk(self.0.as_mut(), rtmp_context, WindowAcknowledgementSize::CHANNEL.into(), Duration::default(), WindowAcknowledgementSize::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, WindowAcknowledgementSize::CHANNEL.into(), Duration::default(), WindowAcknowledgementSize::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_subscriber_status(SubscriberStatus::WindowAcknowledgementSizeGotSent);
 
@@ -245,7 +336,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("releaseStream"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&ReleaseStream::new(rtmp_context.get_topic_id().unwrap().clone()));
-        write_chunk(self.0.as_mut(), rtmp_context, ReleaseStream::CHANNEL.into(), Duration::default(), ReleaseStream::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, ReleaseStream::CHANNEL.into(), Duration::default(), ReleaseStream::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         info!("releaseStream got sent.");
         Ok(())
@@ -258,7 +349,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("FCPublish"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&FcPublish::new(rtmp_context.get_topic_id().unwrap().clone()));
-        write_chunk(self.0.as_mut(), rtmp_context, FcPublish::CHANNEL.into(), Duration::default(), FcPublish::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, FcPublish::CHANNEL.into(), Duration::default(), FcPublish::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         info!("FCPublish got sent.");
         Ok(())
@@ -271,7 +362,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("createStream"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&CreateStream);
-        write_chunk(self.0.as_mut(), rtmp_context, CreateStream::CHANNEL.into(), Duration::default(), CreateStream::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, CreateStream::CHANNEL.into(), Duration::default(), CreateStream::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         info!("createStream got sent.");
         Ok(())
@@ -284,7 +375,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&FcSubscribe::new(topic_id));
-        write_chunk(self.0.as_mut(), rtmp_context, FcSubscribe::CHANNEL.into(), Duration::default(), FcSubscribe::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, FcSubscribe::CHANNEL.into(), Duration::default(), FcSubscribe::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_subscriber_status(SubscriberStatus::FcSubscribed);
 
@@ -302,7 +393,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&Publish::new(publishing_name.clone(), publishing_type.into()));
         let message_id = rtmp_context.get_message_id().unwrap();
-        write_chunk(self.0.as_mut(), rtmp_context, Publish::CHANNEL.into(), Duration::default(), Publish::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, Publish::CHANNEL.into(), Duration::default(), Publish::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
 
         info!("publish got sent.");
         Ok(())
@@ -311,7 +402,10 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
     async fn write_play_request(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         rtmp_context.increase_transaction_id();
 
-        let stream_name = rtmp_context.get_topic_id().unwrap().clone();
+        let mut stream_name = rtmp_context.get_topic_id().unwrap().clone();
+        if let Some(track_selection) = rtmp_context.get_track_selection_mut() {
+            stream_name = AmfString::new(format!("{stream_name}?{}", track_selection.to_query()));
+        }
         let play_mode = rtmp_context.get_play_mode().unwrap();
         let start_time: Number = if let Some(start_time) = rtmp_context.get_start_time() {
             Number::new(start_time.as_millis() as u64 as f64)
@@ -323,7 +417,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("play"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&Play::new(stream_name.clone(), start_time));
-        write_chunk(self.0.as_mut(), rtmp_context, Play::CHANNEL.into(), Duration::default(), Play::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, Play::CHANNEL.into(), Duration::default(), Play::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         info!("play got sent.");
         Ok(())
@@ -334,7 +428,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         let mut buffer = ByteBuffer::default();
         buffer.put_u16_be(SetBufferLength::EVENT_TYPE.into());
         buffer.encode(&SetBufferLength::new(message_id, rtmp_context.get_buffer_length()));
-        write_chunk(self.0.as_mut(), rtmp_context, SetBufferLength::CHANNEL.into(), Duration::default(), SetBufferLength::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, SetBufferLength::CHANNEL.into(), Duration::default(), SetBufferLength::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_subscriber_status(SubscriberStatus::BufferLengthGotSent);
 
@@ -343,8 +437,27 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
     }
 
     async fn write_flv(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
-        for next in rtmp_context.get_topic_mut().unwrap() {
+        loop {
+            /* NOTE: A tag to be paused must remain in the topic, so this reads it from a copy. */
+            let mut topic = rtmp_context.get_topic_mut().unwrap().clone();
+            let Some(next) = topic.next() else {
+                break
+            };
             let flv_tag = next?;
+
+            match rtmp_context.get_flow_control_mut().pace(&flv_tag) {
+                Pacing::Send => rtmp_context.set_topic(topic),
+                Pacing::Drop => {
+                    rtmp_context.set_topic(topic);
+                    info!("Non-keyframe video got dropped.");
+                    continue
+                },
+                Pacing::Pause => {
+                    info!("Sending FLV got paused until the peer acknowledges.");
+                    return Ok(())
+                }
+            }
+
             let message_id = rtmp_context.get_message_id().unwrap();
 
             let channel;
@@ -368,15 +481,15 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
                 }
             }
             let timestamp = flv_tag.get_timestamp();
-            let data: Vec<u8> = if let MessageType::Data = message_type {
+            let data: Bytes = if let MessageType::Data = message_type {
                 let mut buffer = ByteBuffer::default();
                 buffer.encode(&AmfString::from("@setDataFrame"));
                 buffer.put_bytes(flv_tag.get_data());
                 buffer.into()
             } else {
-                flv_tag.get_data().to_vec()
+                flv_tag.get_data().clone()
             };
-            write_chunk(self.0.as_mut(), rtmp_context, channel.into(), timestamp, message_type, message_id, &data).await?;
+            write_chunk(self.1.as_mut(), rtmp_context, channel.into(), timestamp, message_type, message_id, &data).await?;
 
             info!("FLV chunk got sent.");
             return Ok(())
@@ -387,13 +500,22 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         Err(stream_got_exhausted())
     }
 
-    async fn handle_acknowledgement(&mut self, _: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
-        Decoder::<Acknowledgement>::decode(&mut buffer)?;
+    async fn handle_acknowledgement(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let acknowledgement: Acknowledgement = buffer.decode()?;
+        rtmp_context.get_flow_control_mut().acknowledge(acknowledgement);
 
         info!("Acknowledgement got handled.");
         Ok(())
     }
 
+    async fn handle_peer_bandwidth(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let peer_bandwidth: PeerBandwidth = buffer.decode()?;
+        rtmp_context.get_flow_control_mut().apply_peer_bandwidth(peer_bandwidth);
+
+        info!("Peer Bandwidth got handled.");
+        Ok(())
+    }
+
     async fn handle_stream_begin(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
         use ClientType::*;
 
@@ -410,13 +532,115 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         Ok(())
     }
 
+    async fn handle_stream_eof(&mut self, _: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        Decoder::<StreamEof>::decode(&mut buffer)?;
+
+        info!("Stream EOF got handled.");
+        Ok(())
+    }
+
+    async fn handle_stream_dry(&mut self, _: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        Decoder::<StreamDry>::decode(&mut buffer)?;
+
+        info!("Stream Dry got handled.");
+        Ok(())
+    }
+
+    async fn handle_stream_is_recorded(&mut self, _: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        Decoder::<StreamIsRecorded>::decode(&mut buffer)?;
+
+        info!("Stream Is Recorded got handled.");
+        Ok(())
+    }
+
+    async fn handle_buffer_empty(&mut self, _: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        Decoder::<BufferEmpty>::decode(&mut buffer)?;
+
+        info!("Buffer Empty got handled.");
+        Ok(())
+    }
+
+    async fn handle_buffer_ready(&mut self, _: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        Decoder::<BufferReady>::decode(&mut buffer)?;
+
+        info!("Buffer Ready got handled.");
+        Ok(())
+    }
+
+    async fn handle_ping_request(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let ping_request: PingRequest = buffer.decode()?;
+
+        info!("Ping Request got handled.");
+        self.write_ping_response(rtmp_context, ping_request.get_timestamp()).await
+    }
+
+    async fn handle_ping_response(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let ping_response: PingResponse = buffer.decode()?;
+
+        /* NOTE: Only the response to our own request is measured, otherwise the partner could set any round-trip time. */
+        if rtmp_context.get_ping_timestamp() != Some(ping_response.get_timestamp()) {
+            warn!("Ping Response didn't echo the timestamp of our request: {}", ping_response.get_timestamp());
+            return Ok(())
+        }
+        rtmp_context.reset_ping_timestamp();
+
+        let round_trip_time = elapsed_since(Duration::from_millis(ping_response.get_timestamp() as u64));
+        debug!("Round-trip time: {round_trip_time:?}");
+        rtmp_context.set_round_trip_time(round_trip_time);
+
+        info!("Ping Response got handled.");
+        Ok(())
+    }
+
+    async fn write_ping_response(&mut self, rtmp_context: &mut RtmpContext, timestamp: u32) -> IOResult<()> {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingResponse::EVENT_TYPE.into());
+        buffer.encode(&PingResponse::new(timestamp));
+        write_chunk(self.1.as_mut(), rtmp_context, PingResponse::CHANNEL.into(), Duration::default(), PingResponse::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+
+        info!("Ping Response got sent.");
+        Ok(())
+    }
+
+    async fn handle_swf_verify_request(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        Decoder::<SwfVerifyRequest>::decode(&mut buffer)?;
+
+        info!("SWF Verify Request got handled.");
+        match rtmp_context.get_swf_verify_response() {
+            Some(swf_verify_response) => self.write_swf_verify_response(rtmp_context, swf_verify_response).await,
+            None => {
+                /* NOTE: Leaves the server to decide whether to refuse this client. */
+                warn!("SWF Verify Request got received, but no SWF file is specified.");
+                Ok(())
+            }
+        }
+    }
+
+    async fn write_swf_verify_response(&mut self, rtmp_context: &mut RtmpContext, swf_verify_response: SwfVerifyResponse) -> IOResult<()> {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(SwfVerifyResponse::EVENT_TYPE.into());
+        buffer.encode(&swf_verify_response);
+        write_chunk(self.1.as_mut(), rtmp_context, SwfVerifyResponse::CHANNEL.into(), Duration::default(), SwfVerifyResponse::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+
+        info!("SWF Verify Response got sent.");
+        Ok(())
+    }
+
     async fn handle_user_control(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
         use EventType::*;
 
-        let event_type: EventType = buffer.get_u16_be()?.into();
-        match event_type {
+        let event_type = buffer.get_u16_be()?;
+        match EventType::from(event_type) {
             StreamBegin => self.handle_stream_begin(rtmp_context, buffer).await,
-            _ => unreachable!("Publisher gets just a Stream Begin event.")
+            StreamEof => self.handle_stream_eof(rtmp_context, buffer).await,
+            StreamDry => self.handle_stream_dry(rtmp_context, buffer).await,
+            StreamIsRecorded => self.handle_stream_is_recorded(rtmp_context, buffer).await,
+            BufferEmpty => self.handle_buffer_empty(rtmp_context, buffer).await,
+            BufferReady => self.handle_buffer_ready(rtmp_context, buffer).await,
+            PingRequest => self.handle_ping_request(rtmp_context, buffer).await,
+            PingResponse => self.handle_ping_response(rtmp_context, buffer).await,
+            SwfVerifyRequest => self.handle_swf_verify_request(rtmp_context, buffer).await,
+            _ => rtmp_context.get_unknown_message_policy().apply(unknown_event(event_type))
         }
     }
 
@@ -428,12 +652,27 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         Err(error)
     }
 
+    async fn handle_window_acknowledgement_size(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let window_acknowledgement_size: WindowAcknowledgementSize = buffer.decode()?;
+        rtmp_context.set_window_acknowledgement_size(window_acknowledgement_size);
+
+        info!("Window Acknowledgement Size got handled.");
+        Ok(())
+    }
+
     async fn handle_connect_response(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
         use ClientType::*;
 
         let response: ConnectResult = buffer.decode()?;
         let (properties, information): (Object, Object) = response.into();
 
+        /* NOTE: Nothing is negotiated if the server isn't aware of Enhanced RTMP. */
+        if let Some(advertised) = rtmp_context.get_capabilities().cloned() {
+            let negotiated = Capabilities::from_properties(properties.get_properties()).map(|server_capabilities| advertised.negotiate(&server_capabilities)).unwrap_or_default();
+            debug!("Negotiated capabilities: {negotiated:?}");
+            rtmp_context.set_capabilities(negotiated);
+        }
+
         rtmp_context.set_properties(properties);
         rtmp_context.set_information(information);
 
@@ -491,7 +730,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
          *      Some error in publication step is checkable only by information the field.
          *      Because the publish command doesn't have _error command.
          */
-        if information.get_properties()["level"] == AmfString::from("error") {
+        if information.get_properties().get("level").and_then(AmfValue::as_str) == Some("error") {
             return self.handle_error_response(rtmp_context, information).await
         }
 
@@ -563,7 +802,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
          *      Some error in subscription step is checkable only by information the field.
          *      Because the play command doesn't have _error command.
          */
-        if information.get_properties()["level"] == AmfString::from("error") {
+        if information.get_properties().get("level").and_then(AmfValue::as_str) == Some("error") {
             return self.handle_error_response(rtmp_context, information).await
         }
 
@@ -620,58 +859,81 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
             Decoder::<AmfString>::decode(&mut buffer)?;
         }
 
-        let data: Vec<u8> = buffer.into();
+        let data: Bytes = buffer.into();
         let flv_tag = FlvTag::new(tag_type, timestamp, data);
         topic.append_flv_tag(flv_tag)?;
 
         info!("FLV chunk got handled.");
         Ok(())
     }
+
+    async fn handle_aggregate(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer, timestamp: Duration) -> IOResult<()> {
+        let topic = rtmp_context.get_topic().unwrap();
+
+        let mut aggregate: Aggregate = buffer.decode()?;
+        aggregate.rebase(timestamp);
+        for flv_tag in Vec::<FlvTag>::from(aggregate) {
+            topic.append_flv_tag(flv_tag)?;
+        }
+
+        info!("Aggregate chunk got handled.");
+        Ok(())
+    }
 }
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for MessageHandler<'_, RW> {
-    fn poll_handle(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> MessageHandler<'_, R, W> {
+    async fn handle(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         use MessageType::*;
 
         if let Some(publisher_status) = rtmp_context.get_publisher_status() {
             match publisher_status {
-                PublisherStatus::Connected => ready!(pin!(self.write_release_stream_request(rtmp_context)).poll(cx))?,
-                PublisherStatus::Released => ready!(pin!(self.write_fc_publish_request(rtmp_context)).poll(cx))?,
-                PublisherStatus::FcPublished => ready!(pin!(self.write_create_stream_request(rtmp_context)).poll(cx))?,
-                PublisherStatus::Created => ready!(pin!(self.write_publish_request(rtmp_context)).poll(cx))?,
-                PublisherStatus::Published => ready!(pin!(self.write_flv(rtmp_context)).poll(cx))?,
+                PublisherStatus::Connected => self.write_release_stream_request(rtmp_context).await?,
+                PublisherStatus::Released => self.write_fc_publish_request(rtmp_context).await?,
+                PublisherStatus::FcPublished => self.write_create_stream_request(rtmp_context).await?,
+                PublisherStatus::Created => self.write_publish_request(rtmp_context).await?,
+                PublisherStatus::Published => self.write_flv(rtmp_context).await?,
                 _ => {}
             }
         } else if let Some(subscriber_status) = rtmp_context.get_subscriber_status() {
             match subscriber_status {
                 SubscriberStatus::Connected => {
-                    ready!(pin!(self.write_window_acknowledgement_size(rtmp_context)).poll(cx))?;
-                    ready!(pin!(self.write_create_stream_request(rtmp_context)).poll(cx))?
+                    self.write_window_acknowledgement_size(rtmp_context).await?;
+                    self.write_create_stream_request(rtmp_context).await?
                 },
                 SubscriberStatus::Created => {
-                    ready!(pin!(self.write_fc_subscribe_request(rtmp_context)).poll(cx))?;
+                    self.write_fc_subscribe_request(rtmp_context).await?;
                     rtmp_context.set_subscriber_status(SubscriberStatus::AdditionalCommandGotSent);
                 },
                 SubscriberStatus::AdditionalCommandGotSent => {
-                    ready!(pin!(self.write_play_request(rtmp_context)).poll(cx))?;
-                    ready!(pin!(self.write_buffer_length(rtmp_context)).poll(cx))?
+                    self.write_play_request(rtmp_context).await?;
+                    self.write_buffer_length(rtmp_context).await?
                 },
                 _ => {}
             }
         } else {
-            ready!(pin!(self.write_connect_request(rtmp_context)).poll(cx))?;
+            self.write_connect_request(rtmp_context).await?;
         }
 
         let basic_header = if let Some(PublisherStatus::Published) = rtmp_context.get_publisher_status() {
-            ready!(pin!(read_basic_header(pin!(self.0.try_read_after(rtmp_context.get_await_duration().unwrap())))).poll(cx))?
+            /* NOTE: Awaits the server's acknowledgement only while sending is paused, otherwise just picks up what has already arrived. */
+            let await_duration = if rtmp_context.get_flow_control().is_window_exceeded() {
+                rtmp_context.get_await_duration().unwrap()
+            } else {
+                Duration::ZERO
+            };
+            match read_basic_header(pin!(self.0.try_read_after(await_duration))).await {
+                Ok(basic_header) => basic_header,
+                Err(e) if e.kind() == ErrorKind::TimedOut => return Ok(()),
+                Err(e) => return Err(e)
+            }
         } else {
-            ready!(pin!(read_basic_header(pin!(self.0.await_until_receiving()))).poll(cx))?
+            read_basic_header(pin!(self.0.await_until_receiving())).await?
         };
-        let message_header = ready!(pin!(read_message_header(pin!(self.0.await_until_receiving()), basic_header.get_message_format())).poll(cx))?;
+        let message_header = read_message_header(pin!(self.0.await_until_receiving()), basic_header.get_message_format()).await?;
         let extended_timestamp = if let Some(timestamp) = message_header.get_timestamp() {
             if timestamp.as_millis() == U24_MAX as u128 {
-                let extended_timestamp = ready!(pin!(read_extended_timestamp(pin!(self.0.await_until_receiving()))).poll(cx))?;
+                let extended_timestamp = read_extended_timestamp(pin!(self.0.await_until_receiving())).await?;
                 Some(extended_timestamp)
             } else {
                 None
@@ -702,47 +964,79 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for MessageHandler<'_, RW>
                 last_received_chunk.set_message_id(message_id);
             }
         } else {
-            rtmp_context.insert_received_chunk(
-                chunk_id,
-                LastChunk::new(
-                    message_header.get_timestamp().unwrap(),
-                    message_header.get_message_length().unwrap(),
-                    message_header.get_message_type().unwrap(),
-                    message_header.get_message_id().unwrap()
-                )
-            );
+            /* NOTE: The first chunk of every chunk stream is required to have its full message header. */
+            let (Some(timestamp), Some(message_length), Some(message_type), Some(message_id)) = (
+                message_header.get_timestamp(),
+                message_header.get_message_length(),
+                message_header.get_message_type(),
+                message_header.get_message_id()
+            ) else {
+                return Err(unknown_chunk_stream(chunk_id))
+            };
+            rtmp_context.insert_received_chunk(chunk_id, LastChunk::new(timestamp, message_length, message_type, message_id));
         }
 
         let message_length = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_message_length();
         let receiving_chunk_size = rtmp_context.get_receiving_chunk_size();
-        let data = ready!(pin!(read_chunk_data(pin!(self.0.await_until_receiving()), receiving_chunk_size, message_length)).poll(cx))?;
-        let buffer: ByteBuffer = data.into();
+        let data = read_chunk_data(pin!(self.0.await_until_receiving()), receiving_chunk_size, message_length, rtmp_context.get_max_message_size()).await?;
+        let mut buffer: ByteBuffer = data.into();
 
         let message_type = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_message_type();
         match message_type {
-            Acknowledgement => pin!(self.handle_acknowledgement(rtmp_context, buffer)).poll(cx),
-            UserControl => pin!(self.handle_user_control(rtmp_context, buffer)).poll(cx),
-            Command => pin!(self.handle_command_response(rtmp_context, buffer)).poll(cx),
+            Acknowledgement => self.handle_acknowledgement(rtmp_context, buffer).await,
+            UserControl => self.handle_user_control(rtmp_context, buffer).await,
+            WindowAcknowledgementSize => self.handle_window_acknowledgement_size(rtmp_context, buffer).await,
+            PeerBandwidth => self.handle_peer_bandwidth(rtmp_context, buffer).await,
+            Command => self.handle_command_response(rtmp_context, buffer).await,
+            Amf3Data => {
+                /* NOTE: AMF3 messages start with the format selector byte (always 0), then same as AMF0 ones. */
+                buffer.get_u8()?;
+                let timestamp = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_timestamp();
+                self.handle_flv(rtmp_context, buffer, Data, timestamp).await
+            },
+            Amf3Command => {
+                buffer.get_u8()?;
+                self.handle_command_response(rtmp_context, buffer).await
+            },
             Audio | Video | Data => {
                 let timestamp = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_timestamp();
-                pin!(self.handle_flv(rtmp_context, buffer, message_type, timestamp)).poll(cx)
+                self.handle_flv(rtmp_context, buffer, message_type, timestamp).await
             },
-            other => unimplemented!("Undefined Message: {other:?}")
+            Aggregate => {
+                let timestamp = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_timestamp();
+                self.handle_aggregate(rtmp_context, buffer, timestamp).await
+            },
+            other => rtmp_context.get_unknown_message_policy().apply(unknown_message(other))
         }
     }
 }
 
 #[doc(hidden)]
-fn handle_message<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>) -> MessageHandler<'a, RW> {
-    MessageHandler(stream)
+fn handle_message<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(reader: Pin<&'a mut R>, writer: Pin<&'a mut W>) -> MessageHandler<'a, R, W> {
+    MessageHandler(reader, writer)
 }
 
 #[doc(hidden)]
 #[derive(Debug)]
-struct CloseHandler<'a, RW: AsyncRead + AsyncWrite + Unpin>(Pin<&'a mut RW>);
+struct MessageStep;
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> CloseHandler<'_, RW> {
+impl AsyncHandler for MessageStep {
+    async fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        handle_message(reader, writer).handle(rtmp_context).await
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+struct CloseHandler<'a, W: AsyncWrite + Unpin>(Pin<&'a mut W>);
+
+#[doc(hidden)]
+impl<W: AsyncWrite + Unpin> CloseHandler<'_, W> {
     async fn write_fc_unpublish_request(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         rtmp_context.increase_transaction_id();
 
@@ -771,27 +1065,42 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> CloseHandler<'_, RW> {
 }
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> ErrorHandler for CloseHandler<'_, RW> {
-    fn poll_handle_error(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext, error: IOError) -> Poll<IOResult<()>> {
+impl<W: AsyncWrite + Unpin> CloseHandler<'_, W> {
+    async fn handle_error(&mut self, rtmp_context: &mut RtmpContext, error: IOError) -> IOResult<()> {
         if error.kind() != ErrorKind::Other {
             if let Some(publisher_status) = rtmp_context.get_publisher_status() {
                 if publisher_status >= PublisherStatus::FcPublished {
-                    ready!(pin!(self.write_fc_unpublish_request(rtmp_context)).poll(cx))?;
+                    self.write_fc_unpublish_request(rtmp_context).await?;
                 }
 
                 if publisher_status >= PublisherStatus::Created {
-                    ready!(pin!(self.write_delete_stream_request(rtmp_context)).poll(cx))?;
+                    self.write_delete_stream_request(rtmp_context).await?;
                 }
             }
         }
 
-        self.0.as_mut().poll_shutdown(cx)
+        self.0.as_mut().shutdown().await
     }
 }
 
 #[doc(hidden)]
-fn handle_close<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>) -> CloseHandler<'a, RW> {
-    CloseHandler(stream)
+fn handle_close<'a, W: AsyncWrite + Unpin>(writer: Pin<&'a mut W>) -> CloseHandler<'a, W> {
+    CloseHandler(writer)
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+struct CloseStep;
+
+#[doc(hidden)]
+impl ErrorHandler for CloseStep {
+    async fn handle_error<R, W>(&mut self, _: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext, error: IOError) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        handle_close(writer).handle_error(rtmp_context, error).await
+    }
 }
 
 /// The default RTMP handler.
@@ -825,6 +1134,9 @@ fn handle_close<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>)
 ///
 /// If receiving data size exceeds client's bandwidth, this reports its thing via the [`Acknowledgement`] message to its server.
 ///
+/// In both sides, this answers the [`SwfVerifyRequest`] event with the [`SwfVerifyResponse`] event if a SWF file has been specified (see [`RtmpContext::set_swf_verification`]).
+/// Also in both sides, this sends the [`PingRequest`] event every [`PING_INTERVAL`], then measures the round-trip time only from the [`PingResponse`] event which echoes its timestamp.
+///
 /// # Examples
 ///
 /// ```rust
@@ -840,7 +1152,7 @@ fn handle_close<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>)
 ///
 /// let stream = VecStream::default();
 /// let rtmp_context = RtmpContext::default();
-/// let client = Client::new(stream, rtmp_context, PhantomData::<RtmpHandler<VecStream>>);
+/// let client = Client::new(stream, rtmp_context, PhantomData::<RtmpHandler>);
 /// ```
 ///
 /// [`Connect`]: sheave_core::messages::Connect
@@ -855,77 +1167,250 @@ fn handle_close<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>)
 /// [`SetPlaylist`]: sheave_core::messages::SetPlaylist
 /// [`Play`]: sheave_core::messages::Play
 /// [`SetBufferLength`]: sheave_core::messages::SetBufferLength
-#[derive(Debug)]
-pub struct RtmpHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<StreamWrapper<RW>>);
-
-impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for RtmpHandler<RW> {
-    fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        pin!(
-            handle_handshake(self.0.make_weak_pin())
-                .while_ok(handle_message(self.0.make_weak_pin()).wrap(write_acknowledgement(self.0.make_weak_pin())))
-                .map_err(handle_close(self.0.make_weak_pin()))
-        ).poll_handle(cx, rtmp_context)
+/// [`SwfVerifyRequest`]: sheave_core::messages::SwfVerifyRequest
+/// [`SwfVerifyResponse`]: sheave_core::messages::SwfVerifyResponse
+/// [`RtmpContext::set_swf_verification`]: sheave_core::handlers::RtmpContext::set_swf_verification
+/// [`PingRequest`]: sheave_core::messages::PingRequest
+/// [`PingResponse`]: sheave_core::messages::PingResponse
+/// [`PING_INTERVAL`]: sheave_core::handlers::RtmpContext::PING_INTERVAL
+#[derive(Debug, Default)]
+pub struct RtmpHandler;
+
+impl AsyncHandler for RtmpHandler {
+    async fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        HandshakeStep
+            .while_ok(MessageStep.wrap(write_acknowledgement()).wrap(write_ping_request()))
+            .map_err(CloseStep)
+            .handle(reader, writer, rtmp_context)
+            .await
     }
 }
 
-impl<RW: AsyncRead + AsyncWrite + Unpin> HandlerConstructor<StreamWrapper<RW>> for RtmpHandler<RW> {
-    fn new(stream: Arc<StreamWrapper<RW>>) -> Self {
-        Self(stream)
+impl HandlerConstructor for RtmpHandler {
+    fn new() -> Self {
+        Self
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use tokio::io::split;
     use uuid::Uuid;
     use sheave_core::{
-        handlers::VecStream,
-        messages::PlayMode
+        handlers::{
+            UnknownMessagePolicy,
+            VecStream
+        },
+        messages::PlayMode,
+        net::rtmpe::RtmpeKeys,
+        session::UnknownChunkStream
     };
     use super::*;
 
     #[tokio::test]
     async fn ok_handshake_got_handled() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_signed(true);
 
-        handle_handshake(stream.as_mut()).handle_first_handshake(&mut rtmp_context).await.unwrap();
+        handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await.unwrap();
 
-        let sent_encryption_algorithm = read_encryption_algorithm(stream.as_mut()).await.unwrap();
-        let mut sent_client_handshake = read_handshake(stream.as_mut()).await.unwrap();
+        let sent_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let mut sent_client_handshake = read_handshake(reader.as_mut()).await.unwrap();
         assert_eq!(EncryptionAlgorithm::NotEncrypted, sent_encryption_algorithm);
-        assert!(sent_client_handshake.did_digest_match(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY));
+        assert!(sent_client_handshake.did_digest_match(DigestScheme::Scheme0, Handshake::CLIENT_KEY));
 
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let received_encryption_algorithm = EncryptionAlgorithm::NotEncrypted;
-        let mut received_server_handshake = Handshake::new(Instant::now().elapsed(), Version::LATEST_SERVER);
-        received_server_handshake.imprint_digest(received_encryption_algorithm, Handshake::SERVER_KEY);
+        let mut received_server_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_SERVER);
+        received_server_handshake.imprint_digest(DigestScheme::from(received_encryption_algorithm), Handshake::SERVER_KEY);
         let mut server_response_key: Vec<u8> = Vec::new();
         server_response_key.extend_from_slice(Handshake::SERVER_KEY);
         server_response_key.extend_from_slice(Handshake::COMMON_KEY);
-        sent_client_handshake.imprint_signature(sent_encryption_algorithm, &server_response_key);
-        write_encryption_algorithm(stream.as_mut(), received_encryption_algorithm).await.unwrap();
-        write_handshake(stream.as_mut(), &received_server_handshake).await.unwrap();
-        write_handshake(stream.as_mut(), &sent_client_handshake).await.unwrap();
-        assert!(handle_handshake(stream.as_mut()).handle_second_handshake(&mut rtmp_context).await.is_ok());
-
-        let sent_server_handshake = read_handshake(stream.as_mut()).await.unwrap();
+        sent_client_handshake.imprint_signature(DigestScheme::from(sent_encryption_algorithm), &server_response_key);
+        write_encryption_algorithm(writer.as_mut(), received_encryption_algorithm).await.unwrap();
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        assert!(handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await.is_ok());
+        assert!(rtmp_context.get_round_trip_time().is_some());
+
+        let sent_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
         let mut client_response_key: Vec<u8> = Vec::new();
         client_response_key.extend_from_slice(Handshake::CLIENT_KEY);
         client_response_key.extend_from_slice(Handshake::COMMON_KEY);
-        assert!(sent_server_handshake.did_signature_match(sent_encryption_algorithm, &client_response_key))
+        assert!(sent_server_handshake.did_signature_match(DigestScheme::from(sent_encryption_algorithm), &client_response_key))
+    }
+
+    #[tokio::test]
+    async fn err_server_digest_did_not_match() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_signed(true);
+
+        handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await.unwrap();
+        read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let sent_client_handshake = read_handshake(reader.as_mut()).await.unwrap();
+
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let received_server_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_SERVER);
+        write_encryption_algorithm(writer.as_mut(), EncryptionAlgorithm::NotEncrypted).await.unwrap();
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        assert!(handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await.is_err())
+    }
+
+    #[tokio::test]
+    async fn ok_encrypted_handshake_got_handled() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        let rtmpe_keys = RtmpeKeys::default();
+        rtmp_context.set_signed(true);
+        rtmp_context.set_rtmpe_keys(rtmpe_keys.clone());
+
+        handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await.unwrap();
+
+        let sent_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let mut sent_client_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        assert_eq!(EncryptionAlgorithm::DiffieHellman, sent_encryption_algorithm);
+        assert!(sent_client_handshake.did_digest_match(DigestScheme::from(sent_encryption_algorithm), Handshake::CLIENT_KEY));
+
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let server_diffie_hellman = DiffieHellman::generate();
+        let mut received_server_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_SERVER);
+        received_server_handshake.imprint_public_key(DigestScheme::from(sent_encryption_algorithm), server_diffie_hellman.get_public_key());
+        received_server_handshake.imprint_digest(DigestScheme::from(sent_encryption_algorithm), Handshake::SERVER_KEY);
+        let mut server_response_key: Vec<u8> = Vec::new();
+        server_response_key.extend_from_slice(Handshake::SERVER_KEY);
+        server_response_key.extend_from_slice(Handshake::COMMON_KEY);
+        sent_client_handshake.imprint_signature(DigestScheme::from(sent_encryption_algorithm), &server_response_key);
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        assert!(!rtmpe_keys.is_encrypted());
+        assert!(handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await.is_ok());
+        assert!(rtmpe_keys.is_encrypted())
+    }
+
+    #[tokio::test]
+    async fn ok_ping_got_answered() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_client_type(ClientType::Subscriber);
+
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingRequest::EVENT_TYPE.into());
+        buffer.encode(&PingRequest::new(1000));
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok());
+
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        assert_eq!(Some(MessageType::UserControl), message_header.get_message_type());
+        let data = read_chunk_data(reader.as_mut(), rtmp_context.get_receiving_chunk_size(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
+        let mut buffer: ByteBuffer = data.into();
+        assert_eq!(EventType::PingResponse, buffer.get_u16_be().unwrap().into());
+        let ping_response: PingResponse = buffer.decode().unwrap();
+        assert_eq!(1000, ping_response.get_timestamp())
+    }
+
+    #[tokio::test]
+    async fn ok_ping_response_got_measured() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_client_type(ClientType::Subscriber);
+
+        let timestamp = epoch_timestamp().as_millis() as u32;
+        rtmp_context.set_ping_timestamp(timestamp.wrapping_sub(500));
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingResponse::EVENT_TYPE.into());
+        buffer.encode(&PingResponse::new(timestamp.wrapping_sub(500)));
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok());
+        assert!(rtmp_context.get_round_trip_time().unwrap() >= Duration::from_millis(500));
+        assert!(rtmp_context.get_ping_timestamp().is_none())
+    }
+
+    #[tokio::test]
+    async fn ok_unsolicited_ping_response_got_ignored() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_client_type(ClientType::Subscriber);
+        rtmp_context.set_round_trip_time(Duration::from_millis(50));
+
+        let timestamp = epoch_timestamp().as_millis() as u32;
+        rtmp_context.set_ping_timestamp(timestamp);
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingResponse::EVENT_TYPE.into());
+        buffer.encode(&PingResponse::new(timestamp.wrapping_sub(60000)));
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok());
+        assert_eq!(Some(Duration::from_millis(50)), rtmp_context.get_round_trip_time());
+        assert_eq!(Some(timestamp), rtmp_context.get_ping_timestamp())
+    }
+
+    #[tokio::test]
+    async fn err_unknown_chunk_stream() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_subscriber_status(SubscriberStatus::Played);
+
+        /* NOTE: A chunk which continues a chunk stream that hasn't started yet. */
+        writer.write_all(&[0xc3, 0x00]).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).handle(&mut rtmp_context).await;
+        assert!(result.unwrap_err().get_ref().unwrap().is::<UnknownChunkStream>())
+    }
+
+    #[tokio::test]
+    async fn unknown_event_follows_policy() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_client_type(ClientType::Publisher);
+
+        for (policy, is_ok) in [(UnknownMessagePolicy::Ignore, true), (UnknownMessagePolicy::Log, true), (UnknownMessagePolicy::Disconnect, false)] {
+            rtmp_context.set_unknown_message_policy(policy);
+            let mut buffer = ByteBuffer::default();
+            buffer.put_u16_be(0xffff);
+            assert_eq!(is_ok, handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok())
+        }
     }
 
     #[tokio::test]
     async fn ok_publisher_sequence() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_tc_url("");
         rtmp_context.set_app("");
         rtmp_context.set_client_type(ClientType::Publisher);
 
-        handle_message(stream.as_mut()).write_connect_request(&mut rtmp_context).await.unwrap();
-        let mut stream = pin!(VecStream::default());
+        handle_message(reader.as_mut(), writer.as_mut()).write_connect_request(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(
             &ConnectResult::new(
@@ -941,37 +1426,45 @@ mod tests {
                 )
             )
         );
-        assert!(handle_message(stream.as_mut()).handle_connect_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_connect_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(PublisherStatus::Connected, rtmp_context.get_publisher_status().unwrap());
 
         rtmp_context.set_topic_id(AmfString::new(Uuid::now_v7().to_string()));
-        let mut stream = pin!(VecStream::default());
-        handle_message(stream.as_mut()).write_release_stream_request(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        handle_message(reader.as_mut(), writer.as_mut()).write_release_stream_request(&mut rtmp_context).await.unwrap();
         let mut buffer = ByteBuffer::default();
         buffer.encode(&ReleaseStreamResult);
-        assert!(handle_message(stream.as_mut()).handle_release_stream_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_release_stream_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(PublisherStatus::Released, rtmp_context.get_publisher_status().unwrap());
 
-        handle_message(stream.as_mut()).write_fc_publish_request(&mut rtmp_context).await.unwrap();
-        let mut stream = pin!(VecStream::default());
+        handle_message(reader.as_mut(), writer.as_mut()).write_fc_publish_request(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(&OnFcPublish);
-        assert!(handle_message(stream.as_mut()).handle_fc_publish_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_fc_publish_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(PublisherStatus::FcPublished, rtmp_context.get_publisher_status().unwrap());
 
-        handle_message(stream.as_mut()).write_create_stream_request(&mut rtmp_context).await.unwrap();
-        let mut stream = pin!(VecStream::default());
+        handle_message(reader.as_mut(), writer.as_mut()).write_create_stream_request(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(&CreateStreamResult::new(0.into()));
-        assert!(handle_message(stream.as_mut()).handle_create_stream_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_create_stream_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(PublisherStatus::Created, rtmp_context.get_publisher_status().unwrap());
 
-        handle_message(stream.as_mut()).write_publish_request(&mut rtmp_context).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).write_publish_request(&mut rtmp_context).await.unwrap();
         let message_id = rtmp_context.get_message_id().unwrap();
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(&StreamBegin::new(message_id));
-        assert!(handle_message(stream.as_mut()).handle_stream_begin(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_stream_begin(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(PublisherStatus::Began, rtmp_context.get_publisher_status().unwrap());
 
         let topic_id = rtmp_context.get_topic_id().unwrap().clone();
@@ -986,19 +1479,21 @@ mod tests {
                 )
             )
         );
-        assert!(handle_message(stream.as_mut()).handle_publish_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_publish_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(PublisherStatus::Published, rtmp_context.get_publisher_status().unwrap())
     }
 
     #[tokio::test]
     async fn ok_subscriber_sequence() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_tc_url("");
         rtmp_context.set_app("");
         rtmp_context.set_client_type(ClientType::Subscriber);
 
-        handle_message(stream.as_mut()).write_connect_request(&mut rtmp_context).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).write_connect_request(&mut rtmp_context).await.unwrap();
         let mut buffer = ByteBuffer::default();
         buffer.encode(
             &ConnectResult::new(
@@ -1014,32 +1509,40 @@ mod tests {
                 )
             )
         );
-        assert!(handle_message(stream.as_mut()).handle_connect_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_connect_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(SubscriberStatus::Connected, rtmp_context.get_subscriber_status().unwrap());
 
-        let mut stream = pin!(VecStream::default());
-        handle_message(stream.as_mut()).write_window_acknowledgement_size(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        handle_message(reader.as_mut(), writer.as_mut()).write_window_acknowledgement_size(&mut rtmp_context).await.unwrap();
         assert_eq!(SubscriberStatus::WindowAcknowledgementSizeGotSent, rtmp_context.get_subscriber_status().unwrap());
 
-        let mut stream = pin!(VecStream::default());
-        handle_message(stream.as_mut()).write_create_stream_request(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        handle_message(reader.as_mut(), writer.as_mut()).write_create_stream_request(&mut rtmp_context).await.unwrap();
         let mut buffer = ByteBuffer::default();
         buffer.encode(&CreateStreamResult::new(0.into()));
-        assert!(handle_message(stream.as_mut()).handle_create_stream_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_create_stream_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(SubscriberStatus::Created, rtmp_context.get_subscriber_status().unwrap());
 
         rtmp_context.set_topic_id(AmfString::new(Uuid::now_v7().to_string()));
-        let mut stream = pin!(VecStream::default());
-        handle_message(stream.as_mut()).write_fc_subscribe_request(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        handle_message(reader.as_mut(), writer.as_mut()).write_fc_subscribe_request(&mut rtmp_context).await.unwrap();
         assert_eq!(SubscriberStatus::FcSubscribed, rtmp_context.get_subscriber_status().unwrap());
 
         rtmp_context.set_start_time(Some(Duration::default()));
         rtmp_context.set_play_mode(PlayMode::Both);
-        let mut stream = pin!(VecStream::default());
-        handle_message(stream.as_mut()).write_play_request(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        handle_message(reader.as_mut(), writer.as_mut()).write_play_request(&mut rtmp_context).await.unwrap();
         let mut buffer = ByteBuffer::default();
         buffer.encode(&StreamBegin::new(rtmp_context.get_message_id().unwrap()));
-        assert!(handle_message(stream.as_mut()).handle_stream_begin(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_stream_begin(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(SubscriberStatus::Began, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
@@ -1052,12 +1555,14 @@ mod tests {
                 )
             )
         );
-        assert!(handle_message(stream.as_mut()).handle_play_response(&mut rtmp_context, buffer).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_play_response(&mut rtmp_context, buffer).await.is_ok());
         assert_eq!(SubscriberStatus::Played, rtmp_context.get_subscriber_status().unwrap());
 
         rtmp_context.set_buffer_length(30000);
-        let mut stream = pin!(VecStream::default());
-        handle_message(stream.as_mut()).write_buffer_length(&mut rtmp_context).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        handle_message(reader.as_mut(), writer.as_mut()).write_buffer_length(&mut rtmp_context).await.unwrap();
         assert_eq!(SubscriberStatus::BufferLengthGotSent, rtmp_context.get_subscriber_status().unwrap())
     }
 }
@@ -1,8 +1,11 @@
 pub mod handlers;
+pub mod net;
 mod client;
 mod invalid_uri;
+mod unsupported_protocol;
 
 pub use self::{
     client::Client,
-    invalid_uri::*
+    invalid_uri::*,
+    unsupported_protocol::*
 };
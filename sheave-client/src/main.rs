@@ -1,6 +1,8 @@
 pub mod handlers;
+pub mod net;
 mod client;
 mod invalid_uri;
+mod unsupported_protocol;
 
 use std::{
     fmt::{
@@ -12,7 +14,9 @@ use std::{
         Error as IOError,
         Result as IOResult
     },
+    fs::read,
     marker::PhantomData,
+    sync::Arc,
     time::Duration
 };
 use log::{
@@ -26,20 +30,49 @@ use clap::{
     Parser,
     ValueEnum
 };
-use tokio::spawn;
+use tokio::{
+    io::{
+        AsyncRead,
+        AsyncWrite
+    },
+    spawn
+};
+use tokio_rustls::rustls::pki_types::ServerName;
 use sheave_core::{
-    flv::*,
+    flv::{
+        *,
+        tags::{
+            AudioFourCc,
+            TrackSelection,
+            VideoFourCc
+        }
+    },
     handlers::{
+        Capabilities,
         ClientType as CoreClientType,
-        RtmpContext
+        RtmpContext,
+        UnknownMessagePolicy
     },
+    handshake::SwfVerification,
     messages::amf::v0::AmfString,
-    net::rtmp::RtmpStream
+    net::{
+        rtmp::RtmpStream,
+        rtmpe::{
+            RtmpeKeys,
+            RtmpeStream
+        },
+        rtmps::RtmpsStream,
+        rtmpt::RtmptStream
+    }
+};
+use self::{
+    handlers::RtmpHandler,
+    net::rtmps::client_config
 };
-use self::handlers::RtmpHandler;
 pub use self::{
     client::Client,
-    invalid_uri::*
+    invalid_uri::*,
+    unsupported_protocol::*
 };
 
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -129,6 +162,8 @@ impl Display for PublishingType {
 ///
 /// * loglevel
 /// * awaiting duration
+/// * unknown message policy
+/// * maximum message/tag sizes
 ///
 /// `sheave-client --client-type publisher --publishing-type live -f flv -i test.flv --await-duration 1000 --loglevel error rtmp://127.0.0.1/app/path`
 /// `sheave-client --client-type publisher --publishing-type live --formatf flv --input test.flv --await-duration 1000 --loglevel error rtmp://127.0.0.1/app/path`
@@ -155,12 +190,16 @@ struct ClientOptions {
     /// An awaiting duration (in milliseconds).
     ///
     /// While stream publication, the client may receive some message from the server, but not always.
+    /// When the server's bandwidth is exhausted, the client awaits its acknowledgement at most this duration, then tries sending again.
     ///
-    /// The default is `1000`.
-    #[arg(long, value_name = "Duration", default_value_t = 1000)]
-    await_duration: u64,
+    /// If this isn't specified, it is decided from the round-trip time which has been measured in the handshake.
+    /// That is, 4 times the round-trip time within `100` to `10000`, or `1000` if it couldn't be measured.
+    #[arg(long, value_name = "Duration")]
+    await_duration: Option<u64>,
 
     /// Indicates whether this client requires to perform handshake with HMAC(SHA-256).
+    ///
+    /// Note this is always regarded as `true` in `rtmpe`, because the encryption requires the signed handshake.
     #[arg(long)]
     signed: Option<bool>,
 
@@ -196,6 +235,20 @@ struct ClientOptions {
     #[arg(long, value_name = "Duration", required_if_eq("client_type", "subscriber"), allow_negative_numbers = true, default_value_t = -2)]
     start_time: i64,
 
+    /// The audio track which this client receives out of Multitrack packets. (for `subscriber` only)
+    ///
+    /// Either the track ID or the track name, which the server resolves with `audioTrackIdInfoMap` of the metadata, is allowed.
+    /// If this isn't specified, the server sends every track as it is.
+    #[arg(long, value_name = "ID / Name")]
+    audio_track: Option<String>,
+
+    /// The video track which this client receives out of Multitrack packets. (for `subscriber` only)
+    ///
+    /// Either the track ID or the track name, which the server resolves with `videoTrackIdInfoMap` of the metadata, is allowed.
+    /// If this isn't specified, the server sends every track as it is.
+    #[arg(long, value_name = "ID / Name")]
+    video_track: Option<String>,
+
     /// Formats of input files (required with input/output files).
     ///
     /// Following values are available.
@@ -220,13 +273,97 @@ struct ClientOptions {
 
     /// The URI of destination.
     ///
-    /// Currently only remote host via the RTMP stream is allowed.
-    /// e.g. `rtmp://127.0.0.1/app/path`
+    /// Currently only remote host via any of the RTMP stream, the RTMPS stream, the RTMPE stream and the RTMPT stream is allowed.
+    /// e.g. `rtmp://127.0.0.1/app/path`, `rtmps://example.com/app/path`, `rtmpe://127.0.0.1/app/path`, `rtmpt://127.0.0.1/app/path`
+    ///
+    /// Note that RTMPS URIs and RTMPT URIs are appended the port of `443` and `80` respectively as defaults if they are without ports.
     #[arg(value_name = "URI", requires = "format")]
     uri: String,
+
+    /// The path to a PEM file of CAs which are trusted in addition to the Mozilla's root CAs. (for `rtmps` only)
+    #[arg(long, value_name = "Path", conflicts_with = "insecure")]
+    ca_file: Option<String>,
+
+    /// Accepts any certificate of the server without verification. (for `rtmps` only)
+    ///
+    /// This is insecure, so use it only for testing with self-signed certificates.
+    #[arg(long)]
+    insecure: bool,
+
+    /// Specifies how to treat messages/events which the client doesn't know or doesn't support.
+    ///
+    /// * ignore: Discards them silently.
+    /// * log: Discards them after logging as warnings.
+    /// * disconnect: Closes the connection.
+    ///
+    /// The default is `log`.
+    #[arg(long, value_enum, value_name = "ignore / log / disconnect", default_value_t)]
+    unknown_message_policy: UnknownMessagePolicy,
+
+    /// The maximum size of a message which the client receives, in bytes.
+    ///
+    /// Every message which exceeds it makes the connection close, instead of being allocated as is.
+    /// The default is `8388608` (8 MiB).
+    #[arg(long, value_name = "Bytes", default_value_t = RtmpContext::DEFAULT_MAX_MESSAGE_SIZE)]
+    max_message_size: u32,
+
+    /// The maximum size of a FLV tag which the client reads from input files, in bytes.
+    ///
+    /// The default is `8388608` (8 MiB).
+    #[arg(long, value_name = "Bytes", default_value_t = Flv::DEFAULT_MAX_TAG_SIZE)]
+    max_tag_size: u32,
+
+    /// The path to a SWF file which is hashed for the SWF verification.
+    ///
+    /// Both compressed (`CWS`) and uncompressed (`FWS`) files are accepted.
+    /// Specify this only if the server requires the SWF verification.
+    #[arg(long, value_name = "Path", conflicts_with = "swf_hash")]
+    swf_file: Option<String>,
+
+    /// The SHA-256 HMAC of a SWF file in hex, which is used for the SWF verification instead of hashing a file.
+    #[arg(long, value_name = "Hex", value_parser = parse_swf_hash, requires = "swf_size")]
+    swf_hash: Option<[u8; 32]>,
+
+    /// The uncompressed size of a SWF file, which is used with `--swf-hash`.
+    #[arg(long, value_name = "Bytes", requires = "swf_hash")]
+    swf_size: Option<u32>,
+
+    /// FourCCs of Enhanced RTMP which this client advertises in the `connect` command.
+    ///
+    /// A subscriber advertises them as decodable, and a publisher advertises them as encodable.
+    /// The server sends only codecs which a subscriber has advertised.
+    /// If this isn't specified, every FourCC which this client can handle is advertised.
+    /// e.g. `av01`, `hvc1`, `Opus`, `mp4a`
+    #[arg(long, action = ArgAction::Append, value_name = "FourCC", value_parser = parse_four_cc)]
+    four_cc: Vec<AdvertisedFourCc>,
     // TODO: Makes other options if they are required.
 }
 
+#[derive(Debug, Clone, Copy)]
+enum AdvertisedFourCc {
+    Video(VideoFourCc),
+    Audio(AudioFourCc)
+}
+
+fn parse_four_cc(four_cc: &str) -> Result<AdvertisedFourCc, String> {
+    let bytes: [u8; 4] = four_cc.as_bytes().try_into().map_err(|_| format!("FourCC must be 4 bytes: {four_cc}"))?;
+    VideoFourCc::try_from(bytes).map(AdvertisedFourCc::Video)
+        .or_else(|_| AudioFourCc::try_from(bytes).map(AdvertisedFourCc::Audio))
+        .map_err(|e| e.to_string())
+}
+
+fn parse_swf_hash(hex: &str) -> Result<[u8; 32], String> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(format!("SWF hash must be 64 hex digits: {hex}"))
+    }
+
+    let mut swf_hash: [u8; 32] = [0; 32];
+    for (i, byte) in swf_hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[(i * 2)..(i * 2 + 2)], 16).map_err(|e| e.to_string())?;
+    }
+    Ok(swf_hash)
+}
+
 fn split_uri(uri: &str) -> IOResult<(&str, &str, &str, &str)> {
     let protocol_len = match uri.find(':') {
         Some(protocol_len) => protocol_len,
@@ -274,23 +411,99 @@ fn split_uri(uri: &str) -> IOResult<(&str, &str, &str, &str)> {
     Ok((protocol, addr, app, topic_id))
 }
 
+/* NOTE: Splits the host part from `host[:port]`, where IPv6 addresses are enclosed in brackets. */
+fn split_host(addr: &str) -> &str {
+    if let Some(rest) = addr.strip_prefix('[') {
+        rest.split(']').next().unwrap_or_default()
+    } else {
+        addr.split(':').next().unwrap_or_default()
+    }
+}
+
 async fn run_as_rtmp(addr: &str, app: &str, topic_id: &str, options: ClientOptions) -> IOResult<()> {
     let stream = RtmpStream::connect(addr).await?;
+    run_client(stream, None, app, topic_id, options).await
+}
+
+async fn run_as_rtmps(addr: &str, app: &str, topic_id: &str, options: ClientOptions) -> IOResult<()> {
+    let server_name = ServerName::try_from(split_host(addr).to_string()).map_err(|e| invalid_uri(e.to_string()))?;
+    let config = client_config(options.ca_file.as_ref(), options.insecure)?;
+
+    /* NOTE: Addresses can be specified without ports. */
+    let stream = if addr.ends_with(']') || !addr.contains(':') {
+        RtmpsStream::connect(format!("{addr}:443"), server_name, Arc::new(config)).await?
+    } else {
+        RtmpsStream::connect(addr, server_name, Arc::new(config)).await?
+    };
+    run_client(stream, None, app, topic_id, options).await
+}
+
+async fn run_as_rtmpe(addr: &str, app: &str, topic_id: &str, options: ClientOptions) -> IOResult<()> {
+    let stream = RtmpeStream::connect(addr).await?;
+    let rtmpe_keys = stream.get_keys();
+    run_client(stream, Some(rtmpe_keys), app, topic_id, options).await
+}
+
+async fn run_as_rtmpt(addr: &str, app: &str, topic_id: &str, options: ClientOptions) -> IOResult<()> {
+    /* NOTE: Addresses can be specified without ports. */
+    let stream = if addr.ends_with(']') || !addr.contains(':') {
+        RtmptStream::connect(format!("{addr}:80")).await?
+    } else {
+        RtmptStream::connect(addr).await?
+    };
+    run_client(stream, None, app, topic_id, options).await
+}
 
+async fn run_client<RW>(stream: RW, rtmpe_keys: Option<RtmpeKeys>, app: &str, topic_id: &str, options: ClientOptions) -> IOResult<()>
+where RW: AsyncRead + AsyncWrite + Unpin + Send + 'static
+{
     let mut rtmp_context = RtmpContext::default();
-    rtmp_context.set_signed(options.signed.unwrap_or_default());
+    if let Some(rtmpe_keys) = rtmpe_keys {
+        rtmp_context.set_signed(true);
+        rtmp_context.set_rtmpe_keys(rtmpe_keys);
+    } else {
+        rtmp_context.set_signed(options.signed.unwrap_or_default());
+    }
     rtmp_context.set_app(app);
     rtmp_context.set_topic_id(topic_id.into());
     rtmp_context.set_tc_url(&options.uri);
+    rtmp_context.set_unknown_message_policy(options.unknown_message_policy);
+    rtmp_context.set_max_message_size(options.max_message_size);
+    if let Some(swf_file) = options.swf_file.as_ref() {
+        rtmp_context.set_swf_verification(SwfVerification::from_swf(&read(swf_file)?)?);
+    } else if let (Some(swf_hash), Some(swf_size)) = (options.swf_hash, options.swf_size) {
+        rtmp_context.set_swf_verification(SwfVerification::new(swf_size, swf_hash));
+    }
 
     let client_type: CoreClientType = options.client_type.into();
+    let mask = match client_type {
+        CoreClientType::Publisher => Capabilities::CAN_ENCODE,
+        CoreClientType::Subscriber => Capabilities::CAN_DECODE
+    };
+    let capabilities = if options.four_cc.is_empty() {
+        Capabilities::supported(mask)
+    } else {
+        let mut capabilities = Capabilities::default();
+        capabilities.set_caps_ex(Capabilities::CAPS_EX_MULTITRACK);
+        for four_cc in options.four_cc.iter().copied() {
+            match four_cc {
+                AdvertisedFourCc::Video(four_cc) => capabilities.get_video_mut().insert(four_cc, mask),
+                AdvertisedFourCc::Audio(four_cc) => capabilities.get_audio_mut().insert(four_cc, mask)
+            }
+        }
+        capabilities
+    };
+    rtmp_context.set_capabilities(capabilities);
     match client_type {
         CoreClientType::Publisher => match options.format[0] {
             FileFormat::Flv => {
-                let topic = Flv::open(&options.input[0])?;
+                let mut topic = Flv::open(&options.input[0])?;
+                topic.set_max_tag_size(options.max_tag_size);
                 rtmp_context.set_topic(topic);
 
-                rtmp_context.set_await_duration(Duration::from_millis(options.await_duration));
+                if let Some(await_duration) = options.await_duration {
+                    rtmp_context.set_await_duration(Duration::from_millis(await_duration));
+                }
 
                 rtmp_context.set_publishing_name(topic_id.into());
                 rtmp_context.set_publishing_type(AmfString::new(options.publishing_type.to_string()));
@@ -306,12 +519,20 @@ async fn run_as_rtmp(addr: &str, app: &str, topic_id: &str, options: ClientOptio
                     rtmp_context.set_start_time(Some(Duration::from_secs(options.start_time as u64)));
                 }
                 rtmp_context.set_play_mode(options.start_time.into());
+
+                let track_selection = TrackSelection::new(
+                    options.audio_track.as_deref().map(Into::into),
+                    options.video_track.as_deref().map(Into::into)
+                );
+                if !track_selection.is_empty() {
+                    rtmp_context.set_track_selection(track_selection);
+                }
             }
         }
     };
     rtmp_context.set_client_type(client_type);
 
-    let client = Client::new(stream, rtmp_context, PhantomData::<RtmpHandler<RtmpStream>>);
+    let client = Client::new(stream, rtmp_context, PhantomData::<RtmpHandler>);
     spawn(client).await?
 }
 
@@ -330,7 +551,22 @@ async fn main() -> IOResult<()> {
             error!("Some error got occurred: {e}");
             return Err(e)
         },
-        _ => unimplemented!("Protocol: Currently RTMP only.")
+        "rtmps" => if let Err(e) = run_as_rtmps(addr, app, topic_id, options).await {
+            error!("Some error got occurred: {e}");
+            return Err(e)
+        },
+        "rtmpe" => if let Err(e) = run_as_rtmpe(addr, app, topic_id, options).await {
+            error!("Some error got occurred: {e}");
+            return Err(e)
+        },
+        "rtmpt" => if let Err(e) = run_as_rtmpt(addr, app, topic_id, options).await {
+            error!("Some error got occurred: {e}");
+            return Err(e)
+        },
+        _ => {
+            error!("Unsupported protocol got specified: {protocol}");
+            return Err(unsupported_protocol(protocol.into()))
+        }
     }
 
     info!("RTMP communication got completed.");
@@ -370,6 +606,95 @@ mod tests {
         assert_eq!(("rtmp", "localhost:1935", "live", "stream1"), result.unwrap())
     }
 
+    #[test]
+    fn ok_rtmps_scheme() {
+        let result = split_uri("rtmps://example.com/live/stream1");
+        assert!(result.is_ok());
+        assert_eq!(("rtmps", "example.com", "live", "stream1"), result.unwrap())
+    }
+
+    #[test]
+    fn ok_host_of_addr() {
+        assert_eq!("example.com", split_host("example.com"));
+        assert_eq!("example.com", split_host("example.com:443"));
+        assert_eq!("::1", split_host("[::1]:443"))
+    }
+
+    #[test]
+    fn err_insecure_with_ca_file() {
+        let result = ClientOptions::command()
+            .try_get_matches_from(
+                vec![
+                    "sheave-client",
+                    "--client-type", "publisher",
+                    "--publishing-type", "live",
+                    "-f", "flv",
+                    "-i", "test.flv",
+                    "-f", "flv",
+                    "--ca-file", "ca.crt",
+                    "--insecure",
+                    "rtmps://localhost"
+                ]
+            );
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn ok_swf_hash_with_size() {
+        let result = ClientOptions::command()
+            .try_get_matches_from(
+                vec![
+                    "sheave-client",
+                    "--client-type", "publisher",
+                    "--publishing-type", "live",
+                    "-f", "flv",
+                    "-i", "test.flv",
+                    "-f", "flv",
+                    "--swf-hash", "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+                    "--swf-size", "1024",
+                    "rtmp://localhost"
+                ]
+            );
+        assert!(result.is_ok())
+    }
+
+    #[test]
+    fn err_swf_hash_without_size() {
+        let result = ClientOptions::command()
+            .try_get_matches_from(
+                vec![
+                    "sheave-client",
+                    "--client-type", "publisher",
+                    "--publishing-type", "live",
+                    "-f", "flv",
+                    "-i", "test.flv",
+                    "-f", "flv",
+                    "--swf-hash", "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+                    "rtmp://localhost"
+                ]
+            );
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn err_swf_hash_not_hex() {
+        let result = ClientOptions::command()
+            .try_get_matches_from(
+                vec![
+                    "sheave-client",
+                    "--client-type", "publisher",
+                    "--publishing-type", "live",
+                    "-f", "flv",
+                    "-i", "test.flv",
+                    "-f", "flv",
+                    "--swf-hash", "not a hash",
+                    "--swf-size", "1024",
+                    "rtmp://localhost"
+                ]
+            );
+        assert!(result.is_err())
+    }
+
     #[test]
     fn err_not_uri() {
         let result = split_uri("/path/to/stream1");
@@ -509,6 +834,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn err_unknown_four_cc() {
+        let result = ClientOptions::command()
+            .try_get_matches_from(
+                vec![
+                    "sheave-client",
+                    "--client-type", "subscriber",
+                    "--start-time", "-2",
+                    "-f", "flv",
+                    "-o", "test.flv",
+                    "--four-cc", "av01",
+                    "--four-cc", "xxxx",
+                    "rtmp://localhost"
+                ]
+            );
+        assert!(result.is_err())
+    }
+
     #[test]
     fn ok_presenting_inputs() {
         let single_file_as_publisher = ClientOptions::command()
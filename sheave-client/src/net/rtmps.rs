@@ -0,0 +1,173 @@
+use std::{
+    io::{
+        Error as IOError,
+        Result as IOResult
+    },
+    path::Path,
+    sync::Arc
+};
+use tokio_rustls::rustls::{
+    ClientConfig,
+    DigitallySignedStruct,
+    Error as TlsError,
+    RootCertStore,
+    SignatureScheme,
+    client::danger::{
+        HandshakeSignatureValid,
+        ServerCertVerified,
+        ServerCertVerifier
+    },
+    crypto::{
+        CryptoProvider,
+        ring::default_provider,
+        verify_tls12_signature,
+        verify_tls13_signature
+    },
+    pki_types::{
+        CertificateDer,
+        ServerName,
+        UnixTime
+    }
+};
+use webpki_roots::TLS_SERVER_ROOTS;
+use sheave_core::net::rtmps::load_certificates;
+
+/// The verifier which accepts any certificate of servers.
+///
+/// Note this still checks signatures in the handshake, that is, only the trust of certificates is skipped.
+#[derive(Debug)]
+struct InsecureVerifier(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for InsecureVerifier {
+    fn verify_server_cert(&self, _end_entity: &CertificateDer<'_>, _intermediates: &[CertificateDer<'_>], _server_name: &ServerName<'_>, _ocsp_response: &[u8], _now: UnixTime) -> Result<ServerCertVerified, TlsError> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Builds the TLS configuration of clients.
+///
+/// Certificates of servers are verified against the Mozilla's root CAs, and CAs in `ca_file` if it is present.
+/// If `insecure` is true, every certificate is accepted without verification.
+/// That is, it must be used only for testing with self-signed certificates.
+pub fn client_config<P: AsRef<Path>>(ca_file: Option<P>, insecure: bool) -> IOResult<ClientConfig> {
+    let provider = Arc::new(default_provider());
+    let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(IOError::other)?;
+
+    if insecure {
+        return Ok(
+            builder.dangerous()
+                .with_custom_certificate_verifier(Arc::new(InsecureVerifier(provider)))
+                .with_no_client_auth()
+        )
+    }
+
+    let mut root_cert_store = RootCertStore::empty();
+    root_cert_store.extend(TLS_SERVER_ROOTS.iter().cloned());
+    if let Some(ca_file) = ca_file {
+        for certificate in load_certificates(ca_file)? {
+            root_cert_store.add(certificate).map_err(IOError::other)?;
+        }
+    }
+
+    Ok(builder.with_root_certificates(root_cert_store).with_no_client_auth())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::write
+    };
+    use rcgen::generate_simple_self_signed;
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt
+        },
+        net::TcpListener,
+        spawn,
+        task::JoinHandle
+    };
+    use tokio_rustls::{
+        TlsAcceptor,
+        rustls::ServerConfig
+    };
+    use sheave_core::net::{
+        rtmp::RtmpStream,
+        rtmps::RtmpsStream
+    };
+    use super::*;
+
+    /* NOTE: Serves a self-signed certificate of `localhost`, and echoes a byte after the handshake. */
+    async fn serve_self_signed(name: &str) -> (String, JoinHandle<IOResult<()>>, String) {
+        let certified_key = generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let ca_file = temp_dir().join(format!("sheave-client-{name}.crt"));
+        write(&ca_file, certified_key.cert.pem()).unwrap();
+        let config = ServerConfig::builder_with_provider(Arc::new(default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(vec![certified_key.cert.der().clone()], certified_key.signing_key.serialize_der().try_into().unwrap())
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let serving = spawn(
+            async move {
+                let (tcp_stream, _) = listener.accept().await?;
+                let mut tls_stream = acceptor.accept(RtmpStream::from(tcp_stream)).await?;
+                let received = tls_stream.read_u8().await?;
+                tls_stream.write_u8(received).await?;
+                tls_stream.flush().await
+            }
+        );
+        (addr, serving, ca_file.to_str().unwrap().to_string())
+    }
+
+    async fn echo(addr: &str, config: ClientConfig) -> IOResult<u8> {
+        let server_name = "localhost".try_into().unwrap();
+        let mut stream = RtmpsStream::connect(addr, server_name, Arc::new(config)).await?;
+        stream.write_u8(3).await?;
+        stream.flush().await?;
+        stream.read_u8().await
+    }
+
+    #[tokio::test]
+    async fn ok_trusted_ca_file() {
+        let (addr, serving, ca_file) = serve_self_signed("ok-trusted-ca-file").await;
+        let config = client_config(Some(&ca_file), false).unwrap();
+        assert_eq!(3, echo(&addr, config).await.unwrap());
+        assert!(serving.await.unwrap().is_ok())
+    }
+
+    #[tokio::test]
+    async fn ok_insecure() {
+        let (addr, serving, _) = serve_self_signed("ok-insecure").await;
+        let config = client_config(None::<&str>, true).unwrap();
+        assert_eq!(3, echo(&addr, config).await.unwrap());
+        assert!(serving.await.unwrap().is_ok())
+    }
+
+    #[tokio::test]
+    async fn err_untrusted_certificate() {
+        let (addr, serving, _) = serve_self_signed("err-untrusted-certificate").await;
+        let config = client_config(None::<&str>, false).unwrap();
+        assert!(echo(&addr, config).await.is_err());
+        assert!(serving.await.unwrap().is_err())
+    }
+}
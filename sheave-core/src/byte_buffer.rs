@@ -1,13 +1,19 @@
 mod insufficient_buffer_length;
 
 use std::io::Result as IOResult;
+use bytes::{
+    Bytes,
+    BytesMut
+};
 use crate::U24_MAX;
 pub use self::insufficient_buffer_length::*;
 
 /// The stream buffer for encoding/decoding chunk data.
+///
+/// Bytes are kept as reference-counted ones, so that media data can be handed over without copying them.
 #[derive(Debug, Clone, Default)]
 pub struct ByteBuffer {
-    bytes: Vec<u8>,
+    bytes: BytesMut,
     offset: usize
 }
 
@@ -296,9 +302,44 @@ impl ByteBuffer {
         ).ok_or(insufficient_buffer_length(len, self.remained()))
     }
 
+    /// Tries getting arbitrary bytes from buffer, without copying them.
+    ///
+    /// Bytes which have been already consumed are released at this point.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least specified length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::ByteBuffer;
+    ///
+    /// let mut buffer: ByteBuffer = b"hello world!".to_vec().into();
+    /// buffer.get_bytes(6).unwrap();
+    /// let world = buffer.get_shared_bytes(5).unwrap();
+    /// assert_eq!(b"world".as_slice(), &world);
+    /// assert_eq!(1, buffer.remained());
+    ///
+    /// assert!(buffer.get_shared_bytes(2).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: InsufficientBufferLength
+    pub fn get_shared_bytes(&mut self, len: usize) -> IOResult<Bytes> {
+        if self.remained() < len {
+            return Err(insufficient_buffer_length(len, self.remained()))
+        }
+
+        let offset = self.offset;
+        self.offset = 0;
+        Ok(self.bytes.split_to(offset + len).freeze().slice(offset..))
+    }
+
     /// Puts 1 byte into buffer.
     pub fn put_u8(&mut self, n: u8) {
-        self.bytes.push(n);
+        self.bytes.extend_from_slice(&[n]);
     }
 
     /// Puts 2 bytes into buffer, as the big endian.
@@ -378,13 +419,40 @@ impl ByteBuffer {
 
 impl From<Vec<u8>> for ByteBuffer {
     fn from(bytes: Vec<u8>) -> Self {
+        Bytes::from(bytes).into()
+    }
+}
+
+impl From<Bytes> for ByteBuffer {
+    /// Wraps bytes without copying them unless they are shared with others.
+    fn from(bytes: Bytes) -> Self {
         Self {
-            bytes,
+            bytes: bytes.into(),
             offset: 0
         }
     }
 }
 
+impl From<ByteBuffer> for Bytes {
+    /// Converts remained bytes into Bytes, without copying them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use sheave_core::ByteBuffer;
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_bytes(&[0, 1, 2, 3]);
+    /// buffer.get_u8().unwrap();
+    /// let bytes: Bytes = buffer.into();
+    /// assert_eq!([1, 2, 3].as_slice(), &bytes)
+    /// ```
+    fn from(buffer: ByteBuffer) -> Self {
+        buffer.bytes.freeze().slice(buffer.offset..)
+    }
+}
+
 impl From<ByteBuffer> for Vec<u8> {
     /// Converts remained bytes into Vec.
     ///
@@ -31,6 +31,7 @@
 //! [`ScriptDataTag`]: tags::ScriptDataTag
 mod not_flv_container;
 mod unknown_tag;
+mod too_large_tag;
 pub mod tags;
 
 use std::{
@@ -58,6 +59,7 @@ use self::tags::*;
 pub use self::{
     not_flv_container::*,
     unknown_tag::*,
+    too_large_tag::*
 };
 
 /// Patterns of the FilterName field.
@@ -91,12 +93,16 @@ impl Display for FilterName {
 /// * A path to actual FLV file
 /// * Offset in FLV file (for reading).
 ///
+/// Additionally, this holds the maximum size of a FLV tag to read.
+/// Every tag which exceeds it is treated as an error, instead of allocating its data as is.
+///
 /// By not to hold actual file handle, this makes plural users to read/write FLV file not to bump.
 /// Actual file handle is gotten only while file opens/creates and file reads/writes.
 #[derive(Debug, Clone)]
 pub struct Flv {
     offset: u64,
-    path: String
+    path: String,
+    max_tag_size: u32
 }
 
 impl Flv {
@@ -104,6 +110,9 @@ impl Flv {
     const LATEST_VERSION: u8 = 10;
     const HEADER_LEN: usize = 9;
 
+    /// The maximum size of a FLV tag to read by default. (8 MiB)
+    pub const DEFAULT_MAX_TAG_SIZE: u32 = 0x800000;
+
     /// Constructs a FLV container from a file.
     ///
     /// # Errors
@@ -185,11 +194,47 @@ impl Flv {
                     // NOTE: Seeks to the position of first FLV tag.
                     offset: 13,
                     path: referred_path.to_str().unwrap().into(),
+                    max_tag_size: Self::DEFAULT_MAX_TAG_SIZE
                 }
             )
         }
     }
 
+    /// Sets the maximum size of a FLV tag to read.
+    ///
+    /// Note this counts only the data part of the tag. That is, its metadata (11 bytes) isn't counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::flv::{
+    ///     Flv,
+    ///     tags::{
+    ///         FlvTag,
+    ///         TagType
+    ///     }
+    /// };
+    ///
+    /// let _ = std::fs::remove_file("/tmp/max_tag_size.flv");
+    /// let mut flv = Flv::create("/tmp/max_tag_size.flv").unwrap();
+    /// flv.append_flv_tag(FlvTag::new(TagType::Audio, Duration::default(), vec![0; 16].into())).unwrap();
+    ///
+    /// flv.set_max_tag_size(15);
+    /// assert!(flv.next().unwrap().is_err());
+    ///
+    /// flv.set_max_tag_size(16);
+    /// assert!(flv.next().unwrap().is_ok())
+    /// ```
+    pub fn set_max_tag_size(&mut self, max_tag_size: u32) {
+        self.max_tag_size = max_tag_size;
+    }
+
+    /// Gets the maximum size of a FLV tag to read.
+    pub fn get_max_tag_size(&self) -> u32 {
+        self.max_tag_size
+    }
+
     /// Constructs an empty FLV container from a name.
     pub fn create<P: AsRef<Path>>(path: P) -> IOResult<Self> {
         let referred_path = path.as_ref();
@@ -210,6 +255,7 @@ impl Flv {
                 // NOTE: Seeks to the position of first FLV tag.
                 offset: 13,
                 path: referred_path.to_str().unwrap().into(),
+                max_tag_size: Self::DEFAULT_MAX_TAG_SIZE
             }
         )
     }
@@ -271,7 +317,7 @@ impl Flv {
             .open(&self.path)?;
 
         if let TagType::ScriptData = flv_tag.get_tag_type() {
-            let mut buffer: ByteBuffer = flv_tag.get_data().to_vec().into();
+            let mut buffer: ByteBuffer = flv_tag.get_data().clone().into();
             let script_data: ScriptDataTag = buffer.decode()?;
             let has_audio = script_data.get_value().get_properties().get("audiocodecid").is_some() as u8;
             let has_video = script_data.get_value().get_properties().get("videocodecid").is_some() as u8;
@@ -308,6 +354,10 @@ impl Iterator for Flv {
     /// Currently, the tag type should be one of 8(Audio), 9(Video) or 18(Data) in the FLV container.
     /// That is, this library doesn't know any way of handling other type.
     ///
+    /// * `TooLargeTag`
+    ///
+    /// When the data size of the tag exceeds the maximum tag size.
+    ///
     /// * Something else
     ///
     /// When reading/seeking got failed by some cause.
@@ -337,10 +387,16 @@ impl Iterator for Flv {
         let mut data_size_bytes: [u8; 4] = [0; 4];
         data_size_bytes[1..].copy_from_slice(&metadata_bytes[1..4]);
         let data_size = u32::from_be_bytes(data_size_bytes);
-        let mut data: Vec<u8> = Vec::with_capacity(data_size as usize);
-        unsafe { data.set_len(data_size as usize); }
-        if let Err(e) = file.read(&mut data) {
-            return Some(Err(e))
+        if data_size > self.max_tag_size {
+            return Some(Err(too_large_tag(data_size, self.max_tag_size)))
+        }
+
+        let mut data: Vec<u8> = Vec::new();
+        match (&mut file).take(data_size as u64).read_to_end(&mut data) {
+            Err(e) => return Some(Err(e)),
+            /* NOTE: The tag hasn't been written completely yet. Reads it again from same offset at the next time. */
+            Ok(len) if len < data_size as usize => return None,
+            _ => {}
         }
 
         // NOTE: Previous Tag Size is unnecessary in reading.
@@ -357,6 +413,6 @@ impl Iterator for Flv {
             Err(e) => return Some(Err(e)),
             Ok(offset) => offset
         };
-        Some(Ok(FlvTag::new(tag_type, Duration::from_millis(timestamp as u64), data)))
+        Some(Ok(FlvTag::new(tag_type, Duration::from_millis(timestamp as u64), data.into())))
     }
 }
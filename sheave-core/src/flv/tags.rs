@@ -58,12 +58,35 @@
 //! |`6`|Nellymoser|
 //! |`7`|G.711 (A-law)|
 //! |`8`|G.711 (mu-law)|
-//! |`9`|reserved|
+//! |`9`|ExHeader (See [ExAudioTagHeader](#exaudiotagheader))|
 //! |`10`|AAC|
 //! |`11`|Speex|
 //! |`14`|MP3 (8 kHz)|
 //! |`15`|Device-specific format|
 //!
+//! ### ExAudioTagHeader
+//!
+//! If the SoundFormat field is 9, the audio tag has the ExAudioTagHeader instead, which consists of:
+//!
+//! 1. SoundFormat (4 bits. This is fixed to 9.)
+//! 2. AudioPacketType (4 bits)
+//!    * SequenceStart (0)
+//!    * CodedFrames (1)
+//!    * SequenceEnd (2)
+//!    * MultichannelConfig (4, See [`MultichannelConfig`].)
+//!    * Multitrack (5, See [Multitrack](#multitrack).)
+//! 3. AudioFourCc (32 bits. See the table below.)
+//! 4. AudioData (Arbitrary size)
+//!
+//! |FourCC|Codec|
+//! | :- | :- |
+//! |`ac-3`|AC-3|
+//! |`ec-3`|E-AC-3|
+//! |`Opus`|Opus|
+//! |`.mp3`|MP3|
+//! |`fLaC`|FLAC|
+//! |`mp4a`|AAC|
+//!
 //! ## [`Video`]
 //!
 //! The video tag consists of:
@@ -95,6 +118,57 @@
 //! |`6`|Screen Video (v2)|
 //! |`7`|AVC|
 //!
+//! ### ExVideoTagHeader
+//!
+//! If the most significant bit of the first byte is set, the video tag has the ExVideoTagHeader instead, which consists of:
+//!
+//! 1. IsExHeader (1 bit. This is fixed to 1.)
+//! 2. FrameType (3 bits. Same as above.)
+//! 3. VideoPacketType (4 bits)
+//!    * SequenceStart (0)
+//!    * CodedFrames (1)
+//!    * SequenceEnd (2)
+//!    * CodedFramesX (3, that is, CodedFrames whose CompositionTime is 0.)
+//!    * Metadata (4)
+//!    * MPEG2TSSequenceStart (5)
+//!    * Multitrack (6, See [Multitrack](#multitrack).)
+//! 4. VideoCommand (8 bits if the frame is a video info/command frame.)
+//! 5. VideoFourCc (32 bits otherwise. See the table below.)
+//! 6. CompositionTime (**signed** 24 bits if the packet is CodedFrames of either the AVC or the HEVC.)
+//! 7. VideoData (Arbitrary size)
+//!
+//! |FourCC|Codec|Decoder Configuration Record|
+//! | :- | :- | :- |
+//! |`vp08`|VP8|[`VpCodecConfigurationRecord`]|
+//! |`vp09`|VP9|[`VpCodecConfigurationRecord`]|
+//! |`av01`|AV1|[`Av1CodecConfigurationRecord`]|
+//! |`avc1`|AVC|[`AvcDecoderConfigurationRecord`]|
+//! |`hvc1`|HEVC|[`HevcDecoderConfigurationRecord`]|
+//!
+//! The decoder configuration record is sent as the body of SequenceStart.
+//!
+//! ### Multitrack
+//!
+//! Both of the ExAudioTagHeader and the ExVideoTagHeader can bundle several tracks into one tag.
+//! In that case, the packet type field is Multitrack and following fields are inserted before the FourCC:
+//!
+//! 1. AvMultitrackType (4 bits. See [`AvMultitrackType`].)
+//!    * OneTrack (0)
+//!    * ManyTracks (1, that is, all tracks have same codec.)
+//!    * ManyTracksManyCodecs (2)
+//! 2. The actual packet type which is applied to all tracks (4 bits)
+//!
+//! Then every track consists of:
+//!
+//! 1. FourCC (32 bits only if ManyTracksManyCodecs. Otherwise the one in the header is shared with all tracks.)
+//! 2. TrackId (8 bits)
+//! 3. SizeOfTrack (24 bits unless OneTrack)
+//! 4. CompositionTime (Same as above.)
+//! 5. AudioData/VideoData (Same as SizeOfTrack, otherwise the rest of the tag.)
+//!
+//! The track whose ID is 0 is regarded as the default one.
+//! Which tracks a subscriber receives is specified with the query of the stream name. (See [`TrackSelection`].)
+//!
 //! ## [`ScriptData`]
 //!
 //! Currently, this is used to contain following pair:
@@ -139,12 +213,29 @@
 mod audio;
 mod video;
 mod script_data;
+mod unknown_sound_format;
+mod unknown_sound_rate;
+mod unknown_video_packet_type;
+mod unknown_audio_packet_type;
+mod unknown_audio_channel_order;
+mod unknown_four_cc;
+mod multitrack;
+mod unknown_av_multitrack_type;
 
 use std::time::Duration;
+use bytes::Bytes;
 pub use self::{
     audio::*,
     video::*,
-    script_data::*
+    script_data::*,
+    unknown_sound_format::*,
+    unknown_sound_rate::*,
+    unknown_video_packet_type::*,
+    unknown_audio_packet_type::*,
+    unknown_audio_channel_order::*,
+    unknown_four_cc::*,
+    multitrack::*,
+    unknown_av_multitrack_type::*
 };
 
 /// The length of metadata which are common to every FLV tag.
@@ -195,12 +286,14 @@ impl From<TagType> for u8 {
 pub struct FlvTag {
     tag_type: TagType,
     timestamp: Duration,
-    data: Vec<u8>
+    data: Bytes
 }
 
 impl FlvTag {
     /// Constructs a FlvTag.
-    pub fn new(tag_type: TagType, timestamp: Duration, data: Vec<u8>) -> Self {
+    ///
+    /// Its data is shared with others instead of being copied, when this is cloned.
+    pub fn new(tag_type: TagType, timestamp: Duration, data: Bytes) -> Self {
         Self {
             tag_type,
             timestamp,
@@ -219,7 +312,34 @@ impl FlvTag {
     }
 
     /// Gets a message data. 
-    pub fn get_data(&self) -> &[u8] {
+    pub fn get_data(&self) -> &Bytes {
         &self.data
     }
+
+    /// Checks whether this is a video tag which contains a keyframe.
+    ///
+    /// Any non-video tag and any video tag without its data aren't counted as keyframes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::flv::tags::{
+    ///     FlvTag,
+    ///     TagType
+    /// };
+    ///
+    /// let key_frame = FlvTag::new(TagType::Video, Duration::default(), vec![0x17, 0x01].into());
+    /// assert!(key_frame.is_key_frame());
+    /// let ex_key_frame = FlvTag::new(TagType::Video, Duration::default(), vec![0x91, b'h', b'v', b'c', b'1'].into());
+    /// assert!(ex_key_frame.is_key_frame());
+    /// let inter_frame = FlvTag::new(TagType::Video, Duration::default(), vec![0x27, 0x01].into());
+    /// assert!(!inter_frame.is_key_frame());
+    /// let audio = FlvTag::new(TagType::Audio, Duration::default(), vec![0x1f].into());
+    /// assert!(!audio.is_key_frame())
+    /// ```
+    pub fn is_key_frame(&self) -> bool {
+        /* NOTE: The IsExHeader bit of Enhanced RTMP is excluded from the FrameType field. */
+        self.tag_type == TagType::Video && self.data.first().is_some_and(|byte| FrameType::from((byte >> 4) & 0x07) == FrameType::Key)
+    }
 }
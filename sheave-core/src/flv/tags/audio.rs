@@ -1,3 +1,5 @@
+mod multichannel_config;
+
 use std::io::{
     Error as IOError,
     Result as IOResult
@@ -8,6 +10,14 @@ use crate::{
     Encoder,
     messages::Audio
 };
+use super::{
+    AvMultitrackType,
+    unknown_sound_format,
+    unknown_sound_rate,
+    unknown_audio_packet_type,
+    unknown_four_cc
+};
+pub use self::multichannel_config::*;
 
 /// Representation of the SoundFormat field of the AudioTag.
 ///
@@ -24,7 +34,7 @@ use crate::{
 /// |`NellyMoserStereo`|`6`|
 /// |`G711A`|`7`|
 /// |`G711Mu`|`8`|
-/// |`Reserved`|`9`|
+/// |`ExHeader`|`9`|
 /// |`Aac`|`10`|
 /// |`Speex`|`11`|
 /// |`Mp3_8`|`14`|
@@ -41,7 +51,7 @@ pub enum SoundFormat {
     NellyMoserStereo,
     G711A,
     G711Mu,
-    Reserved,
+    ExHeader,
     Aac,
     Speex,
     Mp3_8 = 14,
@@ -55,50 +65,60 @@ impl SoundFormat {
             _ => false
         }
     }
+
+    /// Checks whether this indicates the ExAudioTagHeader of Enhanced RTMP.
+    pub fn is_ex_header(&self) -> bool {
+        *self == SoundFormat::ExHeader
+    }
 }
 
-impl From<u8> for SoundFormat {
+impl TryFrom<u8> for SoundFormat {
+    type Error = IOError;
+
     /// Converts a SoundFormat field into a variant.
     ///
-    /// # Panics
+    /// # Errors
+    ///
+    /// * [`UnknownSoundFormat`]
     ///
-    /// Because of FLV specification, this is implemented in such a way as to emit a panic when is passed a value either 12, 13 or any of above 15.
+    /// Because of FLV specification, this returns an error when is passed a value either 12, 13 or any of above 15.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use std::panic::catch_unwind;
     /// use sheave_core::flv::tags::{
     ///     SoundFormat,
     ///     SoundFormat::*
     /// };
     ///
-    /// assert_eq!(Speex, SoundFormat::from(11)); // => ok
-    /// assert_eq!(Mp3_8, SoundFormat::from(14)); // => ok
-    /// assert_eq!(DeviceSpecific, SoundFormat::from(15)); // => ok
-    /// assert!(catch_unwind(|| SoundFormat::from(12)).is_err()); // => this will be backtrace.
-    /// assert!(catch_unwind(|| SoundFormat::from(13)).is_err()); // => this is too.
-    /// assert!(catch_unwind(|| SoundFormat::from(16)).is_err()) // => same as above.
+    /// assert_eq!(Speex, SoundFormat::try_from(11).unwrap()); // => ok
+    /// assert_eq!(Mp3_8, SoundFormat::try_from(14).unwrap()); // => ok
+    /// assert_eq!(DeviceSpecific, SoundFormat::try_from(15).unwrap()); // => ok
+    /// assert!(SoundFormat::try_from(12).is_err()); // => this will be an error.
+    /// assert!(SoundFormat::try_from(13).is_err()); // => this is too.
+    /// assert!(SoundFormat::try_from(16).is_err()) // => same as above.
     /// ```
-    fn from(sound_format: u8) -> Self {
+    ///
+    /// [`UnknownSoundFormat`]: super::UnknownSoundFormat
+    fn try_from(sound_format: u8) -> Result<Self, Self::Error> {
         use SoundFormat::*;
 
         match sound_format {
-            0 => LinearPcmNe,
-            1 => AdPcm,
-            2 => Mp3,
-            3 => LinearPcmLe,
-            4 => NellyMoserMono16,
-            5 => NellyMoserMono8,
-            6 => NellyMoserStereo,
-            7 => G711A,
-            8 => G711Mu,
-            9 => Reserved,
-            10 => Aac,
-            11 => Speex,
-            14 => Mp3_8,
-            15 => DeviceSpecific,
-            _ => unreachable!("SoundFormat")
+            0 => Ok(LinearPcmNe),
+            1 => Ok(AdPcm),
+            2 => Ok(Mp3),
+            3 => Ok(LinearPcmLe),
+            4 => Ok(NellyMoserMono16),
+            5 => Ok(NellyMoserMono8),
+            6 => Ok(NellyMoserStereo),
+            7 => Ok(G711A),
+            8 => Ok(G711Mu),
+            9 => Ok(ExHeader),
+            10 => Ok(Aac),
+            11 => Ok(Speex),
+            14 => Ok(Mp3_8),
+            15 => Ok(DeviceSpecific),
+            _ => Err(unknown_sound_format(sound_format))
         }
     }
 }
@@ -128,37 +148,42 @@ pub enum SoundRate {
     FourtyFour
 }
 
-impl From<u8> for SoundRate {
+impl TryFrom<u8> for SoundRate {
+    type Error = IOError;
+
     /// Converts a SoundRate field into a variant.
     ///
-    /// # Panics
+    /// # Errors
+    ///
+    /// * [`UnknownSoundRate`]
     ///
-    /// Because of FLV specification, this is implemented in such a way as to emit a panic when is passed any value above 3.
+    /// Because of FLV specification, this returns an error when is passed any value above 3.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use std::panic::catch_unwind;
     /// use sheave_core::flv::tags::{
     ///     SoundRate,
     ///     SoundRate::*
     /// };
     ///
-    /// assert_eq!(FivePointFive, SoundRate::from(0)); // => ok
-    /// assert_eq!(Eleven, SoundRate::from(1)); // => ok
-    /// assert_eq!(TwentyTwo, SoundRate::from(2)); // => ok
-    /// assert_eq!(FourtyFour, SoundRate::from(3)); // => ok
-    /// assert!(catch_unwind(|| SoundRate::from(4)).is_err()) // => this will be backtrace.
+    /// assert_eq!(FivePointFive, SoundRate::try_from(0).unwrap()); // => ok
+    /// assert_eq!(Eleven, SoundRate::try_from(1).unwrap()); // => ok
+    /// assert_eq!(TwentyTwo, SoundRate::try_from(2).unwrap()); // => ok
+    /// assert_eq!(FourtyFour, SoundRate::try_from(3).unwrap()); // => ok
+    /// assert!(SoundRate::try_from(4).is_err()) // => this will be an error.
     /// ```
-    fn from(sound_rate: u8) -> Self {
+    ///
+    /// [`UnknownSoundRate`]: super::UnknownSoundRate
+    fn try_from(sound_rate: u8) -> Result<Self, Self::Error> {
         use SoundRate::*;
 
         match sound_rate {
-            0 => FivePointFive,
-            1 => Eleven,
-            2 => TwentyTwo,
-            3 => FourtyFour,
-            _ => unreachable!("SoundRate.")
+            0 => Ok(FivePointFive),
+            1 => Ok(Eleven),
+            2 => Ok(TwentyTwo),
+            3 => Ok(FourtyFour),
+            _ => Err(unknown_sound_rate(sound_rate))
         }
     }
 }
@@ -184,6 +209,33 @@ impl AudioTagHeader {
     pub fn new(sound_format: SoundFormat, sound_rate: SoundRate, is_sixteen_bits: bool, is_stereo: bool, is_aac_raw: Option<bool>) -> Self {
         Self { sound_format, sound_rate, is_sixteen_bits, is_stereo, is_aac_raw }
     }
+
+    /// Gets the SoundFormat field.
+    pub fn get_sound_format(&self) -> SoundFormat {
+        self.sound_format
+    }
+
+    /// Gets the SoundRate field.
+    pub fn get_sound_rate(&self) -> SoundRate {
+        self.sound_rate
+    }
+
+    /// Indicates whether samples are 16 bits.
+    pub fn is_sixteen_bits(&self) -> bool {
+        self.is_sixteen_bits
+    }
+
+    /// Indicates whether samples are stereo.
+    pub fn is_stereo(&self) -> bool {
+        self.is_stereo
+    }
+
+    /// Indicates whether AAC data are raw frames rather than the sequence header.
+    ///
+    /// Note this returns `None` unless the sound format is the AAC.
+    pub fn is_aac_raw(&self) -> Option<bool> {
+        self.is_aac_raw
+    }
 }
 
 impl Decoder<AudioTagHeader> for ByteBuffer {
@@ -195,6 +247,10 @@ impl Decoder<AudioTagHeader> for ByteBuffer {
     ///
     /// When some field misses.
     ///
+    /// * [`UnknownSoundFormat`]
+    ///
+    /// When the SoundFormat field is either 12 or 13.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -209,14 +265,19 @@ impl Decoder<AudioTagHeader> for ByteBuffer {
     /// assert!(Decoder::<AudioTagHeader>::decode(&mut buffer).is_ok());
     ///
     /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<AudioTagHeader>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0xc0);
     /// assert!(Decoder::<AudioTagHeader>::decode(&mut buffer).is_err())
     /// ```
     ///
     /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownSoundFormat`]: super::UnknownSoundFormat
     fn decode(&mut self) -> IOResult<AudioTagHeader> {
         let byte = self.get_u8()?;
-        let sound_format: SoundFormat = ((byte & 0xf0) >> 4).into();
-        let sound_rate: SoundRate = ((byte & 0x0c) >> 2).into();
+        let sound_format: SoundFormat = ((byte & 0xf0) >> 4).try_into()?;
+        let sound_rate: SoundRate = ((byte & 0x0c) >> 2).try_into()?;
         let is_sixteen_bits = ((byte & 0x02) >> 1) == 1;
         let is_stereo = (byte & 0x01) == 1;
 
@@ -246,17 +307,622 @@ impl Encoder<AudioTagHeader> for ByteBuffer {
     }
 }
 
+/// Representation of the AudioPacketType field of the ExAudioTagHeader.
+///
+/// Variants correspond to respectively following numbers:
+///
+/// |Variant|Number|
+/// | :- | :- |
+/// |`SequenceStart`|`0`|
+/// |`CodedFrames`|`1`|
+/// |`SequenceEnd`|`2`|
+/// |`MultichannelConfig`|`4`|
+/// |`Multitrack`|`5`|
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioPacketType {
+    SequenceStart,
+    CodedFrames,
+    SequenceEnd,
+    MultichannelConfig = 4,
+    Multitrack
+}
+
+impl TryFrom<u8> for AudioPacketType {
+    type Error = IOError;
+
+    /// Converts an AudioPacketType field into a variant.
+    ///
+    /// # Errors
+    ///
+    /// * [`UnknownAudioPacketType`]
+    ///
+    /// When is passed either 3 or any value above 5.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     AudioPacketType,
+    ///     AudioPacketType::*
+    /// };
+    ///
+    /// assert_eq!(SequenceStart, AudioPacketType::try_from(0).unwrap()); // => ok
+    /// assert_eq!(MultichannelConfig, AudioPacketType::try_from(4).unwrap()); // => ok
+    /// assert_eq!(Multitrack, AudioPacketType::try_from(5).unwrap()); // => ok
+    /// assert!(AudioPacketType::try_from(3).is_err()) // => this will be an error.
+    /// ```
+    ///
+    /// [`UnknownAudioPacketType`]: super::UnknownAudioPacketType
+    fn try_from(audio_packet_type: u8) -> Result<Self, Self::Error> {
+        use AudioPacketType::*;
+
+        match audio_packet_type {
+            0 => Ok(SequenceStart),
+            1 => Ok(CodedFrames),
+            2 => Ok(SequenceEnd),
+            4 => Ok(MultichannelConfig),
+            5 => Ok(Multitrack),
+            _ => Err(unknown_audio_packet_type(audio_packet_type))
+        }
+    }
+}
+
+impl From<AudioPacketType> for u8 {
+    fn from(audio_packet_type: AudioPacketType) -> Self {
+        audio_packet_type as u8
+    }
+}
+
+/// Representation of the AudioFourCc field of the ExAudioTagHeader.
+///
+/// Variants correspond to respectively following FourCCs:
+///
+/// |Variant|FourCC|
+/// | :- | :- |
+/// |`Ac3`|`ac-3`|
+/// |`Eac3`|`ec-3`|
+/// |`Opus`|`Opus`|
+/// |`Mp3`|`.mp3`|
+/// |`Flac`|`fLaC`|
+/// |`Aac`|`mp4a`|
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFourCc {
+    Ac3,
+    Eac3,
+    Opus,
+    Mp3,
+    Flac,
+    Aac
+}
+
+impl TryFrom<[u8; 4]> for AudioFourCc {
+    type Error = IOError;
+
+    /// Converts a FourCC into a variant.
+    ///
+    /// # Errors
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When is passed any FourCC of unsupported codecs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::AudioFourCc;
+    ///
+    /// assert_eq!(AudioFourCc::Opus, AudioFourCc::try_from(*b"Opus").unwrap()); // => ok
+    /// assert_eq!(AudioFourCc::Flac, AudioFourCc::try_from(*b"fLaC").unwrap()); // => ok
+    /// assert!(AudioFourCc::try_from(*b"hvc1").is_err()) // => this will be an error.
+    /// ```
+    ///
+    /// [`UnknownFourCc`]: super::UnknownFourCc
+    fn try_from(four_cc: [u8; 4]) -> Result<Self, Self::Error> {
+        use AudioFourCc::*;
+
+        match &four_cc {
+            b"ac-3" => Ok(Ac3),
+            b"ec-3" => Ok(Eac3),
+            b"Opus" => Ok(Opus),
+            b".mp3" => Ok(Mp3),
+            b"fLaC" => Ok(Flac),
+            b"mp4a" => Ok(Aac),
+            _ => Err(unknown_four_cc(four_cc))
+        }
+    }
+}
+
+impl From<AudioFourCc> for [u8; 4] {
+    fn from(four_cc: AudioFourCc) -> Self {
+        use AudioFourCc::*;
+
+        match four_cc {
+            Ac3 => *b"ac-3",
+            Eac3 => *b"ec-3",
+            Opus => *b"Opus",
+            Mp3 => *b".mp3",
+            Flac => *b"fLaC",
+            Aac => *b"mp4a"
+        }
+    }
+}
+
+/// The ExAudioTagHeader of Enhanced RTMP.
+///
+/// This is distinguished from the legacy AudioTagHeader by the SoundFormat field which is 9.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |SoundFormat|4 bits (fixed to `9`)|
+/// |AudioPacketType|4 bits|
+/// |AvMultitrackType|4 bits if the AudioPacketType is `Multitrack`. Then the actual AudioPacketType follows in 4 bits.|
+/// |AudioFourCc|32 bits. However the `ManyTracksManyCodecs` Multitrack packet has this in every track instead.|
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExAudioTagHeader {
+    packet_type: AudioPacketType,
+    multitrack_type: Option<AvMultitrackType>,
+    four_cc: Option<AudioFourCc>
+}
+
+impl ExAudioTagHeader {
+    /// Constructs an ExAudioTagHeader.
+    pub fn new(packet_type: AudioPacketType, four_cc: AudioFourCc) -> Self {
+        Self {
+            packet_type,
+            multitrack_type: None,
+            four_cc: Some(four_cc)
+        }
+    }
+
+    /// Constructs an ExAudioTagHeader of the Multitrack packet.
+    ///
+    /// The `four_cc` is shared with all tracks, so it is ignored for `ManyTracksManyCodecs`.
+    /// Note the `packet_type` is the one which is applied to all tracks, rather than `Multitrack` itself.
+    pub fn new_multitrack(packet_type: AudioPacketType, multitrack_type: AvMultitrackType, four_cc: Option<AudioFourCc>) -> Self {
+        let four_cc = if multitrack_type == AvMultitrackType::ManyTracksManyCodecs {
+            None
+        } else {
+            four_cc
+        };
+
+        Self {
+            packet_type,
+            multitrack_type: Some(multitrack_type),
+            four_cc
+        }
+    }
+
+    /// Gets the AudioPacketType field.
+    ///
+    /// Note this returns the packet type which is applied to all tracks if this is the Multitrack packet.
+    pub fn get_packet_type(&self) -> AudioPacketType {
+        self.packet_type
+    }
+
+    /// Gets the AvMultitrackType field.
+    ///
+    /// Note this returns `None` unless this is the Multitrack packet.
+    pub fn get_multitrack_type(&self) -> Option<AvMultitrackType> {
+        self.multitrack_type
+    }
+
+    /// Gets the AudioFourCc field.
+    ///
+    /// Note this returns `None` if this is the `ManyTracksManyCodecs` Multitrack packet.
+    pub fn get_four_cc(&self) -> Option<AudioFourCc> {
+        self.four_cc
+    }
+}
+
+impl Decoder<ExAudioTagHeader> for ByteBuffer {
+    /// Decodes bytes into an ExAudioTagHeader.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// * [`UnknownAudioPacketType`]
+    ///
+    /// When the AudioPacketType field is either 3 or above 5, or the Multitrack packet nests another Multitrack packet.
+    ///
+    /// * [`UnknownAvMultitrackType`]
+    ///
+    /// When the AvMultitrackType field is above 2.
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When the AudioFourCc field is none of `ac-3`, `ec-3`, `Opus`, `.mp3`, `fLaC` and `mp4a`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::{
+    ///         AudioFourCc,
+    ///         AudioPacketType,
+    ///         ExAudioTagHeader
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0x91);
+    /// buffer.put_bytes(b"Opus");
+    /// let header: ExAudioTagHeader = buffer.decode().unwrap();
+    /// assert_eq!(AudioPacketType::CodedFrames, header.get_packet_type());
+    /// assert_eq!(Some(AudioFourCc::Opus), header.get_four_cc());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0x90);
+    /// buffer.put_bytes(b"xxxx");
+    /// assert!(Decoder::<ExAudioTagHeader>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownAudioPacketType`]: super::UnknownAudioPacketType
+    /// [`UnknownAvMultitrackType`]: super::UnknownAvMultitrackType
+    /// [`UnknownFourCc`]: super::UnknownFourCc
+    fn decode(&mut self) -> IOResult<ExAudioTagHeader> {
+        let byte = self.get_u8()?;
+        let packet_type: AudioPacketType = (byte & 0x0f).try_into()?;
+        let (packet_type, multitrack_type) = if packet_type == AudioPacketType::Multitrack {
+            let byte = self.get_u8()?;
+            let multitrack_type: AvMultitrackType = (byte >> 4).try_into()?;
+            let packet_type: AudioPacketType = (byte & 0x0f).try_into()?;
+            if packet_type == AudioPacketType::Multitrack {
+                return Err(unknown_audio_packet_type(packet_type.into()))
+            }
+            (packet_type, Some(multitrack_type))
+        } else {
+            (packet_type, None)
+        };
+
+        let four_cc = if multitrack_type == Some(AvMultitrackType::ManyTracksManyCodecs) {
+            None
+        } else {
+            let mut four_cc_bytes: [u8; 4] = [0; 4];
+            four_cc_bytes.copy_from_slice(self.get_bytes(4)?);
+            Some(AudioFourCc::try_from(four_cc_bytes)?)
+        };
+
+        Ok(ExAudioTagHeader { packet_type, multitrack_type, four_cc })
+    }
+}
+
+impl Encoder<ExAudioTagHeader> for ByteBuffer {
+    /// Encodes an ExAudioTagHeader into bytes.
+    fn encode(&mut self, ex_audio_tag_header: &ExAudioTagHeader) {
+        let mut byte = u8::from(SoundFormat::ExHeader) << 4;
+        if let Some(multitrack_type) = ex_audio_tag_header.multitrack_type {
+            byte |= u8::from(AudioPacketType::Multitrack);
+            self.put_u8(byte);
+            self.put_u8((u8::from(multitrack_type) << 4) | u8::from(ex_audio_tag_header.packet_type));
+        } else {
+            byte |= u8::from(ex_audio_tag_header.packet_type);
+            self.put_u8(byte);
+        }
+        if let Some(four_cc) = ex_audio_tag_header.four_cc {
+            self.put_bytes(&<[u8; 4]>::from(four_cc));
+        }
+    }
+}
+
+/// Either the legacy AudioTagHeader or the ExAudioTagHeader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioHeader {
+    Legacy(AudioTagHeader),
+    Ex(ExAudioTagHeader)
+}
+
+impl From<AudioTagHeader> for AudioHeader {
+    fn from(audio_tag_header: AudioTagHeader) -> Self {
+        Self::Legacy(audio_tag_header)
+    }
+}
+
+impl From<ExAudioTagHeader> for AudioHeader {
+    fn from(ex_audio_tag_header: ExAudioTagHeader) -> Self {
+        Self::Ex(ex_audio_tag_header)
+    }
+}
+
+impl Decoder<AudioHeader> for ByteBuffer {
+    /// Decodes bytes into either the legacy AudioTagHeader or the ExAudioTagHeader.
+    ///
+    /// Which header is decoded is decided by the SoundFormat field.
+    ///
+    /// # Errors
+    ///
+    /// Same as either [`AudioTagHeader`] or [`ExAudioTagHeader`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::AudioHeader
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0xaf);
+    /// buffer.put_u8(0x01);
+    /// assert!(matches!(Decoder::<AudioHeader>::decode(&mut buffer).unwrap(), AudioHeader::Legacy(_)));
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0x90);
+    /// buffer.put_bytes(b"fLaC");
+    /// assert!(matches!(Decoder::<AudioHeader>::decode(&mut buffer).unwrap(), AudioHeader::Ex(_)))
+    /// ```
+    fn decode(&mut self) -> IOResult<AudioHeader> {
+        if SoundFormat::try_from(self.peek_u8()? >> 4)?.is_ex_header() {
+            Decoder::<ExAudioTagHeader>::decode(self).map(AudioHeader::Ex)
+        } else {
+            Decoder::<AudioTagHeader>::decode(self).map(AudioHeader::Legacy)
+        }
+    }
+}
+
+impl Encoder<AudioHeader> for ByteBuffer {
+    /// Encodes either the legacy AudioTagHeader or the ExAudioTagHeader into bytes.
+    fn encode(&mut self, audio_header: &AudioHeader) {
+        match audio_header {
+            AudioHeader::Legacy(header) => self.encode(header),
+            AudioHeader::Ex(header) => self.encode(header)
+        }
+    }
+}
+
+/// A track in the Multitrack packet.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |AudioFourCc|32 bits only in `ManyTracksManyCodecs`.|
+/// |TrackId|8 bits|
+/// |SizeOfAudioTrack|24 bits except `OneTrack`.|
+/// |AudioData|Same as SizeOfAudioTrack, otherwise the rest of the tag.|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioTrack {
+    track_id: u8,
+    four_cc: AudioFourCc,
+    data: Vec<u8>
+}
+
+impl AudioTrack {
+    /// Constructs an AudioTrack.
+    pub fn new(track_id: u8, four_cc: AudioFourCc, data: Vec<u8>) -> Self {
+        Self { track_id, four_cc, data }
+    }
+
+    /// Gets the track ID.
+    pub fn get_track_id(&self) -> u8 {
+        self.track_id
+    }
+
+    /// Gets the codec of this track.
+    pub fn get_four_cc(&self) -> AudioFourCc {
+        self.four_cc
+    }
+
+    /// Gets the audio data of this track.
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
 /// The audio data format.
+///
+/// Its header is either the legacy AudioTagHeader or the ExAudioTagHeader of Enhanced RTMP.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AudioTag {
-    header: AudioTagHeader,
+    header: AudioHeader,
     body: Vec<u8>
 }
 
 impl AudioTag {
     /// Constructs a AudioTag.
-    pub fn new(header: AudioTagHeader, body: Vec<u8>) -> Self {
-        Self { header, body }
+    pub fn new<H: Into<AudioHeader>>(header: H, body: Vec<u8>) -> Self {
+        Self { header: header.into(), body }
+    }
+
+    /// Constructs an AudioTag of the Multitrack packet.
+    ///
+    /// The AvMultitrackType is chosen from tracks, that is:
+    ///
+    /// * `OneTrack` if there is just one track.
+    /// * `ManyTracks` if all tracks have same codec.
+    /// * `ManyTracksManyCodecs` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     AudioFourCc,
+    ///     AudioHeader,
+    ///     AudioPacketType,
+    ///     AudioTag,
+    ///     AudioTrack,
+    ///     AvMultitrackType
+    /// };
+    ///
+    /// let tracks = vec![
+    ///     AudioTrack::new(0, AudioFourCc::Opus, vec![0; 8]),
+    ///     AudioTrack::new(1, AudioFourCc::Opus, vec![1; 4])
+    /// ];
+    /// let audio_tag = AudioTag::new_multitrack(AudioPacketType::CodedFrames, tracks.clone());
+    /// let AudioHeader::Ex(header) = audio_tag.get_header() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(Some(AvMultitrackType::ManyTracks), header.get_multitrack_type());
+    /// assert_eq!(tracks, audio_tag.get_tracks().unwrap())
+    /// ```
+    pub fn new_multitrack(packet_type: AudioPacketType, tracks: Vec<AudioTrack>) -> Self {
+        let four_cc = tracks.first().map(|track| track.four_cc);
+        let multitrack_type = if tracks.len() == 1 {
+            AvMultitrackType::OneTrack
+        } else if four_cc.is_some() && tracks.iter().all(|track| Some(track.four_cc) == four_cc) {
+            AvMultitrackType::ManyTracks
+        } else {
+            AvMultitrackType::ManyTracksManyCodecs
+        };
+        let header = ExAudioTagHeader::new_multitrack(packet_type, multitrack_type, four_cc);
+
+        let mut buffer = ByteBuffer::default();
+        for track in tracks {
+            if multitrack_type == AvMultitrackType::ManyTracksManyCodecs {
+                buffer.put_bytes(&<[u8; 4]>::from(track.four_cc));
+            }
+            buffer.put_u8(track.track_id);
+            if multitrack_type != AvMultitrackType::OneTrack {
+                buffer.put_u24_be(track.data.len() as u32);
+            }
+            buffer.put_bytes(&track.data);
+        }
+
+        Self { header: header.into(), body: buffer.into() }
+    }
+
+    /// Gets the header.
+    pub fn get_header(&self) -> AudioHeader {
+        self.header
+    }
+
+    /// Gets the audio data after the header.
+    pub fn get_body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Decodes the body as the multichannel configuration.
+    ///
+    /// Note this returns `None` unless this is the MultichannelConfig packet.
+    ///
+    /// # Errors
+    ///
+    /// When the body isn't a multichannel configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     AudioChannelOrder,
+    ///     AudioFourCc,
+    ///     AudioPacketType,
+    ///     AudioTag,
+    ///     ExAudioTagHeader
+    /// };
+    ///
+    /// let header = ExAudioTagHeader::new(AudioPacketType::MultichannelConfig, AudioFourCc::Opus);
+    /// let audio_tag = AudioTag::new(header, vec![0x00, 0x06]);
+    /// let multichannel_config = audio_tag.get_multichannel_config().unwrap().unwrap();
+    /// assert_eq!(6, multichannel_config.get_channel_count());
+    /// assert_eq!(&AudioChannelOrder::Unspecified, multichannel_config.get_channel_order());
+    ///
+    /// let header = ExAudioTagHeader::new(AudioPacketType::CodedFrames, AudioFourCc::Opus);
+    /// let audio_tag = AudioTag::new(header, Vec::new());
+    /// assert!(audio_tag.get_multichannel_config().unwrap().is_none())
+    /// ```
+    pub fn get_multichannel_config(&self) -> IOResult<Option<MultichannelConfig>> {
+        match self.header {
+            AudioHeader::Ex(header) if header.packet_type == AudioPacketType::MultichannelConfig => {
+                let mut buffer: ByteBuffer = self.body.clone().into();
+                buffer.decode().map(Some)
+            },
+            _ => Ok(None)
+        }
+    }
+
+    /// Gets tracks in this tag.
+    ///
+    /// The ExAudioTagHeader without the Multitrack packet is regarded as the track whose ID is 0.
+    /// Note this returns an empty vector if this has the legacy AudioTagHeader.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some track is shorter than its size.
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When some track of `ManyTracksManyCodecs` has an unsupported codec.
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownFourCc`]: super::UnknownFourCc
+    pub fn get_tracks(&self) -> IOResult<Vec<AudioTrack>> {
+        let AudioHeader::Ex(header) = self.header else {
+            return Ok(Vec::new())
+        };
+        let Some(multitrack_type) = header.multitrack_type else {
+            return Ok(header.four_cc.map(|four_cc| AudioTrack::new(0, four_cc, self.body.clone())).into_iter().collect())
+        };
+
+        let mut buffer: ByteBuffer = self.body.clone().into();
+        let mut tracks: Vec<AudioTrack> = Vec::new();
+        while buffer.remained() > 0 {
+            let four_cc = match header.four_cc {
+                Some(four_cc) => four_cc,
+                None => {
+                    let mut four_cc_bytes: [u8; 4] = [0; 4];
+                    four_cc_bytes.copy_from_slice(buffer.get_bytes(4)?);
+                    AudioFourCc::try_from(four_cc_bytes)?
+                }
+            };
+            let track_id = buffer.get_u8()?;
+            let track_size = if multitrack_type == AvMultitrackType::OneTrack {
+                buffer.remained()
+            } else {
+                buffer.get_u24_be()? as usize
+            };
+            let data = buffer.get_bytes(track_size)?.to_vec();
+            tracks.push(AudioTrack { track_id, four_cc, data });
+        }
+
+        Ok(tracks)
+    }
+
+    /// Extracts the specified track from this tag.
+    ///
+    /// If this is the Multitrack packet, the track is kept as the `OneTrack` Multitrack packet with its ID.
+    /// Otherwise this is regarded as the track whose ID is 0.
+    /// This returns `None` if this tag doesn't contain the track.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`get_tracks`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     AudioFourCc,
+    ///     AudioPacketType,
+    ///     AudioTag,
+    ///     AudioTrack
+    /// };
+    ///
+    /// let tracks = vec![
+    ///     AudioTrack::new(1, AudioFourCc::Opus, vec![1; 4]),
+    ///     AudioTrack::new(2, AudioFourCc::Aac, vec![2; 4])
+    /// ];
+    /// let audio_tag = AudioTag::new_multitrack(AudioPacketType::CodedFrames, tracks.clone());
+    /// let selected = audio_tag.select_track(2).unwrap().unwrap();
+    /// assert_eq!(&tracks[1..], selected.get_tracks().unwrap());
+    /// assert!(audio_tag.select_track(0).unwrap().is_none())
+    /// ```
+    ///
+    /// [`get_tracks`]: AudioTag::get_tracks
+    pub fn select_track(&self, track_id: u8) -> IOResult<Option<AudioTag>> {
+        match self.header {
+            AudioHeader::Ex(header) if header.multitrack_type.is_some() => {
+                let track = self.get_tracks()?.into_iter().find(|track| track.track_id == track_id);
+                Ok(track.map(|track| AudioTag::new_multitrack(header.packet_type, vec![track])))
+            },
+            _ => Ok((track_id == 0).then(|| self.clone()))
+        }
     }
 }
 
@@ -269,6 +935,22 @@ impl Decoder<AudioTag> for ByteBuffer {
     ///
     /// When some field misses.
     ///
+    /// * [`UnknownSoundFormat`]
+    ///
+    /// When the SoundFormat field is either 12 or 13.
+    ///
+    /// * [`UnknownAudioPacketType`]
+    ///
+    /// When the header is the ExAudioTagHeader and its AudioPacketType field is either 3 or above 5.
+    ///
+    /// * [`UnknownAvMultitrackType`]
+    ///
+    /// When the header is the ExAudioTagHeader and its AvMultitrackType field is above 2.
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When the header is the ExAudioTagHeader and its codec is unsupported.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -297,8 +979,12 @@ impl Decoder<AudioTag> for ByteBuffer {
     /// ```
     ///
     /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownSoundFormat`]: super::UnknownSoundFormat
+    /// [`UnknownAudioPacketType`]: super::UnknownAudioPacketType
+    /// [`UnknownAvMultitrackType`]: super::UnknownAvMultitrackType
+    /// [`UnknownFourCc`]: super::UnknownFourCc
     fn decode(&mut self) -> IOResult<AudioTag> {
-        let header: AudioTagHeader = self.decode()?;
+        let header: AudioHeader = self.decode()?;
         let remained = self.remained();
         let body = self.get_bytes(remained)?.to_vec();
 
@@ -374,4 +1060,60 @@ mod tests {
         let actual_data: Vec<u8> = buffer.into();
         assert_eq!(expected_data.as_slice(), actual_data)
     }
+    #[test]
+    fn decode_ex_audio_tag() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8((u8::from(SoundFormat::ExHeader) << 4) | u8::from(AudioPacketType::CodedFrames));
+        buffer.put_bytes(b"ec-3");
+        let mut data: [u8; 128] = [0; 128];
+        fill(&mut data);
+        buffer.put_bytes(&data);
+
+        let audio_tag: AudioTag = buffer.decode().unwrap();
+        assert_eq!(AudioHeader::Ex(ExAudioTagHeader::new(AudioPacketType::CodedFrames, AudioFourCc::Eac3)), audio_tag.get_header());
+        assert_eq!(data.as_slice(), audio_tag.get_body())
+    }
+
+    #[test]
+    fn decode_multitrack_audio_tag() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8((u8::from(SoundFormat::ExHeader) << 4) | u8::from(AudioPacketType::Multitrack));
+        buffer.put_u8((u8::from(AvMultitrackType::ManyTracksManyCodecs) << 4) | u8::from(AudioPacketType::CodedFrames));
+        buffer.put_bytes(b"Opus");
+        buffer.put_u8(0);
+        buffer.put_u24_be(4);
+        buffer.put_bytes(&[0; 4]);
+        buffer.put_bytes(b"mp4a");
+        buffer.put_u8(1);
+        buffer.put_u24_be(2);
+        buffer.put_bytes(&[1; 2]);
+
+        let audio_tag: AudioTag = buffer.decode().unwrap();
+        let AudioHeader::Ex(header) = audio_tag.get_header() else {
+            panic!("ExAudioTagHeader didn't get decoded.")
+        };
+        assert_eq!(AudioPacketType::CodedFrames, header.get_packet_type());
+        assert_eq!(Some(AvMultitrackType::ManyTracksManyCodecs), header.get_multitrack_type());
+        assert!(header.get_four_cc().is_none());
+
+        let expected = vec![
+            AudioTrack::new(0, AudioFourCc::Opus, vec![0; 4]),
+            AudioTrack::new(1, AudioFourCc::Aac, vec![1; 2])
+        ];
+        assert_eq!(expected, audio_tag.get_tracks().unwrap());
+        assert_eq!(audio_tag, AudioTag::new_multitrack(AudioPacketType::CodedFrames, expected))
+    }
+
+    #[test]
+    fn round_trip_multichannel_config() {
+        let expected = MultichannelConfig::new(
+            0,
+            AudioChannelOrder::Custom(vec![AudioChannel::FrontLeft, AudioChannel::FrontRight, AudioChannel::LowFrequency1])
+        );
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&expected);
+        let bytes: Vec<u8> = buffer.clone().into();
+        assert_eq!(vec![0x02, 0x03, 0x00, 0x01, 0x03], bytes);
+        assert_eq!(expected, Decoder::<MultichannelConfig>::decode(&mut buffer).unwrap())
+    }
 }
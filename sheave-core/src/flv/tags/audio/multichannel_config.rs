@@ -0,0 +1,264 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    flv::tags::unknown_audio_channel_order
+};
+
+/// Representation of speaker positions in the multichannel configuration.
+///
+/// Variants correspond to respectively following numbers:
+///
+/// |Variant|Number|
+/// | :- | :- |
+/// |`FrontLeft`|`0`|
+/// |`FrontRight`|`1`|
+/// |`FrontCenter`|`2`|
+/// |`LowFrequency1`|`3`|
+/// |`BackLeft`|`4`|
+/// |`BackRight`|`5`|
+/// |`FrontLeftCenter`|`6`|
+/// |`FrontRightCenter`|`7`|
+/// |`BackCenter`|`8`|
+/// |`SideLeft`|`9`|
+/// |`SideRight`|`10`|
+/// |`TopCenter`|`11`|
+/// |`TopFrontLeft`|`12`|
+/// |`TopFrontCenter`|`13`|
+/// |`TopFrontRight`|`14`|
+/// |`TopBackLeft`|`15`|
+/// |`TopBackCenter`|`16`|
+/// |`TopBackRight`|`17`|
+/// |`LowFrequency2`|`18`|
+/// |`TopSideLeft`|`19`|
+/// |`TopSideRight`|`20`|
+/// |`BottomFrontCenter`|`21`|
+/// |`BottomFrontLeft`|`22`|
+/// |`BottomFrontRight`|`23`|
+/// |`Unused`|`254`|
+/// |`Unknown`|other numbers|
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioChannel {
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    LowFrequency1,
+    BackLeft,
+    BackRight,
+    FrontLeftCenter,
+    FrontRightCenter,
+    BackCenter,
+    SideLeft,
+    SideRight,
+    TopCenter,
+    TopFrontLeft,
+    TopFrontCenter,
+    TopFrontRight,
+    TopBackLeft,
+    TopBackCenter,
+    TopBackRight,
+    LowFrequency2,
+    TopSideLeft,
+    TopSideRight,
+    BottomFrontCenter,
+    BottomFrontLeft,
+    BottomFrontRight,
+    Unused = 0xfe,
+    Unknown = 0xff
+}
+
+impl AudioChannel {
+    /// Gets the bit of this speaker in the AudioChannelFlags field.
+    ///
+    /// Note `Unused` and `Unknown` have no bit, that is, this returns 0 for them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::AudioChannel;
+    ///
+    /// assert_eq!(0x000001, AudioChannel::FrontLeft.to_mask());
+    /// assert_eq!(0x800000, AudioChannel::BottomFrontRight.to_mask());
+    /// assert_eq!(0, AudioChannel::Unknown.to_mask())
+    /// ```
+    pub fn to_mask(&self) -> u32 {
+        match *self {
+            AudioChannel::Unused | AudioChannel::Unknown => 0,
+            audio_channel => 1 << (audio_channel as u8)
+        }
+    }
+}
+
+impl From<u8> for AudioChannel {
+    fn from(audio_channel: u8) -> Self {
+        use AudioChannel::*;
+
+        match audio_channel {
+            0 => FrontLeft,
+            1 => FrontRight,
+            2 => FrontCenter,
+            3 => LowFrequency1,
+            4 => BackLeft,
+            5 => BackRight,
+            6 => FrontLeftCenter,
+            7 => FrontRightCenter,
+            8 => BackCenter,
+            9 => SideLeft,
+            10 => SideRight,
+            11 => TopCenter,
+            12 => TopFrontLeft,
+            13 => TopFrontCenter,
+            14 => TopFrontRight,
+            15 => TopBackLeft,
+            16 => TopBackCenter,
+            17 => TopBackRight,
+            18 => LowFrequency2,
+            19 => TopSideLeft,
+            20 => TopSideRight,
+            21 => BottomFrontCenter,
+            22 => BottomFrontLeft,
+            23 => BottomFrontRight,
+            0xfe => Unused,
+            _ => Unknown
+        }
+    }
+}
+
+impl From<AudioChannel> for u8 {
+    fn from(audio_channel: AudioChannel) -> Self {
+        audio_channel as u8
+    }
+}
+
+/// Representation of the AudioChannelOrder field and the channel layout which follows it.
+///
+/// |Variant|Number|Following Field|
+/// | :- | :- | :- |
+/// |`Unspecified`|`0`|Nothing.|
+/// |`Native`|`1`|AudioChannelFlags (32 bits) which are combined [`AudioChannel`] bits.|
+/// |`Custom`|`2`|AudioChannelMapping ([`AudioChannel`] per channel)|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioChannelOrder {
+    Unspecified,
+    Native(u32),
+    Custom(Vec<AudioChannel>)
+}
+
+impl AudioChannelOrder {
+    fn to_u8(&self) -> u8 {
+        match self {
+            AudioChannelOrder::Unspecified => 0,
+            AudioChannelOrder::Native(_) => 1,
+            AudioChannelOrder::Custom(_) => 2
+        }
+    }
+}
+
+/// The body of the MultichannelConfig packet.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |AudioChannelOrder|8 bits|
+/// |ChannelCount|8 bits|
+/// |AudioChannelMapping|8 bits per channel if the AudioChannelOrder is Custom.|
+/// |AudioChannelFlags|32 bits if the AudioChannelOrder is Native.|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultichannelConfig {
+    channel_count: u8,
+    channel_order: AudioChannelOrder
+}
+
+impl MultichannelConfig {
+    /// Constructs a MultichannelConfig.
+    ///
+    /// Note the channel count of the Custom order is the length of its mapping.
+    pub fn new(channel_count: u8, channel_order: AudioChannelOrder) -> Self {
+        let channel_count = match &channel_order {
+            AudioChannelOrder::Custom(mapping) => mapping.len() as u8,
+            _ => channel_count
+        };
+
+        Self { channel_count, channel_order }
+    }
+
+    /// Gets the channel count.
+    pub fn get_channel_count(&self) -> u8 {
+        self.channel_count
+    }
+
+    /// Gets the channel order and its layout.
+    pub fn get_channel_order(&self) -> &AudioChannelOrder {
+        &self.channel_order
+    }
+}
+
+impl Decoder<MultichannelConfig> for ByteBuffer {
+    /// Decodes bytes into a MultichannelConfig.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// * [`UnknownAudioChannelOrder`]
+    ///
+    /// When the AudioChannelOrder field is above 2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::{
+    ///         AudioChannel,
+    ///         AudioChannelOrder,
+    ///         MultichannelConfig
+    ///     }
+    /// };
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x01, 0x02, 0x00, 0x00, 0x00, 0x03].into();
+    /// let multichannel_config: MultichannelConfig = buffer.decode().unwrap();
+    /// assert_eq!(2, multichannel_config.get_channel_count());
+    /// assert_eq!(&AudioChannelOrder::Native(AudioChannel::FrontLeft.to_mask() | AudioChannel::FrontRight.to_mask()), multichannel_config.get_channel_order());
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x03, 0x02].into();
+    /// assert!(Decoder::<MultichannelConfig>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownAudioChannelOrder`]: crate::flv::tags::UnknownAudioChannelOrder
+    fn decode(&mut self) -> IOResult<MultichannelConfig> {
+        let channel_order = self.get_u8()?;
+        let channel_count = self.get_u8()?;
+        let channel_order = match channel_order {
+            0 => AudioChannelOrder::Unspecified,
+            1 => AudioChannelOrder::Native(self.get_u32_be()?),
+            2 => {
+                let mapping = self.get_bytes(channel_count as usize)?.iter().map(|audio_channel| AudioChannel::from(*audio_channel)).collect();
+                AudioChannelOrder::Custom(mapping)
+            },
+            _ => return Err(unknown_audio_channel_order(channel_order))
+        };
+
+        Ok(MultichannelConfig { channel_count, channel_order })
+    }
+}
+
+impl Encoder<MultichannelConfig> for ByteBuffer {
+    /// Encodes a MultichannelConfig into bytes.
+    fn encode(&mut self, multichannel_config: &MultichannelConfig) {
+        self.put_u8(multichannel_config.channel_order.to_u8());
+        self.put_u8(multichannel_config.channel_count);
+        match &multichannel_config.channel_order {
+            AudioChannelOrder::Unspecified => {},
+            AudioChannelOrder::Native(channel_flags) => self.put_u32_be(*channel_flags),
+            AudioChannelOrder::Custom(mapping) => for audio_channel in mapping {
+                self.put_u8((*audio_channel).into());
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        Result as IOResult
+    }
+};
+use bytes::Bytes;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::amf::v0::AmfValue
+};
+use super::{
+    AudioTag,
+    FlvTag,
+    ScriptDataTag,
+    TagType,
+    VideoTag,
+    unknown_av_multitrack_type
+};
+
+/// Representation of the AvMultitrackType field of the Multitrack packet.
+///
+/// Variants correspond to respectively following numbers:
+///
+/// |Variant|Number|
+/// | :- | :- |
+/// |`OneTrack`|`0`|
+/// |`ManyTracks`|`1`|
+/// |`ManyTracksManyCodecs`|`2`|
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvMultitrackType {
+    OneTrack,
+    ManyTracks,
+    ManyTracksManyCodecs
+}
+
+impl TryFrom<u8> for AvMultitrackType {
+    type Error = IOError;
+
+    /// Converts an AvMultitrackType field into a variant.
+    ///
+    /// # Errors
+    ///
+    /// * [`UnknownAvMultitrackType`]
+    ///
+    /// When is passed any value above 2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     AvMultitrackType,
+    ///     AvMultitrackType::*
+    /// };
+    ///
+    /// assert_eq!(OneTrack, AvMultitrackType::try_from(0).unwrap()); // => ok
+    /// assert_eq!(ManyTracks, AvMultitrackType::try_from(1).unwrap()); // => ok
+    /// assert_eq!(ManyTracksManyCodecs, AvMultitrackType::try_from(2).unwrap()); // => ok
+    /// assert!(AvMultitrackType::try_from(3).is_err()) // => this will be an error.
+    /// ```
+    ///
+    /// [`UnknownAvMultitrackType`]: super::UnknownAvMultitrackType
+    fn try_from(av_multitrack_type: u8) -> Result<Self, Self::Error> {
+        use AvMultitrackType::*;
+
+        match av_multitrack_type {
+            0 => Ok(OneTrack),
+            1 => Ok(ManyTracks),
+            2 => Ok(ManyTracksManyCodecs),
+            _ => Err(unknown_av_multitrack_type(av_multitrack_type))
+        }
+    }
+}
+
+impl From<AvMultitrackType> for u8 {
+    fn from(av_multitrack_type: AvMultitrackType) -> Self {
+        av_multitrack_type as u8
+    }
+}
+
+/// The way to specify a track.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackSelector {
+    Id(u8),
+    Name(String)
+}
+
+impl From<&str> for TrackSelector {
+    /// Converts a query value into a selector.
+    ///
+    /// A numeric value is regarded as the track ID, otherwise as the track name.
+    fn from(value: &str) -> Self {
+        value.parse::<u8>().map(TrackSelector::Id).unwrap_or_else(|_| TrackSelector::Name(value.into()))
+    }
+}
+
+impl Display for TrackSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        match self {
+            TrackSelector::Id(track_id) => write!(f, "{track_id}"),
+            TrackSelector::Name(name) => write!(f, "{name}")
+        }
+    }
+}
+
+/// The tracks which a subscriber wants to receive out of Multitrack packets.
+///
+/// This is specified by the query of the stream name in the `play` command, e.g. `"stream?audioTrack=1&videoTrack=main"`.
+/// Every value is either the track ID or the track name.
+/// Track names are resolved with the `"name"` property of every entry in `audioTrackIdInfoMap`/`videoTrackIdInfoMap` of the `onMetaData`.
+/// Note that is a convention of this crate.
+/// The track whose name can't be resolved is regarded as the default one (that is, its ID is 0).
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::flv::tags::TrackSelection;
+///
+/// let (stream_name, track_selection) = TrackSelection::from_stream_name("stream?audioTrack=1&videoTrack=main");
+/// assert_eq!("stream", stream_name);
+/// assert_eq!("audioTrack=1&videoTrack=main", track_selection.to_query());
+///
+/// let (stream_name, track_selection) = TrackSelection::from_stream_name("stream");
+/// assert_eq!("stream", stream_name);
+/// assert!(track_selection.is_empty())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackSelection {
+    audio: Option<TrackSelector>,
+    video: Option<TrackSelector>,
+    audio_track_id: Option<u8>,
+    video_track_id: Option<u8>
+}
+
+impl TrackSelection {
+    /// Constructs a TrackSelection.
+    pub fn new(audio: Option<TrackSelector>, video: Option<TrackSelector>) -> Self {
+        let audio_track_id = audio.as_ref().map(resolve_id);
+        let video_track_id = video.as_ref().map(resolve_id);
+
+        Self { audio, video, audio_track_id, video_track_id }
+    }
+
+    /// Parses a query such as `"audioTrack=1&videoTrack=main"`.
+    ///
+    /// Unknown keys are ignored.
+    pub fn from_query(query: &str) -> Self {
+        let mut audio: Option<TrackSelector> = None;
+        let mut video: Option<TrackSelector> = None;
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            match key {
+                "audioTrack" => audio = Some(value.into()),
+                "videoTrack" => video = Some(value.into()),
+                _ => {}
+            }
+        }
+
+        Self::new(audio, video)
+    }
+
+    /// Splits a stream name into its actual name and the TrackSelection of its query.
+    pub fn from_stream_name(stream_name: &str) -> (&str, Self) {
+        match stream_name.split_once('?') {
+            Some((stream_name, query)) => (stream_name, Self::from_query(query)),
+            None => (stream_name, Self::default())
+        }
+    }
+
+    /// Converts this into the query of the stream name.
+    pub fn to_query(&self) -> String {
+        let mut pairs: Vec<String> = Vec::new();
+        if let Some(ref audio) = self.audio {
+            pairs.push(format!("audioTrack={audio}"));
+        }
+        if let Some(ref video) = self.video {
+            pairs.push(format!("videoTrack={video}"));
+        }
+        pairs.join("&")
+    }
+
+    /// Checks whether no track is specified.
+    pub fn is_empty(&self) -> bool {
+        self.audio.is_none() && self.video.is_none()
+    }
+
+    /// Gets the resolved audio track ID.
+    pub fn get_audio_track_id(&self) -> Option<u8> {
+        self.audio_track_id
+    }
+
+    /// Gets the resolved video track ID.
+    pub fn get_video_track_id(&self) -> Option<u8> {
+        self.video_track_id
+    }
+
+    /// Applies this selection to an FLV tag.
+    ///
+    /// * A ScriptData tag resolves track names by its track ID info maps, then is passed through.
+    /// * An audio/a video tag is reduced to the selected track, or is dropped (that is, `None` is returned) if it doesn't contain the track.
+    /// * Any tag whose kind of track isn't specified is passed through.
+    ///
+    /// # Errors
+    ///
+    /// When some tag has failed to be decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use bytes::Bytes;
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Encoder,
+    ///     flv::tags::{
+    ///         AudioFourCc,
+    ///         AudioPacketType,
+    ///         AudioTag,
+    ///         AudioTrack,
+    ///         FlvTag,
+    ///         TagType,
+    ///         TrackSelection
+    ///     }
+    /// };
+    ///
+    /// let audio_tag = AudioTag::new_multitrack(
+    ///     AudioPacketType::CodedFrames,
+    ///     vec![
+    ///         AudioTrack::new(0, AudioFourCc::Opus, vec![0; 4]),
+    ///         AudioTrack::new(1, AudioFourCc::Opus, vec![1; 4])
+    ///     ]
+    /// );
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.encode(&audio_tag);
+    /// let flv_tag = FlvTag::new(TagType::Audio, Duration::default(), Bytes::from(Vec::<u8>::from(buffer)));
+    ///
+    /// let mut track_selection = TrackSelection::from_query("audioTrack=2");
+    /// assert!(track_selection.select(flv_tag.clone()).unwrap().is_none());
+    ///
+    /// let mut track_selection = TrackSelection::from_query("audioTrack=1");
+    /// assert!(track_selection.select(flv_tag).unwrap().is_some())
+    /// ```
+    pub fn select(&mut self, flv_tag: FlvTag) -> IOResult<Option<FlvTag>> {
+        match flv_tag.get_tag_type() {
+            TagType::ScriptData => {
+                self.resolve(&flv_tag)?;
+                Ok(Some(flv_tag))
+            },
+            TagType::Audio => {
+                let Some(track_id) = self.audio_track_id else {
+                    return Ok(Some(flv_tag))
+                };
+                let mut buffer: ByteBuffer = flv_tag.get_data().to_vec().into();
+                let audio_tag: AudioTag = buffer.decode()?;
+                Ok(audio_tag.select_track(track_id)?.map(|audio_tag| replace_data(&flv_tag, &audio_tag)))
+            },
+            TagType::Video => {
+                let Some(track_id) = self.video_track_id else {
+                    return Ok(Some(flv_tag))
+                };
+                let mut buffer: ByteBuffer = flv_tag.get_data().to_vec().into();
+                let video_tag: VideoTag = buffer.decode()?;
+                Ok(video_tag.select_track(track_id)?.map(|video_tag| replace_data(&flv_tag, &video_tag)))
+            },
+            TagType::Other => Ok(Some(flv_tag))
+        }
+    }
+
+    fn resolve(&mut self, flv_tag: &FlvTag) -> IOResult<()> {
+        let mut buffer: ByteBuffer = flv_tag.get_data().to_vec().into();
+        let script_data: ScriptDataTag = buffer.decode()?;
+        let properties = script_data.get_value().get_properties();
+
+        if let Some(TrackSelector::Name(ref name)) = self.audio {
+            self.audio_track_id = Some(resolve_name(properties.get("audioTrackIdInfoMap"), name));
+        }
+        if let Some(TrackSelector::Name(ref name)) = self.video {
+            self.video_track_id = Some(resolve_name(properties.get("videoTrackIdInfoMap"), name));
+        }
+
+        Ok(())
+    }
+}
+
+fn resolve_id(selector: &TrackSelector) -> u8 {
+    match selector {
+        TrackSelector::Id(track_id) => *track_id,
+        TrackSelector::Name(_) => 0
+    }
+}
+
+fn resolve_name(track_id_info_map: Option<&AmfValue>, name: &str) -> u8 {
+    track_id_info_map.and_then(AmfValue::get_properties)
+        .and_then(
+            |properties| properties.iter().find_map(
+                |(track_id, info)| (info.get("name").and_then(AmfValue::as_str) == Some(name)).then(|| track_id.parse::<u8>().ok()).flatten()
+            )
+        )
+        .unwrap_or_default()
+}
+
+pub(crate) fn replace_data<T>(flv_tag: &FlvTag, tag: &T) -> FlvTag
+where ByteBuffer: Encoder<T>
+{
+    let mut buffer = ByteBuffer::default();
+    buffer.encode(tag);
+    FlvTag::new(flv_tag.get_tag_type(), flv_tag.get_timestamp(), Bytes::from(Vec::<u8>::from(buffer)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::{
+        ecma_array,
+        messages::amf::v0::AmfString,
+        object
+    };
+    use super::*;
+
+    #[test]
+    fn resolve_track_name() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(
+            &ScriptDataTag::new(
+                AmfString::from("onMetaData"),
+                ecma_array!(
+                    "videoTrackIdInfoMap" => object!(
+                        "1" => object!("name" => AmfString::from("main")),
+                        "2" => object!("name" => AmfString::from("sub"))
+                    )
+                )
+            )
+        );
+        let flv_tag = FlvTag::new(TagType::ScriptData, Duration::default(), Bytes::from(Vec::<u8>::from(buffer)));
+
+        let mut track_selection = TrackSelection::from_query("videoTrack=sub&audioTrack=unknown");
+        assert_eq!(Some(0), track_selection.get_video_track_id());
+        assert!(track_selection.select(flv_tag).unwrap().is_some());
+        assert_eq!(Some(2), track_selection.get_video_track_id());
+        assert_eq!(Some(0), track_selection.get_audio_track_id())
+    }
+}
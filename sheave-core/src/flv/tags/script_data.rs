@@ -26,7 +26,7 @@ use crate::{
 ///
 /// [`String`]: crate::messages::amf::v0::AmfString
 /// [`EcmaArray`]: crate::messages::amf::v0::EcmaArray
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ScriptDataTag {
     name: AmfString,
     value: EcmaArray
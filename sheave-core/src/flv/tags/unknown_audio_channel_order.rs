@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the AudioChannelOrder field of the multichannel configuration is unknown/undefined.
+#[derive(Debug)]
+pub struct UnknownAudioChannelOrder(u8);
+
+impl Display for UnknownAudioChannelOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown AudioChannelOrder: {}", self.0)
+    }
+}
+
+impl Error for UnknownAudioChannelOrder {}
+
+/// A utility function of constructing an `UnknownAudioChannelOrder` error.
+pub fn unknown_audio_channel_order(audio_channel_order: u8) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownAudioChannelOrder(audio_channel_order)
+    )
+}
@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the AudioPacketType field of the ExAudioTagHeader is unknown/undefined.
+#[derive(Debug)]
+pub struct UnknownAudioPacketType(u8);
+
+impl Display for UnknownAudioPacketType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown AudioPacketType: {}", self.0)
+    }
+}
+
+impl Error for UnknownAudioPacketType {}
+
+/// A utility function of constructing an `UnknownAudioPacketType` error.
+pub fn unknown_audio_packet_type(audio_packet_type: u8) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownAudioPacketType(audio_packet_type)
+    )
+}
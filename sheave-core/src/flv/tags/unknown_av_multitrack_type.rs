@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the AvMultitrackType field of the Multitrack packet is unknown/undefined.
+#[derive(Debug)]
+pub struct UnknownAvMultitrackType(u8);
+
+impl Display for UnknownAvMultitrackType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown AvMultitrackType: {}", self.0)
+    }
+}
+
+impl Error for UnknownAvMultitrackType {}
+
+/// A utility function of constructing an `UnknownAvMultitrackType` error.
+pub fn unknown_av_multitrack_type(av_multitrack_type: u8) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownAvMultitrackType(av_multitrack_type)
+    )
+}
@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the FourCC of Enhanced RTMP is unknown/unsupported.
+#[derive(Debug)]
+pub struct UnknownFourCc([u8; 4]);
+
+impl Display for UnknownFourCc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown FourCC: {}", String::from_utf8_lossy(&self.0))
+    }
+}
+
+impl Error for UnknownFourCc {}
+
+/// A utility function of constructing an `UnknownFourCc` error.
+pub fn unknown_four_cc(four_cc: [u8; 4]) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownFourCc(four_cc)
+    )
+}
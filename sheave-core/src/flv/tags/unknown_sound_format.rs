@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the SoundFormat field is unknown/undefined.
+#[derive(Debug)]
+pub struct UnknownSoundFormat(u8);
+
+impl Display for UnknownSoundFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown SoundFormat: {}", self.0)
+    }
+}
+
+impl Error for UnknownSoundFormat {}
+
+/// A utility function of constructing an `UnknownSoundFormat` error.
+pub fn unknown_sound_format(sound_format: u8) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownSoundFormat(sound_format)
+    )
+}
@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the SoundRate field is unknown/undefined.
+#[derive(Debug)]
+pub struct UnknownSoundRate(u8);
+
+impl Display for UnknownSoundRate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown SoundRate: {}", self.0)
+    }
+}
+
+impl Error for UnknownSoundRate {}
+
+/// A utility function of constructing an `UnknownSoundRate` error.
+pub fn unknown_sound_rate(sound_rate: u8) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownSoundRate(sound_rate)
+    )
+}
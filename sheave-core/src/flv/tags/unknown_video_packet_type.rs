@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the VideoPacketType field of the ExVideoTagHeader is unknown/undefined.
+#[derive(Debug)]
+pub struct UnknownVideoPacketType(u8);
+
+impl Display for UnknownVideoPacketType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown VideoPacketType: {}", self.0)
+    }
+}
+
+impl Error for UnknownVideoPacketType {}
+
+/// A utility function of constructing an `UnknownVideoPacketType` error.
+pub fn unknown_video_packet_type(video_packet_type: u8) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownVideoPacketType(video_packet_type)
+    )
+}
@@ -1,3 +1,8 @@
+mod avc_decoder_configuration_record;
+mod hevc_decoder_configuration_record;
+mod av1_codec_configuration_record;
+mod vp_codec_configuration_record;
+
 use std::io::{
     Error as IOError,
     Result as IOResult
@@ -8,6 +13,23 @@ use crate::{
     Encoder,
     messages::Video
 };
+use super::{
+    AvMultitrackType,
+    unknown_four_cc,
+    unknown_video_packet_type
+};
+pub use self::{
+    avc_decoder_configuration_record::AvcDecoderConfigurationRecord,
+    hevc_decoder_configuration_record::{
+        HevcDecoderConfigurationRecord,
+        NalUnitArray
+    },
+    av1_codec_configuration_record::Av1CodecConfigurationRecord,
+    vp_codec_configuration_record::VpCodecConfigurationRecord
+};
+
+/// The bit which indicates the header is the ExVideoTagHeader of Enhanced RTMP.
+const IS_EX_HEADER: u8 = 0x80;
 
 /// Representation of the FrameType field.
 ///
@@ -19,7 +41,10 @@ use crate::{
 /// |`Inter`|`2`|
 /// |`Disposable`|`3`|
 /// |`Generated`|`4`|
+/// |`Info`|`5`|
 /// |`Other`|other numbers|
+///
+/// Note the ExVideoTagHeader has only 3 bits for this field.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameType {
@@ -152,6 +177,30 @@ impl VideoTagHeader {
     pub fn new(frame_type: FrameType, codec: Codec, avc_packet_type: Option<AvcPacketType>, composition_time: Option<i32>) -> Self {
         Self { frame_type, codec, avc_packet_type, composition_time }
     }
+
+    /// Gets the FrameType field.
+    pub fn get_frame_type(&self) -> FrameType {
+        self.frame_type
+    }
+
+    /// Gets the CodecID field.
+    pub fn get_codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Gets the AVCPacketType field.
+    ///
+    /// Note this returns `None` unless the codec is the AVC.
+    pub fn get_avc_packet_type(&self) -> Option<AvcPacketType> {
+        self.avc_packet_type
+    }
+
+    /// Gets the CompositionTime field.
+    ///
+    /// Note this returns `None` unless the codec is the AVC.
+    pub fn get_composition_time(&self) -> Option<i32> {
+        self.composition_time
+    }
 }
 
 impl Decoder<VideoTagHeader> for ByteBuffer {
@@ -217,17 +266,821 @@ impl Encoder<VideoTagHeader> for ByteBuffer {
     }
 }
 
+/// Representation of the VideoPacketType field of the ExVideoTagHeader.
+///
+/// Variants correspond to respectively following numbers:
+///
+/// |Variant|Number|
+/// | :- | :- |
+/// |`SequenceStart`|`0`|
+/// |`CodedFrames`|`1`|
+/// |`SequenceEnd`|`2`|
+/// |`CodedFramesX`|`3`|
+/// |`Metadata`|`4`|
+/// |`Mpeg2TsSequenceStart`|`5`|
+/// |`Multitrack`|`6`|
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoPacketType {
+    SequenceStart,
+    CodedFrames,
+    SequenceEnd,
+    CodedFramesX,
+    Metadata,
+    Mpeg2TsSequenceStart,
+    Multitrack
+}
+
+impl TryFrom<u8> for VideoPacketType {
+    type Error = IOError;
+
+    /// Converts a VideoPacketType field into a variant.
+    ///
+    /// # Errors
+    ///
+    /// * [`UnknownVideoPacketType`]
+    ///
+    /// When is passed any value above 6.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     VideoPacketType,
+    ///     VideoPacketType::*
+    /// };
+    ///
+    /// assert_eq!(SequenceStart, VideoPacketType::try_from(0).unwrap()); // => ok
+    /// assert_eq!(Mpeg2TsSequenceStart, VideoPacketType::try_from(5).unwrap()); // => ok
+    /// assert_eq!(Multitrack, VideoPacketType::try_from(6).unwrap()); // => ok
+    /// assert!(VideoPacketType::try_from(15).is_err()) // => this will be an error.
+    /// ```
+    ///
+    /// [`UnknownVideoPacketType`]: super::UnknownVideoPacketType
+    fn try_from(video_packet_type: u8) -> Result<Self, Self::Error> {
+        use VideoPacketType::*;
+
+        match video_packet_type {
+            0 => Ok(SequenceStart),
+            1 => Ok(CodedFrames),
+            2 => Ok(SequenceEnd),
+            3 => Ok(CodedFramesX),
+            4 => Ok(Metadata),
+            5 => Ok(Mpeg2TsSequenceStart),
+            6 => Ok(Multitrack),
+            _ => Err(unknown_video_packet_type(video_packet_type))
+        }
+    }
+}
+
+impl From<VideoPacketType> for u8 {
+    fn from(video_packet_type: VideoPacketType) -> Self {
+        video_packet_type as u8
+    }
+}
+
+/// Representation of the VideoFourCc field of the ExVideoTagHeader.
+///
+/// Variants correspond to respectively following FourCCs:
+///
+/// |Variant|FourCC|
+/// | :- | :- |
+/// |`Vp8`|`vp08`|
+/// |`Vp9`|`vp09`|
+/// |`Av1`|`av01`|
+/// |`Avc`|`avc1`|
+/// |`Hevc`|`hvc1`|
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoFourCc {
+    Vp8,
+    Vp9,
+    Av1,
+    Avc,
+    Hevc
+}
+
+impl VideoFourCc {
+    /// Checks whether the CodedFrames packet of this codec has the CompositionTime field.
+    ///
+    /// This is true only for the AVC and the HEVC.
+    pub fn has_composition_time(&self) -> bool {
+        matches!(*self, VideoFourCc::Avc | VideoFourCc::Hevc)
+    }
+}
+
+impl TryFrom<[u8; 4]> for VideoFourCc {
+    type Error = IOError;
+
+    /// Converts a FourCC into a variant.
+    ///
+    /// # Errors
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When is passed any FourCC of unsupported codecs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::VideoFourCc;
+    ///
+    /// assert_eq!(VideoFourCc::Hevc, VideoFourCc::try_from(*b"hvc1").unwrap()); // => ok
+    /// assert_eq!(VideoFourCc::Av1, VideoFourCc::try_from(*b"av01").unwrap()); // => ok
+    /// assert!(VideoFourCc::try_from(*b"Opus").is_err()) // => this will be an error.
+    /// ```
+    ///
+    /// [`UnknownFourCc`]: super::UnknownFourCc
+    fn try_from(four_cc: [u8; 4]) -> Result<Self, Self::Error> {
+        use VideoFourCc::*;
+
+        match &four_cc {
+            b"vp08" => Ok(Vp8),
+            b"vp09" => Ok(Vp9),
+            b"av01" => Ok(Av1),
+            b"avc1" => Ok(Avc),
+            b"hvc1" => Ok(Hevc),
+            _ => Err(unknown_four_cc(four_cc))
+        }
+    }
+}
+
+impl From<VideoFourCc> for [u8; 4] {
+    fn from(four_cc: VideoFourCc) -> Self {
+        use VideoFourCc::*;
+
+        match four_cc {
+            Vp8 => *b"vp08",
+            Vp9 => *b"vp09",
+            Av1 => *b"av01",
+            Avc => *b"avc1",
+            Hevc => *b"hvc1"
+        }
+    }
+}
+
+/// Representation of the VideoCommand field, which is sent in the video info/command frame.
+///
+/// Variants correspond to respectively following numbers:
+///
+/// |Variant|Number|
+/// | :- | :- |
+/// |`StartSeek`|`0`|
+/// |`EndSeek`|`1`|
+/// |`Other`|other numbers|
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoCommand {
+    StartSeek,
+    EndSeek,
+    Other = 0xff
+}
+
+impl From<u8> for VideoCommand {
+    fn from(video_command: u8) -> Self {
+        use VideoCommand::*;
+
+        match video_command {
+            0 => StartSeek,
+            1 => EndSeek,
+            _ => Other
+        }
+    }
+}
+
+impl From<VideoCommand> for u8 {
+    fn from(video_command: VideoCommand) -> Self {
+        video_command as u8
+    }
+}
+
+/// The ExVideoTagHeader of Enhanced RTMP.
+///
+/// This is distinguished from the legacy VideoTagHeader by the most significant bit of the first byte.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |IsExHeader|1 bit (fixed to `1`)|
+/// |FrameType|3 bits|
+/// |VideoPacketType|4 bits|
+/// |VideoCommand|8 bits if the FrameType is `Info` and the VideoPacketType isn't `Metadata`.|
+/// |AvMultitrackType|4 bits if the VideoPacketType is `Multitrack`. Then the actual VideoPacketType follows in 4 bits.|
+/// |VideoFourCc|32 bits otherwise. However the `ManyTracksManyCodecs` Multitrack packet has this in every track instead.|
+/// |CompositionTime|**signed** 24 bits if the VideoPacketType is `CodedFrames` and the codec is either the AVC or the HEVC. The Multitrack packet has this in every track instead.|
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExVideoTagHeader {
+    frame_type: FrameType,
+    packet_type: VideoPacketType,
+    multitrack_type: Option<AvMultitrackType>,
+    video_command: Option<VideoCommand>,
+    four_cc: Option<VideoFourCc>,
+    composition_time: Option<i32>
+}
+
+impl ExVideoTagHeader {
+    /// Constructs an ExVideoTagHeader which is followed by video data.
+    ///
+    /// Note the `composition_time` is written only into the CodedFrames packet of the AVC and the HEVC.
+    pub fn new(frame_type: FrameType, packet_type: VideoPacketType, four_cc: VideoFourCc, composition_time: Option<i32>) -> Self {
+        let composition_time = if packet_type == VideoPacketType::CodedFrames && four_cc.has_composition_time() {
+            Some(composition_time.unwrap_or_default())
+        } else {
+            None
+        };
+
+        Self {
+            frame_type,
+            packet_type,
+            multitrack_type: None,
+            video_command: None,
+            four_cc: Some(four_cc),
+            composition_time
+        }
+    }
+
+    /// Constructs an ExVideoTagHeader of the Multitrack packet.
+    ///
+    /// The `four_cc` is shared with all tracks, so it is ignored for `ManyTracksManyCodecs`.
+    /// Note the `packet_type` is the one which is applied to all tracks, rather than `Multitrack` itself.
+    pub fn new_multitrack(frame_type: FrameType, packet_type: VideoPacketType, multitrack_type: AvMultitrackType, four_cc: Option<VideoFourCc>) -> Self {
+        let four_cc = if multitrack_type == AvMultitrackType::ManyTracksManyCodecs {
+            None
+        } else {
+            four_cc
+        };
+
+        Self {
+            frame_type,
+            packet_type,
+            multitrack_type: Some(multitrack_type),
+            video_command: None,
+            four_cc,
+            composition_time: None
+        }
+    }
+
+    /// Constructs an ExVideoTagHeader of the video info/command frame.
+    pub fn new_command(video_command: VideoCommand) -> Self {
+        Self {
+            frame_type: FrameType::Info,
+            packet_type: VideoPacketType::CodedFrames,
+            multitrack_type: None,
+            video_command: Some(video_command),
+            four_cc: None,
+            composition_time: None
+        }
+    }
+
+    /// Gets the FrameType field.
+    pub fn get_frame_type(&self) -> FrameType {
+        self.frame_type
+    }
+
+    /// Gets the VideoPacketType field.
+    ///
+    /// Note this returns the packet type which is applied to all tracks if this is the Multitrack packet.
+    pub fn get_packet_type(&self) -> VideoPacketType {
+        self.packet_type
+    }
+
+    /// Gets the AvMultitrackType field.
+    ///
+    /// Note this returns `None` unless this is the Multitrack packet.
+    pub fn get_multitrack_type(&self) -> Option<AvMultitrackType> {
+        self.multitrack_type
+    }
+
+    /// Gets the VideoCommand field.
+    ///
+    /// Note this returns `None` unless this is the video info/command frame.
+    pub fn get_video_command(&self) -> Option<VideoCommand> {
+        self.video_command
+    }
+
+    /// Gets the VideoFourCc field.
+    ///
+    /// Note this returns `None` if this is either the video info/command frame or the `ManyTracksManyCodecs` Multitrack packet.
+    pub fn get_four_cc(&self) -> Option<VideoFourCc> {
+        self.four_cc
+    }
+
+    /// Gets the CompositionTime field.
+    ///
+    /// Note the CodedFramesX packet omits this field, which means the CompositionTime is 0.
+    pub fn get_composition_time(&self) -> Option<i32> {
+        self.composition_time
+    }
+}
+
+impl Decoder<ExVideoTagHeader> for ByteBuffer {
+    /// Decodes bytes into an ExVideoTagHeader.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// * [`UnknownVideoPacketType`]
+    ///
+    /// When the VideoPacketType field is above 6, or the Multitrack packet nests another Multitrack packet.
+    ///
+    /// * [`UnknownAvMultitrackType`]
+    ///
+    /// When the AvMultitrackType field is above 2.
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When the VideoFourCc field is none of `vp08`, `vp09`, `av01`, `avc1` and `hvc1`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::{
+    ///         ExVideoTagHeader,
+    ///         FrameType,
+    ///         VideoFourCc,
+    ///         VideoPacketType
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0x91);
+    /// buffer.put_bytes(b"hvc1");
+    /// buffer.put_i24_be(0);
+    /// let header: ExVideoTagHeader = buffer.decode().unwrap();
+    /// assert_eq!(FrameType::Key, header.get_frame_type());
+    /// assert_eq!(VideoPacketType::CodedFrames, header.get_packet_type());
+    /// assert_eq!(Some(VideoFourCc::Hevc), header.get_four_cc());
+    /// assert_eq!(Some(0), header.get_composition_time());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0x90);
+    /// buffer.put_bytes(b"xxxx");
+    /// assert!(Decoder::<ExVideoTagHeader>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownVideoPacketType`]: super::UnknownVideoPacketType
+    /// [`UnknownAvMultitrackType`]: super::UnknownAvMultitrackType
+    /// [`UnknownFourCc`]: super::UnknownFourCc
+    fn decode(&mut self) -> IOResult<ExVideoTagHeader> {
+        let byte = self.get_u8()?;
+        let frame_type: FrameType = ((byte >> 4) & 0x07).into();
+        let packet_type: VideoPacketType = (byte & 0x0f).try_into()?;
+
+        if frame_type == FrameType::Info && packet_type != VideoPacketType::Metadata {
+            let video_command: VideoCommand = self.get_u8()?.into();
+            return Ok(
+                ExVideoTagHeader {
+                    frame_type,
+                    packet_type,
+                    multitrack_type: None,
+                    video_command: Some(video_command),
+                    four_cc: None,
+                    composition_time: None
+                }
+            )
+        }
+
+        if packet_type == VideoPacketType::Multitrack {
+            let byte = self.get_u8()?;
+            let multitrack_type: AvMultitrackType = (byte >> 4).try_into()?;
+            let packet_type: VideoPacketType = (byte & 0x0f).try_into()?;
+            if packet_type == VideoPacketType::Multitrack {
+                return Err(unknown_video_packet_type(packet_type.into()))
+            }
+
+            let four_cc = if multitrack_type == AvMultitrackType::ManyTracksManyCodecs {
+                None
+            } else {
+                let mut four_cc_bytes: [u8; 4] = [0; 4];
+                four_cc_bytes.copy_from_slice(self.get_bytes(4)?);
+                Some(VideoFourCc::try_from(four_cc_bytes)?)
+            };
+
+            return Ok(
+                ExVideoTagHeader {
+                    frame_type,
+                    packet_type,
+                    multitrack_type: Some(multitrack_type),
+                    video_command: None,
+                    four_cc,
+                    composition_time: None
+                }
+            )
+        }
+
+        let mut four_cc_bytes: [u8; 4] = [0; 4];
+        four_cc_bytes.copy_from_slice(self.get_bytes(4)?);
+        let four_cc: VideoFourCc = four_cc_bytes.try_into()?;
+        let composition_time = if packet_type == VideoPacketType::CodedFrames && four_cc.has_composition_time() {
+            Some(self.get_i24_be()?)
+        } else {
+            None
+        };
+
+        Ok(
+            ExVideoTagHeader {
+                frame_type,
+                packet_type,
+                multitrack_type: None,
+                video_command: None,
+                four_cc: Some(four_cc),
+                composition_time
+            }
+        )
+    }
+}
+
+impl Encoder<ExVideoTagHeader> for ByteBuffer {
+    /// Encodes an ExVideoTagHeader into bytes.
+    fn encode(&mut self, ex_video_tag_header: &ExVideoTagHeader) {
+        let mut byte = IS_EX_HEADER;
+        byte |= (u8::from(ex_video_tag_header.frame_type) & 0x07) << 4;
+        if let Some(multitrack_type) = ex_video_tag_header.multitrack_type {
+            byte |= u8::from(VideoPacketType::Multitrack);
+            self.put_u8(byte);
+            self.put_u8((u8::from(multitrack_type) << 4) | u8::from(ex_video_tag_header.packet_type));
+        } else {
+            byte |= u8::from(ex_video_tag_header.packet_type);
+            self.put_u8(byte);
+        }
+
+        if let Some(video_command) = ex_video_tag_header.video_command {
+            self.put_u8(video_command.into());
+        }
+        if let Some(four_cc) = ex_video_tag_header.four_cc {
+            self.put_bytes(&<[u8; 4]>::from(four_cc));
+        }
+        if let Some(composition_time) = ex_video_tag_header.composition_time {
+            self.put_i24_be(composition_time);
+        }
+    }
+}
+
+/// Either the legacy VideoTagHeader or the ExVideoTagHeader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoHeader {
+    Legacy(VideoTagHeader),
+    Ex(ExVideoTagHeader)
+}
+
+impl VideoHeader {
+    /// Gets the FrameType field of either header.
+    pub fn get_frame_type(&self) -> FrameType {
+        match *self {
+            VideoHeader::Legacy(header) => header.frame_type,
+            VideoHeader::Ex(header) => header.frame_type
+        }
+    }
+}
+
+impl From<VideoTagHeader> for VideoHeader {
+    fn from(video_tag_header: VideoTagHeader) -> Self {
+        Self::Legacy(video_tag_header)
+    }
+}
+
+impl From<ExVideoTagHeader> for VideoHeader {
+    fn from(ex_video_tag_header: ExVideoTagHeader) -> Self {
+        Self::Ex(ex_video_tag_header)
+    }
+}
+
+impl Decoder<VideoHeader> for ByteBuffer {
+    /// Decodes bytes into either the legacy VideoTagHeader or the ExVideoTagHeader.
+    ///
+    /// Which header is decoded is decided by the IsExHeader bit.
+    ///
+    /// # Errors
+    ///
+    /// Same as either [`VideoTagHeader`] or [`ExVideoTagHeader`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::VideoHeader
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0x32);
+    /// assert!(matches!(Decoder::<VideoHeader>::decode(&mut buffer).unwrap(), VideoHeader::Legacy(_)));
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0x90);
+    /// buffer.put_bytes(b"av01");
+    /// assert!(matches!(Decoder::<VideoHeader>::decode(&mut buffer).unwrap(), VideoHeader::Ex(_)))
+    /// ```
+    fn decode(&mut self) -> IOResult<VideoHeader> {
+        if (self.peek_u8()? & IS_EX_HEADER) == 0 {
+            Decoder::<VideoTagHeader>::decode(self).map(VideoHeader::Legacy)
+        } else {
+            Decoder::<ExVideoTagHeader>::decode(self).map(VideoHeader::Ex)
+        }
+    }
+}
+
+impl Encoder<VideoHeader> for ByteBuffer {
+    /// Encodes either the legacy VideoTagHeader or the ExVideoTagHeader into bytes.
+    fn encode(&mut self, video_header: &VideoHeader) {
+        match video_header {
+            VideoHeader::Legacy(header) => self.encode(header),
+            VideoHeader::Ex(header) => self.encode(header)
+        }
+    }
+}
+
+/// The decoder configuration record which is sent before any coded frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecoderConfigurationRecord {
+    Avc(AvcDecoderConfigurationRecord),
+    Hevc(HevcDecoderConfigurationRecord),
+    Av1(Av1CodecConfigurationRecord),
+    Vp(VpCodecConfigurationRecord)
+}
+
+/// A track in the Multitrack packet.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |VideoFourCc|32 bits only in `ManyTracksManyCodecs`.|
+/// |TrackId|8 bits|
+/// |SizeOfVideoTrack|24 bits except `OneTrack`.|
+/// |CompositionTime|**signed** 24 bits if the packet is `CodedFrames` and the codec is either the AVC or the HEVC.|
+/// |VideoData|Same as SizeOfVideoTrack, otherwise the rest of the tag.|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoTrack {
+    track_id: u8,
+    four_cc: VideoFourCc,
+    composition_time: Option<i32>,
+    data: Vec<u8>
+}
+
+impl VideoTrack {
+    /// Constructs a VideoTrack.
+    ///
+    /// Note the `composition_time` is written only into the CodedFrames packet of the AVC and the HEVC.
+    pub fn new(track_id: u8, four_cc: VideoFourCc, composition_time: Option<i32>, data: Vec<u8>) -> Self {
+        Self { track_id, four_cc, composition_time, data }
+    }
+
+    /// Gets the track ID.
+    pub fn get_track_id(&self) -> u8 {
+        self.track_id
+    }
+
+    /// Gets the codec of this track.
+    pub fn get_four_cc(&self) -> VideoFourCc {
+        self.four_cc
+    }
+
+    /// Gets the CompositionTime field.
+    pub fn get_composition_time(&self) -> Option<i32> {
+        self.composition_time
+    }
+
+    /// Gets the video data of this track.
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
 /// The video data format.
+///
+/// Its header is either the legacy VideoTagHeader or the ExVideoTagHeader of Enhanced RTMP.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VideoTag {
-    header: VideoTagHeader,
+    header: VideoHeader,
     body: Vec<u8>
 }
 
 impl VideoTag {
     /// Constructs a VideoTag.
-    pub fn new(header: VideoTagHeader, body: Vec<u8>) -> Self {
-        Self { header, body }
+    pub fn new<H: Into<VideoHeader>>(header: H, body: Vec<u8>) -> Self {
+        Self { header: header.into(), body }
+    }
+
+    /// Constructs a VideoTag of the Multitrack packet.
+    ///
+    /// The AvMultitrackType is chosen from tracks, that is:
+    ///
+    /// * `OneTrack` if there is just one track.
+    /// * `ManyTracks` if all tracks have same codec.
+    /// * `ManyTracksManyCodecs` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     AvMultitrackType,
+    ///     FrameType,
+    ///     VideoFourCc,
+    ///     VideoHeader,
+    ///     VideoPacketType,
+    ///     VideoTag,
+    ///     VideoTrack
+    /// };
+    ///
+    /// let tracks = vec![
+    ///     VideoTrack::new(0, VideoFourCc::Hevc, Some(0), vec![0; 8]),
+    ///     VideoTrack::new(1, VideoFourCc::Av1, None, vec![1; 4])
+    /// ];
+    /// let video_tag = VideoTag::new_multitrack(FrameType::Key, VideoPacketType::CodedFrames, tracks.clone());
+    /// let VideoHeader::Ex(header) = video_tag.get_header() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(Some(AvMultitrackType::ManyTracksManyCodecs), header.get_multitrack_type());
+    /// assert_eq!(tracks, video_tag.get_tracks().unwrap())
+    /// ```
+    pub fn new_multitrack(frame_type: FrameType, packet_type: VideoPacketType, tracks: Vec<VideoTrack>) -> Self {
+        let four_cc = tracks.first().map(|track| track.four_cc);
+        let multitrack_type = if tracks.len() == 1 {
+            AvMultitrackType::OneTrack
+        } else if four_cc.is_some() && tracks.iter().all(|track| Some(track.four_cc) == four_cc) {
+            AvMultitrackType::ManyTracks
+        } else {
+            AvMultitrackType::ManyTracksManyCodecs
+        };
+        let header = ExVideoTagHeader::new_multitrack(frame_type, packet_type, multitrack_type, four_cc);
+
+        let mut buffer = ByteBuffer::default();
+        for track in tracks {
+            let mut track_buffer = ByteBuffer::default();
+            if packet_type == VideoPacketType::CodedFrames && track.four_cc.has_composition_time() {
+                track_buffer.put_i24_be(track.composition_time.unwrap_or_default());
+            }
+            track_buffer.put_bytes(&track.data);
+            let track_data: Vec<u8> = track_buffer.into();
+
+            if multitrack_type == AvMultitrackType::ManyTracksManyCodecs {
+                buffer.put_bytes(&<[u8; 4]>::from(track.four_cc));
+            }
+            buffer.put_u8(track.track_id);
+            if multitrack_type != AvMultitrackType::OneTrack {
+                buffer.put_u24_be(track_data.len() as u32);
+            }
+            buffer.put_bytes(&track_data);
+        }
+
+        Self { header: header.into(), body: buffer.into() }
+    }
+
+    /// Gets the header.
+    pub fn get_header(&self) -> VideoHeader {
+        self.header
+    }
+
+    /// Gets the video data after the header.
+    pub fn get_body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Decodes the body as the decoder configuration record.
+    ///
+    /// Note this returns `None` unless this is either the sequence header of the legacy AVC or the SequenceStart packet.
+    ///
+    /// # Errors
+    ///
+    /// When the body isn't a decoder configuration record.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     DecoderConfigurationRecord,
+    ///     ExVideoTagHeader,
+    ///     FrameType,
+    ///     VideoFourCc,
+    ///     VideoPacketType,
+    ///     VideoTag
+    /// };
+    ///
+    /// let header = ExVideoTagHeader::new(FrameType::Key, VideoPacketType::SequenceStart, VideoFourCc::Vp9, None);
+    /// let video_tag = VideoTag::new(header, vec![0x00, 0x1f, 0x82, 0x01, 0x01, 0x01, 0x00, 0x00]);
+    /// assert!(matches!(video_tag.get_decoder_configuration_record().unwrap(), Some(DecoderConfigurationRecord::Vp(_))));
+    ///
+    /// let header = ExVideoTagHeader::new(FrameType::Key, VideoPacketType::CodedFrames, VideoFourCc::Vp9, None);
+    /// let video_tag = VideoTag::new(header, Vec::new());
+    /// assert!(video_tag.get_decoder_configuration_record().unwrap().is_none())
+    /// ```
+    pub fn get_decoder_configuration_record(&self) -> IOResult<Option<DecoderConfigurationRecord>> {
+        /* NOTE: Every track of the Multitrack packet has its own record. */
+        let mut buffer: ByteBuffer = self.body.clone().into();
+        match self.header {
+            VideoHeader::Legacy(header) if header.codec.is_avc() && header.avc_packet_type == Some(AvcPacketType::SequenceHeader) => buffer.decode().map(|record| Some(DecoderConfigurationRecord::Avc(record))),
+            VideoHeader::Ex(header) if header.packet_type == VideoPacketType::SequenceStart && header.multitrack_type.is_none() => match header.four_cc {
+                Some(VideoFourCc::Avc) => buffer.decode().map(|record| Some(DecoderConfigurationRecord::Avc(record))),
+                Some(VideoFourCc::Hevc) => buffer.decode().map(|record| Some(DecoderConfigurationRecord::Hevc(record))),
+                Some(VideoFourCc::Av1) => buffer.decode().map(|record| Some(DecoderConfigurationRecord::Av1(record))),
+                Some(VideoFourCc::Vp8) | Some(VideoFourCc::Vp9) => buffer.decode().map(|record| Some(DecoderConfigurationRecord::Vp(record))),
+                None => Ok(None)
+            },
+            _ => Ok(None)
+        }
+    }
+
+    /// Gets tracks in this tag.
+    ///
+    /// The ExVideoTagHeader without the Multitrack packet is regarded as the track whose ID is 0.
+    /// Note this returns an empty vector if this has either the legacy VideoTagHeader or the video info/command frame.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some track is shorter than its size.
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When some track of `ManyTracksManyCodecs` has an unsupported codec.
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownFourCc`]: super::UnknownFourCc
+    pub fn get_tracks(&self) -> IOResult<Vec<VideoTrack>> {
+        let VideoHeader::Ex(header) = self.header else {
+            return Ok(Vec::new())
+        };
+        let Some(multitrack_type) = header.multitrack_type else {
+            return Ok(header.four_cc.map(|four_cc| VideoTrack::new(0, four_cc, header.composition_time, self.body.clone())).into_iter().collect())
+        };
+
+        let mut buffer: ByteBuffer = self.body.clone().into();
+        let mut tracks: Vec<VideoTrack> = Vec::new();
+        while buffer.remained() > 0 {
+            let four_cc = match header.four_cc {
+                Some(four_cc) => four_cc,
+                None => {
+                    let mut four_cc_bytes: [u8; 4] = [0; 4];
+                    four_cc_bytes.copy_from_slice(buffer.get_bytes(4)?);
+                    VideoFourCc::try_from(four_cc_bytes)?
+                }
+            };
+            let track_id = buffer.get_u8()?;
+            let track_size = if multitrack_type == AvMultitrackType::OneTrack {
+                buffer.remained()
+            } else {
+                buffer.get_u24_be()? as usize
+            };
+            let mut track_buffer: ByteBuffer = buffer.get_bytes(track_size)?.to_vec().into();
+            let composition_time = if header.packet_type == VideoPacketType::CodedFrames && four_cc.has_composition_time() {
+                Some(track_buffer.get_i24_be()?)
+            } else {
+                None
+            };
+            let remained = track_buffer.remained();
+            let data = track_buffer.get_bytes(remained)?.to_vec();
+            tracks.push(VideoTrack { track_id, four_cc, composition_time, data });
+        }
+
+        Ok(tracks)
+    }
+
+    /// Extracts the specified track from this tag.
+    ///
+    /// If this is the Multitrack packet, the track is kept as the `OneTrack` Multitrack packet with its ID.
+    /// Otherwise this is regarded as the track whose ID is 0.
+    /// This returns `None` if this tag doesn't contain the track.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`get_tracks`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::flv::tags::{
+    ///     FrameType,
+    ///     VideoFourCc,
+    ///     VideoPacketType,
+    ///     VideoTag,
+    ///     VideoTrack
+    /// };
+    ///
+    /// let tracks = vec![
+    ///     VideoTrack::new(1, VideoFourCc::Av1, None, vec![1; 4]),
+    ///     VideoTrack::new(2, VideoFourCc::Av1, None, vec![2; 4])
+    /// ];
+    /// let video_tag = VideoTag::new_multitrack(FrameType::Inter, VideoPacketType::CodedFrames, tracks.clone());
+    /// let selected = video_tag.select_track(2).unwrap().unwrap();
+    /// assert_eq!(&tracks[1..], selected.get_tracks().unwrap());
+    /// assert!(video_tag.select_track(0).unwrap().is_none())
+    /// ```
+    ///
+    /// [`get_tracks`]: VideoTag::get_tracks
+    pub fn select_track(&self, track_id: u8) -> IOResult<Option<VideoTag>> {
+        match self.header {
+            VideoHeader::Ex(header) if header.multitrack_type.is_some() => {
+                let track = self.get_tracks()?.into_iter().find(|track| track.track_id == track_id);
+                Ok(track.map(|track| VideoTag::new_multitrack(header.frame_type, header.packet_type, vec![track])))
+            },
+            _ => Ok((track_id == 0).then(|| self.clone()))
+        }
     }
 }
 
@@ -240,6 +1093,18 @@ impl Decoder<VideoTag> for ByteBuffer {
     ///
     /// When some field misses.
     ///
+    /// * [`UnknownVideoPacketType`]
+    ///
+    /// When the header is the ExVideoTagHeader and its VideoPacketType field is above 6.
+    ///
+    /// * [`UnknownAvMultitrackType`]
+    ///
+    /// When the header is the ExVideoTagHeader and its AvMultitrackType field is above 2.
+    ///
+    /// * [`UnknownFourCc`]
+    ///
+    /// When the header is the ExVideoTagHeader and its codec is unsupported.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -269,8 +1134,11 @@ impl Decoder<VideoTag> for ByteBuffer {
     /// ```
     ///
     /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownVideoPacketType`]: super::UnknownVideoPacketType
+    /// [`UnknownAvMultitrackType`]: super::UnknownAvMultitrackType
+    /// [`UnknownFourCc`]: super::UnknownFourCc
     fn decode(&mut self) -> IOResult<VideoTag> {
-        let header: VideoTagHeader = self.decode()?;
+        let header: VideoHeader = self.decode()?;
         let remained = self.remained();
         let body = self.get_bytes(remained)?.to_vec();
 
@@ -345,4 +1213,83 @@ mod tests {
         let actual_data: Vec<u8> = buffer.into();
         assert_eq!(expected_data.as_slice(), actual_data)
     }
+    #[test]
+    fn decode_ex_video_tag() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(IS_EX_HEADER | (u8::from(FrameType::Inter) << 4) | u8::from(VideoPacketType::CodedFramesX));
+        buffer.put_bytes(b"hvc1");
+        let mut data: [u8; 128] = [0; 128];
+        fill(&mut data);
+        buffer.put_bytes(&data);
+
+        let video_tag: VideoTag = buffer.decode().unwrap();
+        let VideoHeader::Ex(header) = video_tag.get_header() else {
+            panic!("ExVideoTagHeader didn't get decoded.")
+        };
+        assert_eq!(FrameType::Inter, header.get_frame_type());
+        assert_eq!(Some(VideoFourCc::Hevc), header.get_four_cc());
+        assert!(header.get_composition_time().is_none());
+        assert_eq!(data.as_slice(), video_tag.get_body())
+    }
+
+    #[test]
+    fn encode_ex_video_tag() {
+        let mut buffer = ByteBuffer::default();
+        let expected = VideoTag::new(
+            ExVideoTagHeader::new(
+                FrameType::Key,
+                VideoPacketType::CodedFrames,
+                VideoFourCc::Avc,
+                Some(40)
+            ),
+            vec![0; 16]
+        );
+        buffer.encode(&expected);
+
+        let bytes: Vec<u8> = buffer.clone().into();
+        assert_eq!(0x91, bytes[0]);
+        assert_eq!(b"avc1", &bytes[1..5]);
+        assert_eq!(expected, Decoder::<VideoTag>::decode(&mut buffer).unwrap())
+    }
+
+    #[test]
+    fn decode_video_command() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&ExVideoTagHeader::new_command(VideoCommand::StartSeek));
+        let bytes: Vec<u8> = buffer.clone().into();
+        assert_eq!(vec![0xd1, 0x00], bytes);
+
+        let header: ExVideoTagHeader = buffer.decode().unwrap();
+        assert_eq!(Some(VideoCommand::StartSeek), header.get_video_command());
+        assert!(header.get_four_cc().is_none())
+    }
+
+    #[test]
+    fn round_trip_multitrack_video_tag() {
+        let tracks = vec![
+            VideoTrack::new(0, VideoFourCc::Avc, Some(40), vec![0; 16]),
+            VideoTrack::new(1, VideoFourCc::Av1, None, vec![1; 8])
+        ];
+        let expected = VideoTag::new_multitrack(FrameType::Key, VideoPacketType::CodedFrames, tracks.clone());
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&expected);
+
+        let bytes: Vec<u8> = buffer.clone().into();
+        assert_eq!(0x96, bytes[0]);
+        assert_eq!(0x21, bytes[1]);
+        assert_eq!(b"avc1", &bytes[2..6]);
+
+        let actual: VideoTag = buffer.decode().unwrap();
+        assert_eq!(expected, actual);
+        assert_eq!(tracks, actual.get_tracks().unwrap());
+
+        let selected = actual.select_track(1).unwrap().unwrap();
+        let VideoHeader::Ex(header) = selected.get_header() else {
+            panic!("ExVideoTagHeader didn't get kept.")
+        };
+        assert_eq!(Some(AvMultitrackType::OneTrack), header.get_multitrack_type());
+        assert_eq!(Some(VideoFourCc::Av1), header.get_four_cc());
+        assert_eq!(&tracks[1..], selected.get_tracks().unwrap());
+        assert!(actual.select_track(2).unwrap().is_none())
+    }
 }
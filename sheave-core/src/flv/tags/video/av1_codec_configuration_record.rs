@@ -0,0 +1,166 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder
+};
+
+/// The AV1CodecConfigurationRecord which is defined in the AV1 Codec ISO Media File Format Binding.
+///
+/// This is sent as the body of the SequenceStart packet of `av01`.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |marker|1 bit (fixed to `1`)|
+/// |version|7 bits (fixed to `1`)|
+/// |seq_profile|3 bits|
+/// |seq_level_idx_0|5 bits|
+/// |seq_tier_0|1 bit|
+/// |high_bitdepth|1 bit|
+/// |twelve_bit|1 bit|
+/// |monochrome|1 bit|
+/// |chroma_subsampling_x|1 bit|
+/// |chroma_subsampling_y|1 bit|
+/// |chroma_sample_position|2 bits|
+/// |initial_presentation_delay_present|1 bit (preceded by 3 reserved bits)|
+/// |initial_presentation_delay_minus_one|4 bits|
+/// |configOBUs|Arbitrary size|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Av1CodecConfigurationRecord {
+    seq_profile: u8,
+    seq_level_idx_0: u8,
+    seq_tier_0: bool,
+    high_bitdepth: bool,
+    twelve_bit: bool,
+    monochrome: bool,
+    chroma_subsampling_x: bool,
+    chroma_subsampling_y: bool,
+    chroma_sample_position: u8,
+    initial_presentation_delay_minus_one: Option<u8>,
+    config_obus: Vec<u8>
+}
+
+impl Av1CodecConfigurationRecord {
+    /// Gets the seq_profile field.
+    pub fn get_seq_profile(&self) -> u8 {
+        self.seq_profile
+    }
+
+    /// Gets the seq_level_idx_0 field.
+    pub fn get_seq_level_idx(&self) -> u8 {
+        self.seq_level_idx_0
+    }
+
+    /// Gets the bit depth of samples, that is, either 8, 10 or 12.
+    pub fn get_bit_depth(&self) -> u8 {
+        match (self.high_bitdepth, self.twelve_bit) {
+            (false, _) => 8,
+            (true, false) => 10,
+            (true, true) => 12
+        }
+    }
+
+    /// Indicates whether samples are monochrome.
+    pub fn is_monochrome(&self) -> bool {
+        self.monochrome
+    }
+
+    /// Gets the OBUs (e.g. the sequence header OBU) which are required to configure the decoder.
+    pub fn get_config_obus(&self) -> &[u8] {
+        &self.config_obus
+    }
+}
+
+impl Decoder<Av1CodecConfigurationRecord> for ByteBuffer {
+    /// Decodes bytes into an AV1CodecConfigurationRecord.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::Av1CodecConfigurationRecord
+    /// };
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x81, 0x08, 0x4c, 0x00, 0x0a, 0x0b].into();
+    /// let record: Av1CodecConfigurationRecord = buffer.decode().unwrap();
+    /// assert_eq!(0, record.get_seq_profile());
+    /// assert_eq!(8, record.get_seq_level_idx());
+    /// assert_eq!(10, record.get_bit_depth());
+    /// assert_eq!(&[0x0a, 0x0b], record.get_config_obus());
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x81, 0x08].into();
+    /// assert!(Decoder::<Av1CodecConfigurationRecord>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<Av1CodecConfigurationRecord> {
+        /* NOTE: Both the marker and the version are fixed to 1. */
+        self.get_u8()?;
+        let byte = self.get_u8()?;
+        let seq_profile = byte >> 5;
+        let seq_level_idx_0 = byte & 0x1f;
+        let byte = self.get_u8()?;
+        let seq_tier_0 = (byte & 0x80) != 0;
+        let high_bitdepth = (byte & 0x40) != 0;
+        let twelve_bit = (byte & 0x20) != 0;
+        let monochrome = (byte & 0x10) != 0;
+        let chroma_subsampling_x = (byte & 0x08) != 0;
+        let chroma_subsampling_y = (byte & 0x04) != 0;
+        let chroma_sample_position = byte & 0x03;
+        let byte = self.get_u8()?;
+        let initial_presentation_delay_minus_one = if (byte & 0x10) == 0 {
+            None
+        } else {
+            Some(byte & 0x0f)
+        };
+        let remained = self.remained();
+        let config_obus = self.get_bytes(remained)?.to_vec();
+
+        Ok(
+            Av1CodecConfigurationRecord {
+                seq_profile,
+                seq_level_idx_0,
+                seq_tier_0,
+                high_bitdepth,
+                twelve_bit,
+                monochrome,
+                chroma_subsampling_x,
+                chroma_subsampling_y,
+                chroma_sample_position,
+                initial_presentation_delay_minus_one,
+                config_obus
+            }
+        )
+    }
+}
+
+impl Encoder<Av1CodecConfigurationRecord> for ByteBuffer {
+    /// Encodes an AV1CodecConfigurationRecord into bytes.
+    fn encode(&mut self, record: &Av1CodecConfigurationRecord) {
+        self.put_u8(0x81);
+        self.put_u8((record.seq_profile << 5) | record.seq_level_idx_0);
+
+        let mut byte = u8::from(record.seq_tier_0) << 7;
+        byte |= u8::from(record.high_bitdepth) << 6;
+        byte |= u8::from(record.twelve_bit) << 5;
+        byte |= u8::from(record.monochrome) << 4;
+        byte |= u8::from(record.chroma_subsampling_x) << 3;
+        byte |= u8::from(record.chroma_subsampling_y) << 2;
+        byte |= record.chroma_sample_position;
+        self.put_u8(byte);
+
+        match record.initial_presentation_delay_minus_one {
+            Some(initial_presentation_delay_minus_one) => self.put_u8(0x10 | initial_presentation_delay_minus_one),
+            None => self.put_u8(0)
+        }
+        self.put_bytes(&record.config_obus);
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder
+};
+
+/// The AVCDecoderConfigurationRecord which is defined in ISO/IEC 14496-15.
+///
+/// This is sent as the body of AVC's sequence header, that is, before any coded frame.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |configurationVersion|8 bits (fixed to `1`)|
+/// |AVCProfileIndication|8 bits|
+/// |profile_compatibility|8 bits|
+/// |AVCLevelIndication|8 bits|
+/// |lengthSizeMinusOne|2 bits (preceded by 6 reserved bits)|
+/// |numOfSequenceParameterSets|5 bits (preceded by 3 reserved bits)|
+/// |sequenceParameterSets|16 bits length and NAL unit in every one|
+/// |numOfPictureParameterSets|8 bits|
+/// |pictureParameterSets|16 bits length and NAL unit in every one|
+///
+/// Any field after them (e.g. the chroma format of the High profile) is kept as it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvcDecoderConfigurationRecord {
+    profile_indication: u8,
+    profile_compatibility: u8,
+    level_indication: u8,
+    length_size_minus_one: u8,
+    sequence_parameter_sets: Vec<Vec<u8>>,
+    picture_parameter_sets: Vec<Vec<u8>>,
+    extensions: Vec<u8>
+}
+
+impl AvcDecoderConfigurationRecord {
+    /// Constructs an AVCDecoderConfigurationRecord.
+    pub fn new(profile_indication: u8, profile_compatibility: u8, level_indication: u8, length_size_minus_one: u8, sequence_parameter_sets: Vec<Vec<u8>>, picture_parameter_sets: Vec<Vec<u8>>) -> Self {
+        Self {
+            profile_indication,
+            profile_compatibility,
+            level_indication,
+            length_size_minus_one,
+            sequence_parameter_sets,
+            picture_parameter_sets,
+            extensions: Vec::default()
+        }
+    }
+
+    /// Gets the AVCProfileIndication field.
+    pub fn get_profile_indication(&self) -> u8 {
+        self.profile_indication
+    }
+
+    /// Gets the AVCLevelIndication field.
+    pub fn get_level_indication(&self) -> u8 {
+        self.level_indication
+    }
+
+    /// Gets the byte length of NAL unit lengths in coded frames.
+    pub fn get_nal_unit_length_size(&self) -> u8 {
+        self.length_size_minus_one + 1
+    }
+
+    /// Gets SPS NAL units.
+    pub fn get_sequence_parameter_sets(&self) -> &[Vec<u8>] {
+        &self.sequence_parameter_sets
+    }
+
+    /// Gets PPS NAL units.
+    pub fn get_picture_parameter_sets(&self) -> &[Vec<u8>] {
+        &self.picture_parameter_sets
+    }
+}
+
+fn get_parameter_sets(buffer: &mut ByteBuffer, count: u8) -> IOResult<Vec<Vec<u8>>> {
+    let mut parameter_sets: Vec<Vec<u8>> = Vec::new();
+    for _ in 0..count {
+        let len = buffer.get_u16_be()?;
+        parameter_sets.push(buffer.get_bytes(len as usize)?.to_vec());
+    }
+    Ok(parameter_sets)
+}
+
+impl Decoder<AvcDecoderConfigurationRecord> for ByteBuffer {
+    /// Decodes bytes into an AVCDecoderConfigurationRecord.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses, or some parameter set is shorter than its length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::AvcDecoderConfigurationRecord
+    /// };
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x01, 0x64, 0x00, 0x1f, 0xff, 0xe1, 0x00, 0x02, 0x67, 0x64, 0x01, 0x00, 0x01, 0x68].into();
+    /// let record: AvcDecoderConfigurationRecord = buffer.decode().unwrap();
+    /// assert_eq!(0x64, record.get_profile_indication());
+    /// assert_eq!(4, record.get_nal_unit_length_size());
+    /// assert_eq!(&[vec![0x67, 0x64]], record.get_sequence_parameter_sets());
+    /// assert_eq!(&[vec![0x68]], record.get_picture_parameter_sets());
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x01, 0x64, 0x00, 0x1f, 0xff, 0xe1, 0x00, 0x02, 0x67].into();
+    /// assert!(Decoder::<AvcDecoderConfigurationRecord>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<AvcDecoderConfigurationRecord> {
+        /* NOTE: The configurationVersion is fixed to 1. */
+        self.get_u8()?;
+        let profile_indication = self.get_u8()?;
+        let profile_compatibility = self.get_u8()?;
+        let level_indication = self.get_u8()?;
+        let length_size_minus_one = self.get_u8()? & 0x03;
+        let sps_count = self.get_u8()? & 0x1f;
+        let sequence_parameter_sets = get_parameter_sets(self, sps_count)?;
+        let pps_count = self.get_u8()?;
+        let picture_parameter_sets = get_parameter_sets(self, pps_count)?;
+        let remained = self.remained();
+        let extensions = self.get_bytes(remained)?.to_vec();
+
+        Ok(
+            AvcDecoderConfigurationRecord {
+                profile_indication,
+                profile_compatibility,
+                level_indication,
+                length_size_minus_one,
+                sequence_parameter_sets,
+                picture_parameter_sets,
+                extensions
+            }
+        )
+    }
+}
+
+impl Encoder<AvcDecoderConfigurationRecord> for ByteBuffer {
+    /// Encodes an AVCDecoderConfigurationRecord into bytes.
+    fn encode(&mut self, record: &AvcDecoderConfigurationRecord) {
+        self.put_u8(1);
+        self.put_u8(record.profile_indication);
+        self.put_u8(record.profile_compatibility);
+        self.put_u8(record.level_indication);
+        self.put_u8(0xfc | record.length_size_minus_one);
+        self.put_u8(0xe0 | record.sequence_parameter_sets.len() as u8);
+        for sps in record.sequence_parameter_sets.iter() {
+            self.put_u16_be(sps.len() as u16);
+            self.put_bytes(sps);
+        }
+        self.put_u8(record.picture_parameter_sets.len() as u8);
+        for pps in record.picture_parameter_sets.iter() {
+            self.put_u16_be(pps.len() as u16);
+            self.put_bytes(pps);
+        }
+        self.put_bytes(&record.extensions);
+    }
+}
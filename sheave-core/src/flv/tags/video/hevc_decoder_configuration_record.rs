@@ -0,0 +1,254 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder
+};
+
+/// An array of NAL units of same type in the HEVCDecoderConfigurationRecord.
+///
+/// e.g. VPS, SPS, PPS and SEI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NalUnitArray {
+    array_completeness: bool,
+    nal_unit_type: u8,
+    nal_units: Vec<Vec<u8>>
+}
+
+impl NalUnitArray {
+    /// Constructs a NalUnitArray.
+    pub fn new(array_completeness: bool, nal_unit_type: u8, nal_units: Vec<Vec<u8>>) -> Self {
+        Self { array_completeness, nal_unit_type, nal_units }
+    }
+
+    /// Indicates whether all NAL units of this type are in this array.
+    pub fn is_complete(&self) -> bool {
+        self.array_completeness
+    }
+
+    /// Gets the NAL unit type. (e.g. `32` for VPS, `33` for SPS, `34` for PPS.)
+    pub fn get_nal_unit_type(&self) -> u8 {
+        self.nal_unit_type
+    }
+
+    /// Gets NAL units in this array.
+    pub fn get_nal_units(&self) -> &[Vec<u8>] {
+        &self.nal_units
+    }
+}
+
+/// The HEVCDecoderConfigurationRecord which is defined in ISO/IEC 14496-15.
+///
+/// This is sent as the body of the SequenceStart packet of `hvc1`.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |configurationVersion|8 bits (fixed to `1`)|
+/// |general_profile_space|2 bits|
+/// |general_tier_flag|1 bit|
+/// |general_profile_idc|5 bits|
+/// |general_profile_compatibility_flags|32 bits|
+/// |general_constraint_indicator_flags|48 bits|
+/// |general_level_idc|8 bits|
+/// |min_spatial_segmentation_idc|12 bits (preceded by 4 reserved bits)|
+/// |parallelismType|2 bits (preceded by 6 reserved bits)|
+/// |chromaFormat|2 bits (preceded by 6 reserved bits)|
+/// |bitDepthLumaMinus8|3 bits (preceded by 5 reserved bits)|
+/// |bitDepthChromaMinus8|3 bits (preceded by 5 reserved bits)|
+/// |avgFrameRate|16 bits|
+/// |constantFrameRate|2 bits|
+/// |numTemporalLayers|3 bits|
+/// |temporalIdNested|1 bit|
+/// |lengthSizeMinusOne|2 bits|
+/// |numOfArrays|8 bits|
+/// |arrays|See [`NalUnitArray`]|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HevcDecoderConfigurationRecord {
+    general_profile_space: u8,
+    general_tier_flag: bool,
+    general_profile_idc: u8,
+    general_profile_compatibility_flags: u32,
+    general_constraint_indicator_flags: u64,
+    general_level_idc: u8,
+    min_spatial_segmentation_idc: u16,
+    parallelism_type: u8,
+    chroma_format: u8,
+    bit_depth_luma_minus8: u8,
+    bit_depth_chroma_minus8: u8,
+    avg_frame_rate: u16,
+    constant_frame_rate: u8,
+    num_temporal_layers: u8,
+    temporal_id_nested: bool,
+    length_size_minus_one: u8,
+    arrays: Vec<NalUnitArray>
+}
+
+impl HevcDecoderConfigurationRecord {
+    /// Gets the general_profile_idc field.
+    pub fn get_profile_idc(&self) -> u8 {
+        self.general_profile_idc
+    }
+
+    /// Indicates whether the tier is High.
+    pub fn is_high_tier(&self) -> bool {
+        self.general_tier_flag
+    }
+
+    /// Gets the general_level_idc field.
+    pub fn get_level_idc(&self) -> u8 {
+        self.general_level_idc
+    }
+
+    /// Gets the chromaFormat field. (`0`: monochrome, `1`: 4:2:0, `2`: 4:2:2, `3`: 4:4:4)
+    pub fn get_chroma_format(&self) -> u8 {
+        self.chroma_format
+    }
+
+    /// Gets the bit depth of luma samples.
+    pub fn get_bit_depth_luma(&self) -> u8 {
+        self.bit_depth_luma_minus8 + 8
+    }
+
+    /// Gets the bit depth of chroma samples.
+    pub fn get_bit_depth_chroma(&self) -> u8 {
+        self.bit_depth_chroma_minus8 + 8
+    }
+
+    /// Gets the byte length of NAL unit lengths in coded frames.
+    pub fn get_nal_unit_length_size(&self) -> u8 {
+        self.length_size_minus_one + 1
+    }
+
+    /// Gets arrays of NAL units such as VPS, SPS and PPS.
+    pub fn get_arrays(&self) -> &[NalUnitArray] {
+        &self.arrays
+    }
+}
+
+impl Decoder<HevcDecoderConfigurationRecord> for ByteBuffer {
+    /// Decodes bytes into an HEVCDecoderConfigurationRecord.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses, or some NAL unit is shorter than its length.
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<HevcDecoderConfigurationRecord> {
+        /* NOTE: The configurationVersion is fixed to 1. */
+        self.get_u8()?;
+        let byte = self.get_u8()?;
+        let general_profile_space = byte >> 6;
+        let general_tier_flag = (byte & 0x20) != 0;
+        let general_profile_idc = byte & 0x1f;
+        let general_profile_compatibility_flags = self.get_u32_be()?;
+        let general_constraint_indicator_flags = (u64::from(self.get_u16_be()?) << 32) | u64::from(self.get_u32_be()?);
+        let general_level_idc = self.get_u8()?;
+        let min_spatial_segmentation_idc = self.get_u16_be()? & 0x0fff;
+        let parallelism_type = self.get_u8()? & 0x03;
+        let chroma_format = self.get_u8()? & 0x03;
+        let bit_depth_luma_minus8 = self.get_u8()? & 0x07;
+        let bit_depth_chroma_minus8 = self.get_u8()? & 0x07;
+        let avg_frame_rate = self.get_u16_be()?;
+        let byte = self.get_u8()?;
+        let constant_frame_rate = byte >> 6;
+        let num_temporal_layers = (byte >> 3) & 0x07;
+        let temporal_id_nested = (byte & 0x04) != 0;
+        let length_size_minus_one = byte & 0x03;
+
+        let array_count = self.get_u8()?;
+        let mut arrays: Vec<NalUnitArray> = Vec::new();
+        for _ in 0..array_count {
+            let byte = self.get_u8()?;
+            let array_completeness = (byte & 0x80) != 0;
+            let nal_unit_type = byte & 0x3f;
+            let nal_unit_count = self.get_u16_be()?;
+            let mut nal_units: Vec<Vec<u8>> = Vec::new();
+            for _ in 0..nal_unit_count {
+                let len = self.get_u16_be()?;
+                nal_units.push(self.get_bytes(len as usize)?.to_vec());
+            }
+            arrays.push(NalUnitArray { array_completeness, nal_unit_type, nal_units });
+        }
+
+        Ok(
+            HevcDecoderConfigurationRecord {
+                general_profile_space,
+                general_tier_flag,
+                general_profile_idc,
+                general_profile_compatibility_flags,
+                general_constraint_indicator_flags,
+                general_level_idc,
+                min_spatial_segmentation_idc,
+                parallelism_type,
+                chroma_format,
+                bit_depth_luma_minus8,
+                bit_depth_chroma_minus8,
+                avg_frame_rate,
+                constant_frame_rate,
+                num_temporal_layers,
+                temporal_id_nested,
+                length_size_minus_one,
+                arrays
+            }
+        )
+    }
+}
+
+impl Encoder<HevcDecoderConfigurationRecord> for ByteBuffer {
+    /// Encodes an HEVCDecoderConfigurationRecord into bytes.
+    fn encode(&mut self, record: &HevcDecoderConfigurationRecord) {
+        self.put_u8(1);
+        self.put_u8((record.general_profile_space << 6) | (u8::from(record.general_tier_flag) << 5) | record.general_profile_idc);
+        self.put_u32_be(record.general_profile_compatibility_flags);
+        self.put_u16_be((record.general_constraint_indicator_flags >> 32) as u16);
+        self.put_u32_be(record.general_constraint_indicator_flags as u32);
+        self.put_u8(record.general_level_idc);
+        self.put_u16_be(0xf000 | record.min_spatial_segmentation_idc);
+        self.put_u8(0xfc | record.parallelism_type);
+        self.put_u8(0xfc | record.chroma_format);
+        self.put_u8(0xf8 | record.bit_depth_luma_minus8);
+        self.put_u8(0xf8 | record.bit_depth_chroma_minus8);
+        self.put_u16_be(record.avg_frame_rate);
+        self.put_u8((record.constant_frame_rate << 6) | (record.num_temporal_layers << 3) | (u8::from(record.temporal_id_nested) << 2) | record.length_size_minus_one);
+
+        self.put_u8(record.arrays.len() as u8);
+        for array in record.arrays.iter() {
+            self.put_u8((u8::from(array.array_completeness) << 7) | array.nal_unit_type);
+            self.put_u16_be(array.nal_units.len() as u16);
+            for nal_unit in array.nal_units.iter() {
+                self.put_u16_be(nal_unit.len() as u16);
+                self.put_bytes(nal_unit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_hevc_decoder_configuration_record() {
+        let bytes: Vec<u8> = vec![
+            0x01, 0x01, 0x60, 0x00, 0x00, 0x00, 0x90, 0x00, 0x00, 0x00, 0x00, 0x00, 0x5d, 0xf0, 0x00, 0xfc,
+            0xfd, 0xf8, 0xf8, 0x00, 0x00, 0x0f, 0x03,
+            0xa0, 0x00, 0x01, 0x00, 0x02, 0x40, 0x01,
+            0xa1, 0x00, 0x01, 0x00, 0x02, 0x42, 0x01,
+            0xa2, 0x00, 0x01, 0x00, 0x02, 0x44, 0x01
+        ];
+        let mut buffer: ByteBuffer = bytes.clone().into();
+        let record: HevcDecoderConfigurationRecord = buffer.decode().unwrap();
+        assert_eq!(1, record.get_profile_idc());
+        assert_eq!(93, record.get_level_idc());
+        assert_eq!(1, record.get_chroma_format());
+        assert_eq!(4, record.get_nal_unit_length_size());
+        assert_eq!(3, record.get_arrays().len());
+        assert_eq!(33, record.get_arrays()[1].get_nal_unit_type());
+
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&record);
+        assert_eq!(bytes, Vec::<u8>::from(buffer))
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder
+};
+
+/// The VPCodecConfigurationRecord which is defined in the VP Codec ISO Media File Format Binding.
+///
+/// This is sent as the body of the SequenceStart packet of `vp08` and `vp09`.
+///
+/// |Field|Length|
+/// | :- | :- |
+/// |profile|8 bits|
+/// |level|8 bits|
+/// |bitDepth|4 bits|
+/// |chromaSubsampling|3 bits|
+/// |videoFullRangeFlag|1 bit|
+/// |colourPrimaries|8 bits|
+/// |transferCharacteristics|8 bits|
+/// |matrixCoefficients|8 bits|
+/// |codecIntializationDataSize|16 bits|
+/// |codecIntializationData|Same as codecIntializationDataSize|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VpCodecConfigurationRecord {
+    profile: u8,
+    level: u8,
+    bit_depth: u8,
+    chroma_subsampling: u8,
+    video_full_range_flag: bool,
+    colour_primaries: u8,
+    transfer_characteristics: u8,
+    matrix_coefficients: u8,
+    codec_initialization_data: Vec<u8>
+}
+
+impl VpCodecConfigurationRecord {
+    /// Gets the profile field.
+    pub fn get_profile(&self) -> u8 {
+        self.profile
+    }
+
+    /// Gets the level field.
+    pub fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    /// Gets the bit depth of samples.
+    pub fn get_bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+
+    /// Gets the chromaSubsampling field.
+    pub fn get_chroma_subsampling(&self) -> u8 {
+        self.chroma_subsampling
+    }
+
+    /// Indicates whether samples are in the full range.
+    pub fn is_full_range(&self) -> bool {
+        self.video_full_range_flag
+    }
+}
+
+impl Decoder<VpCodecConfigurationRecord> for ByteBuffer {
+    /// Decodes bytes into a VPCodecConfigurationRecord.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses, or the codec initialization data is shorter than its size.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     flv::tags::VpCodecConfigurationRecord
+    /// };
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x00, 0x1f, 0x82, 0x01, 0x01, 0x01, 0x00, 0x00].into();
+    /// let record: VpCodecConfigurationRecord = buffer.decode().unwrap();
+    /// assert_eq!(0, record.get_profile());
+    /// assert_eq!(31, record.get_level());
+    /// assert_eq!(8, record.get_bit_depth());
+    /// assert_eq!(1, record.get_chroma_subsampling());
+    ///
+    /// let mut buffer: ByteBuffer = vec![0x00, 0x1f, 0x82, 0x01, 0x01, 0x01, 0x00, 0x01].into();
+    /// assert!(Decoder::<VpCodecConfigurationRecord>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<VpCodecConfigurationRecord> {
+        let profile = self.get_u8()?;
+        let level = self.get_u8()?;
+        let byte = self.get_u8()?;
+        let bit_depth = byte >> 4;
+        let chroma_subsampling = (byte >> 1) & 0x07;
+        let video_full_range_flag = (byte & 0x01) != 0;
+        let colour_primaries = self.get_u8()?;
+        let transfer_characteristics = self.get_u8()?;
+        let matrix_coefficients = self.get_u8()?;
+        let len = self.get_u16_be()?;
+        let codec_initialization_data = self.get_bytes(len as usize)?.to_vec();
+
+        Ok(
+            VpCodecConfigurationRecord {
+                profile,
+                level,
+                bit_depth,
+                chroma_subsampling,
+                video_full_range_flag,
+                colour_primaries,
+                transfer_characteristics,
+                matrix_coefficients,
+                codec_initialization_data
+            }
+        )
+    }
+}
+
+impl Encoder<VpCodecConfigurationRecord> for ByteBuffer {
+    /// Encodes a VPCodecConfigurationRecord into bytes.
+    fn encode(&mut self, record: &VpCodecConfigurationRecord) {
+        self.put_u8(record.profile);
+        self.put_u8(record.level);
+        self.put_u8((record.bit_depth << 4) | (record.chroma_subsampling << 1) | u8::from(record.video_full_range_flag));
+        self.put_u8(record.colour_primaries);
+        self.put_u8(record.transfer_characteristics);
+        self.put_u8(record.matrix_coefficients);
+        self.put_u16_be(record.codec_initialization_data.len() as u16);
+        self.put_bytes(&record.codec_initialization_data);
+    }
+}
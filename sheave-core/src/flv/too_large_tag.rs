@@ -0,0 +1,35 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some FLV tag exceeds the maximum size which is allowed to read.
+#[derive(Debug)]
+pub struct TooLargeTag {
+    data_size: u32,
+    max_tag_size: u32
+}
+
+impl Display for TooLargeTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "FLV tag is too large. data size: {}, max tag size: {}", self.data_size, self.max_tag_size)
+    }
+}
+
+impl Error for TooLargeTag {}
+
+/// A utility function of constructing a `TooLargeTag` error.
+pub fn too_large_tag(data_size: u32, max_tag_size: u32) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        TooLargeTag { data_size, max_tag_size }
+    )
+}
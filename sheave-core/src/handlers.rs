@@ -1,6 +1,7 @@
 mod rtmp_context;
 mod inconsistent_sha;
-mod stream_wrapper;
+mod stream_reader;
+mod stream_writer;
 mod vec_stream;
 mod status;
 mod measure_acknowledgement;
@@ -10,15 +11,14 @@ mod middlewares;
 mod map_err;
 mod stream_got_exhausted;
 mod client_type;
+mod unknown_message_policy;
+mod flow_control;
+mod capabilities;
 
 use std::{
+    future::Future,
     io::Result as IOResult,
-    pin::Pin,
-    sync::Arc,
-    task::{
-        Context as FutureContext,
-        Poll
-    }
+    pin::Pin
 };
 use tokio::io::{
     AsyncRead,
@@ -39,81 +39,89 @@ use self::{
 pub use self::{
     rtmp_context::*,
     inconsistent_sha::*,
-    stream_wrapper::*,
+    stream_reader::*,
+    stream_writer::*,
     vec_stream::*,
     status::*,
     middlewares::Middleware,
     map_err::ErrorHandler,
     measure_acknowledgement::*,
     stream_got_exhausted::*,
-    client_type::*
+    client_type::*,
+    unknown_message_policy::*,
+    flow_control::*,
+    capabilities::*
 };
 
 /// The interface for handling RTMP connection steps with `Future`.
 ///
 /// This trait unifies surfaces of handler APIs:
 ///
-/// * `RtmpContext` is required.
+/// * Both halves of the stream and `RtmpContext` are required.
 /// * Terminating with unit (`()`) is required.
 ///
-/// The first requirement makes `RtmpContext` reusable for upper APIs.
+/// The first requirement makes handlers borrow the stream and `RtmpContext` only while handling, that is, handlers hold no reference to them.
+/// Therefore every handler which is chained can take turns to use them without any aliasing.
 /// And the second requirement makes handlers return `Ok(())` when successfully terminates because currently they are run on `main`.
 ///
+/// The stream is passed as its read half and its write half separately.
+/// These are independent of each other, so that reading and writing can be performed at the same time, or even from separate tasks.
+///
 /// ```rust
 /// use std::{
 ///     io::Result as IOResult,
-///     pin::Pin,
-///     sync::Arc,
-///     task::{
-///         Context as FutureContext,
-///         Poll
+///     pin::{
+///         Pin,
+///         pin
 ///     }
 /// };
-/// use futures::future::poll_fn;
 /// use tokio::io::{
 ///     AsyncRead,
-///     AsyncWrite
+///     AsyncWrite,
+///     split
 /// };
 /// use sheave_core::handlers::{
 ///     AsyncHandler,
+///     MeasureAcknowledgement,
 ///     RtmpContext
 /// };
 ///
-/// struct SomethingHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
+/// struct SomethingHandler;
 ///
-/// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for SomethingHandler<RW> {
-///     fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-///         // Something to handle
+/// impl AsyncHandler for SomethingHandler {
+///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+///     where
+///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+///         W: AsyncWrite + Unpin + Send
+///     {
+///         // Something to handle.
 ///
-///         Poll::Ready(Ok(()))
+///         Ok(())
 ///     }
 /// }
 ///
 /// #[tokio::main]
 /// async fn main() -> IOResult<()> {
+///     use sheave_core::handlers::{
+///         StreamReader,
+///         StreamWriter,
+///         VecStream
+///     };
+///
 ///     // Consider this is Tokio's `JoinHandle` which is run on `main`.
-///     poll_fn(
-///         |cx| {
-///             use std::{
-///                 pin::pin,
-///                 sync::Arc
-///             };
-///             use sheave_core::handlers::{
-///                 AsyncHandler,
-///                 VecStream,
-///                 StreamWrapper
-///             };
-///
-///             let stream = Arc::new(StreamWrapper::new(VecStream::default()));
-///             pin!(SomethingHandler(stream)).poll_handle(cx, &mut RtmpContext::default())
-///         }
-///     ).await
+///     let (reader, writer) = split(VecStream::default());
+///     let mut reader = pin!(StreamReader::new(reader));
+///     let mut writer = pin!(StreamWriter::new(writer));
+///     SomethingHandler.handle(reader.as_mut(), writer.as_mut(), &mut RtmpContext::default()).await
 /// }
 /// ```
 ///
 /// [`RtmpContext`]: RtmpContext
 pub trait AsyncHandler {
-    fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>>;
+    fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> impl Future<Output = IOResult<()>> + Send
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send;
 }
 
 /// The extension methods for handlers.
@@ -137,66 +145,69 @@ pub trait AsyncHandlerExt: AsyncHandler {
     /// ```rust
     /// use std::{
     ///     io::Result as IOResult,
-    ///     pin::Pin,
-    ///     sync::Arc,
-    ///     task::{
-    ///         Context as FutureContext,
-    ///         Poll
+    ///     pin::{
+    ///         Pin,
+    ///         pin
     ///     }
     /// };
-    /// use futures::future::poll_fn;
     /// use tokio::io::{
     ///     AsyncRead,
-    ///     AsyncWrite
+    ///     AsyncWrite,
+    ///     split
     /// };
     /// use sheave_core::handlers::{
     ///     AsyncHandler,
+    ///     MeasureAcknowledgement,
     ///     RtmpContext
     /// };
     ///
-    /// struct HandlerA<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
-    /// struct HandlerB<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
+    /// struct HandlerA;
     ///
-    /// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for HandlerA<RW> {
-    ///     fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
+    /// impl AsyncHandler for HandlerA {
+    ///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send
+    ///     {
     ///         // Something to handle.
     ///
-    ///         Poll::Ready(Ok(()))
+    ///         Ok(())
     ///     }
     /// }
     ///
-    /// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for HandlerB<RW> {
-    ///     fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
+    /// struct HandlerB;
+    ///
+    /// impl AsyncHandler for HandlerB {
+    ///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send
+    ///     {
     ///         // Something to handle.
     ///
-    ///         Poll::Ready(Ok(()))
+    ///         Ok(())
     ///     }
     /// }
     ///
     /// #[tokio::main]
     /// async fn main() -> IOResult<()> {
-    ///     poll_fn(
-    ///         |cx| {
-    ///             use std::pin::pin;
-    ///             use sheave_core::handlers::{
-    ///                 AsyncHandlerExt,
-    ///                 StreamWrapper,
-    ///                 VecStream
-    ///             };
-    ///
-    ///             let stream = Arc::new(StreamWrapper::new(VecStream::default()));
-    ///             pin!(
-    ///                 HandlerA(Arc::clone(&stream))
-    ///                     .chain(HandlerB(Arc::clone(&stream)))
-    ///             ).poll_handle(cx, &mut RtmpContext::default())
-    ///         }
-    ///     ).await
+    ///     use sheave_core::handlers::{
+    ///         AsyncHandlerExt,
+    ///         StreamReader,
+    ///         StreamWriter,
+    ///         VecStream
+    ///     };
+    ///
+    ///     let (reader, writer) = split(VecStream::default());
+    ///     let mut reader = pin!(StreamReader::new(reader));
+    ///     let mut writer = pin!(StreamWriter::new(writer));
+    ///     HandlerA.chain(HandlerB).handle(reader.as_mut(), writer.as_mut(), &mut RtmpContext::default()).await
     /// }
     /// ```
     fn chain<H>(self, next: H) -> Chain<Self, H>
     where
-        H: AsyncHandler + Unpin,
-        Self: Sized + Unpin
+        H: AsyncHandler,
+        Self: Sized
     {
         chain(self, next)
     }
@@ -208,78 +219,78 @@ pub trait AsyncHandlerExt: AsyncHandler {
     /// ```rust
     /// use std::{
     ///     io::Result as IOResult,
-    ///     pin::Pin,
-    ///     sync::Arc,
-    ///     task::{
-    ///         Context as FutureContext,
-    ///         Poll
+    ///     pin::{
+    ///         Pin,
+    ///         pin
     ///     }
     /// };
-    /// use futures::{
-    ///     future::poll_fn,
-    ///     ready
-    /// };
     /// use tokio::io::{
     ///     AsyncRead,
-    ///     AsyncWrite
+    ///     AsyncWrite,
+    ///     split
     /// };
     /// use sheave_core::handlers::{
     ///     AsyncHandler,
+    ///     MeasureAcknowledgement,
     ///     Middleware,
     ///     RtmpContext
     /// };
     ///
-    /// struct SomethingHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
+    /// struct SomethingHandler;
     ///
-    /// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for SomethingHandler<RW> {
-    ///     fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
+    /// impl AsyncHandler for SomethingHandler {
+    ///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send
+    ///     {
     ///         // Something to handle.
     ///
-    ///         Poll::Ready(Ok(()))
+    ///         Ok(())
     ///     }
     /// }
     ///
-    /// struct SomethingMiddleware<'a, W: Unpin>(Pin<&'a mut W>);
+    /// struct SomethingMiddleware;
     ///
-    /// impl<W: Unpin> Middleware for SomethingMiddleware<'_, W> {
-    ///     fn poll_handle_wrapped<H: AsyncHandler + Unpin>(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext, handler: Pin<&mut H>) -> Poll<IOResult<()>> {
+    /// impl Middleware for SomethingMiddleware {
+    ///     async fn handle_wrapped<R, W, H>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext, handler: &mut H) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send,
+    ///         H: AsyncHandler + Send
+    ///     {
     ///         println!("Starts wrapping.");
-    ///         ready!(handler.poll_handle(cx, rtmp_context))?;
+    ///         handler.handle(reader, writer, rtmp_context).await?;
     ///         println!("Ends wrapping.");
-    ///         Poll::Ready(Ok(()))
+    ///         Ok(())
     ///     }
     /// }
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let result = poll_fn(
-    ///         |cx| {
-    ///             use std::pin::pin;
-    ///             use sheave_core::handlers::{
-    ///                 AsyncHandlerExt,
-    ///                 StreamWrapper,
-    ///                 VecStream
-    ///             };
-    ///
-    ///             let stream = Arc::new(StreamWrapper::new(VecStream::default()));
-    ///             pin!(
-    ///                 SomethingHandler(Arc::clone(&stream))
-    ///                     .wrap(SomethingMiddleware(stream.make_weak_pin()))
-    ///             ).poll_handle(cx, &mut RtmpContext::default())
-    ///         }
-    ///     ).await;
+    ///     use sheave_core::handlers::{
+    ///         AsyncHandlerExt,
+    ///         StreamReader,
+    ///         StreamWriter,
+    ///         VecStream
+    ///     };
+    ///
+    ///     let (reader, writer) = split(VecStream::default());
+    ///     let mut reader = pin!(StreamReader::new(reader));
+    ///     let mut writer = pin!(StreamWriter::new(writer));
+    ///     let result = SomethingHandler.wrap(SomethingMiddleware).handle(reader.as_mut(), writer.as_mut(), &mut RtmpContext::default()).await;
     ///     assert!(result.is_ok())
     /// }
     /// ```
     fn wrap<M>(self, middleware: M) -> Wrap<M, Self>
     where
-        M: Middleware + Unpin,
-        Self: Sized + Unpin
+        M: Middleware,
+        Self: Sized
     {
         wrap(middleware, self)
     }
 
-    /// Loops while the body returns `Ok(())` or `Pending`.
+    /// Loops while the body returns `Ok(())`.
     ///
     /// # Examples
     ///
@@ -290,66 +301,68 @@ pub trait AsyncHandlerExt: AsyncHandler {
     ///         ErrorKind,
     ///         Result as IOResult
     ///     },
-    ///     pin::Pin,
-    ///     sync::Arc,
-    ///     task::{
-    ///         Context as FutureContext,
-    ///         Poll
+    ///     pin::{
+    ///         Pin,
+    ///         pin
     ///     }
     /// };
-    /// use futures::future::poll_fn;
     /// use tokio::io::{
     ///     AsyncRead,
-    ///     AsyncWrite
+    ///     AsyncWrite,
+    ///     split
     /// };
     /// use sheave_core::handlers::{
     ///     AsyncHandler,
-    ///     RtmpContext,
-    ///     StreamWrapper
+    ///     MeasureAcknowledgement,
+    ///     RtmpContext
     /// };
     ///
-    /// struct SomethingHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
+    /// struct SomethingHandler;
     ///
-    /// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for SomethingHandler<RW> {
-    ///     fn poll_handle(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>, _rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
+    /// impl AsyncHandler for SomethingHandler {
+    ///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send
+    ///     {
     ///         // Something to handle.
     ///
-    ///         Poll::Ready(Ok(()))
+    ///         Ok(())
     ///     }
     /// }
     ///
-    /// struct AnotherHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
+    /// struct AnotherHandler;
     ///
-    /// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for AnotherHandler<RW> {
-    ///     fn poll_handle(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>, _rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-    ///         Poll::Ready(Err(IOError::from(ErrorKind::Other)))
+    /// impl AsyncHandler for AnotherHandler {
+    ///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send
+    ///     {
+    ///         Err(IOError::from(ErrorKind::Other))
     ///     }
     /// }
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let result = poll_fn(
-    ///         |cx| {
-    ///             use std::pin::pin;
-    ///             use sheave_core::handlers::{
-    ///                 AsyncHandlerExt,
-    ///                 VecStream
-    ///             };
-    ///
-    ///             let stream = Arc::new(StreamWrapper::new(VecStream::default()));
-    ///             pin!(
-    ///                 SomethingHandler(Arc::clone(&stream))
-    ///                     .while_ok(AnotherHandler(Arc::clone(&stream)))
-    ///             ).poll_handle(cx, &mut RtmpContext::default())
-    ///         }
-    ///     ).await;
+    ///     use sheave_core::handlers::{
+    ///         AsyncHandlerExt,
+    ///         StreamReader,
+    ///         StreamWriter,
+    ///         VecStream
+    ///     };
+    ///
+    ///     let (reader, writer) = split(VecStream::default());
+    ///     let mut reader = pin!(StreamReader::new(reader));
+    ///     let mut writer = pin!(StreamWriter::new(writer));
+    ///     let result = SomethingHandler.while_ok(AnotherHandler).handle(reader.as_mut(), writer.as_mut(), &mut RtmpContext::default()).await;
     ///     assert!(result.is_err())
     /// }
     /// ```
     fn while_ok<H>(self, body: H) -> WhileOk<Self, H>
     where
-        H: AsyncHandler + Unpin,
-        Self: Sized + Unpin
+        H: AsyncHandler,
+        Self: Sized
     {
         while_ok(self, body)
     }
@@ -364,68 +377,70 @@ pub trait AsyncHandlerExt: AsyncHandler {
     ///         Error as IOError,
     ///         Result as IOResult
     ///     },
-    ///     pin::Pin,
-    ///     sync::Arc,
-    ///     task::{
-    ///         Context as FutureContext,
-    ///         Poll
+    ///     pin::{
+    ///         Pin,
+    ///         pin
     ///     }
     /// };
-    /// use futures::future::poll_fn;
     /// use tokio::io::{
     ///     AsyncRead,
-    ///     AsyncWrite
+    ///     AsyncWrite,
+    ///     split
     /// };
     /// use sheave_core::handlers::{
     ///     AsyncHandler,
     ///     ErrorHandler,
+    ///     MeasureAcknowledgement,
     ///     RtmpContext
     /// };
     ///
-    /// struct SomethingHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
+    /// struct SomethingHandler;
     ///
-    /// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for SomethingHandler<RW> {
-    ///     fn poll_handle(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>, _rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-    ///         Poll::Ready(Err(IOError::other("Something Wrong.")))
+    /// impl AsyncHandler for SomethingHandler {
+    ///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send
+    ///     {
+    ///         Err(IOError::other("Something Wrong."))
     ///     }
     /// }
     ///
-    /// struct SomethingWrongHandler<'a, RW>(Pin<&'a mut RW>);
+    /// struct SomethingWrongHandler;
     ///
-    /// impl<RW> ErrorHandler for SomethingWrongHandler<'_, RW> {
-    ///     fn poll_handle_error(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>, _rtmp_context: &mut RtmpContext, error: IOError) -> Poll<IOResult<()>> {
+    /// impl ErrorHandler for SomethingWrongHandler {
+    ///     async fn handle_error<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext, error: IOError) -> IOResult<()>
+    ///     where
+    ///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+    ///         W: AsyncWrite + Unpin + Send
+    ///     {
     ///         println!("{error}");
     ///
     ///         // This `Ok` means that handled its error successfully.
-    ///         Poll::Ready(Ok(()))
+    ///         Ok(())
     ///     }
     /// }
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let result = poll_fn(
-    ///         |cx| {
-    ///             use std::pin::pin;
-    ///             use sheave_core::handlers::{
-    ///                 AsyncHandlerExt,
-    ///                 StreamWrapper,
-    ///                 VecStream
-    ///             };
-    ///
-    ///             let stream = Arc::new(StreamWrapper::new(VecStream::default()));
-    ///             pin!(
-    ///                 SomethingHandler(Arc::clone(&stream))
-    ///                     .map_err(SomethingWrongHandler(stream.make_weak_pin()))
-    ///             ).poll_handle(cx, &mut RtmpContext::default())
-    ///         }
-    ///     ).await;
+    ///     use sheave_core::handlers::{
+    ///         AsyncHandlerExt,
+    ///         StreamReader,
+    ///         StreamWriter,
+    ///         VecStream
+    ///     };
+    ///
+    ///     let (reader, writer) = split(VecStream::default());
+    ///     let mut reader = pin!(StreamReader::new(reader));
+    ///     let mut writer = pin!(StreamWriter::new(writer));
+    ///     let result = SomethingHandler.map_err(SomethingWrongHandler).handle(reader.as_mut(), writer.as_mut(), &mut RtmpContext::default()).await;
     ///     assert!(result.is_ok())
     /// }
     /// ```
     fn map_err<E>(self, error_handler: E) -> MapErr<Self, E>
     where
-        E: ErrorHandler + Unpin,
-        Self: Sized + Unpin
+        E: ErrorHandler,
+        Self: Sized
     {
         map_err(self, error_handler)
     }
@@ -435,10 +450,8 @@ impl<H: AsyncHandler> AsyncHandlerExt for H {}
 
 /// The interface for providing the way to construct any handler to clients/servers.
 ///
-/// Servers / Clients pass streams and contexts to any handler they contain.
-/// Here we are necessary to be careful that some stream can't clone. (e.g. sockets)
-/// But we need to share these while handling RTMP communication steps.
-/// Therefore this provides the way of cloning stream instances via the (smart) pointer.
+/// Servers / Clients own both halves of the stream and the context, then lend them to the handler they construct.
+/// That is, handlers are constructed without any stream, because they borrow it only while handling.
 ///
 /// # Examples
 ///
@@ -447,11 +460,7 @@ impl<H: AsyncHandler> AsyncHandlerExt for H {}
 ///     future::Future,
 ///     io::Result as IOResult,
 ///     marker::PhantomData,
-///     pin::{
-///         Pin,
-///         pin
-///     },
-///     sync::Arc,
+///     pin::Pin,
 ///     task::{
 ///         Context as FutureContext,
 ///         Poll
@@ -460,12 +469,16 @@ impl<H: AsyncHandler> AsyncHandlerExt for H {}
 /// use tokio::io::{
 ///     AsyncRead,
 ///     AsyncWrite,
-///     ReadBuf
+///     ReadBuf,
+///     split
 /// };
 /// use sheave_core::handlers::{
 ///     AsyncHandler,
 ///     HandlerConstructor,
-///     RtmpContext
+///     MeasureAcknowledgement,
+///     RtmpContext,
+///     StreamReader,
+///     StreamWriter
 /// };
 ///
 /// struct SomethingStream;
@@ -498,55 +511,53 @@ impl<H: AsyncHandler> AsyncHandlerExt for H {}
 ///     }
 /// }
 ///
-/// struct SomethingHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<RW>);
+/// struct SomethingHandler;
 ///
-/// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for SomethingHandler<RW> {
-///     fn poll_handle(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>, _rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
+/// impl AsyncHandler for SomethingHandler {
+///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+///     where
+///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+///         W: AsyncWrite + Unpin + Send
+///     {
 ///         // Something to handle.
 ///
-///         Poll::Ready(Ok(()))
+///         Ok(())
 ///     }
 /// }
 ///
-/// impl<RW: AsyncRead + AsyncWrite + Unpin> HandlerConstructor<RW> for SomethingHandler<RW> {
-///     fn new(stream: Arc<RW>) -> Self {
-///         Self(stream)
+/// impl HandlerConstructor for SomethingHandler {
+///     fn new() -> Self {
+///         Self
 ///     }
 /// }
 ///
-/// struct SomethingRunner<RW, C>
-/// where
-///     RW: AsyncRead + AsyncWrite + Unpin,
-///     C: HandlerConstructor<RW>
-/// {
-///     stream: Arc<RW>,
-///     rtmp_context: Arc<RtmpContext>,
-///     handler_constructor: PhantomData<C>
-/// }
-///
-/// impl<RW, C> SomethingRunner<RW, C>
-/// where
-///     RW: AsyncRead + AsyncWrite + Unpin,
-///     C: HandlerConstructor<RW>
-/// {
-///     pub fn new(stream: RW, rtmp_context: RtmpContext, handler_constructor: PhantomData<C>) -> Self {
-///         Self {
-///             stream: Arc::new(stream),
-///             rtmp_context: Arc::new(rtmp_context),
-///             handler_constructor
-///         }
+/// struct SomethingRunner(Pin<Box<dyn Future<Output = IOResult<()>> + Send>>);
+///
+/// impl SomethingRunner {
+///     pub fn new<RW, C>(stream: RW, rtmp_context: RtmpContext, _handler_constructor: PhantomData<C>) -> Self
+///     where
+///         RW: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+///         C: HandlerConstructor + Send + 'static
+///     {
+///         let (reader, writer) = split(stream);
+///         Self(
+///             Box::pin(
+///                 async move {
+///                     let mut reader = StreamReader::new(reader);
+///                     let mut writer = StreamWriter::new(writer);
+///                     let mut rtmp_context = rtmp_context;
+///                     C::new().handle(Pin::new(&mut reader), Pin::new(&mut writer), &mut rtmp_context).await
+///                 }
+///             )
+///         )
 ///     }
 /// }
 ///
-/// impl<RW, C> Future for SomethingRunner<RW, C>
-/// where
-///     RW: AsyncRead + AsyncWrite + Unpin,
-///     C: HandlerConstructor<RW>
-/// {
+/// impl Future for SomethingRunner {
 ///     type Output = IOResult<()>;
 ///
-///     fn poll(self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<Self::Output> {
-///         pin!(C::new(Arc::clone(&self.stream))).poll_handle(cx, self.rtmp_context.make_weak_mut())
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<Self::Output> {
+///         self.0.as_mut().poll(cx)
 ///     }
 /// }
 ///
@@ -554,13 +565,13 @@ impl<H: AsyncHandler> AsyncHandlerExt for H {}
 /// async fn main() {
 ///     let stream = SomethingStream;
 ///     let rtmp_context = RtmpContext::default();
-///     let handler_constructor = PhantomData::<SomethingHandler<SomethingStream>>;
+///     let handler_constructor = PhantomData::<SomethingHandler>;
 ///     let runner = SomethingRunner::new(stream, rtmp_context, handler_constructor);
 ///     let result = runner.await;
 ///
 ///     assert!(result.is_ok());
 /// }
 /// ```
-pub trait HandlerConstructor<RW: AsyncRead + AsyncWrite + Unpin>: AsyncHandler {
-    fn new(stream: Arc<RW>) -> Self;
+pub trait HandlerConstructor: AsyncHandler {
+    fn new() -> Self;
 }
@@ -0,0 +1,501 @@
+use std::{
+    cmp::min,
+    io::Result as IOResult
+};
+use log::warn;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    flv::tags::{
+        AudioFourCc,
+        AudioTag,
+        AudioTrack,
+        ExAudioTagHeader,
+        ExVideoTagHeader,
+        FlvTag,
+        SoundFormat,
+        TagType,
+        VideoFourCc,
+        VideoTag,
+        VideoTrack,
+        replace_data
+    },
+    messages::amf::v0::{
+        AmfString,
+        AmfValue,
+        Number,
+        Object,
+        Properties,
+        StrictArray
+    }
+};
+
+/// The key which means every FourCC in FourCC info maps.
+const WILDCARD: &str = "*";
+
+/// The length which is enough to decode any ExAudioTagHeader/ExVideoTagHeader.
+///
+/// That is, the PacketType byte, the AvMultitrackType byte, the FourCC and the CompositionTime.
+const MAX_EX_HEADER_LEN: usize = 16;
+
+/// The FourCC info map of either audio or video.
+///
+/// This consists of pairs of a FourCC and a mask which indicates what the peer can do with that codec.
+/// The wildcard (`"*"`) applies its mask to every FourCC which isn't listed explicitly.
+///
+/// |Mask|Value|
+/// | :- | :- |
+/// |[`CAN_DECODE`]|`0x01`|
+/// |[`CAN_ENCODE`]|`0x02`|
+/// |[`CAN_FORWARD`]|`0x04`|
+///
+/// [`CAN_DECODE`]: Capabilities::CAN_DECODE
+/// [`CAN_ENCODE`]: Capabilities::CAN_ENCODE
+/// [`CAN_FORWARD`]: Capabilities::CAN_FORWARD
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FourCcInfoMap<F> {
+    wildcard: u8,
+    entries: Vec<(F, u8)>
+}
+
+impl<F> Default for FourCcInfoMap<F> {
+    fn default() -> Self {
+        Self {
+            wildcard: u8::default(),
+            entries: Vec::default()
+        }
+    }
+}
+
+impl<F: Copy + PartialEq> FourCcInfoMap<F> {
+    /// Stores a mask of the FourCC.
+    ///
+    /// The mask which has been stored is replaced.
+    pub fn insert(&mut self, four_cc: F, mask: u8) {
+        match self.entries.iter_mut().find(|(key, _)| *key == four_cc) {
+            Some((_, value)) => *value = mask,
+            None => self.entries.push((four_cc, mask))
+        }
+    }
+
+    /// Sets a mask of the wildcard.
+    pub fn set_wildcard(&mut self, mask: u8) {
+        self.wildcard = mask;
+    }
+
+    /// Gets a mask of the wildcard.
+    pub fn get_wildcard(&self) -> u8 {
+        self.wildcard
+    }
+
+    /// Gets a mask of the FourCC.
+    ///
+    /// This returns the mask of the wildcard if the FourCC isn't listed explicitly.
+    pub fn get(&self, four_cc: F) -> u8 {
+        self.entries.iter().find(|(key, _)| *key == four_cc).map_or(self.wildcard, |(_, mask)| *mask)
+    }
+
+    /// Checks whether the peer can decode the FourCC.
+    pub fn can_decode(&self, four_cc: F) -> bool {
+        self.get(four_cc) & Capabilities::CAN_DECODE != 0
+    }
+
+    /// Checks whether no FourCC is available.
+    pub fn is_empty(&self) -> bool {
+        self.wildcard == 0 && self.entries.iter().all(|(_, mask)| *mask == 0)
+    }
+
+    fn negotiate(&self, peer: &Self) -> Self {
+        let mut negotiated = Self::default();
+        if peer.wildcard != 0 {
+            negotiated.wildcard = self.wildcard;
+        }
+        for four_cc in self.entries.iter().chain(peer.entries.iter()).map(|(four_cc, _)| *four_cc) {
+            let mask = if peer.get(four_cc) != 0 {
+                self.get(four_cc)
+            } else {
+                0
+            };
+            if mask != negotiated.get(four_cc) {
+                negotiated.insert(four_cc, mask);
+            }
+        }
+        negotiated
+    }
+}
+
+impl<F> FourCcInfoMap<F>
+where
+    F: Copy + PartialEq + TryFrom<[u8; 4]>,
+    [u8; 4]: From<F>
+{
+    fn insert_by_key(&mut self, key: &str, mask: u8) {
+        if key == WILDCARD {
+            self.wildcard = mask;
+        } else if let Some(four_cc) = <[u8; 4]>::try_from(key.as_bytes()).ok().and_then(|bytes| F::try_from(bytes).ok()) {
+            self.insert(four_cc, mask);
+        }
+    }
+
+    fn masks(&self) -> impl Iterator<Item = (String, u8)> + '_ {
+        (self.wildcard != 0).then(|| (WILDCARD.to_string(), self.wildcard))
+            .into_iter()
+            .chain(
+                self.entries.iter()
+                    .filter(|(_, mask)| *mask != 0)
+                    .map(|(four_cc, mask)| (String::from_utf8_lossy(&<[u8; 4]>::from(*four_cc)).into_owned(), *mask))
+            )
+    }
+
+    fn to_object(&self) -> Object {
+        let mut object = Object::default();
+        for (four_cc, mask) in self.masks() {
+            object.get_properties_mut().insert(&four_cc, Number::from(mask));
+        }
+        object
+    }
+}
+
+/// The capabilities of Enhanced RTMP which are negotiated via the `connect` command.
+///
+/// The client advertises following pairs in its command object, then the server echoes what it supports in the properties of its response.
+///
+/// |Name|AMF Type|Value|
+/// | :- | :- | :- |
+/// |`fourCcList`|[`StrictArray`]|FourCCs which are supported. (regarded as all of masks)|
+/// |`videoFourCcInfoMap`|[`Object`]|Pairs of a video FourCC and its mask. (See [`FourCcInfoMap`].)|
+/// |`audioFourCcInfoMap`|[`Object`]|Pairs of an audio FourCC and its mask. (See [`FourCcInfoMap`].)|
+/// |`capsEx`|[`Number`]|Extended capabilities. (See the table below.)|
+///
+/// The FourCC info maps take precedence over `fourCcList`, that is, `fourCcList` is ignored if either of them is specified.
+///
+/// |capsEx|Value|
+/// | :- | :- |
+/// |[`CAPS_EX_RECONNECT`]|`0x01`|
+/// |[`CAPS_EX_MULTITRACK`]|`0x02`|
+/// |[`CAPS_EX_MOD_EX`]|`0x04`|
+/// |[`CAPS_EX_TIMESTAMP_NANO_OFFSET`]|`0x08`|
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::{
+///     flv::tags::{
+///         AudioFourCc,
+///         VideoFourCc
+///     },
+///     handlers::Capabilities,
+///     messages::amf::v0::Object
+/// };
+///
+/// let mut command_object = Object::default();
+/// let mut client = Capabilities::default();
+/// client.get_video_mut().insert(VideoFourCc::Av1, Capabilities::CAN_DECODE);
+/// client.get_audio_mut().set_wildcard(Capabilities::CAN_DECODE);
+/// client.write_into(command_object.get_properties_mut());
+///
+/// let client = Capabilities::from_properties(command_object.get_properties()).unwrap();
+/// let server = Capabilities::supported(Capabilities::CAN_FORWARD);
+/// let negotiated = client.negotiate(&server);
+/// assert!(negotiated.get_video().can_decode(VideoFourCc::Av1));
+/// assert!(!negotiated.get_video().can_decode(VideoFourCc::Hevc));
+/// assert!(negotiated.get_audio().can_decode(AudioFourCc::Opus))
+/// ```
+///
+/// [`StrictArray`]: crate::messages::amf::v0::StrictArray
+/// [`Object`]: crate::messages::amf::v0::Object
+/// [`Number`]: crate::messages::amf::v0::Number
+/// [`CAPS_EX_RECONNECT`]: Capabilities::CAPS_EX_RECONNECT
+/// [`CAPS_EX_MULTITRACK`]: Capabilities::CAPS_EX_MULTITRACK
+/// [`CAPS_EX_MOD_EX`]: Capabilities::CAPS_EX_MOD_EX
+/// [`CAPS_EX_TIMESTAMP_NANO_OFFSET`]: Capabilities::CAPS_EX_TIMESTAMP_NANO_OFFSET
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    video: FourCcInfoMap<VideoFourCc>,
+    audio: FourCcInfoMap<AudioFourCc>,
+    caps_ex: u8
+}
+
+impl Capabilities {
+    pub const CAN_DECODE: u8 = 0x01;
+    pub const CAN_ENCODE: u8 = 0x02;
+    pub const CAN_FORWARD: u8 = 0x04;
+    pub const CAPS_EX_RECONNECT: u8 = 0x01;
+    pub const CAPS_EX_MULTITRACK: u8 = 0x02;
+    pub const CAPS_EX_MOD_EX: u8 = 0x04;
+    pub const CAPS_EX_TIMESTAMP_NANO_OFFSET: u8 = 0x08;
+
+    /// Constructs the Capabilities which contains every codec this crate can handle with the same mask.
+    ///
+    /// Its capsEx is `CAPS_EX_MULTITRACK` because this crate supports the Multitrack packet.
+    pub fn supported(mask: u8) -> Self {
+        use VideoFourCc::*;
+        use AudioFourCc::*;
+
+        let mut capabilities = Self {
+            caps_ex: Self::CAPS_EX_MULTITRACK,
+            ..Default::default()
+        };
+        for four_cc in [Vp8, Vp9, Av1, Avc, Hevc] {
+            capabilities.video.insert(four_cc, mask);
+        }
+        for four_cc in [Ac3, Eac3, Opus, Mp3, Flac, Aac] {
+            capabilities.audio.insert(four_cc, mask);
+        }
+        capabilities
+    }
+
+    /// Reads the capabilities from either the command object of the `connect` or the properties of its response.
+    ///
+    /// Note this returns `None` if no field of Enhanced RTMP is contained, that is, the peer isn't aware of Enhanced RTMP.
+    pub fn from_properties(properties: &Properties) -> Option<Self> {
+        let four_cc_list = properties.get("fourCcList").and_then(AmfValue::as_strict_array);
+        let video_four_cc_info_map = properties.get("videoFourCcInfoMap").and_then(AmfValue::get_properties);
+        let audio_four_cc_info_map = properties.get("audioFourCcInfoMap").and_then(AmfValue::get_properties);
+        let caps_ex = properties.get("capsEx").and_then(AmfValue::as_f64);
+
+        if four_cc_list.is_none() && video_four_cc_info_map.is_none() && audio_four_cc_info_map.is_none() && caps_ex.is_none() {
+            return None
+        }
+
+        let mut capabilities = Self::default();
+        let all = Self::CAN_DECODE | Self::CAN_ENCODE | Self::CAN_FORWARD;
+        /* NOTE: fourCcList can't tell video codecs from audio ones about the wildcard, so it's used only without FourCC info maps. */
+        if video_four_cc_info_map.is_none() && audio_four_cc_info_map.is_none() {
+            for four_cc in four_cc_list.into_iter().flat_map(StrictArray::iter).filter_map(AmfValue::as_str) {
+                capabilities.video.insert_by_key(four_cc, all);
+                capabilities.audio.insert_by_key(four_cc, all);
+            }
+        }
+        for (four_cc, mask) in video_four_cc_info_map.into_iter().flat_map(Properties::iter) {
+            capabilities.video.insert_by_key(four_cc, mask.as_f64().unwrap_or_default() as u8);
+        }
+        for (four_cc, mask) in audio_four_cc_info_map.into_iter().flat_map(Properties::iter) {
+            capabilities.audio.insert_by_key(four_cc, mask.as_f64().unwrap_or_default() as u8);
+        }
+        capabilities.caps_ex = caps_ex.unwrap_or_default() as u8;
+
+        Some(capabilities)
+    }
+
+    /// Writes this into either the command object of the `connect` or the properties of its response.
+    pub fn write_into(&self, properties: &mut Properties) {
+        let mut four_cc_list = StrictArray::default();
+        for (four_cc, _) in self.video.masks().chain(self.audio.masks()) {
+            if !four_cc_list.iter().any(|value| value.as_str() == Some(four_cc.as_str())) {
+                four_cc_list.push(AmfString::new(four_cc));
+            }
+        }
+
+        properties.insert("fourCcList", four_cc_list);
+        properties.insert("videoFourCcInfoMap", self.video.to_object());
+        properties.insert("audioFourCcInfoMap", self.audio.to_object());
+        properties.insert("capsEx", Number::from(self.caps_ex));
+    }
+
+    /// Negotiates this with the peer's capabilities.
+    ///
+    /// The result keeps masks of this side only for codecs which the peer also has any mask of.
+    /// And its capsEx consists of bits which both sides have set.
+    pub fn negotiate(&self, peer: &Self) -> Self {
+        Self {
+            video: self.video.negotiate(&peer.video),
+            audio: self.audio.negotiate(&peer.audio),
+            caps_ex: self.caps_ex & peer.caps_ex
+        }
+    }
+
+    /// Gets the video FourCC info map.
+    pub fn get_video(&self) -> &FourCcInfoMap<VideoFourCc> {
+        &self.video
+    }
+
+    /// Gets the video FourCC info map as mutable.
+    pub fn get_video_mut(&mut self) -> &mut FourCcInfoMap<VideoFourCc> {
+        &mut self.video
+    }
+
+    /// Gets the audio FourCC info map.
+    pub fn get_audio(&self) -> &FourCcInfoMap<AudioFourCc> {
+        &self.audio
+    }
+
+    /// Gets the audio FourCC info map as mutable.
+    pub fn get_audio_mut(&mut self) -> &mut FourCcInfoMap<AudioFourCc> {
+        &mut self.audio
+    }
+
+    /// Sets the capsEx field.
+    pub fn set_caps_ex(&mut self, caps_ex: u8) {
+        self.caps_ex = caps_ex;
+    }
+
+    /// Gets the capsEx field.
+    pub fn get_caps_ex(&self) -> u8 {
+        self.caps_ex
+    }
+
+    /// Removes codecs which the peer can't decode from an FLV tag.
+    ///
+    /// * A tag of Enhanced RTMP is dropped (that is, `None` is returned) unless the peer can decode its codec.
+    /// * A `ManyTracksManyCodecs` Multitrack packet is reduced to tracks which the peer can decode.
+    /// * Any legacy tag is passed through, because every peer is expected to be able to handle legacy codecs.
+    /// * A tag of Enhanced RTMP which has failed to be decoded is dropped with a warning, so that one broken tag doesn't stop the playback.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use bytes::Bytes;
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Encoder,
+    ///     flv::tags::{
+    ///         ExVideoTagHeader,
+    ///         FlvTag,
+    ///         FrameType,
+    ///         TagType,
+    ///         VideoFourCc,
+    ///         VideoPacketType,
+    ///         VideoTag
+    ///     },
+    ///     handlers::Capabilities
+    /// };
+    ///
+    /// let video_tag = VideoTag::new(
+    ///     ExVideoTagHeader::new(FrameType::Key, VideoPacketType::CodedFramesX, VideoFourCc::Av1, None),
+    ///     vec![0; 8]
+    /// );
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.encode(&video_tag);
+    /// let flv_tag = FlvTag::new(TagType::Video, Duration::default(), Bytes::from(Vec::<u8>::from(buffer)));
+    ///
+    /// let mut capabilities = Capabilities::default();
+    /// assert!(capabilities.filter(flv_tag.clone()).is_none());
+    /// capabilities.get_video_mut().insert(VideoFourCc::Av1, Capabilities::CAN_DECODE);
+    /// assert!(capabilities.filter(flv_tag).is_some())
+    /// ```
+    pub fn filter(&self, flv_tag: FlvTag) -> Option<FlvTag> {
+        let Some(&byte) = flv_tag.get_data().first() else {
+            return Some(flv_tag)
+        };
+        let is_ex_header = match flv_tag.get_tag_type() {
+            TagType::Audio => byte >> 4 == u8::from(SoundFormat::ExHeader),
+            TagType::Video => byte & 0x80 != 0,
+            _ => false
+        };
+        if !is_ex_header {
+            return Some(flv_tag)
+        }
+
+        self.filter_ex(&flv_tag).unwrap_or_else(
+            |e| {
+                warn!("Undecodable {:?} tag got dropped: {e}", flv_tag.get_tag_type());
+                None
+            }
+        )
+    }
+
+    fn filter_ex(&self, flv_tag: &FlvTag) -> IOResult<Option<FlvTag>> {
+
+        match flv_tag.get_tag_type() {
+            TagType::Audio => {
+                let header: ExAudioTagHeader = decode_header(flv_tag)?;
+                if let Some(four_cc) = header.get_four_cc() {
+                    return Ok(self.audio.can_decode(four_cc).then(|| flv_tag.clone()))
+                }
+
+                let mut buffer: ByteBuffer = flv_tag.get_data().clone().into();
+                let audio_tag: AudioTag = buffer.decode()?;
+                let tracks: Vec<AudioTrack> = audio_tag.get_tracks()?.into_iter().filter(|track| self.audio.can_decode(track.get_four_cc())).collect();
+                if tracks.is_empty() {
+                    return Ok(None)
+                }
+                Ok(Some(replace_data(flv_tag, &AudioTag::new_multitrack(header.get_packet_type(), tracks))))
+            },
+            TagType::Video => {
+                let header: ExVideoTagHeader = decode_header(flv_tag)?;
+                if let Some(four_cc) = header.get_four_cc() {
+                    return Ok(self.video.can_decode(four_cc).then(|| flv_tag.clone()))
+                }
+                /* NOTE: A video info/command frame doesn't depend on any codec. */
+                if header.get_multitrack_type().is_none() {
+                    return Ok(Some(flv_tag.clone()))
+                }
+
+                let mut buffer: ByteBuffer = flv_tag.get_data().clone().into();
+                let video_tag: VideoTag = buffer.decode()?;
+                let tracks: Vec<VideoTrack> = video_tag.get_tracks()?.into_iter().filter(|track| self.video.can_decode(track.get_four_cc())).collect();
+                if tracks.is_empty() {
+                    return Ok(None)
+                }
+                Ok(Some(replace_data(flv_tag, &VideoTag::new_multitrack(header.get_frame_type(), header.get_packet_type(), tracks))))
+            },
+            _ => Ok(Some(flv_tag.clone()))
+        }
+    }
+}
+
+/// Decodes only the header of an FLV tag.
+///
+/// Just the head of the data is copied, because the header never exceeds [`MAX_EX_HEADER_LEN`].
+fn decode_header<H>(flv_tag: &FlvTag) -> IOResult<H>
+where ByteBuffer: Decoder<H>
+{
+    let data = flv_tag.get_data();
+    let mut buffer: ByteBuffer = data.slice(..min(data.len(), MAX_EX_HEADER_LEN)).into();
+    buffer.decode()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use bytes::Bytes;
+    use super::*;
+
+    #[test]
+    fn negotiate_wildcard() {
+        let mut client = Capabilities::default();
+        client.video.set_wildcard(Capabilities::CAN_DECODE);
+        client.video.insert(VideoFourCc::Vp8, 0);
+        client.caps_ex = Capabilities::CAPS_EX_MULTITRACK | Capabilities::CAPS_EX_RECONNECT;
+        let server = Capabilities::supported(Capabilities::CAN_FORWARD);
+
+        let echoed = server.negotiate(&client);
+        assert_eq!(Capabilities::CAN_FORWARD, echoed.video.get(VideoFourCc::Hevc));
+        assert_eq!(0, echoed.video.get(VideoFourCc::Vp8));
+        assert!(echoed.audio.is_empty());
+        assert_eq!(Capabilities::CAPS_EX_MULTITRACK, echoed.caps_ex);
+
+        let negotiated = client.negotiate(&server);
+        assert!(negotiated.video.can_decode(VideoFourCc::Hevc));
+        assert!(!negotiated.video.can_decode(VideoFourCc::Vp8));
+        assert!(!negotiated.audio.can_decode(AudioFourCc::Aac))
+    }
+
+    #[test]
+    fn read_four_cc_list() {
+        let mut properties = Properties::default();
+        properties.insert("fourCcList", crate::strict_array!(AmfString::from("hvc1"), AmfString::from("Opus"), AmfString::from("xxxx")));
+        let capabilities = Capabilities::from_properties(&properties).unwrap();
+        assert!(capabilities.video.can_decode(VideoFourCc::Hevc));
+        assert!(!capabilities.video.can_decode(VideoFourCc::Av1));
+        assert!(capabilities.audio.can_decode(AudioFourCc::Opus));
+
+        assert!(Capabilities::from_properties(&Properties::default()).is_none())
+    }
+
+    #[test]
+    fn drop_undecodable_tag() {
+        let mut capabilities = Capabilities::default();
+        capabilities.video.set_wildcard(Capabilities::CAN_DECODE);
+
+        /* NOTE: Its FourCC is missing. */
+        let flv_tag = FlvTag::new(TagType::Video, Duration::default(), Bytes::from_static(&[0x91, b'h']));
+        assert!(capabilities.filter(flv_tag).is_none());
+
+        /* NOTE: Legacy tags aren't decoded at all. */
+        let flv_tag = FlvTag::new(TagType::Video, Duration::default(), Bytes::from_static(&[0x17]));
+        assert!(capabilities.filter(flv_tag).is_some())
+    }
+}
@@ -1,47 +1,45 @@
 use std::{
     io::Result as IOResult,
-    pin::Pin,
-    task::{
-        Context as FutureContext,
-        Poll
-    }
+    pin::Pin
+};
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite
 };
-use pin_project_lite::pin_project;
-use futures::ready;
 use super::{
     AsyncHandler,
+    MeasureAcknowledgement,
     RtmpContext
 };
 
-pin_project! {
-    #[doc(hidden)]
-    #[derive(Debug)]
-    pub struct Chain<H1, H2> {
-        #[pin] current: H1,
-        #[pin] next: H2
-    }
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Chain<H1, H2> {
+    current: H1,
+    next: H2
 }
 
 #[doc(hidden)]
 impl<H1, H2> AsyncHandler for Chain<H1, H2>
 where
-    H1: AsyncHandler + Unpin,
-    H2: AsyncHandler + Unpin
+    H1: AsyncHandler + Send,
+    H2: AsyncHandler + Send
 {
-    fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        let this = self.project();
-        match ready!(this.current.poll_handle(cx, rtmp_context)) {
-            Ok(_) => this.next.poll_handle(cx, rtmp_context),
-            Err(e) => Poll::Ready(Err(e))
-        }
+    async fn handle<R, W>(&mut self, mut reader: Pin<&mut R>, mut writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        self.current.handle(reader.as_mut(), writer.as_mut(), rtmp_context).await?;
+        self.next.handle(reader, writer, rtmp_context).await
     }
 }
 
 #[doc(hidden)]
 pub fn chain<H1, H2>(current: H1, next: H2) -> Chain<H1, H2>
 where
-    H1: AsyncHandler + Unpin,
-    H2: AsyncHandler + Unpin
+    H1: AsyncHandler,
+    H2: AsyncHandler
 {
     Chain { current, next }
 }
@@ -0,0 +1,263 @@
+use crate::{
+    flv::tags::{
+        FlvTag,
+        TagType
+    },
+    messages::{
+        Acknowledgement,
+        LimitType,
+        PeerBandwidth
+    }
+};
+
+/// The decision what to do with a next FLV tag to send.
+///
+/// |Pattern|Behavior|
+/// | :- | :- |
+/// |`Send`|Sends it as usual.|
+/// |`Drop`|Discards it without sending.|
+/// |`Pause`|Keeps it until the peer acknowledges more bytes.|
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pacing {
+    Send,
+    Drop,
+    Pause
+}
+
+/// The outbound flow control which follows the peer's bandwidth.
+///
+/// The peer tells the window size via the Set Peer Bandwidth message, then reports how many bytes it has received via Acknowledgement messages.
+/// This tracks the amount of sent bytes which aren't acknowledged yet, and decides whether a next FLV tag can be sent.
+///
+/// While unacknowledged bytes exceed the window:
+///
+/// * Non-keyframe video tags are dropped, and subsequent non-keyframe video tags are also dropped until a next keyframe arrives.
+///   Because they can't be decoded without their preceding frames.
+/// * Any other tag is kept, that is, sending is paused until an Acknowledgement arrives.
+///
+/// Note the peer counts also bytes of the handshake, so the sent amount should be seeded with [`HANDSHAKE_SIZE`] once the handshake has been done.
+/// An acknowledgement which is ahead of sent bytes is treated as to have acknowledged everything.
+///
+/// [`HANDSHAKE_SIZE`]: FlowControl::HANDSHAKE_SIZE
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use sheave_core::{
+///     flv::tags::{
+///         FlvTag,
+///         TagType
+///     },
+///     handlers::{
+///         FlowControl,
+///         Pacing
+///     },
+///     messages::{
+///         Acknowledgement,
+///         LimitType,
+///         PeerBandwidth
+///     }
+/// };
+///
+/// let mut flow_control = FlowControl::default();
+/// flow_control.apply_peer_bandwidth(PeerBandwidth::new(128, LimitType::Hard));
+/// flow_control.add_sent_amount(256);
+/// assert!(flow_control.is_window_exceeded());
+///
+/// let audio = FlvTag::new(TagType::Audio, Duration::default(), vec![0xaf].into());
+/// assert_eq!(Pacing::Pause, flow_control.pace(&audio));
+/// let inter_frame = FlvTag::new(TagType::Video, Duration::default(), vec![0x27].into());
+/// assert_eq!(Pacing::Drop, flow_control.pace(&inter_frame));
+///
+/// flow_control.acknowledge(Acknowledgement::new(256));
+/// assert!(!flow_control.is_window_exceeded());
+/// assert_eq!(Pacing::Send, flow_control.pace(&audio));
+/// // Inter frames are dropped until a next keyframe.
+/// assert_eq!(Pacing::Drop, flow_control.pace(&inter_frame));
+/// let key_frame = FlvTag::new(TagType::Video, Duration::default(), vec![0x17].into());
+/// assert_eq!(Pacing::Send, flow_control.pace(&key_frame));
+/// assert_eq!(Pacing::Send, flow_control.pace(&inter_frame))
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlowControl {
+    sent_amount: u32,
+    acknowledged_amount: u32,
+    window_size: Option<u32>,
+    limit_type: Option<LimitType>,
+    awaiting_key_frame: bool
+}
+
+impl FlowControl {
+    /// The amount of bytes which each side sends in the handshake, that is, C0-C2 or S0-S2.
+    pub const HANDSHAKE_SIZE: u32 = 1 + 1536 * 2;
+
+    /// Adds an amount of bytes which have been sent.
+    ///
+    /// This wraps around like the sequence number of Acknowledgement messages.
+    pub fn add_sent_amount(&mut self, amount: u32) {
+        self.sent_amount = self.sent_amount.wrapping_add(amount);
+    }
+
+    /// Gets the total amount of sent bytes.
+    pub fn get_sent_amount(&self) -> u32 {
+        self.sent_amount
+    }
+
+    /// Stores the sequence number which the peer has reported.
+    pub fn acknowledge(&mut self, acknowledgement: Acknowledgement) {
+        self.acknowledged_amount = acknowledgement.get_inner();
+    }
+
+    /// Gets the amount of sent bytes which aren't acknowledged yet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     handlers::FlowControl,
+    ///     messages::Acknowledgement
+    /// };
+    ///
+    /// let mut flow_control = FlowControl::default();
+    /// flow_control.add_sent_amount(u32::MAX);
+    /// flow_control.add_sent_amount(16);
+    /// flow_control.acknowledge(Acknowledgement::new(u32::MAX));
+    /// assert_eq!(16, flow_control.get_unacknowledged_amount());
+    ///
+    /// // An acknowledgement ahead of us is regarded as to have acknowledged everything.
+    /// flow_control.acknowledge(Acknowledgement::new(32));
+    /// assert_eq!(0, flow_control.get_unacknowledged_amount())
+    /// ```
+    pub fn get_unacknowledged_amount(&self) -> u32 {
+        let unacknowledged_amount = self.sent_amount.wrapping_sub(self.acknowledged_amount);
+
+        /* NOTE: Distances over the half of the sequence space are regarded as acknowledgements ahead of us. */
+        if unacknowledged_amount > u32::MAX / 2 {
+            0
+        } else {
+            unacknowledged_amount
+        }
+    }
+
+    /// Applies the Set Peer Bandwidth message in accordance with its limit type.
+    ///
+    /// |Limit Type|Behavior|
+    /// | :- | :- |
+    /// |`Hard`|Limits to the indicated window.|
+    /// |`Soft`|Limits to either the indicated window or the window already in effect, whichever is smaller.|
+    /// |`Dynamic`|Treated as `Hard` if the previous limit type was `Hard`, otherwise ignored.|
+    /// |`Other`|Ignored.|
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     handlers::FlowControl,
+    ///     messages::{
+    ///         LimitType,
+    ///         PeerBandwidth
+    ///     }
+    /// };
+    ///
+    /// let mut flow_control = FlowControl::default();
+    /// // Dynamic is ignored unless a Hard limit is in effect.
+    /// flow_control.apply_peer_bandwidth(PeerBandwidth::new(4096, LimitType::Dynamic));
+    /// assert!(flow_control.get_window_size().is_none());
+    ///
+    /// flow_control.apply_peer_bandwidth(PeerBandwidth::new(4096, LimitType::Soft));
+    /// assert_eq!(Some(4096), flow_control.get_window_size());
+    /// flow_control.apply_peer_bandwidth(PeerBandwidth::new(8192, LimitType::Soft));
+    /// assert_eq!(Some(4096), flow_control.get_window_size());
+    ///
+    /// flow_control.apply_peer_bandwidth(PeerBandwidth::new(8192, LimitType::Hard));
+    /// assert_eq!(Some(8192), flow_control.get_window_size());
+    /// flow_control.apply_peer_bandwidth(PeerBandwidth::new(16384, LimitType::Dynamic));
+    /// assert_eq!(Some(16384), flow_control.get_window_size())
+    /// ```
+    pub fn apply_peer_bandwidth(&mut self, peer_bandwidth: PeerBandwidth) {
+        let bandwidth = peer_bandwidth.get_inner_bandwidth();
+        match peer_bandwidth.get_inner_limit_type() {
+            LimitType::Hard => {
+                self.window_size = Some(bandwidth);
+                self.limit_type = Some(LimitType::Hard);
+            },
+            LimitType::Soft => {
+                self.window_size = Some(self.window_size.map_or(bandwidth, |window_size| window_size.min(bandwidth)));
+                self.limit_type = Some(LimitType::Soft);
+            },
+            LimitType::Dynamic => if let Some(LimitType::Hard) = self.limit_type {
+                self.window_size = Some(bandwidth);
+            },
+            LimitType::Other => {}
+        }
+    }
+
+    /// Gets the window size which is in effect.
+    /// This is `None` until the peer sends the Set Peer Bandwidth message.
+    pub fn get_window_size(&self) -> Option<u32> {
+        self.window_size
+    }
+
+    /// Checks whether unacknowledged bytes exceed the peer's window.
+    pub fn is_window_exceeded(&self) -> bool {
+        self.window_size.is_some_and(|window_size| self.get_unacknowledged_amount() > window_size)
+    }
+
+    /// Decides what to do with a next FLV tag to send.
+    pub fn pace(&mut self, flv_tag: &FlvTag) -> Pacing {
+        let is_video = flv_tag.get_tag_type() == TagType::Video;
+        if is_video && flv_tag.is_key_frame() {
+            self.awaiting_key_frame = false;
+        }
+
+        if is_video && !flv_tag.is_key_frame() && (self.awaiting_key_frame || self.is_window_exceeded()) {
+            self.awaiting_key_frame = true;
+            Pacing::Drop
+        } else if self.is_window_exceeded() {
+            Pacing::Pause
+        } else {
+            Pacing::Send
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use bytes::Bytes;
+    use super::*;
+
+    #[test]
+    fn no_pacing_without_peer_bandwidth() {
+        let mut flow_control = FlowControl::default();
+        flow_control.add_sent_amount(u32::MAX / 2);
+        assert!(!flow_control.is_window_exceeded());
+
+        let inter_frame = FlvTag::new(TagType::Video, Duration::default(), vec![0x27].into());
+        assert_eq!(Pacing::Send, flow_control.pace(&inter_frame))
+    }
+
+    #[test]
+    fn pause_key_frame_while_exceeded() {
+        let mut flow_control = FlowControl::default();
+        flow_control.apply_peer_bandwidth(PeerBandwidth::new(128, LimitType::Hard));
+        flow_control.add_sent_amount(129);
+
+        let key_frame = FlvTag::new(TagType::Video, Duration::default(), vec![0x17].into());
+        assert_eq!(Pacing::Pause, flow_control.pace(&key_frame));
+        let script_data = FlvTag::new(TagType::ScriptData, Duration::default(), Bytes::new());
+        assert_eq!(Pacing::Pause, flow_control.pace(&script_data));
+
+        flow_control.acknowledge(Acknowledgement::new(1));
+        assert_eq!(Pacing::Send, flow_control.pace(&key_frame))
+    }
+
+    #[test]
+    fn ignore_other_limit_type() {
+        let mut flow_control = FlowControl::default();
+        flow_control.apply_peer_bandwidth(PeerBandwidth::new(128, LimitType::Hard));
+        flow_control.apply_peer_bandwidth(PeerBandwidth::new(64, LimitType::Other));
+        assert_eq!(Some(128), flow_control.get_window_size())
+    }
+}
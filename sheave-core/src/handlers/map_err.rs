@@ -1,46 +1,50 @@
 use std::{
+    future::Future,
     io::{
         Error as IOError,
         Result as IOResult
     },
-    pin::Pin,
-    task::{
-        Context as FutureContext,
-        Poll
-    }
+    pin::Pin
+};
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite
 };
-use pin_project_lite::pin_project;
-use futures::ready;
 use super::{
     AsyncHandler,
+    MeasureAcknowledgement,
     RtmpContext
 };
 
 pub trait ErrorHandler {
-    fn poll_handle_error(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext, error: IOError) -> Poll<IOResult<()>>;
+    fn handle_error<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext, error: IOError) -> impl Future<Output = IOResult<()>> + Send
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send;
 }
 
-pin_project! {
-    #[doc(hidden)]
-    #[derive(Debug)]
-    pub struct MapErr<H, E> {
-        #[pin] body: H,
-        #[pin] error_handler: E
-    }
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct MapErr<H, E> {
+    body: H,
+    error_handler: E
 }
 
 #[doc(hidden)]
 impl<H, E> AsyncHandler for MapErr<H, E>
 where
-    H: AsyncHandler + Unpin,
-    E: ErrorHandler + Unpin
+    H: AsyncHandler + Send,
+    E: ErrorHandler + Send
 {
-    fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        let this = self.project();
-        if let Err(e) = ready!(this.body.poll_handle(cx, rtmp_context)) {
-            this.error_handler.poll_handle_error(cx, rtmp_context, e)
+    async fn handle<R, W>(&mut self, mut reader: Pin<&mut R>, mut writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        if let Err(e) = self.body.handle(reader.as_mut(), writer.as_mut(), rtmp_context).await {
+            self.error_handler.handle_error(reader, writer, rtmp_context, e).await
         } else {
-            Poll::Ready(Ok(()))
+            Ok(())
         }
     }
 }
@@ -48,8 +52,8 @@ where
 #[doc(hidden)]
 pub fn map_err<H, E>(body: H, error_handler: E) -> MapErr<H, E>
 where
-    H: AsyncHandler + Unpin,
-    E: ErrorHandler + Unpin
+    H: AsyncHandler,
+    E: ErrorHandler
 {
     MapErr { body, error_handler }
 }
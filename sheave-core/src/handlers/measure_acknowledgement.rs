@@ -1,26 +1,44 @@
 use crate::messages::Acknowledgement;
 
-/// Measurement receiving chunk size.
+/// Measurement of received bytes.
 ///
-/// Clients and the server are each required to send [`Acknowledgement`] messages when received chunk sizes exceed their bandwidths.
-/// This trait defines ways to measure receiving chunk sizes for any stream.
+/// Clients and the server are each required to send [`Acknowledgement`] messages every time they receive bytes equal to the window size which the other peer has told.
+/// Its sequence number is the total of bytes received so far, including the handshake.
+/// Note the sequence number wraps around when it exceeds 32 bits.
+///
+/// This trait defines ways to measure received bytes for any stream.
 pub trait MeasureAcknowledgement {
-    /// Resets measured count and turns the flag into on, for beginning measurement.
-    fn begin_measuring(&mut self);
+    /// Adds received bytes to the total.
+    fn add_amount(&mut self, amount: u32);
 
-    /// Resets measured count and turns the flag into off, for finishing measurement.
-    fn finish_measuring(&mut self);
+    /// Gets the total of bytes received so far. (modulo 2^32)
+    fn get_sequence_number(&mut self) -> u32;
 
-    /// Adds received chunk size to current one.
-    fn add_amount(&mut self, amount: u32);
+    /// Gets the amount of bytes received since the last window boundary.
+    fn get_unacknowledged_amount(&mut self) -> u32;
+
+    /// Sets the amount of bytes received since the last window boundary.
+    fn set_unacknowledged_amount(&mut self, unacknowledged_amount: u32);
 
-    /// Gets current chunk size.
-    fn get_current_amount(&mut self) -> u32;
+    /// Checks whether received bytes have reached the next window boundary.
+    ///
+    /// When they have reached, this consumes passed windows and returns `true`.
+    /// Bytes which exceed the boundary are carried over to the next window, so boundaries don't drift.
+    fn pass_window(&mut self, window_size: u32) -> bool {
+        let unacknowledged_amount = self.get_unacknowledged_amount();
+
+        if window_size == 0 || unacknowledged_amount < window_size {
+            false
+        } else {
+            self.set_unacknowledged_amount(unacknowledged_amount % window_size);
+            true
+        }
+    }
 
-    /// Wraps current chunk size into an [`Acknowledgement`] message.
+    /// Wraps current sequence number into an [`Acknowledgement`] message.
     ///
     /// [`Acknowledgement`]: crate::messages::Acknowledgement
     fn as_acknowledgement(&mut self) -> Acknowledgement {
-        Acknowledgement::new(self.get_current_amount())
+        Acknowledgement::new(self.get_sequence_number())
     }
 }
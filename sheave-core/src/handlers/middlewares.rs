@@ -1,47 +1,53 @@
 use std::{
+    future::Future,
     io::Result as IOResult,
-    pin::Pin,
-    task::{
-        Context as FutureContext,
-        Poll
-    }
+    pin::Pin
+};
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite
 };
-use pin_project_lite::pin_project;
 use super::{
     AsyncHandler,
+    MeasureAcknowledgement,
     RtmpContext
 };
 
 pub trait Middleware {
-    fn poll_handle_wrapped<H: AsyncHandler + Unpin>(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext, handler: Pin<&mut H>) -> Poll<IOResult<()>>;
+    fn handle_wrapped<R, W, H>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext, handler: &mut H) -> impl Future<Output = IOResult<()>> + Send
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        H: AsyncHandler + Send;
 }
 
-pin_project! {
-    #[doc(hidden)]
-    #[derive(Debug)]
-    pub struct Wrap<M, H> {
-        #[pin] middleware: M,
-        #[pin] handler: H
-    }
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Wrap<M, H> {
+    middleware: M,
+    handler: H
 }
 
 #[doc(hidden)]
 impl<M, H> AsyncHandler for Wrap<M, H>
 where
-    M: Middleware + Unpin,
-    H: AsyncHandler + Unpin
+    M: Middleware + Send,
+    H: AsyncHandler + Send
 {
-    fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        let this = self.project();
-        this.middleware.poll_handle_wrapped(cx, rtmp_context, this.handler)
+    async fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        self.middleware.handle_wrapped(reader, writer, rtmp_context, &mut self.handler).await
     }
 }
 
 #[doc(hidden)]
 pub fn wrap<M, H>(middleware: M, handler: H) -> Wrap<M, H>
 where
-    M: Middleware + Unpin,
-    H: AsyncHandler + Unpin
+    M: Middleware,
+    H: AsyncHandler
 {
     Wrap { middleware, handler }
 }
@@ -3,19 +3,24 @@ mod last_chunk;
 use std::{
     collections::HashMap,
     net::SocketAddr,
-    sync::Arc,
     time::Duration
 };
 use crate::{
+    U24_MAX,
     handshake::{
+        DiffieHellman,
+        DigestScheme,
         EncryptionAlgorithm,
-        Handshake
+        Handshake,
+        SwfVerification
     },
+    net::rtmpe::RtmpeKeys,
     messages::{
         ChunkSize,
         WindowAcknowledgementSize,
         PeerBandwidth,
         PlayMode,
+        SwfVerifyResponse,
         amf::v0::{
             Number,
             AmfString,
@@ -23,12 +28,18 @@ use crate::{
             EcmaArray
         }
     },
-    flv::Flv
+    flv::{
+        Flv,
+        tags::TrackSelection
+    }
 };
 use super::{
+    Capabilities,
     ClientType,
+    FlowControl,
     PublisherStatus,
-    SubscriberStatus
+    SubscriberStatus,
+    UnknownMessagePolicy
 };
 pub use self::last_chunk::*;
 
@@ -53,7 +64,7 @@ pub struct RtmpContext {
     sending_chunk_size: ChunkSize,
     window_acknowledgement_size: WindowAcknowledgementSize,
     peer_bandwidth: PeerBandwidth,
-    buffer_length: u32,
+    buffer_length: Option<u32>,
     last_transaction_id: Number,
     database_url: Option<String>,
     storage_path: Option<String>,
@@ -66,11 +77,20 @@ pub struct RtmpContext {
     publisher_status: Option<PublisherStatus>,
     subscriber_status: Option<SubscriberStatus>,
     encryption_algorithm: Option<EncryptionAlgorithm>,
+    digest_scheme: Option<DigestScheme>,
+    round_trip_time: Option<Duration>,
+    ping_timestamp: Option<u32>,
+    diffie_hellman: Option<DiffieHellman>,
+    rtmpe_keys: Option<RtmpeKeys>,
+    swf_verification: Option<SwfVerification>,
+    swf_verify_response: Option<SwfVerifyResponse>,
+    swf_verified: bool,
     client_handshake: Option<Handshake>,
     server_handshake: Option<Handshake>,
     command_object: Option<Object>,
     properties: Option<Object>,
     information: Option<Object>,
+    capabilities: Option<Capabilities>,
     message_id: Option<u32>,
     playlist: Option<EcmaArray>,
     publishing_name: Option<AmfString>,
@@ -78,7 +98,13 @@ pub struct RtmpContext {
     stream_name: Option<AmfString>,
     start_time: Option<Duration>,
     play_mode: Option<PlayMode>,
+    track_selection: Option<TrackSelection>,
     await_duration: Option<Duration>,
+    aggregation_size: Option<u32>,
+    unknown_message_policy: UnknownMessagePolicy,
+    max_message_size: u32,
+    max_tag_size: u32,
+    flow_control: FlowControl,
     topic: Option<Flv>,
     last_received_chunks: HashMap<u16, LastChunk>,
     last_sent_chunks: HashMap<u16, LastChunk>
@@ -92,7 +118,7 @@ impl Default for RtmpContext {
             sending_chunk_size: ChunkSize::default(),
             window_acknowledgement_size: WindowAcknowledgementSize::default(),
             peer_bandwidth: PeerBandwidth::default(),
-            buffer_length: 30000,
+            buffer_length: Option::default(),
             last_transaction_id: Number::default(),
             database_url: Option::default(),
             storage_path: Option::default(),
@@ -105,11 +131,20 @@ impl Default for RtmpContext {
             publisher_status: Option::default(),
             subscriber_status: Option::default(),
             encryption_algorithm: Option::default(),
+            digest_scheme: Option::default(),
+            round_trip_time: Option::default(),
+            ping_timestamp: Option::default(),
+            diffie_hellman: Option::default(),
+            rtmpe_keys: Option::default(),
+            swf_verification: Option::default(),
+            swf_verify_response: Option::default(),
+            swf_verified: bool::default(),
             client_handshake: Option::default(),
             server_handshake: Option::default(),
             command_object: Option::default(),
             properties: Option::default(),
             information: Option::default(),
+            capabilities: Option::default(),
             message_id: Option::default(),
             playlist: Option::default(),
             publishing_name: Option::default(),
@@ -117,7 +152,13 @@ impl Default for RtmpContext {
             stream_name: Option::default(),
             start_time: Option::default(),
             play_mode: Option::default(),
+            track_selection: Option::default(),
             await_duration: Option::default(),
+            aggregation_size: Option::default(),
+            unknown_message_policy: UnknownMessagePolicy::default(),
+            max_message_size: Self::DEFAULT_MAX_MESSAGE_SIZE,
+            max_tag_size: Flv::DEFAULT_MAX_TAG_SIZE,
+            flow_control: FlowControl::default(),
             topic: Option::default(),
             last_received_chunks: HashMap::default(),
             last_sent_chunks: HashMap::default()
@@ -126,13 +167,14 @@ impl Default for RtmpContext {
 }
 
 impl RtmpContext {
-    /// Gets a mutable reference via this wrapped by `Arc`.
-    ///
-    /// Sheave uses this after wrapping into `Arc`.
-    /// Because of making this shareable between every handling steps.
-    pub fn make_weak_mut<'a>(self: &'a Arc<Self>) -> &'a mut Self {
-        unsafe { &mut *(Arc::downgrade(self).as_ptr() as *mut Self) }
-    }
+    /// The maximum size of a message to receive by default. (8 MiB)
+    pub const DEFAULT_MAX_MESSAGE_SIZE: u32 = 0x800000;
+    /// The buffer length in milliseconds when it can't be decided from the round-trip time. (30 seconds)
+    pub const DEFAULT_BUFFER_LENGTH: u32 = 30000;
+    /// The duration for awaiting when it can't be decided from the round-trip time. (1 second)
+    pub const DEFAULT_AWAIT_DURATION: Duration = Duration::from_secs(1);
+    /// The interval to send the Ping Request event to measure the round-trip time. (10 seconds)
+    pub const PING_INTERVAL: Duration = Duration::from_secs(10);
 
     /// Stores a flag to mean this handshake is signed.
     pub fn set_signed(&mut self, signed: bool) {
@@ -186,12 +228,25 @@ impl RtmpContext {
 
     /// Sets the buffer length.
     pub fn set_buffer_length(&mut self, buffer_length: u32) {
-        self.buffer_length = buffer_length;
+        self.buffer_length = Some(buffer_length);
     }
 
     /// Gets the buffer length.
+    ///
+    /// This returns [`DEFAULT_BUFFER_LENGTH`] if this has neither been set nor been seeded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert_eq!(RtmpContext::DEFAULT_BUFFER_LENGTH, rtmp_context.get_buffer_length())
+    /// ```
+    ///
+    /// [`DEFAULT_BUFFER_LENGTH`]: RtmpContext::DEFAULT_BUFFER_LENGTH
     pub fn get_buffer_length(&mut self) -> u32 {
-        self.buffer_length
+        self.buffer_length.unwrap_or(Self::DEFAULT_BUFFER_LENGTH)
     }
 
     /// Sets a transaction ID.
@@ -464,6 +519,241 @@ impl RtmpContext {
         self.encryption_algorithm
     }
 
+    /// Stores the digest scheme which both sides use in this handshake.
+    pub fn set_digest_scheme(&mut self, digest_scheme: DigestScheme) {
+        self.digest_scheme = Some(digest_scheme);
+    }
+
+    /// Gets the digest scheme which both sides use in this handshake.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is, or the handshake isn't signed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_digest_scheme().is_none())
+    /// ```
+    pub fn get_digest_scheme(&mut self) -> Option<DigestScheme> {
+        self.digest_scheme
+    }
+
+    /// Stores an estimate of the round-trip time.
+    ///
+    /// If some estimate has already been stored, new sample is smoothed into it as same as TCP does (RFC 6298).
+    /// That is, the estimate gets `7/8` of the old one plus `1/8` of the new one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// rtmp_context.set_round_trip_time(Duration::from_millis(80));
+    /// assert_eq!(Some(Duration::from_millis(80)), rtmp_context.get_round_trip_time());
+    /// rtmp_context.set_round_trip_time(Duration::from_millis(160));
+    /// assert_eq!(Some(Duration::from_millis(90)), rtmp_context.get_round_trip_time())
+    /// ```
+    pub fn set_round_trip_time(&mut self, round_trip_time: Duration) {
+        self.round_trip_time = Some(
+            match self.round_trip_time {
+                Some(estimate) => (estimate * 7 + round_trip_time) / 8,
+                None => round_trip_time
+            }
+        );
+    }
+
+    /// Gets the estimate of the round-trip time.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_round_trip_time().is_none())
+    /// ```
+    pub fn get_round_trip_time(&mut self) -> Option<Duration> {
+        self.round_trip_time
+    }
+
+    /// Stores the timestamp of the Ping Request event which is waiting for its response.
+    ///
+    /// If a previous request hasn't been answered yet, it is abandoned.
+    pub fn set_ping_timestamp(&mut self, ping_timestamp: u32) {
+        self.ping_timestamp = Some(ping_timestamp);
+    }
+
+    /// Resets the timestamp of the Ping Request event from this context.
+    ///
+    /// This is prepared for accepting only one Ping Response event per request.
+    pub fn reset_ping_timestamp(&mut self) {
+        self.ping_timestamp = None;
+    }
+
+    /// Gets the timestamp of the Ping Request event which is waiting for its response.
+    ///
+    /// Note this can return `None`. e.g. When no request has been sent, or it has already been answered.
+    pub fn get_ping_timestamp(&mut self) -> Option<u32> {
+        self.ping_timestamp
+    }
+
+    /// Decides the await duration and the buffer length from the estimate of the round-trip time, unless they have already been set.
+    ///
+    /// |Field|Seeded value|Without the estimate|
+    /// | :- | :- | :- |
+    /// |Await duration|4 times the round-trip time, within 100 milliseconds to 10 seconds.|[`DEFAULT_AWAIT_DURATION`]|
+    /// |Buffer length|100 times the round-trip time, within 3 seconds to 30 seconds.|[`DEFAULT_BUFFER_LENGTH`]|
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// rtmp_context.set_round_trip_time(Duration::from_millis(50));
+    /// rtmp_context.seed_from_round_trip_time();
+    /// assert_eq!(Some(Duration::from_millis(200)), rtmp_context.get_await_duration());
+    /// assert_eq!(5000, rtmp_context.get_buffer_length());
+    ///
+    /// // Values which have been set explicitly are kept.
+    /// let mut rtmp_context = RtmpContext::default();
+    /// rtmp_context.set_await_duration(Duration::from_secs(3));
+    /// rtmp_context.seed_from_round_trip_time();
+    /// assert_eq!(Some(Duration::from_secs(3)), rtmp_context.get_await_duration());
+    /// assert_eq!(RtmpContext::DEFAULT_BUFFER_LENGTH, rtmp_context.get_buffer_length())
+    /// ```
+    ///
+    /// [`DEFAULT_AWAIT_DURATION`]: RtmpContext::DEFAULT_AWAIT_DURATION
+    /// [`DEFAULT_BUFFER_LENGTH`]: RtmpContext::DEFAULT_BUFFER_LENGTH
+    pub fn seed_from_round_trip_time(&mut self) {
+        let round_trip_time = self.round_trip_time;
+        if self.await_duration.is_none() {
+            self.await_duration = Some(
+                round_trip_time.map_or(Self::DEFAULT_AWAIT_DURATION, |round_trip_time| (round_trip_time * 4).clamp(Duration::from_millis(100), Duration::from_secs(10)))
+            );
+        }
+        if self.buffer_length.is_none() && let Some(round_trip_time) = round_trip_time {
+            let buffer_length = (round_trip_time * 100).clamp(Duration::from_secs(3), Duration::from_secs(30));
+            self.buffer_length = Some(buffer_length.as_millis() as u32);
+        }
+    }
+
+    /// Stores own Diffie-Hellman key pair until the partner's public key arrives.
+    ///
+    /// Currently, this is used only clients during the RTMPE handshake.
+    pub fn set_diffie_hellman(&mut self, diffie_hellman: DiffieHellman) {
+        self.diffie_hellman = Some(diffie_hellman);
+    }
+
+    /// Gets own Diffie-Hellman key pair.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_diffie_hellman().is_none())
+    /// ```
+    pub fn get_diffie_hellman(&mut self) -> Option<&DiffieHellman> {
+        self.diffie_hellman.as_ref()
+    }
+
+    /// Stores the RC4 keys which are shared with a [`RtmpeStream`].
+    ///
+    /// If this is present, handlers perform the RTMPE handshake, that is, they try to encrypt chunks.
+    ///
+    /// [`RtmpeStream`]: crate::net::rtmpe::RtmpeStream
+    pub fn set_rtmpe_keys(&mut self, rtmpe_keys: RtmpeKeys) {
+        self.rtmpe_keys = Some(rtmpe_keys);
+    }
+
+    /// Gets the RC4 keys which are shared with a [`RtmpeStream`].
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is.
+    /// In this case, chunks aren't encrypted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_rtmpe_keys().is_none())
+    /// ```
+    ///
+    /// [`RtmpeStream`]: crate::net::rtmpe::RtmpeStream
+    pub fn get_rtmpe_keys(&mut self) -> Option<&RtmpeKeys> {
+        self.rtmpe_keys.as_ref()
+    }
+
+    /// Sets the SWF file for the SWF verification.
+    ///
+    /// Clients answer the SwfVerifyRequest with this.
+    /// Servers require clients to answer with this before publishing/playing.
+    pub fn set_swf_verification(&mut self, swf_verification: SwfVerification) {
+        self.swf_verification = Some(swf_verification);
+    }
+
+    /// Gets the SWF file for the SWF verification.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is.
+    /// In this case, clients don't answer any SwfVerifyRequest, and servers don't require the SWF verification.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_swf_verification().is_none())
+    /// ```
+    pub fn get_swf_verification(&mut self) -> Option<SwfVerification> {
+        self.swf_verification
+    }
+
+    /// Stores the SwfVerifyResponse which has been computed from S1 of this connection.
+    ///
+    /// Clients send this, and servers compare the client's response with this.
+    pub fn set_swf_verify_response(&mut self, swf_verify_response: SwfVerifyResponse) {
+        self.swf_verify_response = Some(swf_verify_response);
+    }
+
+    /// Gets the SwfVerifyResponse which has been computed from S1 of this connection.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_swf_verify_response().is_none())
+    /// ```
+    pub fn get_swf_verify_response(&mut self) -> Option<SwfVerifyResponse> {
+        self.swf_verify_response
+    }
+
+    /// Stores a flag to mean the client has passed the SWF verification.
+    pub fn set_swf_verified(&mut self, swf_verified: bool) {
+        self.swf_verified = swf_verified;
+    }
+
+    /// Indicates whether the client has passed the SWF verification.
+    pub fn is_swf_verified(&mut self) -> bool {
+        self.swf_verified
+    }
+
     /// Stores a cleint-side handshake bytes.
     pub fn set_client_handshake(&mut self, handshake: Handshake) {
         self.client_handshake = Some(handshake);
@@ -588,6 +878,30 @@ impl RtmpContext {
         self.information.as_ref()
     }
 
+    /// Stores capabilities of Enhanced RTMP.
+    ///
+    /// Clients store what they advertise before the `connect`, then replace it with the negotiated one after its response.
+    /// Servers store the negotiated one when they respond to the `connect`.
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = Some(capabilities);
+    }
+
+    /// Gets capabilities of Enhanced RTMP.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is, or the peer isn't aware of Enhanced RTMP.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_capabilities().is_none())
+    /// ```
+    pub fn get_capabilities(&mut self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
     /// Sets a message ID of this stream.
     pub fn set_message_id(&mut self, message_id: u32) {
         self.message_id = Some(message_id);
@@ -746,9 +1060,32 @@ impl RtmpContext {
         self.play_mode
     }
 
+    /// Sets tracks which the subscriber wants to receive out of Multitrack packets.
+    pub fn set_track_selection(&mut self, track_selection: TrackSelection) {
+        self.track_selection = Some(track_selection);
+    }
+
+    /// Gets tracks which the subscriber wants to receive out of Multitrack packets.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is.
+    /// In this case, every track is sent as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_track_selection_mut().is_none())
+    /// ```
+    pub fn get_track_selection_mut(&mut self) -> Option<&mut TrackSelection> {
+        self.track_selection.as_mut()
+    }
+
     /// Sets a duration for awaiting of receiving some message.
     ///
     /// Currently, this is used only clients during publishing audio/video data.
+    /// That is, clients await an acknowledgement at most this duration while the peer's bandwidth is exhausted.
     pub fn set_await_duration(&mut self, await_duration: Duration) {
         self.await_duration = Some(await_duration);
     }
@@ -756,10 +1093,150 @@ impl RtmpContext {
     /// Gets a duration for awaiting of receiving some message.
     ///
     /// Currently, this is used only clients during publishing audio/video data.
+    /// If this hasn't been set, it is decided by [`seed_from_round_trip_time`] after the handshake.
+    ///
+    /// [`seed_from_round_trip_time`]: RtmpContext::seed_from_round_trip_time
     pub fn get_await_duration(&mut self) -> Option<Duration> {
         self.await_duration
     }
 
+    /// Sets a total size of FLV tags to bundle into one Aggregate message.
+    ///
+    /// Currently, this is used only servers during sending audio/video data.
+    /// Because the length of messages is 3 bytes, this is clamped within `1` to [`U24_MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     U24_MAX,
+    ///     handlers::RtmpContext
+    /// };
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// rtmp_context.set_aggregation_size(U24_MAX + 1);
+    /// assert_eq!(Some(U24_MAX), rtmp_context.get_aggregation_size());
+    /// rtmp_context.set_aggregation_size(0);
+    /// assert_eq!(Some(1), rtmp_context.get_aggregation_size())
+    /// ```
+    ///
+    pub fn set_aggregation_size(&mut self, aggregation_size: u32) {
+        self.aggregation_size = Some(aggregation_size.clamp(1, U24_MAX));
+    }
+
+    /// Gets a total size of FLV tags to bundle into one Aggregate message.
+    ///
+    /// Note this can return `None`. e.g. When this field is default as it is.
+    /// In this case, FLV tags are sent one by one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_aggregation_size().is_none())
+    /// ```
+    pub fn get_aggregation_size(&mut self) -> Option<u32> {
+        self.aggregation_size
+    }
+
+    /// Sets the policy to treat unknown/unsupported messages and user control events.
+    pub fn set_unknown_message_policy(&mut self, unknown_message_policy: UnknownMessagePolicy) {
+        self.unknown_message_policy = unknown_message_policy;
+    }
+
+    /// Gets the policy to treat unknown/unsupported messages and user control events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::{
+    ///     RtmpContext,
+    ///     UnknownMessagePolicy
+    /// };
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert_eq!(UnknownMessagePolicy::Log, rtmp_context.get_unknown_message_policy())
+    /// ```
+    pub fn get_unknown_message_policy(&mut self) -> UnknownMessagePolicy {
+        self.unknown_message_policy
+    }
+
+    /// Sets the maximum size of a message to receive.
+    ///
+    /// Every message which exceeds it is treated as an error before reading its data.
+    pub fn set_max_message_size(&mut self, max_message_size: u32) {
+        self.max_message_size = max_message_size;
+    }
+
+    /// Gets the maximum size of a message to receive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert_eq!(RtmpContext::DEFAULT_MAX_MESSAGE_SIZE, rtmp_context.get_max_message_size())
+    /// ```
+    pub fn get_max_message_size(&mut self) -> u32 {
+        self.max_message_size
+    }
+
+    /// Sets the maximum size of a FLV tag to read from topics.
+    ///
+    /// This is applied to topic files which are opened during handling.
+    pub fn set_max_tag_size(&mut self, max_tag_size: u32) {
+        self.max_tag_size = max_tag_size;
+    }
+
+    /// Gets the maximum size of a FLV tag to read from topics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     flv::Flv,
+    ///     handlers::RtmpContext
+    /// };
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert_eq!(Flv::DEFAULT_MAX_TAG_SIZE, rtmp_context.get_max_tag_size())
+    /// ```
+    pub fn get_max_tag_size(&mut self) -> u32 {
+        self.max_tag_size
+    }
+
+    /// Gets the outbound flow control.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// assert!(rtmp_context.get_flow_control().get_window_size().is_none())
+    /// ```
+    pub fn get_flow_control(&mut self) -> FlowControl {
+        self.flow_control
+    }
+
+    /// Gets the outbound flow control as mutable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handlers::RtmpContext;
+    ///
+    /// let mut rtmp_context = RtmpContext::default();
+    /// rtmp_context.get_flow_control_mut().add_sent_amount(128);
+    /// assert_eq!(128, rtmp_context.get_flow_control().get_unacknowledged_amount())
+    /// ```
+    pub fn get_flow_control_mut(&mut self) -> &mut FlowControl {
+        &mut self.flow_control
+    }
+
     /// Sets a topic file/device.
     pub fn set_topic(&mut self, topic: Flv) {
         self.topic = Some(topic);
@@ -0,0 +1,130 @@
+use std::{
+    io::Result as IOResult,
+    pin::Pin,
+    task::{
+        Context,
+        Poll
+    }
+};
+use futures::ready;
+use tokio::io::{
+    AsyncRead,
+    ReadBuf
+};
+use super::MeasureAcknowledgement;
+
+/// The read half of a connection.
+///
+/// This owns the reading side of a stream, so it can be moved into another task apart from the [`StreamWriter`].
+///
+/// [`StreamWriter`]: super::StreamWriter
+#[derive(Debug)]
+pub struct StreamReader<R: Unpin> {
+    reader: R,
+    sequence_number: u32,
+    unacknowledged_amount: u32
+}
+
+impl<R: Unpin> StreamReader<R> {
+    /// Constructs a read half.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            sequence_number: u32::default(),
+            unacknowledged_amount: u32::default()
+        }
+    }
+
+    /// Takes the inner reader back.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Unpin> MeasureAcknowledgement for StreamReader<R> {
+    fn add_amount(&mut self, amount: u32) {
+        self.sequence_number = self.sequence_number.wrapping_add(amount);
+        self.unacknowledged_amount = self.unacknowledged_amount.saturating_add(amount);
+    }
+
+    fn get_sequence_number(&mut self) -> u32 {
+        self.sequence_number
+    }
+
+    fn get_unacknowledged_amount(&mut self) -> u32 {
+        self.unacknowledged_amount
+    }
+
+    fn set_unacknowledged_amount(&mut self, unacknowledged_amount: u32) {
+        self.unacknowledged_amount = unacknowledged_amount;
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for StreamReader<R> {
+    /// Wraps a stream to make it able to measure the amount of bytes.
+    ///
+    /// When bytes read exceeded some bandwidth limit, RTMP peers are required to send the `Acknowldgement` message to the other peer.
+    /// But prepared stream like Vec, slice, or TCP streams has no implementation above.
+    /// Therefore, StreamReader measures amounts of bytes read and writes `Acknowledgement` messages instead.
+    ///
+    /// Note this counts every byte read from the beginning of the connection, including the handshake.
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.reader).poll_read(cx, buf))?;
+
+        let amount = (buf.filled().len() - filled) as u32;
+        self.add_amount(amount);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{
+        AsyncReadExt,
+        AsyncWriteExt,
+        split
+    };
+    use crate::handlers::VecStream;
+    use super::*;
+
+    #[tokio::test]
+    async fn count_total_amount() {
+        let (reader, mut writer) = split(VecStream::default());
+        let mut reader = StreamReader::new(reader);
+        writer.write_all(&[0u8; 16]).await.unwrap();
+        let mut bytes: [u8; 16] = [0; 16];
+        reader.read_exact(&mut bytes[..8]).await.unwrap();
+        reader.read_exact(&mut bytes[8..]).await.unwrap();
+        assert_eq!(16, reader.get_sequence_number());
+        assert_eq!(16, reader.get_unacknowledged_amount())
+    }
+
+    #[test]
+    fn pass_window_boundaries() {
+        let mut reader = StreamReader::new(VecStream::default());
+        reader.add_amount(9);
+        assert!(!reader.pass_window(10));
+        reader.add_amount(2);
+        assert!(reader.pass_window(10));
+        /* NOTE: Exceeded bytes are carried over. */
+        assert_eq!(1, reader.get_unacknowledged_amount());
+        reader.add_amount(8);
+        assert!(!reader.pass_window(10));
+        reader.add_amount(1);
+        assert!(reader.pass_window(10));
+        assert_eq!(20, reader.get_sequence_number())
+    }
+
+    #[test]
+    fn wrap_sequence_number() {
+        let mut reader = StreamReader::new(VecStream::default());
+        reader.add_amount(u32::MAX);
+        assert!(reader.pass_window(u32::MAX));
+        reader.add_amount(2);
+        assert_eq!(1, reader.get_sequence_number());
+        assert_eq!(2, reader.get_unacknowledged_amount());
+        assert_eq!(1, reader.as_acknowledgement().get_inner())
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::VecDeque,
+    io::{
+        Error as IOError,
+        ErrorKind,
+        IoSlice,
+        Result as IOResult
+    },
+    pin::Pin,
+    task::{
+        Context,
+        Poll
+    }
+};
+use bytes::{
+    Buf,
+    Bytes
+};
+use futures::ready;
+use tokio::io::AsyncWrite;
+
+/// The write half of a connection.
+///
+/// This owns the writing side of a stream, so it can be moved into another task apart from the [`StreamReader`].
+///
+/// Bytes to write are buffered in this until the inner stream accepts them.
+/// Because writers in this crate are restartable futures, that is, they can write same bytes again if the inner stream returns `Pending` halfway.
+/// Buffered bytes are sent on flushing, or when they exceed [`DRAIN_THRESHOLD`] on writing.
+/// Therefore headers and chunks of a message reach the inner stream at once instead of one write per each.
+/// While buffered bytes exceed that, writing waits until the inner stream accepts them, so that a slow peer can't make the buffer grow without bound.
+///
+/// Buffered bytes are kept as separate chunks and are handed over to the inner stream as one vectored write.
+/// A vectored write which is larger than [`DRAIN_THRESHOLD`] by itself isn't buffered, but is forwarded to the inner stream as is after buffered bytes have been sent.
+///
+/// [`DRAIN_THRESHOLD`]: StreamWriter::DRAIN_THRESHOLD
+///
+/// [`StreamReader`]: super::StreamReader
+#[derive(Debug)]
+pub struct StreamWriter<W: Unpin> {
+    writer: W,
+    outgoing: VecDeque<Bytes>,
+    outgoing_len: usize
+}
+
+impl<W: Unpin> StreamWriter<W> {
+    /// The amount of buffered bytes to start sending them without waiting for flushing.
+    pub const DRAIN_THRESHOLD: usize = 65536;
+
+    /// Constructs a write half.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            outgoing: VecDeque::new(),
+            outgoing_len: 0
+        }
+    }
+
+    /// Takes the inner writer back.
+    ///
+    /// Note bytes which aren't flushed yet are discarded.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn enqueue(&mut self, buf: &[u8]) {
+        if !buf.is_empty() {
+            self.outgoing.push_back(Bytes::copy_from_slice(buf));
+            self.outgoing_len += buf.len();
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> StreamWriter<W> {
+    /// Writes buffered bytes into the inner stream as much as it accepts.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        while !self.outgoing.is_empty() {
+            let slices: Vec<IoSlice<'_>> = self.outgoing.iter().map(|bytes| IoSlice::new(bytes)).collect();
+            let written = ready!(Pin::new(&mut self.writer).poll_write_vectored(cx, &slices))?;
+            if written == 0 {
+                return Poll::Ready(Err(IOError::from(ErrorKind::WriteZero)))
+            }
+
+            self.outgoing_len -= written;
+            let mut remained = written;
+            while let Some(bytes) = self.outgoing.front_mut() {
+                if bytes.len() > remained {
+                    bytes.advance(remained);
+                    break
+                }
+                remained -= bytes.len();
+                self.outgoing.pop_front();
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Sends buffered bytes ahead of flushing, if they have grown enough.
+    ///
+    /// This returns `Pending` until the inner stream accepts all of them.
+    fn poll_drain_over_threshold(&mut self, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        if self.outgoing_len < Self::DRAIN_THRESHOLD {
+            return Poll::Ready(Ok(()))
+        }
+
+        self.poll_drain(cx)
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for StreamWriter<W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IOResult<usize>> {
+        ready!(self.poll_drain_over_threshold(cx))?;
+        self.enqueue(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_write_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>]) -> Poll<IOResult<usize>> {
+        let total_len: usize = bufs.iter().map(|buf| buf.len()).sum();
+        if total_len >= Self::DRAIN_THRESHOLD {
+            /* NOTE: Large media data are handed over without copying, because buffering them doesn't save any write. */
+            ready!(self.poll_drain(cx))?;
+            return Pin::new(&mut self.writer).poll_write_vectored(cx, bufs)
+        }
+
+        ready!(self.poll_drain_over_threshold(cx))?;
+        for buf in bufs {
+            self.enqueue(buf);
+        }
+        Poll::Ready(Ok(total_len))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        ready!(self.poll_drain(cx))?;
+        Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        ready!(self.poll_drain(cx))?;
+        Pin::new(&mut self.writer).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::pin,
+        time::Duration
+    };
+    use futures::poll;
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt,
+            duplex
+        },
+        join,
+        time::timeout
+    };
+    use super::*;
+
+    #[tokio::test]
+    async fn keep_bytes_until_accepted() {
+        let (client, mut server) = duplex(4);
+        let mut writer = StreamWriter::new(client);
+        assert_eq!(8, writer.write(&[1u8; 8]).await.unwrap());
+
+        let mut bytes: [u8; 8] = [0; 8];
+        let (flushed, read) = join!(writer.flush(), server.read_exact(&mut bytes));
+        flushed.unwrap();
+        read.unwrap();
+        assert_eq!([1u8; 8], bytes)
+    }
+
+    #[tokio::test]
+    async fn buffer_until_flushing() {
+        let (client, mut server) = duplex(64);
+        let mut writer = StreamWriter::new(client);
+        let slices = [IoSlice::new(&[1, 2]), IoSlice::new(&[3]), IoSlice::new(&[4, 5, 6])];
+        assert_eq!(6, writer.write_vectored(&slices).await.unwrap());
+
+        let mut bytes: [u8; 6] = [0; 6];
+        assert!(timeout(Duration::from_millis(10), server.read_exact(&mut bytes)).await.is_err());
+        writer.flush().await.unwrap();
+        server.read_exact(&mut bytes).await.unwrap();
+        assert_eq!([1, 2, 3, 4, 5, 6], bytes)
+    }
+
+    #[tokio::test]
+    async fn wait_for_draining_over_threshold() {
+        let (client, mut server) = duplex(4);
+        let mut writer = StreamWriter::new(client);
+        let threshold = StreamWriter::<()>::DRAIN_THRESHOLD;
+        assert_eq!(threshold, writer.write(&vec![1u8; threshold]).await.unwrap());
+        assert!(poll!(pin!(writer.write(&[2]))).is_pending());
+
+        let mut bytes = vec![0u8; threshold + 1];
+        let (written, read) = join!(
+            async {
+                let written = writer.write(&[2]).await?;
+                writer.flush().await?;
+                Ok::<usize, IOError>(written)
+            },
+            server.read_exact(&mut bytes)
+        );
+        assert_eq!(1, written.unwrap());
+        read.unwrap();
+        assert!(bytes[..threshold].iter().all(|byte| *byte == 1));
+        assert_eq!(2, bytes[threshold])
+    }
+
+    #[tokio::test]
+    async fn forward_large_vectored_write() {
+        let (client, mut server) = duplex(4);
+        let mut writer = StreamWriter::new(client);
+        let threshold = StreamWriter::<()>::DRAIN_THRESHOLD;
+        let large = vec![2u8; threshold];
+        assert_eq!(1, writer.write(&[1]).await.unwrap());
+
+        let mut bytes = vec![0u8; threshold + 1];
+        let (written, read) = join!(
+            async {
+                let mut written = 0;
+                while written < large.len() {
+                    written += writer.write_vectored(&[IoSlice::new(&large[written..])]).await?;
+                }
+                writer.flush().await?;
+                Ok::<usize, IOError>(written)
+            },
+            server.read_exact(&mut bytes)
+        );
+        assert_eq!(threshold, written.unwrap());
+        read.unwrap();
+        assert_eq!(1, bytes[0]);
+        assert!(bytes[1..].iter().all(|byte| *byte == 2));
+        assert!(writer.outgoing.is_empty())
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::{
+    Error as IOError,
+    Result as IOResult
+};
+use log::{
+    error,
+    warn
+};
+use clap::ValueEnum;
+
+/// The pattern to decide how to treat messages/events which are unknown or unsupported.
+///
+/// Some peer can send any message or any user control event which we don't know (or don't handle yet).
+/// A single malformed packet shouldn't stop the connection necessarily, so this lets you choose the behavior.
+///
+/// |Pattern|Behavior|
+/// | :- | :- |
+/// |`Ignore`|Discards it silently.|
+/// |`Log`|Discards it after logging as a warning.|
+/// |`Disconnect`|Returns an error to close the connection.|
+///
+/// This can be also specified as a command line option. (`ignore`, `log` or `disconnect`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum UnknownMessagePolicy {
+    Ignore,
+    #[default]
+    Log,
+    Disconnect
+}
+
+impl UnknownMessagePolicy {
+    /// Treats an error which has reported an unknown message/event, in accordance with this policy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     handlers::UnknownMessagePolicy,
+    ///     messages::unknown_event
+    /// };
+    ///
+    /// assert!(UnknownMessagePolicy::Ignore.apply(unknown_event(0xffff)).is_ok());
+    /// assert!(UnknownMessagePolicy::Log.apply(unknown_event(0xffff)).is_ok());
+    /// assert!(UnknownMessagePolicy::Disconnect.apply(unknown_event(0xffff)).is_err())
+    /// ```
+    pub fn apply(self, unknown: IOError) -> IOResult<()> {
+        match self {
+            UnknownMessagePolicy::Ignore => Ok(()),
+            UnknownMessagePolicy::Log => {
+                warn!("{unknown}");
+                Ok(())
+            },
+            UnknownMessagePolicy::Disconnect => {
+                error!("{unknown}");
+                Err(unknown)
+            }
+        }
+    }
+}
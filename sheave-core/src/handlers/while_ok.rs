@@ -1,43 +1,39 @@
 use std::{
     io::Result as IOResult,
-    pin::Pin,
-    task::{
-        Context as FutureContext,
-        Poll
-    }
+    pin::Pin
+};
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite
 };
-use pin_project_lite::pin_project;
-use futures::ready;
 use super::{
     AsyncHandler,
+    MeasureAcknowledgement,
     RtmpContext
 };
 
-pin_project! {
-    #[doc(hidden)]
-    #[derive(Debug)]
-    pub struct WhileOk<H1, H2> {
-        #[pin] before: H1,
-        #[pin] body: H2
-    }
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct WhileOk<H1, H2> {
+    before: H1,
+    body: H2
 }
 
 #[doc(hidden)]
 impl<H1, H2> AsyncHandler for WhileOk<H1, H2>
 where
-    H1: AsyncHandler + Unpin,
-    H2: AsyncHandler + Unpin,
+    H1: AsyncHandler + Send,
+    H2: AsyncHandler + Send,
 {
-    fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        let mut this = self.project();
-
-        ready!(this.before.poll_handle(cx, rtmp_context))?;
+    async fn handle<R, W>(&mut self, mut reader: Pin<&mut R>, mut writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        self.before.handle(reader.as_mut(), writer.as_mut(), rtmp_context).await?;
 
         loop {
-            match this.body.as_mut().poll_handle(cx, rtmp_context) {
-                Poll::Pending => continue,
-                Poll::Ready(result) => result?
-            }
+            self.body.handle(reader.as_mut(), writer.as_mut(), rtmp_context).await?;
         }
     }
 }
@@ -45,8 +41,8 @@ where
 #[doc(hidden)]
 pub fn while_ok<H1, H2>(before: H1, body: H2) -> WhileOk<H1, H2>
 where
-    H1: AsyncHandler + Unpin,
-    H2: AsyncHandler + Unpin,
+    H1: AsyncHandler,
+    H2: AsyncHandler,
 {
     WhileOk { before, body }
 }
@@ -47,40 +47,69 @@
 //! use sheave_core::handshake::{
 //!     Handshake,
 //!     Version,
-//!     EncryptionAlgorithm
+//!     DigestScheme
 //! };
 //!
 //! // In a case of exchanging client-side request with server-side response.
 //! let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
-//! client_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY);
+//! client_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::CLIENT_KEY);
 //! let mut key: Vec<u8> = Vec::new();
 //! key.extend_from_slice(Handshake::SERVER_KEY);
 //! key.extend_from_slice(Handshake::COMMON_KEY);
-//! client_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
-//! assert!(client_handshake.did_signature_match(EncryptionAlgorithm::NotEncrypted, key.as_slice()));
+//! client_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
+//! assert!(client_handshake.did_signature_match(DigestScheme::Scheme0, key.as_slice()));
 //!
 //! // In a case of exchanging server-side request with client-side response.
 //! let mut server_handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
-//! server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY);
+//! server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::SERVER_KEY);
 //! let mut key: Vec<u8> = Vec::new();
 //! key.extend_from_slice(Handshake::CLIENT_KEY);
 //! key.extend_from_slice(Handshake::COMMON_KEY);
-//! server_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
-//! assert!(server_handshake.did_signature_match(EncryptionAlgorithm::NotEncrypted, key.as_slice()));
+//! server_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
+//! assert!(server_handshake.did_signature_match(DigestScheme::Scheme0, key.as_slice()));
 //! ```
 //!
+//! ### Timestamps
+//!
+//! First 4 bytes of C1/S1 are the time when it is sent, in milliseconds since the UNIX epoch (see [`epoch_timestamp`]).
+//! The partner echoes it in C2/S2, so that the sender can measure the round-trip time via [`elapsed_since`].
+//!
+//! ### Digest schemes
+//!
+//! The place of the digest is decided by either of two schemes (see [`DigestScheme`]).
+//! Clients choose either scheme, so servers find it via [`find_digest_scheme`], then respond with the same scheme.
+//!
+//! [`find_digest_scheme`]: Handshake::find_digest_scheme
+//!
 //! ### Encryption
 //!
-//! Currently, to implement handshake encryption isn't planned following causes:
+//! When the RTMP version is `6` (RTMPE), both sides additionally imprint their Diffie-Hellman public keys into handshake data.
+//! Then RC4 keys are derived from the shared secret, and all chunks after the handshake are encrypted.
+//! See [`DiffieHellman`] and [`RtmpeStream`].
+//!
+//! Note RTMPE isn't secure in the modern sense, because neither side is authenticated and RC4 is weak.
+//! It is implemented only to interoperate with legacy Flash clients and appliances, so use RTMPS instead if you can choose.
+//!
+//! [`RtmpeStream`]: crate::net::rtmpe::RtmpeStream
 //!
-//! 1. Connected socket is in full view from outside. This is insecure though chunk is encrypted.
-//! 2. If chunk encryption is implemented on RTMPTS, To decrypt chunk/socket takes both sides time in no small way. This is inefficient for real-time communications.
-//! 3. Therefore I'm thinking we should leave encryption to only HTTPS.
+//! ### SWF verification
+//!
+//! Some servers additionally require clients to prove which SWF file they have been loaded from.
+//! The proof depends on the last 32 bytes of S1, so it is computed from the handshake data (see [`SwfVerification`]).
 
 mod version;
 mod encryption_algorithm;
+mod digest_scheme;
+mod diffie_hellman;
+mod invalid_public_key;
+mod swf_verification;
+mod invalid_swf;
 
-use std::time::Duration;
+use std::time::{
+    Duration,
+    SystemTime,
+    UNIX_EPOCH
+};
 use rand::fill;
 use digest::{
     CtOutput,
@@ -93,11 +122,57 @@ use hmac::{
 };
 pub use self::{
     version::Version,
-    encryption_algorithm::EncryptionAlgorithm
+    encryption_algorithm::EncryptionAlgorithm,
+    digest_scheme::DigestScheme,
+    diffie_hellman::DiffieHellman,
+    invalid_public_key::*,
+    swf_verification::SwfVerification,
+    invalid_swf::*
 };
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Gets current time as milliseconds since the UNIX epoch.
+///
+/// Timestamps in RTMP are 4 bytes, so this wraps around in about 49.7 days.
+/// Use [`elapsed_since`] to get the time from this, rather than subtracting it directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use sheave_core::handshake::epoch_timestamp;
+///
+/// assert!(epoch_timestamp() < Duration::from_millis(u32::MAX as u64 + 1))
+/// ```
+pub fn epoch_timestamp() -> Duration {
+    /* NOTE: The clock can't be earlier than the UNIX epoch in practice, otherwise it is treated as the epoch itself. */
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    Duration::from_millis(since_epoch.as_millis() as u32 as u64)
+}
+
+/// Gets the time which has elapsed since a timestamp got by [`epoch_timestamp`].
+///
+/// This is used to measure the round-trip time from timestamps which the partner has echoed.
+/// The wraparound of 4 bytes timestamps is taken into account.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use sheave_core::handshake::{
+///     elapsed_since,
+///     epoch_timestamp
+/// };
+///
+/// let timestamp = epoch_timestamp();
+/// assert!(elapsed_since(timestamp) < Duration::from_secs(1))
+/// ```
+pub fn elapsed_since(timestamp: Duration) -> Duration {
+    let now = epoch_timestamp().as_millis() as u32;
+    Duration::from_millis(now.wrapping_sub(timestamp.as_millis() as u32) as u64)
+}
+
 /// The 1536 bytes handshake data.
 /// This respectively consists of following parts:
 ///
@@ -129,7 +204,7 @@ impl Handshake {
     /// use sheave_core::handshake::{
     ///     Handshake,
     ///     Version,
-    ///     EncryptionAlgorithm
+    ///     DigestScheme
     /// };
     ///
     /// // If you are a client.
@@ -159,6 +234,33 @@ impl Handshake {
         Duration::from_millis(u32::from_be_bytes(timestamp_bytes) as u64)
     }
 
+    /// Measures the round-trip time from the partner's echo of this handshake data.
+    ///
+    /// This returns `None` if the echo doesn't have the same timestamp as this.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::handshake::{
+    ///     Handshake,
+    ///     Version,
+    ///     epoch_timestamp
+    /// };
+    ///
+    /// let client_request = Handshake::new(epoch_timestamp(), Version::UNSIGNED);
+    /// let mut echo_bytes: [u8; 1536] = [0; 1536];
+    /// echo_bytes.copy_from_slice(client_request.get_bytes());
+    /// let echo = Handshake::from(echo_bytes);
+    /// assert!(client_request.measure_round_trip_time(&echo).unwrap() < Duration::from_secs(1));
+    ///
+    /// let not_echo = Handshake::new(Duration::default(), Version::UNSIGNED);
+    /// assert!(Handshake::new(Duration::from_millis(1), Version::UNSIGNED).measure_round_trip_time(&not_echo).is_none())
+    /// ```
+    pub fn measure_round_trip_time(&self, echo: &Handshake) -> Option<Duration> {
+        (self.get_timestamp() == echo.get_timestamp()).then(|| elapsed_since(self.get_timestamp()))
+    }
+
     /// Gets second 4 bytes as Flash Player version/Flash Media Server version.
     pub fn get_version(&self) -> Version {
         let mut version_bytes: [u8; 4] = [0; 4];
@@ -166,15 +268,15 @@ impl Handshake {
         version_bytes.into()
     }
 
-    fn get_digest_position(&self, encryption_algorithm: EncryptionAlgorithm) -> usize {
+    fn get_digest_position(&self, digest_scheme: DigestScheme) -> usize {
         let offset: usize;
         let adder: usize;
-        match encryption_algorithm {
-            EncryptionAlgorithm::NotEncrypted => {
+        match digest_scheme {
+            DigestScheme::Scheme0 => {
                 offset = 8;
                 adder = 12;
             },
-            _ => {
+            DigestScheme::Scheme1 => {
                 offset = 772;
                 adder = 776;
             }
@@ -182,40 +284,40 @@ impl Handshake {
         self.0[offset..(offset + 4)].iter().map(|byte| usize::from(*byte)).sum::<usize>() % 728 + adder
     }
 
-    fn get_digest_message(&self, encryption_algorithm: EncryptionAlgorithm) -> Vec<u8> {
-        let digest_position = self.get_digest_position(encryption_algorithm);
+    fn get_digest_message(&self, digest_scheme: DigestScheme) -> Vec<u8> {
+        let digest_position = self.get_digest_position(digest_scheme);
         let mut message: Vec<u8> = Vec::new();
         message.extend_from_slice(&self.0[..digest_position]);
         message.extend_from_slice(&self.0[(digest_position + HmacSha256::output_size())..]);
         message
     }
 
-    fn compute_digest(&self, encryption_algorithm: EncryptionAlgorithm, key: &[u8]) -> CtOutput<HmacSha256> {
-        let message = self.get_digest_message(encryption_algorithm);
+    fn compute_digest(&self, digest_scheme: DigestScheme, key: &[u8]) -> CtOutput<HmacSha256> {
+        let message = self.get_digest_message(digest_scheme);
         let mut hmac = HmacSha256::new_from_slice(key).unwrap();
         hmac.update(message.as_slice());
         hmac.finalize()
     }
 
     /// Gets a digest contained in this handshake bytes.
-    /// Note its place is different by whether encrypts handshake bytes.
+    /// Note its place is different by the digest scheme.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use std::time::Instant;
     /// use sheave_core::handshake::{
-    ///     EncryptionAlgorithm,
+    ///     DigestScheme,
     ///     Handshake,
     ///     Version
     /// };
     ///
     /// let handshake = Handshake::new(Instant::now().elapsed(), Version::LATEST_CLIENT);
     ///
-    /// assert_ne!(handshake.get_digest(EncryptionAlgorithm::NotEncrypted), handshake.get_digest(EncryptionAlgorithm::DiffieHellman))
+    /// assert_ne!(handshake.get_digest(DigestScheme::Scheme0), handshake.get_digest(DigestScheme::Scheme1))
     /// ```
-    pub fn get_digest(&self, encryption_algorithm: EncryptionAlgorithm) -> &[u8] {
-        let digest_position = self.get_digest_position(encryption_algorithm);
+    pub fn get_digest(&self, digest_scheme: DigestScheme) -> &[u8] {
+        let digest_position = self.get_digest_position(digest_scheme);
         &self.0[digest_position..(digest_position + HmacSha256::output_size())]
     }
 
@@ -228,20 +330,20 @@ impl Handshake {
     /// use sheave_core::handshake::{
     ///     Handshake,
     ///     Version,
-    ///     EncryptionAlgorithm
+    ///     DigestScheme
     /// };
     ///
     /// // In a case of sending client-side request.
     /// let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
-    /// client_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY);
+    /// client_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::CLIENT_KEY);
     ///
     /// // In a case of sending server-side request.
     /// let mut server_handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
-    /// server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY);
+    /// server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::SERVER_KEY);
     /// ```
-    pub fn imprint_digest(&mut self, encryption_algorithm: EncryptionAlgorithm, key: &[u8]) {
-        let digest_position = self.get_digest_position(encryption_algorithm);
-        let digest = self.compute_digest(encryption_algorithm, key);
+    pub fn imprint_digest(&mut self, digest_scheme: DigestScheme, key: &[u8]) {
+        let digest_position = self.get_digest_position(digest_scheme);
+        let digest = self.compute_digest(digest_scheme, key);
         self.0[digest_position..(digest_position + HmacSha256::output_size())].copy_from_slice(digest.into_bytes().as_slice());
     }
 
@@ -254,22 +356,95 @@ impl Handshake {
     /// use sheave_core::handshake::{
     ///     Handshake,
     ///     Version,
-    ///     EncryptionAlgorithm
+    ///     DigestScheme
     /// };
     ///
     /// // In a case of checking server-side request.
     /// let mut server_handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
-    /// server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY);
-    /// assert!(server_handshake.did_digest_match(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY));
+    /// server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::SERVER_KEY);
+    /// assert!(server_handshake.did_digest_match(DigestScheme::Scheme0, Handshake::SERVER_KEY));
     ///
     /// // In a case of checking client-side request.
     /// let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
-    /// server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY);
-    /// assert!(server_handshake.did_digest_match(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY));
+    /// server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::CLIENT_KEY);
+    /// assert!(server_handshake.did_digest_match(DigestScheme::Scheme0, Handshake::CLIENT_KEY));
+    /// ```
+    pub fn did_digest_match(&self, digest_scheme: DigestScheme, key: &[u8]) -> bool {
+        let expected = self.compute_digest(digest_scheme, key);
+        self.get_digest(digest_scheme) == expected.into_bytes().as_slice()
+    }
+
+    /// Finds the digest scheme which the imprinted digest matches with, in order of `Scheme0` and `Scheme1`.
+    ///
+    /// Returns `None` if the digest matches with neither scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::handshake::{
+    ///     DigestScheme,
+    ///     Handshake,
+    ///     Version
+    /// };
+    ///
+    /// let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
+    /// assert!(client_handshake.find_digest_scheme(Handshake::CLIENT_KEY).is_none());
+    /// client_handshake.imprint_digest(DigestScheme::Scheme1, Handshake::CLIENT_KEY);
+    /// assert_eq!(Some(DigestScheme::Scheme1), client_handshake.find_digest_scheme(Handshake::CLIENT_KEY))
     /// ```
-    pub fn did_digest_match(&self, encryption_algorithm: EncryptionAlgorithm, key: &[u8]) -> bool {
-        let expected = self.compute_digest(encryption_algorithm, key);
-        self.get_digest(encryption_algorithm) == expected.into_bytes().as_slice()
+    pub fn find_digest_scheme(&self, key: &[u8]) -> Option<DigestScheme> {
+        DigestScheme::ALL.into_iter().find(|digest_scheme| self.did_digest_match(*digest_scheme, key))
+    }
+
+    fn get_public_key_position(&self, digest_scheme: DigestScheme) -> usize {
+        let offset: usize;
+        let adder: usize;
+        match digest_scheme {
+            DigestScheme::Scheme0 => {
+                offset = 1532;
+                adder = 772;
+            },
+            DigestScheme::Scheme1 => {
+                offset = 768;
+                adder = 8;
+            }
+        }
+        self.0[offset..(offset + 4)].iter().map(|byte| usize::from(*byte)).sum::<usize>() % 632 + adder
+    }
+
+    /// Gets a Diffie-Hellman public key contained in this handshake bytes.
+    /// Note its place is different by the digest scheme, as same as the digest.
+    pub fn get_public_key(&self, digest_scheme: DigestScheme) -> &[u8] {
+        let public_key_position = self.get_public_key_position(digest_scheme);
+        &self.0[public_key_position..(public_key_position + DiffieHellman::KEY_LENGTH)]
+    }
+
+    /// Imprints a Diffie-Hellman public key into handshake data.
+    ///
+    /// This is required to be done before imprinting the digest, because the digest covers the public key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::handshake::{
+    ///     DiffieHellman,
+    ///     DigestScheme,
+    ///     Handshake,
+    ///     Version
+    /// };
+    ///
+    /// let diffie_hellman = DiffieHellman::generate();
+    /// let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
+    /// client_handshake.imprint_public_key(DigestScheme::Scheme1, diffie_hellman.get_public_key());
+    /// client_handshake.imprint_digest(DigestScheme::Scheme1, Handshake::CLIENT_KEY);
+    /// assert_eq!(diffie_hellman.get_public_key(), client_handshake.get_public_key(DigestScheme::Scheme1));
+    /// assert!(client_handshake.did_digest_match(DigestScheme::Scheme1, Handshake::CLIENT_KEY))
+    /// ```
+    pub fn imprint_public_key(&mut self, digest_scheme: DigestScheme, public_key: &[u8]) {
+        let public_key_position = self.get_public_key_position(digest_scheme);
+        self.0[public_key_position..(public_key_position + DiffieHellman::KEY_LENGTH)].copy_from_slice(public_key);
     }
 
     fn get_signature_position(&self) -> usize {
@@ -281,8 +456,11 @@ impl Handshake {
         &self.0[..signature_position]
     }
 
-    fn compute_signature(&self, encryption_algorithm: EncryptionAlgorithm, key: &[u8]) -> CtOutput<HmacSha256> {
-        let digest = self.get_digest(encryption_algorithm);
+    fn compute_signature(&self, digest_scheme: DigestScheme, key: &[u8]) -> CtOutput<HmacSha256> {
+        self.compute_signature_from(self.get_digest(digest_scheme), key)
+    }
+
+    fn compute_signature_from(&self, digest: &[u8], key: &[u8]) -> CtOutput<HmacSha256> {
         let mut hmac = HmacSha256::new_from_slice(key).unwrap();
         hmac.update(digest);
         let key_from_digest = hmac.finalize();
@@ -309,28 +487,28 @@ impl Handshake {
     /// use sheave_core::handshake::{
     ///     Handshake,
     ///     Version,
-    ///     EncryptionAlgorithm
+    ///     DigestScheme
     /// };
     ///
     /// // In a case of exchanging client-side request with server-side response.
     /// let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
-    /// client_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY);
+    /// client_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::CLIENT_KEY);
     /// let mut key: Vec<u8> = Vec::new();
     /// key.extend_from_slice(Handshake::SERVER_KEY);
     /// key.extend_from_slice(Handshake::COMMON_KEY);
-    /// client_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
+    /// client_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
     ///
     /// // In a case of exchanging server-side request with client-side response.
     /// let mut server_handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
-    /// server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY);
+    /// server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::SERVER_KEY);
     /// let mut key: Vec<u8> = Vec::new();
     /// key.extend_from_slice(Handshake::CLIENT_KEY);
     /// key.extend_from_slice(Handshake::COMMON_KEY);
-    /// server_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
+    /// server_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
     /// ```
-    pub fn imprint_signature(&mut self, encryption_algorithm: EncryptionAlgorithm, key: &[u8]) {
+    pub fn imprint_signature(&mut self, digest_scheme: DigestScheme, key: &[u8]) {
         let signature_position = self.get_signature_position();
-        let signature = self.compute_signature(encryption_algorithm, key);
+        let signature = self.compute_signature(digest_scheme, key);
         self.0[signature_position..].copy_from_slice(signature.into_bytes().as_slice());
     }
 
@@ -343,29 +521,58 @@ impl Handshake {
     /// use sheave_core::handshake::{
     ///     Handshake,
     ///     Version,
-    ///     EncryptionAlgorithm
+    ///     DigestScheme
     /// };
     ///
     /// // In a case of checking client-side response.
     /// let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
-    /// client_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY);
+    /// client_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::CLIENT_KEY);
     /// let mut key: Vec<u8> = Vec::new();
     /// key.extend_from_slice(Handshake::SERVER_KEY);
     /// key.extend_from_slice(Handshake::COMMON_KEY);
-    /// client_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
-    /// assert!(client_handshake.did_signature_match(EncryptionAlgorithm::NotEncrypted, key.as_slice()));
+    /// client_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
+    /// assert!(client_handshake.did_signature_match(DigestScheme::Scheme0, key.as_slice()));
     ///
     /// // In a case of checking server-side response.
     /// let mut server_handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
-    /// server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY);
+    /// server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::SERVER_KEY);
     /// let mut key: Vec<u8> = Vec::new();
     /// key.extend_from_slice(Handshake::CLIENT_KEY);
     /// key.extend_from_slice(Handshake::COMMON_KEY);
-    /// server_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
-    /// assert!(server_handshake.did_signature_match(EncryptionAlgorithm::NotEncrypted, key.as_slice()));
+    /// server_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
+    /// assert!(server_handshake.did_signature_match(DigestScheme::Scheme0, key.as_slice()));
+    /// ```
+    pub fn did_signature_match(&self, digest_scheme: DigestScheme, key: &[u8]) -> bool {
+        let expected = self.compute_signature(digest_scheme, key);
+        self.get_signature() == expected.into_bytes().as_slice()
+    }
+
+    /// Checks whether imprinted signature matches one computed by given key and the digest of our request which this responds to.
+    ///
+    /// The signature occupies last 32 bytes, so it can overwrite the echoed digest in `Scheme1`.
+    /// Therefore the response should be checked with the digest of the request as it has been sent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sheave_core::handshake::{
+    ///     Handshake,
+    ///     Version,
+    ///     DigestScheme
+    /// };
+    ///
+    /// let mut client_request = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
+    /// client_request.imprint_digest(DigestScheme::Scheme1, Handshake::CLIENT_KEY);
+    /// let mut key: Vec<u8> = Vec::new();
+    /// key.extend_from_slice(Handshake::SERVER_KEY);
+    /// key.extend_from_slice(Handshake::COMMON_KEY);
+    /// let mut server_response = Handshake::from(<[u8; 1536]>::try_from(client_request.get_bytes()).unwrap());
+    /// server_response.imprint_signature(DigestScheme::Scheme1, key.as_slice());
+    /// assert!(server_response.did_signature_match_request(&client_request, DigestScheme::Scheme1, key.as_slice()))
     /// ```
-    pub fn did_signature_match(&self, encryption_algorithm: EncryptionAlgorithm, key: &[u8]) -> bool {
-        let expected = self.compute_signature(encryption_algorithm, key);
+    pub fn did_signature_match_request(&self, request: &Handshake, digest_scheme: DigestScheme, key: &[u8]) -> bool {
+        let expected = self.compute_signature_from(request.get_digest(digest_scheme), key);
         self.get_signature() == expected.into_bytes().as_slice()
     }
 }
@@ -383,36 +590,63 @@ mod tests {
     #[test]
     fn did_client_digest_match() {
         let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
-        client_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY);
-        assert!(client_handshake.did_digest_match(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY))
+        client_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::CLIENT_KEY);
+        assert!(client_handshake.did_digest_match(DigestScheme::Scheme0, Handshake::CLIENT_KEY))
     }
 
     #[test]
     fn did_server_digest_match() {
         let mut server_handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
-        server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY);
-        assert!(server_handshake.did_digest_match(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY))
+        server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::SERVER_KEY);
+        assert!(server_handshake.did_digest_match(DigestScheme::Scheme0, Handshake::SERVER_KEY))
     }
 
     #[test]
     fn did_client_signature_match() {
         let mut client_handshake = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
-        client_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::CLIENT_KEY);
+        client_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::CLIENT_KEY);
         let mut key: Vec<u8> = Vec::new();
         key.extend_from_slice(Handshake::SERVER_KEY);
         key.extend_from_slice(Handshake::COMMON_KEY);
-        client_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
-        assert!(client_handshake.did_signature_match(EncryptionAlgorithm::NotEncrypted, key.as_slice()))
+        client_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
+        assert!(client_handshake.did_signature_match(DigestScheme::Scheme0, key.as_slice()))
     }
 
     #[test]
     fn did_server_signature_match() {
         let mut server_handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
-        server_handshake.imprint_digest(EncryptionAlgorithm::NotEncrypted, Handshake::SERVER_KEY);
+        server_handshake.imprint_digest(DigestScheme::Scheme0, Handshake::SERVER_KEY);
         let mut key: Vec<u8> = Vec::new();
         key.extend_from_slice(Handshake::CLIENT_KEY);
         key.extend_from_slice(Handshake::COMMON_KEY);
-        server_handshake.imprint_signature(EncryptionAlgorithm::NotEncrypted, key.as_slice());
-        assert!(server_handshake.did_signature_match(EncryptionAlgorithm::NotEncrypted, key.as_slice()))
+        server_handshake.imprint_signature(DigestScheme::Scheme0, key.as_slice());
+        assert!(server_handshake.did_signature_match(DigestScheme::Scheme0, key.as_slice()))
+    }
+
+    #[test]
+    fn did_signature_overwriting_digest_match() {
+        let mut client_request = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
+        /* NOTE: Places the digest at 1503 (= 727 + 776), that is, the signature overwrites it. */
+        client_request.0[772..776].copy_from_slice(&[182, 182, 182, 181]);
+        client_request.imprint_digest(DigestScheme::Scheme1, Handshake::CLIENT_KEY);
+        let mut key: Vec<u8> = Vec::new();
+        key.extend_from_slice(Handshake::SERVER_KEY);
+        key.extend_from_slice(Handshake::COMMON_KEY);
+        let mut server_response = Handshake(client_request.0);
+        server_response.imprint_signature(DigestScheme::Scheme1, key.as_slice());
+        assert!(!server_response.did_signature_match(DigestScheme::Scheme1, key.as_slice()));
+        assert!(server_response.did_signature_match_request(&client_request, DigestScheme::Scheme1, key.as_slice()))
+    }
+
+    #[test]
+    fn public_key_and_digest_are_separated() {
+        for digest_scheme in [DigestScheme::Scheme0, DigestScheme::Scheme1] {
+            let diffie_hellman = DiffieHellman::generate();
+            let mut handshake = Handshake::new(Duration::default(), Version::LATEST_SERVER);
+            handshake.imprint_public_key(digest_scheme, diffie_hellman.get_public_key());
+            handshake.imprint_digest(digest_scheme, Handshake::SERVER_KEY);
+            assert_eq!(diffie_hellman.get_public_key(), handshake.get_public_key(digest_scheme));
+            assert!(handshake.did_digest_match(digest_scheme, Handshake::SERVER_KEY))
+        }
     }
 }
@@ -0,0 +1,114 @@
+use std::{
+    fmt::{
+        Debug,
+        Formatter,
+        Result as FormatResult
+    },
+    io::Result as IOResult
+};
+use num_bigint::BigUint;
+use rand::fill;
+use super::invalid_public_key;
+
+/// The 1024 bits MODP group of RFC 2409 (Oakley Group 2), which the RTMPE handshake uses.
+const PRIME: &[u8] = &[
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xC9, 0x0F, 0xDA, 0xA2, 0x21, 0x68, 0xC2, 0x34,
+    0xC4, 0xC6, 0x62, 0x8B, 0x80, 0xDC, 0x1C, 0xD1, 0x29, 0x02, 0x4E, 0x08, 0x8A, 0x67, 0xCC, 0x74,
+    0x02, 0x0B, 0xBE, 0xA6, 0x3B, 0x13, 0x9B, 0x22, 0x51, 0x4A, 0x08, 0x79, 0x8E, 0x34, 0x04, 0xDD,
+    0xEF, 0x95, 0x19, 0xB3, 0xCD, 0x3A, 0x43, 0x1B, 0x30, 0x2B, 0x0A, 0x6D, 0xF2, 0x5F, 0x14, 0x37,
+    0x4F, 0xE1, 0x35, 0x6D, 0x6D, 0x51, 0xC2, 0x45, 0xE4, 0x85, 0xB5, 0x76, 0x62, 0x5E, 0x7E, 0xC6,
+    0xF4, 0x4C, 0x42, 0xE9, 0xA6, 0x37, 0xED, 0x6B, 0x0B, 0xFF, 0x5C, 0xB6, 0xF4, 0x06, 0xB7, 0xED,
+    0xEE, 0x38, 0x6B, 0xFB, 0x5A, 0x89, 0x9F, 0xA5, 0xAE, 0x9F, 0x24, 0x11, 0x7C, 0x4B, 0x1F, 0xE6,
+    0x49, 0x28, 0x66, 0x51, 0xEC, 0xE6, 0x53, 0x81, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF
+];
+const GENERATOR: u32 = 2;
+
+/// The Diffie-Hellman key exchange in the RTMPE handshake.
+///
+/// Both sides imprint their public keys into their handshake data, then compute the same shared secret from the partner's public key.
+/// The shared secret is used to derive RC4 keys which encrypt all chunks after the handshake.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::handshake::DiffieHellman;
+///
+/// let client = DiffieHellman::generate();
+/// let server = DiffieHellman::generate();
+/// let client_secret = client.compute_shared_secret(server.get_public_key()).unwrap();
+/// let server_secret = server.compute_shared_secret(client.get_public_key()).unwrap();
+/// assert_eq!(client_secret, server_secret)
+/// ```
+pub struct DiffieHellman {
+    private_key: BigUint,
+    public_key: [u8; DiffieHellman::KEY_LENGTH]
+}
+
+impl DiffieHellman {
+    /// The length of both public keys and shared secrets in bytes.
+    pub const KEY_LENGTH: usize = 128;
+
+    /// Generates a key pair from random bytes.
+    pub fn generate() -> Self {
+        let prime = BigUint::from_bytes_be(PRIME);
+        let mut private_key_bytes: [u8; Self::KEY_LENGTH] = [0; Self::KEY_LENGTH];
+        fill(&mut private_key_bytes);
+        /* NOTE: Makes the private key be in [2, p - 2]. */
+        let private_key = BigUint::from_bytes_be(&private_key_bytes) % (&prime - 3u32) + 2u32;
+        let public_key = BigUint::from(GENERATOR).modpow(&private_key, &prime);
+        Self {
+            private_key,
+            public_key: to_key_bytes(&public_key)
+        }
+    }
+
+    /// Gets the public key as 128 bytes in big endian.
+    pub fn get_public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// Computes the shared secret from the partner's public key.
+    ///
+    /// This fails with `ErrorKind::InvalidData` if the partner's public key is outside of the group.
+    pub fn compute_shared_secret(&self, peer_public_key: &[u8]) -> IOResult<[u8; Self::KEY_LENGTH]> {
+        let prime = BigUint::from_bytes_be(PRIME);
+        let peer_key = BigUint::from_bytes_be(peer_public_key);
+
+        /* NOTE: Rejects 0, 1, p - 1 and keys which aren't in the subgroup of order (p - 1) / 2, so that the secret can't be forced to a small set. */
+        let order = (&prime - 1u32) >> 1;
+        if peer_key <= BigUint::from(1u32) || peer_key >= &prime - 1u32 || peer_key.modpow(&order, &prime) != BigUint::from(1u32) {
+            return Err(invalid_public_key(peer_public_key.to_vec()))
+        }
+
+        Ok(to_key_bytes(&peer_key.modpow(&self.private_key, &prime)))
+    }
+}
+
+impl Debug for DiffieHellman {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_struct("DiffieHellman").field("public_key", &self.public_key).finish_non_exhaustive()
+    }
+}
+
+/* NOTE: Pads the value on the left with zeros, because it can be shorter than 128 bytes. */
+fn to_key_bytes(value: &BigUint) -> [u8; DiffieHellman::KEY_LENGTH] {
+    let bytes = value.to_bytes_be();
+    let mut key_bytes: [u8; DiffieHellman::KEY_LENGTH] = [0; DiffieHellman::KEY_LENGTH];
+    key_bytes[(DiffieHellman::KEY_LENGTH - bytes.len())..].copy_from_slice(&bytes);
+    key_bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn err_out_of_group() {
+        let diffie_hellman = DiffieHellman::generate();
+        assert!(diffie_hellman.compute_shared_secret(&[0; DiffieHellman::KEY_LENGTH]).is_err());
+        let mut one: [u8; DiffieHellman::KEY_LENGTH] = [0; DiffieHellman::KEY_LENGTH];
+        one[DiffieHellman::KEY_LENGTH - 1] = 1;
+        assert!(diffie_hellman.compute_shared_secret(&one).is_err());
+        assert!(diffie_hellman.compute_shared_secret(PRIME).is_err())
+    }
+}
@@ -0,0 +1,46 @@
+use super::EncryptionAlgorithm;
+
+/// The place where HMAC-SHA256 digest and Diffie-Hellman public key are imprinted in the handshake data.
+///
+/// Each scheme decides the place by the sum of 4 bytes at the offset, as following:
+///
+/// |Pattern|Offset of digest|Offset of public key|
+/// | :- | :- | :- |
+/// |`Scheme0` (Default)|`8`|`1532`|
+/// |`Scheme1`|`772`|`768`|
+///
+/// Clients choose either scheme independently of the encryption.
+/// Therefore servers are required to try both schemes when they check the client's digest, then respond with the same scheme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DigestScheme {
+    #[default]
+    Scheme0,
+    Scheme1
+}
+
+impl DigestScheme {
+    /// All schemes in the order which they are tried.
+    pub const ALL: [Self; 2] = [Self::Scheme0, Self::Scheme1];
+}
+
+/// Flash Player uses `Scheme1` when it requires the encryption, otherwise uses `Scheme0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::handshake::{
+///     DigestScheme,
+///     EncryptionAlgorithm
+/// };
+///
+/// assert_eq!(DigestScheme::Scheme0, EncryptionAlgorithm::NotEncrypted.into());
+/// assert_eq!(DigestScheme::Scheme1, EncryptionAlgorithm::DiffieHellman.into())
+/// ```
+impl From<EncryptionAlgorithm> for DigestScheme {
+    fn from(encryption_algorithm: EncryptionAlgorithm) -> Self {
+        match encryption_algorithm {
+            EncryptionAlgorithm::NotEncrypted => Self::Scheme0,
+            _ => Self::Scheme1
+        }
+    }
+}
@@ -10,7 +10,7 @@
 /// |`Blowfish`|`9`|
 /// |`Other`|other numbers|
 ///
-/// Because of the design policy, the variant to be used actually will only be `NotEncrypted`.
+/// The variants to be used actually are `NotEncrypted` and `DiffieHellman` (RTMPE).
 /// Other variants are prepared to keep their meaning of known numbers.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
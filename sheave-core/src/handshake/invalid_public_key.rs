@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the partner's Diffie-Hellman public key is unusable to compute a shared secret.
+#[derive(Debug)]
+pub struct InvalidPublicKey(Vec<u8>);
+
+impl Display for InvalidPublicKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Invalid Diffie-Hellman public key: {:x?}", self.0)
+    }
+}
+
+impl Error for InvalidPublicKey {}
+
+/// A utility function of constructing an `InvalidPublicKey` error.
+pub fn invalid_public_key(public_key: Vec<u8>) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        InvalidPublicKey(public_key)
+    )
+}
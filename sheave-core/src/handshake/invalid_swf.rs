@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some SWF file is unusable to compute its hash for the SWF verification.
+#[derive(Debug)]
+pub struct InvalidSwf(String);
+
+impl Display for InvalidSwf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Invalid SWF file: {}", self.0)
+    }
+}
+
+impl Error for InvalidSwf {}
+
+/// A utility function of constructing an `InvalidSwf` error.
+pub fn invalid_swf(message: String) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        InvalidSwf(message)
+    )
+}
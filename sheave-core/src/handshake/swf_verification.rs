@@ -0,0 +1,180 @@
+use std::io::{
+    Read,
+    Result as IOResult
+};
+use flate2::read::ZlibDecoder;
+use hmac::Mac;
+use crate::messages::SwfVerifyResponse;
+use super::{
+    Handshake,
+    HmacSha256,
+    invalid_swf
+};
+
+/// The length of SWF headers, that is, the signature, the version and the file length.
+const HEADER_LEN: usize = 8;
+
+/// The SWF file which a client proves to have been loaded from.
+///
+/// Some servers require clients to answer the [`SwfVerifyRequest`] with a [`SwfVerifyResponse`].
+/// Its hash is computed from the size and the HMAC-SHA256 of the decompressed SWF file, and the last 32 bytes of S1.
+/// Therefore the response is different in every connection even if the SWF file is same.
+///
+/// |Field|Value|
+/// | :- | :- |
+/// |SWF Size|The length of the decompressed SWF file.|
+/// |SWF Hash|The HMAC-SHA256 of the decompressed SWF file, with [`Handshake::CLIENT_KEY`].|
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use sheave_core::handshake::{
+///     Handshake,
+///     SwfVerification,
+///     Version
+/// };
+///
+/// let swf_verification = SwfVerification::new(1, [0; 32]);
+/// let server_request = Handshake::new(Duration::default(), Version::LATEST_SERVER);
+/// let swf_verify_response = swf_verification.respond(&server_request);
+/// assert_eq!(1, swf_verify_response.get_swf_size());
+/// assert_ne!(&[0; 32], swf_verify_response.get_swf_hash())
+/// ```
+///
+/// [`SwfVerifyRequest`]: crate::messages::SwfVerifyRequest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwfVerification {
+    swf_size: u32,
+    swf_hash: [u8; 32]
+}
+
+impl SwfVerification {
+    /// Constructs a SWF verification from the size and the hash which have been computed in advance.
+    pub fn new(swf_size: u32, swf_hash: [u8; 32]) -> Self {
+        Self { swf_size, swf_hash }
+    }
+
+    /// Computes a SWF verification from the content of a SWF file.
+    ///
+    /// Both uncompressed (`FWS`) and zlib compressed (`CWS`) files are available.
+    ///
+    /// # Errors
+    ///
+    /// * [`InvalidSwf`]
+    ///
+    /// When the content isn't a SWF file, is LZMA compressed (`ZWS`), or differs from the length in its header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::handshake::SwfVerification;
+    ///
+    /// let mut swf: Vec<u8> = b"FWS\x0a".to_vec();
+    /// swf.extend_from_slice(&9u32.to_le_bytes());
+    /// swf.push(0);
+    /// assert_eq!(9, SwfVerification::from_swf(&swf).unwrap().get_swf_size());
+    ///
+    /// assert!(SwfVerification::from_swf(b"GIF89a").is_err())
+    /// ```
+    ///
+    /// [`InvalidSwf`]: super::InvalidSwf
+    pub fn from_swf(swf: &[u8]) -> IOResult<Self> {
+        if swf.len() < HEADER_LEN {
+            return Err(invalid_swf(format!("The header is truncated: {} bytes", swf.len())))
+        }
+
+        let mut file_len_bytes: [u8; 4] = [0; 4];
+        file_len_bytes.copy_from_slice(&swf[4..HEADER_LEN]);
+        let file_len = u32::from_le_bytes(file_len_bytes);
+
+        let mut hmac = HmacSha256::new_from_slice(Handshake::CLIENT_KEY).unwrap();
+        /* NOTE: The hash is computed as if the file is uncompressed, that is, the signature is always `FWS`. */
+        hmac.update(b"FWS");
+        hmac.update(&swf[3..HEADER_LEN]);
+        let swf_size = match &swf[..3] {
+            b"FWS" => {
+                hmac.update(&swf[HEADER_LEN..]);
+                swf.len()
+            },
+            b"CWS" => {
+                let mut body: Vec<u8> = Vec::new();
+                ZlibDecoder::new(&swf[HEADER_LEN..]).take(u64::from(file_len)).read_to_end(&mut body).map_err(|e| invalid_swf(e.to_string()))?;
+                hmac.update(&body);
+                HEADER_LEN + body.len()
+            },
+            b"ZWS" => return Err(invalid_swf("LZMA compressed SWF files aren't supported.".into())),
+            signature => return Err(invalid_swf(format!("Unknown signature: {signature:x?}")))
+        };
+
+        if swf_size != file_len as usize {
+            return Err(invalid_swf(format!("The length is inconsistent with the header. expected: {file_len}, actual: {swf_size}")))
+        }
+
+        let mut swf_hash: [u8; 32] = [0; 32];
+        swf_hash.copy_from_slice(&hmac.finalize().into_bytes());
+        Ok(Self::new(file_len, swf_hash))
+    }
+
+    /// Gets the length of the decompressed SWF file.
+    pub fn get_swf_size(&self) -> u32 {
+        self.swf_size
+    }
+
+    /// Gets the HMAC-SHA256 of the decompressed SWF file.
+    pub fn get_swf_hash(&self) -> &[u8; 32] {
+        &self.swf_hash
+    }
+
+    /// Computes the SwfVerifyResponse for a connection.
+    ///
+    /// The `server_request` must be S1 as it has been sent by the server, that is, before the client imprints its signature into C2.
+    pub fn respond(&self, server_request: &Handshake) -> SwfVerifyResponse {
+        let mut hmac = HmacSha256::new_from_slice(server_request.get_signature()).unwrap();
+        hmac.update(&self.swf_hash);
+        let mut swf_hash: [u8; 32] = [0; 32];
+        swf_hash.copy_from_slice(&hmac.finalize().into_bytes());
+        SwfVerifyResponse::new(self.swf_size, swf_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use flate2::{
+        Compression,
+        write::ZlibEncoder
+    };
+    use super::*;
+
+    fn uncompressed_swf() -> Vec<u8> {
+        let body: Vec<u8> = (0..=u8::MAX).cycle().take(1000).collect();
+        let mut swf: Vec<u8> = b"FWS\x0a".to_vec();
+        swf.extend_from_slice(&((HEADER_LEN + body.len()) as u32).to_le_bytes());
+        swf.extend_from_slice(&body);
+        swf
+    }
+
+    #[test]
+    fn compressed_swf_has_same_hash() {
+        let uncompressed = uncompressed_swf();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&uncompressed[HEADER_LEN..]).unwrap();
+        let mut compressed: Vec<u8> = b"CWS\x0a".to_vec();
+        compressed.extend_from_slice(&uncompressed[4..HEADER_LEN]);
+        compressed.extend_from_slice(&encoder.finish().unwrap());
+
+        let expected = SwfVerification::from_swf(&uncompressed).unwrap();
+        let actual = SwfVerification::from_swf(&compressed).unwrap();
+        assert_eq!(expected, actual);
+        assert_eq!(uncompressed.len() as u32, actual.get_swf_size())
+    }
+
+    #[test]
+    fn err_inconsistent_length() {
+        let mut swf = uncompressed_swf();
+        swf.pop();
+        assert!(SwfVerification::from_swf(&swf).is_err());
+        assert!(SwfVerification::from_swf(b"ZWS\x0d\x00\x00\x00\x00").is_err())
+    }
+}
@@ -10,6 +10,7 @@ mod encoder;
 mod byte_buffer;
 pub mod handlers;
 pub mod flv;
+pub mod session;
 
 pub use self::{
     decoder::Decoder,
@@ -25,7 +25,13 @@
 //! Currently, following events are used:
 //!
 //! * [`Stream Begin`]
+//! * [`Stream EOF`]
+//! * [`Stream Dry`]
 //! * [`Set Buffer Length`]
+//! * [`Stream Is Recorded`]
+//! * [`Ping Request`] / [`Ping Response`]
+//! * [`SWF Verify Request`] / [`SWF Verify Response`]
+//! * [`Buffer Empty`] / [`Buffer Ready`]
 //!
 //! ## Command
 //!
@@ -74,6 +80,9 @@
 //! |Command Name (probably)|[`String`]|`"@setDataFrame"`|
 //! |Setting Data|Various|In this case, see [`ScriptDataTag`].|
 //!
+//! Some encoders also bundle several audio/video data into one message.
+//! Such message is called [`Aggregate`], and its body consists of FLV tags as they are.
+//!
 //! [the Action Message Format 0]: amf::v0
 //! [`sheave_core::messages::headers`]: headers
 //! [`Number`]: amf::v0::Number
@@ -97,6 +106,15 @@
 //! [`set_playlist`]: SetPlaylist
 //! [`play`]: Play
 //! [`Set Buffer Length`]: SetBufferLength
+//! [`Stream EOF`]: StreamEof
+//! [`Stream Dry`]: StreamDry
+//! [`Stream Is Recorded`]: StreamIsRecorded
+//! [`Ping Request`]: PingRequest
+//! [`Ping Response`]: PingResponse
+//! [`SWF Verify Request`]: SwfVerifyRequest
+//! [`SWF Verify Response`]: SwfVerifyResponse
+//! [`Buffer Empty`]: BufferEmpty
+//! [`Buffer Ready`]: BufferReady
 
 pub mod headers;
 pub mod amf;
@@ -129,7 +147,20 @@ mod set_playlist;
 mod playlist_ready;
 mod play;
 mod set_buffer_length;
+mod stream_eof;
+mod stream_dry;
+mod stream_is_recorded;
+mod ping_request;
+mod ping_response;
+mod swf_verify_request;
+mod swf_verify_response;
+mod buffer_empty;
+mod buffer_ready;
 mod command_error;
+mod aggregate;
+mod unknown_message;
+mod unknown_event;
+mod too_large_message;
 
 use std::cmp::Ordering;
 use self::headers::MessageType;
@@ -163,7 +194,20 @@ pub use self::{
     playlist_ready::*,
     play::*,
     set_buffer_length::*,
-    command_error::*
+    stream_eof::*,
+    stream_dry::*,
+    stream_is_recorded::*,
+    ping_request::*,
+    ping_response::*,
+    swf_verify_request::*,
+    swf_verify_response::*,
+    buffer_empty::*,
+    buffer_ready::*,
+    command_error::*,
+    aggregate::*,
+    unknown_message::*,
+    unknown_event::*,
+    too_large_message::*
 };
 
 /// The IDs which are assigned every roles of chunks.
@@ -220,19 +264,46 @@ pub trait Command {}
 ///
 /// Variants correspond to respectively following events:
 ///
-/// |Patttern|Event Type|
-/// | :- | :- |
-/// |`StreamBegin`|[`StreamBegin`]|
-/// |`SetBufferLength`|[`SetBufferLength`]|
-/// |`Other`|other event type|
+/// |Patttern|Number|Event Type|
+/// | :- | :- | :- |
+/// |`StreamBegin`|`0`|[`StreamBegin`]|
+/// |`StreamEof`|`1`|[`StreamEof`]|
+/// |`StreamDry`|`2`|[`StreamDry`]|
+/// |`SetBufferLength`|`3`|[`SetBufferLength`]|
+/// |`StreamIsRecorded`|`4`|[`StreamIsRecorded`]|
+/// |`PingRequest`|`6`|[`PingRequest`]|
+/// |`PingResponse`|`7`|[`PingResponse`]|
+/// |`SwfVerifyRequest`|`26`|[`SwfVerifyRequest`]|
+/// |`SwfVerifyResponse`|`27`|[`SwfVerifyResponse`]|
+/// |`BufferEmpty`|`31`|[`BufferEmpty`]|
+/// |`BufferReady`|`32`|[`BufferReady`]|
+/// |`Other`|other numbers|other event type|
 ///
 /// [`StreamBegin`]: StreamBegin
+/// [`StreamEof`]: StreamEof
+/// [`StreamDry`]: StreamDry
 /// [`SetBufferLength`]: SetBufferLength
+/// [`StreamIsRecorded`]: StreamIsRecorded
+/// [`PingRequest`]: PingRequest
+/// [`PingResponse`]: PingResponse
+/// [`SwfVerifyRequest`]: SwfVerifyRequest
+/// [`SwfVerifyResponse`]: SwfVerifyResponse
+/// [`BufferEmpty`]: BufferEmpty
+/// [`BufferReady`]: BufferReady
 #[repr(u16)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventType {
     StreamBegin,
-    SetBufferLength = 3,
+    StreamEof,
+    StreamDry,
+    SetBufferLength,
+    StreamIsRecorded,
+    PingRequest = 6,
+    PingResponse,
+    SwfVerifyRequest = 26,
+    SwfVerifyResponse,
+    BufferEmpty = 31,
+    BufferReady,
     Other = 0xffff
 }
 
@@ -242,7 +313,16 @@ impl From<u16> for EventType {
 
         match event_type {
             0 => StreamBegin,
+            1 => StreamEof,
+            2 => StreamDry,
             3 => SetBufferLength,
+            4 => StreamIsRecorded,
+            6 => PingRequest,
+            7 => PingResponse,
+            26 => SwfVerifyRequest,
+            27 => SwfVerifyResponse,
+            31 => BufferEmpty,
+            32 => BufferReady,
             _ => Other
         }
     }
@@ -0,0 +1,275 @@
+use std::{
+    io::Result as IOResult,
+    time::Duration
+};
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    flv::{
+        tags::{
+            DEFAULT_MESSAGE_ID,
+            FlvTag,
+            METADATA_LEN,
+            TagType
+        },
+        unknown_tag
+    }
+};
+use super::{
+    Channel,
+    ChunkData,
+    headers::MessageType
+};
+
+/// The message to bundle several audio/video/data messages into one.
+///
+/// Its body consists of FLV tags as they are, that is, every sub-message has following format:
+///
+/// |Field|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Tag Type|1|Same as the message type of a sub-message. (8, 9 or 18)|
+/// |Data Size|3|A length of actual sub-message data.|
+/// |Timestamp|3|A timestamp of this sub-message.|
+/// |Timestamp (extended)|1|An upper 8 bits of the timestamp.|
+/// |Message Stream ID|3|This is ignored.|
+/// |Data|Various|Actual sub-message data.|
+/// |Back Pointer|4|A size of this sub-message. (that is, Metadata + Data Size)|
+///
+/// Note timestamps of sub-messages are relative to the first one.
+/// They should be rebased onto the timestamp of the Aggregate message header via [`rebase`].
+///
+/// [`rebase`]: Aggregate::rebase
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aggregate(Vec<FlvTag>);
+
+impl Aggregate {
+    /// Constructs an Aggregate message.
+    pub fn new(flv_tags: Vec<FlvTag>) -> Self {
+        Self(flv_tags)
+    }
+
+    /// Gets sub-messages as FLV tags.
+    pub fn get_flv_tags(&self) -> &[FlvTag] {
+        &self.0
+    }
+
+    /// Gets a timestamp of the first sub-message.
+    ///
+    /// Note this returns `None` if this message is empty.
+    pub fn get_timestamp(&self) -> Option<Duration> {
+        self.0.first().map(|flv_tag| flv_tag.get_timestamp())
+    }
+
+    /// Shifts timestamps of sub-messages so that the first one starts at specified timestamp.
+    ///
+    /// Senders can write sub-messages' timestamps in their own time bases.
+    /// Therefore receivers must correct them with the timestamp in the message header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use bytes::Bytes;
+    /// use sheave_core::{
+    ///     flv::tags::{
+    ///         FlvTag,
+    ///         TagType
+    ///     },
+    ///     messages::Aggregate
+    /// };
+    ///
+    /// let mut aggregate = Aggregate::new(
+    ///     vec![
+    ///         FlvTag::new(TagType::Audio, Duration::from_millis(1000), Bytes::new()),
+    ///         FlvTag::new(TagType::Video, Duration::from_millis(1033), Bytes::new())
+    ///     ]
+    /// );
+    /// aggregate.rebase(Duration::from_millis(5000));
+    /// assert_eq!(Duration::from_millis(5000), aggregate.get_flv_tags()[0].get_timestamp());
+    /// assert_eq!(Duration::from_millis(5033), aggregate.get_flv_tags()[1].get_timestamp())
+    /// ```
+    pub fn rebase(&mut self, timestamp: Duration) {
+        let Some(first) = self.get_timestamp() else {
+            return
+        };
+
+        for flv_tag in self.0.iter_mut() {
+            let rebased = (timestamp + flv_tag.get_timestamp()).saturating_sub(first);
+            *flv_tag = FlvTag::new(flv_tag.get_tag_type(), rebased, flv_tag.get_data().clone());
+        }
+    }
+}
+
+impl From<Aggregate> for Vec<FlvTag> {
+    fn from(aggregate: Aggregate) -> Self {
+        aggregate.0
+    }
+}
+
+impl ChunkData for Aggregate {
+    const CHANNEL: Channel = Channel::Video;
+    const MESSAGE_TYPE: MessageType = MessageType::Aggregate;
+}
+
+impl Decoder<Aggregate> for ByteBuffer {
+    /// Decodes bytes into an Aggregate message.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some sub-message is shorter than its data size.
+    ///
+    /// * [`UnknownTag`]
+    ///
+    /// When some sub-message is neither of audio, video nor data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::Aggregate
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(8);
+    /// buffer.put_u24_be(1);
+    /// buffer.put_u24_be(0);
+    /// buffer.put_u8(0);
+    /// buffer.put_u24_be(0);
+    /// buffer.put_u8(0xaf);
+    /// buffer.put_u32_be(12);
+    /// assert!(Decoder::<Aggregate>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(8);
+    /// buffer.put_u24_be(1);
+    /// assert!(Decoder::<Aggregate>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(1);
+    /// buffer.put_u24_be(0);
+    /// buffer.put_u24_be(0);
+    /// buffer.put_u8(0);
+    /// buffer.put_u24_be(0);
+    /// buffer.put_u32_be(11);
+    /// assert!(Decoder::<Aggregate>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownTag`]: crate::flv::UnknownTag
+    fn decode(&mut self) -> IOResult<Aggregate> {
+        let mut flv_tags: Vec<FlvTag> = Vec::new();
+
+        while self.remained() > 0 {
+            let tag_type_byte = self.get_u8()? & 0x1f;
+            let tag_type: TagType = match tag_type_byte {
+                8 | 9 | 18 => tag_type_byte.into(),
+                other => return Err(unknown_tag(other))
+            };
+            let data_size = self.get_u24_be()?;
+            let timestamp = self.get_u24_be()? | ((self.get_u8()? as u32) << 24);
+            // NOTE: The message stream ID of sub-messages is ignored.
+            self.get_u24_be()?;
+            let data = self.get_shared_bytes(data_size as usize)?;
+            // NOTE: The back pointer is unnecessary in reading.
+            self.get_u32_be()?;
+
+            flv_tags.push(FlvTag::new(tag_type, Duration::from_millis(timestamp as u64), data));
+        }
+
+        Ok(Aggregate(flv_tags))
+    }
+}
+
+impl Encoder<Aggregate> for ByteBuffer {
+    /// Encodes an Aggregate message into bytes.
+    ///
+    /// # Panics
+    ///
+    /// When the data size of some sub-message exceeds 24 bits.
+    fn encode(&mut self, aggregate: &Aggregate) {
+        for flv_tag in aggregate.get_flv_tags() {
+            let timestamp = flv_tag.get_timestamp().as_millis() as u32;
+            let data_size = flv_tag.get_data().len();
+
+            self.put_u8(flv_tag.get_tag_type().into());
+            self.put_u24_be(data_size as u32);
+            self.put_u24_be(timestamp & 0x00ffffff);
+            self.put_u8((timestamp >> 24) as u8);
+            self.put_u24_be(DEFAULT_MESSAGE_ID);
+            self.put_bytes(flv_tag.get_data());
+            self.put_u32_be((METADATA_LEN + data_size) as u32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use super::*;
+
+    #[test]
+    fn decode_aggregate() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(8);
+        buffer.put_u24_be(2);
+        buffer.put_u24_be(0x000010);
+        buffer.put_u8(0x01);
+        buffer.put_u24_be(0);
+        buffer.put_bytes(&[0xaf, 0x01]);
+        buffer.put_u32_be(13);
+        buffer.put_u8(9);
+        buffer.put_u24_be(1);
+        buffer.put_u24_be(0x000030);
+        buffer.put_u8(0x01);
+        buffer.put_u24_be(0);
+        buffer.put_u8(0x17);
+        buffer.put_u32_be(12);
+        let result: IOResult<Aggregate> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = Aggregate::new(
+            vec![
+                FlvTag::new(TagType::Audio, Duration::from_millis(0x01000010), vec![0xaf, 0x01].into()),
+                FlvTag::new(TagType::Video, Duration::from_millis(0x01000030), vec![0x17].into())
+            ]
+        );
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_aggregate() {
+        let mut buffer = ByteBuffer::default();
+        let expected = Aggregate::new(
+            vec![
+                FlvTag::new(TagType::Audio, Duration::from_millis(0x01000010), vec![0xaf, 0x01].into()),
+                FlvTag::new(TagType::ScriptData, Duration::from_millis(0x01000020), Bytes::new())
+            ]
+        );
+        buffer.encode(&expected);
+        let bytes: Vec<u8> = buffer.into();
+        assert_eq!(13 + 4 + 11 + 4, bytes.len());
+        assert_eq!([8, 0x00, 0x00, 0x02, 0x00, 0x00, 0x10, 0x01], bytes[..8]);
+        assert_eq!(13u32.to_be_bytes(), bytes[13..17]);
+        let mut buffer: ByteBuffer = bytes.into();
+        let actual: Aggregate = buffer.decode().unwrap();
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn rebase_aggregate() {
+        let mut aggregate = Aggregate::new(
+            vec![
+                FlvTag::new(TagType::Audio, Duration::from_millis(90000), Bytes::new()),
+                FlvTag::new(TagType::Video, Duration::from_millis(90040), Bytes::new())
+            ]
+        );
+        aggregate.rebase(Duration::from_millis(40));
+        let timestamps: Vec<Duration> = aggregate.get_flv_tags().iter().map(|flv_tag| flv_tag.get_timestamp()).collect();
+        assert_eq!(vec![Duration::from_millis(40), Duration::from_millis(80)], timestamps)
+    }
+}
@@ -2,17 +2,32 @@
 //!
 //! These are data types which are defined as the Action Message Format.
 //! There are two formats which are version 0 and version 3 in AMF.
-//! Currently the RTMP uses only AMF version 0.
+//! The RTMP uses AMF version 0 mainly, and AMF version 3 is embedded into it via the AVM+ marker.
 
 pub mod v0;
+pub mod v3;
 mod inconsistent_marker;
 mod invalid_string;
+mod unknown_marker;
+mod invalid_reference;
+mod unsupported_externalizable;
+mod too_deep_value;
+mod too_large_value;
+#[cfg(feature = "serde")]
+mod serde_error;
 
 use std::io::Result as IOResult;
 pub use self::{
     inconsistent_marker::*,
-    invalid_string::*
+    invalid_string::*,
+    unknown_marker::*,
+    invalid_reference::*,
+    unsupported_externalizable::*,
+    too_deep_value::*,
+    too_large_value::*
 };
+#[cfg(feature = "serde")]
+pub use self::serde_error::*;
 
 #[doc(hidden)]
 pub(self) fn ensure_marker(expected: u8, actual: u8) -> IOResult<()> {
@@ -0,0 +1,45 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some reference points out of its reference table.
+#[derive(Debug)]
+pub struct InvalidReference {
+    index: usize,
+    len: usize
+}
+
+impl InvalidReference {
+    /// Constructs this error.
+    pub fn new(index: usize, len: usize) -> Self {
+        Self { index, len }
+    }
+}
+
+impl Display for InvalidReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Reference is out of its table. index: {}, table length: {}", self.index, self.len)
+    }
+}
+
+impl Error for InvalidReference {}
+
+/// A utility function of constructing an `InvalidReference` error.
+pub fn invalid_reference(index: usize, len: usize) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        InvalidReference {
+            index,
+            len
+        }
+    )
+}
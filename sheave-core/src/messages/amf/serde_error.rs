@@ -0,0 +1,60 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+use serde::{
+    de,
+    ser
+};
+
+/// An error that some Rust value can't be mapped from/to AMF values.
+///
+/// This is raised via the [`Serializer`] and the [`Deserializer`].
+///
+/// [`Serializer`]: super::v0::Serializer
+/// [`Deserializer`]: super::v0::Deserializer
+#[derive(Debug)]
+pub struct SerdeError(String);
+
+impl SerdeError {
+    /// Constructs this error.
+    pub fn new(message: String) -> Self {
+        Self(message)
+    }
+}
+
+impl Display for SerdeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "AMF value couldn't be mapped: {}", self.0)
+    }
+}
+
+impl Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    fn custom<T: Display>(message: T) -> Self {
+        Self(message.to_string())
+    }
+}
+
+impl de::Error for SerdeError {
+    fn custom<T: Display>(message: T) -> Self {
+        Self(message.to_string())
+    }
+}
+
+/// A utility function of constructing a `SerdeError` error.
+pub fn serde_error(error: SerdeError) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        error
+    )
+}
@@ -0,0 +1,34 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some AMF value nests deeper than the limit.
+#[derive(Debug)]
+pub struct TooDeepValue {
+    max_depth: usize
+}
+
+impl Display for TooDeepValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Value nests too deep. max depth: {}", self.max_depth)
+    }
+}
+
+impl Error for TooDeepValue {}
+
+/// A utility function of constructing a `TooDeepValue` error.
+pub fn too_deep_value(max_depth: usize) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        TooDeepValue { max_depth }
+    )
+}
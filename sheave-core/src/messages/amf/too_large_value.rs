@@ -0,0 +1,34 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some AMF value expands beyond the decoding budget, including values which are copied by references.
+#[derive(Debug)]
+pub struct TooLargeValue {
+    budget: usize
+}
+
+impl Display for TooLargeValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Value is too large to decode. budget: {}", self.budget)
+    }
+}
+
+impl Error for TooLargeValue {}
+
+/// A utility function of constructing a `TooLargeValue` error.
+pub fn too_large_value(budget: usize) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        TooLargeValue { budget }
+    )
+}
@@ -0,0 +1,39 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some AMF type marker is undefined in its version.
+#[derive(Debug)]
+pub struct UnknownMarker(u8);
+
+impl UnknownMarker {
+    /// Constructs this error.
+    pub fn new(marker: u8) -> Self {
+        Self(marker)
+    }
+}
+
+impl Display for UnknownMarker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown marker: {}", self.0)
+    }
+}
+
+impl Error for UnknownMarker {}
+
+/// A utility function of constructing an `UnknownMarker` error.
+pub fn unknown_marker(marker: u8) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownMarker(marker)
+    )
+}
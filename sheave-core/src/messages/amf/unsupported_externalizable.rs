@@ -0,0 +1,42 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some externalizable class can't be read.
+///
+/// Externalizable objects are serialized by their own classes.
+/// Therefore they can't be read unless their formats are known.
+#[derive(Debug)]
+pub struct UnsupportedExternalizable(String);
+
+impl UnsupportedExternalizable {
+    /// Constructs this error.
+    pub fn new(class_name: String) -> Self {
+        Self(class_name)
+    }
+}
+
+impl Display for UnsupportedExternalizable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unsupported externalizable class: {}", self.0)
+    }
+}
+
+impl Error for UnsupportedExternalizable {}
+
+/// A utility function of constructing an `UnsupportedExternalizable` error.
+pub fn unsupported_externalizable(class_name: String) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnsupportedExternalizable(class_name)
+    )
+}
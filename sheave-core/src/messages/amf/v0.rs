@@ -1,7 +1,7 @@
 //! # The AMF Data Types (version 0).
 //!
 //! These are data types which are defined in the Action Message Format version 0 specification.
-//! Currently the RTMP uses following types:
+//! Following types are supported:
 //!
 //! |Marker|AMF Data Type|Description|
 //! | -: | :- | :- |
@@ -10,8 +10,16 @@
 //! |`2`|[`AmfString`]|The string that is limited its length the range of 2 bytes.|
 //! |`3`|[`Object`]|The key/value-paired object that its value type is flexible.|
 //! |`5`|[`Null`]|Only the marker. Any value doesn't contain.|
+//! |`6`|[`Undefined`]|Only the marker. Any value doesn't contain.|
+//! |`7`|[`Reference`]|The index to refer a complex value which has been sent previously.|
 //! |`8`|[`EcmaArray`]|Same as the [`Object`] except this has its length.|
 //! |`9`|Object End|Indicates a stream of object terminaites there.|
+//! |`10`|[`StrictArray`]|The ordinal array that its value type is flexible.|
+//! |`11`|[`Date`]|Milliseconds from the UNIX epoch and a time zone.|
+//! |`12`|[`LongString`]|The string that is limited its length the range of 4 bytes.|
+//! |`15`|[`XmlDocument`]|Same as the [`LongString`] except its marker.|
+//! |`16`|[`TypedObject`]|Same as the [`Object`] except this has its class name.|
+//! |`17`|AVM+|Switches following value to [AMF3](super::v3).|
 //!
 //! These are checked whether matched its marker with an actual data, by the receiver-side.
 //! Therefore any stream which contains any unmatched marker will be considered it's invalid.
@@ -51,12 +59,29 @@
 //! * [`AmfString`]
 //! * [`Object`]
 //! * [`Null`]
+//! * [`Undefined`]
+//! * [`Reference`]
 //! * [`EcmaArray`]
+//! * [`StrictArray`]
+//! * [`Date`]
+//! * [`LongString`]
+//! * [`XmlDocument`]
+//! * [`TypedObject`]
+//! * [AMF3 values](super::v3::Value) (via the AVM+ marker)
 //!
 //! ## [`Null`]
 //!
 //! 1. [`Marker`] (1 byte)
 //!
+//! ## [`Undefined`]
+//!
+//! 1. [`Marker`] (1 byte)
+//!
+//! ## [`Reference`]
+//!
+//! 1. [`Marker`] (1 byte)
+//! 2. An index of the referent (2 bytes)
+//!
 //! ## [`EcmaArray`]
 //!
 //! 1. [`Marker`] (1 byte)
@@ -64,41 +89,95 @@
 //! 3. Arbitrary number key-value pairs (variable)
 //! 4. The Object End marker that is associated with an empty string.
 //!
+//! ## [`StrictArray`]
+//!
+//! 1. [`Marker`] (1 byte)
+//! 2. Count (4 bytes)
+//! 3. Values as many as the count (variable)
+//!
+//! ## [`Date`]
+//!
+//! 1. [`Marker`] (1 byte)
+//! 2. Milliseconds from the UNIX epoch as an IEEE 754 double precision value (8 bytes)
+//! 3. Time zone (2 bytes)
+//!
+//! ## [`LongString`]
+//!
+//! 1. [`Marker`] (1 byte)
+//! 2. Length (4 bytes)
+//! 3. UTF-8 characters (variable)
+//!
+//! ## [`XmlDocument`]
+//!
+//! 1. [`Marker`] (1 byte)
+//! 2. Length (4 bytes)
+//! 3. UTF-8 characters (variable)
+//!
+//! ## [`TypedObject`]
+//!
+//! 1. [`Marker`] (1 byte)
+//! 2. Class name without the marker (variable)
+//! 3. Same as the [`Object`] except its marker (variable)
+//!
+//! ## AVM+
+//!
+//! 1. [`Marker`] (1 byte)
+//! 2. An [AMF3 value](super::v3::Value) (variable)
+//!
+//! # Inspecting values
+//!
+//! Any value is represented as the [`AmfValue`].
+//! It can be inspected via pattern matching, or via fallible accessors such as [`as_f64`], [`as_str`] and [`get_path`].
+//!
+//! Also, if the `serde` feature is enabled, any Rust value which implements `Serialize`/`Deserialize` can be converted from/to AMF bytes via `to_bytes`/`from_bytes`.
+//!
+//! [`as_f64`]: AmfValue::as_f64
+//! [`as_str`]: AmfValue::as_str
+//! [`get_path`]: AmfValue::get_path
 //! [`Marker`]: Marker
 //! [`Number`]: Number
 //! [`Boolean`]: Boolean
 //! [`AmfString`]: AmfString
 //! [`Object`]: Object
 //! [`Null`]: Null
+//! [`Undefined`]: Undefined
+//! [`Reference`]: Reference
 //! [`EcmaArray`]: EcmaArray
+//! [`StrictArray`]: StrictArray
+//! [`Date`]: Date
+//! [`LongString`]: LongString
+//! [`XmlDocument`]: XmlDocument
+//! [`TypedObject`]: TypedObject
 
 mod number;
 mod boolean;
 mod string;
 mod object;
 mod null;
+mod undefined;
+mod reference;
 mod ecma_array;
+mod strict_array;
+mod date;
+mod long_string;
+mod xml_document;
+mod typed_object;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(feature = "serde")]
+mod de;
 
 use std::{
-    alloc::{
-        GlobalAlloc,
-        Layout,
-        System
-    },
     borrow::Cow,
-    collections::HashMap,
-    fmt::{
-        Debug,
-        Formatter,
-        Result as FormatResult
+    io::{
+        Error as IOError,
+        Result as IOResult
     },
-    io::Result as IOResult,
     ops::{
         Deref,
         DerefMut,
         Index
-    },
-    sync::Arc
+    }
 };
 use crate::{
     Decoder,
@@ -106,7 +185,10 @@ use crate::{
     ByteBuffer,
     messages::amf::{
         ensure_marker,
-        invalid_string
+        inconsistent_marker,
+        invalid_string,
+        unknown_marker,
+        v3
     }
 };
 pub use self::{
@@ -115,7 +197,27 @@ pub use self::{
     string::AmfString,
     object::Object,
     null::Null,
-    ecma_array::EcmaArray
+    undefined::Undefined,
+    reference::Reference,
+    ecma_array::EcmaArray,
+    strict_array::StrictArray,
+    date::Date,
+    long_string::LongString,
+    xml_document::XmlDocument,
+    typed_object::TypedObject
+};
+#[cfg(feature = "serde")]
+pub use self::{
+    ser::{
+        Serializer,
+        to_bytes,
+        to_value
+    },
+    de::{
+        Deserializer,
+        from_bytes,
+        from_value
+    }
 };
 
 /// Representation of markers of the AMF data types.
@@ -129,8 +231,16 @@ pub use self::{
 /// |`AmfString`|`2`|
 /// |`Object`|`3`|
 /// |`Null`|`5`|
+/// |`Undefined`|`6`|
+/// |`Reference`|`7`|
 /// |`EcmaArray`|`8`|
 /// |`ObjectEnd`|`9`|
+/// |`StrictArray`|`10`|
+/// |`Date`|`11`|
+/// |`LongString`|`12`|
+/// |`XmlDocument`|`15`|
+/// |`TypedObject`|`16`|
+/// |`AvmPlus`|`17`|
 /// |`Other`|other numbers|
 ///
 #[repr(u8)]
@@ -141,8 +251,16 @@ pub enum Marker {
     AmfString,
     Object,
     Null = 0x05,
-    EcmaArray = 0x08,
-    ObjectEnd = 0x09,
+    Undefined,
+    Reference,
+    EcmaArray,
+    ObjectEnd,
+    StrictArray,
+    Date,
+    LongString,
+    XmlDocument = 0x0f,
+    TypedObject,
+    AvmPlus = 0x11,
     Other = 0xff
 }
 
@@ -156,8 +274,16 @@ impl From<u8> for Marker {
             2 => AmfString,
             3 => Object,
             5 => Null,
+            6 => Undefined,
+            7 => Reference,
             8 => EcmaArray,
             9 => ObjectEnd,
+            10 => StrictArray,
+            11 => Date,
+            12 => LongString,
+            15 => XmlDocument,
+            16 => TypedObject,
+            17 => AvmPlus,
             _ => Other
         }
     }
@@ -170,7 +296,7 @@ impl From<Marker> for u8 {
 }
 
 #[doc(hidden)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UnmarkedString(String);
 
 #[doc(hidden)]
@@ -273,290 +399,479 @@ impl Encoder<UnmarkedString> for ByteBuffer {
     }
 }
 
-#[doc(hidden)]
-pub struct Value {
-    layout: Layout,
-    ptr: *mut u8,
-    marker: Marker
+/// Any value of the AMF data types (version 0).
+///
+/// Values are inspected via pattern matching or via fallible accessors.
+/// That is, any accessor returns `None` instead of the panic when a value has another type.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::{
+///     messages::amf::v0::{
+///         AmfString,
+///         AmfValue,
+///         Number
+///     },
+///     object
+/// };
+///
+/// let value = AmfValue::from(
+///     object!(
+///         "app" => AmfString::from("ondemand"),
+///         "objectEncoding" => Number::from(0)
+///     )
+/// );
+/// assert_eq!(Some("ondemand"), value.get_path("app").and_then(AmfValue::as_str));
+/// assert_eq!(Some(0f64), value.get_path("objectEncoding").and_then(AmfValue::as_f64));
+/// assert!(value.get_path("app").and_then(AmfValue::as_f64).is_none());
+///
+/// if let Some(AmfValue::String(app)) = value.get_path("app") {
+///     assert_eq!("ondemand", app)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum AmfValue {
+    Number(Number),
+    Boolean(Boolean),
+    String(AmfString),
+    Object(Object),
+    Null,
+    Undefined,
+    Reference(Reference),
+    EcmaArray(EcmaArray),
+    StrictArray(StrictArray),
+    Date(Date),
+    LongString(LongString),
+    XmlDocument(XmlDocument),
+    TypedObject(TypedObject),
+    AvmPlus(v3::Value)
 }
 
-#[doc(hidden)]
-impl Value {
-    fn as_number(&self) -> &Number {
-        unsafe {
-            assert_eq!(Marker::Number, self.marker);
-            &*self.ptr.cast::<Number>()
+impl AmfValue {
+    /// Gets the marker which corresponds to this value.
+    pub fn get_marker(&self) -> Marker {
+        match self {
+            AmfValue::Number(_) => Marker::Number,
+            AmfValue::Boolean(_) => Marker::Boolean,
+            AmfValue::String(_) => Marker::AmfString,
+            AmfValue::Object(_) => Marker::Object,
+            AmfValue::Null => Marker::Null,
+            AmfValue::Undefined => Marker::Undefined,
+            AmfValue::Reference(_) => Marker::Reference,
+            AmfValue::EcmaArray(_) => Marker::EcmaArray,
+            AmfValue::StrictArray(_) => Marker::StrictArray,
+            AmfValue::Date(_) => Marker::Date,
+            AmfValue::LongString(_) => Marker::LongString,
+            AmfValue::XmlDocument(_) => Marker::XmlDocument,
+            AmfValue::TypedObject(_) => Marker::TypedObject,
+            AmfValue::AvmPlus(_) => Marker::AvmPlus
         }
     }
 
-    fn as_boolean(&self) -> &Boolean {
-        unsafe {
-            assert_eq!(Marker::Boolean, self.marker);
-            &*self.ptr.cast::<Boolean>()
-        }
+    /// Checks whether this is the Null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, AmfValue::Null)
+    }
+
+    /// Checks whether this is the Undefined.
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, AmfValue::Undefined)
     }
 
-    fn as_string(&self) -> &AmfString {
-        unsafe {
-            assert_eq!(Marker::AmfString, self.marker);
-            &*self.ptr.cast::<AmfString>()
+    /// Gets this as a primitive number.
+    ///
+    /// This returns `None` unless this is the Number, or the Integer/Double of AMF3.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AmfValue::Number(number) => Some(number.as_f64()),
+            AmfValue::AvmPlus(v3::Value::Integer(integer)) => Some(*integer as f64),
+            AmfValue::AvmPlus(v3::Value::Double(double)) => Some(*double),
+            _ => None
         }
     }
 
-    fn as_null(&self) -> &Null {
-        unsafe {
-            assert_eq!(Marker::Null, self.marker);
-            &*self.ptr.cast::<Null>()
+    /// Gets this as a primitive boolean.
+    ///
+    /// This returns `None` unless this is the Boolean, or the Boolean of AMF3.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AmfValue::Boolean(boolean) => Some(boolean.as_boolean()),
+            AmfValue::AvmPlus(v3::Value::Boolean(boolean)) => Some(*boolean),
+            _ => None
         }
     }
 
-    fn as_object(&self) -> &Object {
-        unsafe {
-            assert_eq!(Marker::Object, self.marker);
-            &*self.ptr.cast::<Object>()
+    /// Gets this as a string slice.
+    ///
+    /// This returns `None` unless this is the String, the Long String, or the String of AMF3.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AmfValue::String(string) => Some(string.as_str()),
+            AmfValue::LongString(string) => Some(string.as_str()),
+            AmfValue::AvmPlus(v3::Value::String(string)) => Some(string.as_str()),
+            _ => None
         }
     }
 
-    fn as_ecma_array(&self) -> &EcmaArray {
-        unsafe {
-            assert_eq!(Marker::EcmaArray, self.marker);
-            &*self.ptr.cast::<EcmaArray>()
+    /// Gets properties if this is the Object, the ECMA Array or the Typed Object.
+    pub fn get_properties(&self) -> Option<&Properties> {
+        match self {
+            AmfValue::Object(object) => Some(object.get_properties()),
+            AmfValue::EcmaArray(ecma_array) => Some(ecma_array.get_properties()),
+            AmfValue::TypedObject(typed_object) => Some(typed_object.get_properties()),
+            _ => None
         }
     }
-}
 
-#[doc(hidden)]
-impl From<Number> for Value {
-    fn from(value: Number) -> Self {
-        unsafe {
-            let layout = Layout::new::<Number>();
-            let ptr = System.alloc(layout);
-            ptr.cast::<Number>().write(value);
-            Self {
-                layout,
-                ptr,
-                marker: Marker::Number
-            }
+    /// Gets a child value.
+    ///
+    /// The key is treated as a property name for the Object, the ECMA Array and the Typed Object, or as an index for the Strict Array.
+    /// This returns `None` if this has no such child.
+    pub fn get(&self, key: &str) -> Option<&AmfValue> {
+        match self {
+            AmfValue::StrictArray(strict_array) => key.parse::<usize>().ok().and_then(|index| strict_array.get(index)),
+            _ => self.get_properties().and_then(|properties| properties.get(key))
         }
     }
-}
 
-#[doc(hidden)]
-impl<'a> From<&'a Value> for &'a Number {
-    fn from(value: &'a Value) -> Self {
-        value.as_number()
+    /// Gets a descendant value by a dot-separated path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ecma_array,
+    ///     messages::amf::v0::{
+    ///         AmfValue,
+    ///         Number
+    ///     },
+    ///     object,
+    ///     strict_array
+    /// };
+    ///
+    /// let on_metadata = AmfValue::from(
+    ///     ecma_array!(
+    ///         "keyframes" => object!(
+    ///             "times" => strict_array!(Number::from(0), Number::from(2))
+    ///         )
+    ///     )
+    /// );
+    /// assert_eq!(Some(2f64), on_metadata.get_path("keyframes.times.1").and_then(AmfValue::as_f64));
+    /// assert!(on_metadata.get_path("keyframes.filepositions").is_none())
+    /// ```
+    pub fn get_path(&self, path: &str) -> Option<&AmfValue> {
+        path.split('.').try_fold(self, |value, key| value.get(key))
     }
 }
 
-#[doc(hidden)]
-impl From<Boolean> for Value {
-    fn from(value: Boolean) -> Self {
-        unsafe {
-            let layout = Layout::new::<Boolean>();
-            let ptr = System.alloc(layout);
-            ptr.cast::<Boolean>().write(value);
-            Self {
-                layout,
-                ptr,
-                marker: Marker::Boolean
-            }
+macro_rules! impl_amf_value {
+    ($($variant:ident($t:ty, $marker:ident) => $as_x:ident),*) => {
+        impl AmfValue {
+            $(
+                #[doc = concat!("Gets this as the [`", stringify!($t), "`] if it is.")]
+                pub fn $as_x(&self) -> Option<&$t> {
+                    match self {
+                        AmfValue::$variant(value) => Some(value),
+                        _ => None
+                    }
+                }
+            )*
         }
-    }
-}
 
-#[doc(hidden)]
-impl From<AmfString> for Value {
-    fn from(value: AmfString) -> Self {
-        unsafe {
-            let layout = Layout::new::<AmfString>();
-            let ptr = System.alloc(layout);
-            ptr.cast::<AmfString>().write(value);
-            Self {
-                layout,
-                ptr,
-                marker: Marker::AmfString
+        $(
+            impl From<$t> for AmfValue {
+                fn from(value: $t) -> Self {
+                    AmfValue::$variant(value)
+                }
             }
-        }
-    }
-}
 
-#[doc(hidden)]
-impl<'a> From<&'a Value> for &'a AmfString {
-    fn from(value: &'a Value) -> Self {
-        value.as_string()
-    }
-}
+            impl<'a> TryFrom<&'a AmfValue> for &'a $t {
+                type Error = IOError;
+
+                /// Gets a reference to the inner value.
+                ///
+                /// # Errors
+                ///
+                /// * [`InconsistentMarker`]
+                ///
+                /// When the value has another type.
+                ///
+                /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+                fn try_from(value: &'a AmfValue) -> Result<Self, Self::Error> {
+                    match value {
+                        AmfValue::$variant(value) => Ok(value),
+                        _ => Err(inconsistent_marker(Marker::$marker.into(), value.get_marker().into()))
+                    }
+                }
+            }
 
-#[doc(hidden)]
-impl From<Null> for Value {
-    fn from(value: Null) -> Self {
-        unsafe {
-            let layout = Layout::new::<Null>();
-            let ptr = System.alloc(layout);
-            ptr.cast::<Null>().write(value);
-            Self {
-                layout,
-                ptr,
-                marker: Marker::Null
+            impl PartialEq<$t> for AmfValue {
+                fn eq(&self, other: &$t) -> bool {
+                    self.$as_x().is_some_and(|value| value == other)
+                }
             }
-        }
+        )*
     }
 }
 
-#[doc(hidden)]
-impl From<Object> for Value {
-    fn from(value: Object) -> Self {
-        unsafe {
-            let layout = Layout::new::<Object>();
-            let ptr = System.alloc(layout);
-            ptr.cast::<Object>().write(value);
-            Self {
-                layout,
-                ptr,
-                marker: Marker::Object
-            }
+impl_amf_value!(
+    Number(Number, Number) => as_number,
+    Boolean(Boolean, Boolean) => as_boolean,
+    String(AmfString, AmfString) => as_string,
+    Object(Object, Object) => as_object,
+    Reference(Reference, Reference) => as_reference,
+    EcmaArray(EcmaArray, EcmaArray) => as_ecma_array,
+    StrictArray(StrictArray, StrictArray) => as_strict_array,
+    Date(Date, Date) => as_date,
+    LongString(LongString, LongString) => as_long_string,
+    XmlDocument(XmlDocument, XmlDocument) => as_xml_document,
+    TypedObject(TypedObject, TypedObject) => as_typed_object
+);
+
+impl AmfValue {
+    /// Gets this as the [AMF3 value](v3::Value) if it is.
+    pub fn as_avm_plus(&self) -> Option<&v3::Value> {
+        match self {
+            AmfValue::AvmPlus(value) => Some(value),
+            _ => None
         }
     }
 }
 
-#[doc(hidden)]
-impl From<EcmaArray> for Value {
-    fn from(value: EcmaArray) -> Self {
-        unsafe {
-            let layout = Layout::new::<EcmaArray>();
-            let ptr = System.alloc(layout);
-            ptr.cast::<EcmaArray>().write(value);
-            Self {
-                layout,
-                ptr,
-                marker: Marker::EcmaArray
-            }
-        }
+impl From<Null> for AmfValue {
+    fn from(_: Null) -> Self {
+        AmfValue::Null
     }
 }
 
-#[doc(hidden)]
-impl Drop for Value {
-    fn drop(&mut self) {
-        unsafe {
-            System.dealloc(self.ptr, self.layout);
-        }
+impl PartialEq<Null> for AmfValue {
+    fn eq(&self, _: &Null) -> bool {
+        self.is_null()
     }
 }
 
-#[doc(hidden)]
-impl Debug for Value {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
-        match self.marker {
-            Marker::Number => Debug::fmt(self.as_number(), f),
-            Marker::Boolean => Debug::fmt(self.as_boolean(), f),
-            Marker::AmfString => Debug::fmt(self.as_string(), f),
-            Marker::Null => Debug::fmt(self.as_null(), f),
-            Marker::Object => Debug::fmt(self.as_object(), f),
-            Marker::EcmaArray => Debug::fmt(self.as_ecma_array(), f),
-            _ => unimplemented!("Debugging other types.")
-        }
+impl From<Undefined> for AmfValue {
+    fn from(_: Undefined) -> Self {
+        AmfValue::Undefined
     }
 }
 
-#[doc(hidden)]
-impl PartialEq<Self> for Value {
-    fn eq(&self, other: &Self) -> bool {
-        if self.marker != other.marker {
-            false
-        } else {
-            match self.marker {
-                Marker::Number => PartialEq::eq(self.as_number(), other.as_number()),
-                Marker::Boolean => PartialEq::eq(self.as_boolean(), other.as_boolean()),
-                Marker::AmfString => PartialEq::eq(self.as_string(), other.as_string()),
-                Marker::Null => PartialEq::eq(self.as_null(), other.as_null()),
-                Marker::Object => PartialEq::eq(self.as_object(), other.as_object()),
-                Marker::EcmaArray => PartialEq::eq(self.as_ecma_array(), other.as_ecma_array()),
-                _ => unimplemented!("Comparing other types.")
-            }
-        }
+impl PartialEq<Undefined> for AmfValue {
+    fn eq(&self, _: &Undefined) -> bool {
+        self.is_undefined()
     }
 }
 
-#[doc(hidden)]
-impl<T: Into<Value> + Clone> PartialEq<T> for Value {
-    fn eq(&self, other: &T) -> bool {
-        let value: Self = other.clone().into();
-        self.eq(&value)
+impl From<v3::Value> for AmfValue {
+    fn from(value: v3::Value) -> Self {
+        AmfValue::AvmPlus(value)
     }
 }
 
-#[doc(hidden)]
-impl Eq for Value {}
-#[doc(hidden)]
-unsafe impl Send for Value {}
-#[doc(hidden)]
-unsafe impl Sync for Value {}
-
-#[doc(hidden)]
-impl Decoder<Value> for ByteBuffer {
-    fn decode(&mut self) -> IOResult<Value> {
-        let marker: Marker = self.peek_u8()?.into();
+impl PartialEq<v3::Value> for AmfValue {
+    fn eq(&self, other: &v3::Value) -> bool {
+        self.as_avm_plus().is_some_and(|value| value == other)
+    }
+}
 
-        match marker {
-            Marker::Number => Decoder::<Number>::decode(self).map(Value::from),
-            Marker::Boolean => Decoder::<Boolean>::decode(self).map(Value::from),
-            Marker::AmfString => Decoder::<AmfString>::decode(self).map(Value::from),
-            Marker::Null => Decoder::<Null>::decode(self).map(Value::from),
-            Marker::Object => Decoder::<Object>::decode(self).map(Value::from),
-            Marker::EcmaArray => Decoder::<EcmaArray>::decode(self).map(Value::from),
-            _ => unimplemented!("Decoding other types.")
+impl Decoder<AmfValue> for ByteBuffer {
+    /// Decodes bytes into any AMF value.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer is shorter than the value which its marker indicates.
+    ///
+    /// * [`UnknownMarker`]
+    ///
+    /// When a marker byte doesn't indicate any supported type.
+    ///
+    /// * [`InvalidString`]
+    ///
+    /// When some string is invalid for a UTF-8 string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         AmfValue,
+    ///         Marker
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Number as u8);
+    /// buffer.put_f64(0.0);
+    /// assert!(Decoder::<AmfValue>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::ObjectEnd as u8);
+    /// assert!(Decoder::<AmfValue>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<AmfValue>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`UnknownMarker`]: crate::messages::amf::UnknownMarker
+    /// [`InvalidString`]: crate::messages::amf::InvalidString
+    fn decode(&mut self) -> IOResult<AmfValue> {
+        let marker_byte = self.peek_u8()?;
+
+        match Marker::from(marker_byte) {
+            Marker::Number => Decoder::<Number>::decode(self).map(AmfValue::from),
+            Marker::Boolean => Decoder::<Boolean>::decode(self).map(AmfValue::from),
+            Marker::AmfString => Decoder::<AmfString>::decode(self).map(AmfValue::from),
+            Marker::Null => Decoder::<Null>::decode(self).map(AmfValue::from),
+            Marker::Object => Decoder::<Object>::decode(self).map(AmfValue::from),
+            Marker::Undefined => Decoder::<Undefined>::decode(self).map(AmfValue::from),
+            Marker::Reference => Decoder::<Reference>::decode(self).map(AmfValue::from),
+            Marker::EcmaArray => Decoder::<EcmaArray>::decode(self).map(AmfValue::from),
+            Marker::StrictArray => Decoder::<StrictArray>::decode(self).map(AmfValue::from),
+            Marker::Date => Decoder::<Date>::decode(self).map(AmfValue::from),
+            Marker::LongString => Decoder::<LongString>::decode(self).map(AmfValue::from),
+            Marker::XmlDocument => Decoder::<XmlDocument>::decode(self).map(AmfValue::from),
+            Marker::TypedObject => Decoder::<TypedObject>::decode(self).map(AmfValue::from),
+            Marker::AvmPlus => {
+                self.get_u8()?;
+                Decoder::<v3::Value>::decode(self).map(AmfValue::from)
+            },
+            Marker::ObjectEnd | Marker::Other => Err(unknown_marker(marker_byte))
         }
     }
 }
 
-#[doc(hidden)]
-impl Encoder<Value> for ByteBuffer {
-    fn encode(&mut self, value: &Value) {
-        match value.marker {
-            Marker::Number => self.encode(value.as_number()),
-            Marker::Boolean => self.encode(value.as_boolean()),
-            Marker::AmfString => self.encode(value.as_string()),
-            Marker::Null => self.encode(value.as_null()),
-            Marker::Object => self.encode(value.as_object()),
-            Marker::EcmaArray => self.encode(value.as_ecma_array()),
-            _ => unimplemented!("Encoding other types.")
+impl Encoder<AmfValue> for ByteBuffer {
+    /// Encodes any AMF value into bytes.
+    ///
+    /// # Panics
+    ///
+    /// Same as the encoder of each type.
+    fn encode(&mut self, value: &AmfValue) {
+        match value {
+            AmfValue::Number(number) => self.encode(number),
+            AmfValue::Boolean(boolean) => self.encode(boolean),
+            AmfValue::String(string) => self.encode(string),
+            AmfValue::Object(object) => self.encode(object),
+            AmfValue::Null => self.encode(&Null),
+            AmfValue::Undefined => self.encode(&Undefined),
+            AmfValue::Reference(reference) => self.encode(reference),
+            AmfValue::EcmaArray(ecma_array) => self.encode(ecma_array),
+            AmfValue::StrictArray(strict_array) => self.encode(strict_array),
+            AmfValue::Date(date) => self.encode(date),
+            AmfValue::LongString(long_string) => self.encode(long_string),
+            AmfValue::XmlDocument(xml_document) => self.encode(xml_document),
+            AmfValue::TypedObject(typed_object) => self.encode(typed_object),
+            AmfValue::AvmPlus(value) => {
+                self.put_u8(Marker::AvmPlus as u8);
+                self.encode(value);
+            }
         }
     }
 }
 
-#[doc(hidden)]
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct Properties(HashMap<UnmarkedString, Arc<Value>>);
+/// Key/value pairs of the [`Object`], the [`EcmaArray`] and the [`TypedObject`].
+///
+/// Keys are strings without markers, and values are any [`AmfValue`].
+///
+/// Properties keep the order they were inserted (or decoded) in.
+/// That is, decoded properties are encoded into the same bytes as the original.
+/// If some key is duplicate in decoded properties, the last one is effective, that is, getting, inserting and removing act on the last one.
+/// Note comparing properties doesn't care their order.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::{
+///     messages::amf::v0::{
+///         AmfString,
+///         Number
+///     },
+///     object
+/// };
+///
+/// let mut object = object!(
+///     "duration" => Number::from(0),
+///     "encoder" => AmfString::from("Lavf60.10.100")
+/// );
+/// object.get_properties_mut().insert("duration", Number::from(10));
+/// let keys: Vec<&str> = object.get_properties().iter().map(|(key, _)| key).collect();
+/// assert_eq!(vec!["duration", "encoder"], keys)
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Properties(Vec<(UnmarkedString, AmfValue)>);
 
-#[doc(hidden)]
 impl Properties {
-    pub fn insert<V: Into<Value>>(&mut self, key: &str, value: V) {
-        self.0.insert(key.into(), Arc::new(value.into()));
+    /// Inserts a value associated with specified key.
+    ///
+    /// If the key already exists, its value is replaced in place.
+    /// Otherwise the pair is appended to the back.
+    pub fn insert<V: Into<AmfValue>>(&mut self, key: &str, value: V) {
+        let value = value.into();
+        match self.0.iter_mut().rev().find(|(k, _)| k.as_str() == key) {
+            Some((_, v)) => *v = value,
+            None => self.0.push((key.into(), value))
+        }
+    }
+
+    /// Removes a value associated with specified key, keeping the order of others.
+    ///
+    /// If the key is duplicate, every pair of it is removed so that none of them appears again, and the last value is returned.
+    pub fn remove(&mut self, key: &str) -> Option<AmfValue> {
+        let index = self.0.iter().rposition(|(k, _)| k.as_str() == key)?;
+        let (_, value) = self.0.remove(index);
+        self.0.retain(|(k, _)| k.as_str() != key);
+        Some(value)
     }
 
-    pub fn get(&self, key: &str) -> Option<&Value> {
-        self.0.get(&key.into()).map(|value| &**value)
+    /// Gets a value associated with specified key.
+    ///
+    /// If the key is duplicate in decoded properties, the last one is returned.
+    pub fn get(&self, key: &str) -> Option<&AmfValue> {
+        self.0.iter().rev().find(|(k, _)| k.as_str() == key).map(|(_, v)| v)
     }
 
+    /// Gets the number of properties.
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Checks whether this contains no property.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over pairs of keys and values.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AmfValue)> {
+        self.0.iter().map(|(key, value)| (key.as_str(), value))
+    }
 }
 
-#[doc(hidden)]
 impl Index<&str> for Properties {
-    type Output = Value;
+    type Output = AmfValue;
 
     fn index(&self, key: &str) -> &Self::Output {
-        &**self.0.index(&key.into())
+        self.get(key).expect("Property which is associated with specified key doesn't exist.")
+    }
+}
+
+impl PartialEq for Properties {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|(key, _)| self.get(key) == other.get(key))
+            && other.iter().all(|(key, _)| other.get(key) == self.get(key))
     }
 }
 
 #[doc(hidden)]
 impl Decoder<Properties> for ByteBuffer {
     fn decode(&mut self) -> IOResult<Properties> {
-        let mut m: HashMap<UnmarkedString, Arc<Value>> = HashMap::new();
+        let mut m: Vec<(UnmarkedString, AmfValue)> = Vec::new();
         loop {
             let key: UnmarkedString = self.decode()?;
 
@@ -567,8 +882,9 @@ impl Decoder<Properties> for ByteBuffer {
 
                 return Ok(Properties(m))
             } else {
-                let value: Value = self.decode()?;
-                m.insert(key, Arc::new(value));
+                let value: AmfValue = self.decode()?;
+                // NOTE: Duplicate keys are kept as they are, in order to be encoded into the same bytes.
+                m.push((key, value));
             }
         }
     }
@@ -579,7 +895,7 @@ impl Encoder<Properties> for ByteBuffer {
     fn encode(&mut self, properties: &Properties) {
         for (k, v) in &properties.0 {
             self.encode(k);
-            self.encode(&**v);
+            self.encode(v);
         }
         self.encode(&UnmarkedString::from(""));
         self.put_u8(Marker::ObjectEnd as u8);
@@ -588,48 +904,136 @@ impl Encoder<Properties> for ByteBuffer {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        fs::read,
+        path::PathBuf
+    };
+    use crate::flv::tags::TagType;
     use super::*;
 
     #[test]
     fn number_value() {
         let number = Number::new(0f64);
-        let allocated = Value::from(number);
-        assert_eq!(number, *allocated.as_number())
+        let value = AmfValue::from(number);
+        assert_eq!(Some(&number), value.as_number())
     }
 
     #[test]
     fn boolean_value() {
         let boolean = Boolean::new(0u8);
-        let allocated = Value::from(boolean);
-        assert_eq!(boolean, *allocated.as_boolean())
+        let value = AmfValue::from(boolean);
+        assert_eq!(Some(&boolean), value.as_boolean())
     }
 
     #[test]
     fn string_value() {
         let string = AmfString::new("".into());
-        let allocated = Value::from(string.clone());
-        assert_eq!(string, *allocated.as_string())
+        let value = AmfValue::from(string.clone());
+        assert_eq!(Some(&string), value.as_string())
     }
 
     #[test]
     fn null_value() {
-        let null = Null;
-        let allocated = Value::from(null);
-        assert_eq!(null, *allocated.as_null())
+        let value = AmfValue::from(Null);
+        assert!(value.is_null());
+        assert_eq!(value, Null)
     }
 
     #[test]
     fn object_value() {
         let object = Object::default();
-        let allocated = Value::from(object.clone());
-        assert_eq!(object, *allocated.as_object())
+        let value = AmfValue::from(object.clone());
+        assert_eq!(Some(&object), value.as_object())
     }
 
     #[test]
     fn ecma_array_value() {
         let ecma_array = EcmaArray::default();
-        let allocated = Value::from(ecma_array.clone());
-        assert_eq!(ecma_array, *allocated.as_ecma_array());
+        let value = AmfValue::from(ecma_array.clone());
+        assert_eq!(Some(&ecma_array), value.as_ecma_array())
+    }
+
+    #[test]
+    fn undefined_value() {
+        let value = AmfValue::from(Undefined);
+        assert!(value.is_undefined());
+        assert_eq!(value, Undefined)
+    }
+
+    #[test]
+    fn reference_value() {
+        let reference = Reference::new(1);
+        let value = AmfValue::from(reference);
+        assert_eq!(Some(&reference), value.as_reference())
+    }
+
+    #[test]
+    fn strict_array_value() {
+        let strict_array = StrictArray::default();
+        let value = AmfValue::from(strict_array.clone());
+        assert_eq!(Some(&strict_array), value.as_strict_array())
+    }
+
+    #[test]
+    fn date_value() {
+        let date = Date::default();
+        let value = AmfValue::from(date);
+        assert_eq!(Some(&date), value.as_date())
+    }
+
+    #[test]
+    fn long_string_value() {
+        let long_string = LongString::from("");
+        let value = AmfValue::from(long_string.clone());
+        assert_eq!(Some(&long_string), value.as_long_string())
+    }
+
+    #[test]
+    fn xml_document_value() {
+        let xml_document = XmlDocument::from("");
+        let value = AmfValue::from(xml_document.clone());
+        assert_eq!(Some(&xml_document), value.as_xml_document())
+    }
+
+    #[test]
+    fn typed_object_value() {
+        let typed_object = TypedObject::new("Foo", Properties::default());
+        let value = AmfValue::from(typed_object.clone());
+        assert_eq!(Some(&typed_object), value.as_typed_object())
+    }
+
+    #[test]
+    fn avm_plus_value() {
+        let avm_plus = v3::Value::from("ondemand");
+        let value = AmfValue::from(avm_plus.clone());
+        assert_eq!(Some(&avm_plus), value.as_avm_plus())
+    }
+
+    #[test]
+    fn inconsistent_accessors() {
+        let value = AmfValue::from(AmfString::from("ondemand"));
+        assert!(value.as_f64().is_none());
+        assert!(value.as_number().is_none());
+        assert!(value.get("app").is_none());
+        assert!(<&Number>::try_from(&value).is_err());
+        assert!(<&AmfString>::try_from(&value).is_ok())
+    }
+
+    #[test]
+    fn get_value_by_path() {
+        let mut keyframes = Properties::default();
+        let mut times = StrictArray::default();
+        times.push(Number::new(0.0));
+        times.push(Number::new(2.0));
+        keyframes.insert("times", times);
+        let mut properties = Properties::default();
+        properties.insert("keyframes", TypedObject::new("Keyframes", keyframes));
+        properties.insert("encoder", AmfString::from("Lavf60.10.100"));
+        let value = AmfValue::from(EcmaArray::new(properties));
+        assert_eq!(Some("Lavf60.10.100"), value.get_path("encoder").and_then(AmfValue::as_str));
+        assert_eq!(Some(2f64), value.get_path("keyframes.times.1").and_then(AmfValue::as_f64));
+        assert!(value.get_path("keyframes.times.2").is_none());
+        assert!(value.get_path("encoder.name").is_none())
     }
 
     #[test]
@@ -668,20 +1072,167 @@ mod tests {
         buffer.put_u8(Marker::AmfString as u8);
         buffer.put_u16_be(ondemand.len() as u16);
         buffer.put_bytes(ondemand.as_bytes());
-        let result: IOResult<Value> = buffer.decode();
+        let result: IOResult<AmfValue> = buffer.decode();
         assert!(result.is_ok());
         let actual = result.unwrap();
-        assert_eq!(Value::from(AmfString::from(ondemand)), actual)
+        assert_eq!(AmfValue::from(AmfString::from(ondemand)), actual)
     }
 
     #[test]
     fn encode_value() {
         let mut buffer = ByteBuffer::default();
         let ondemand = "ondemand";
-        buffer.encode(&Value::from(AmfString::from(ondemand)));
+        buffer.encode(&AmfValue::from(AmfString::from(ondemand)));
         let result: Vec<u8> = buffer.into();
         assert_eq!(Marker::AmfString as u8, result[0]);
         assert_eq!(&(ondemand.len() as u16).to_be_bytes(), &result[1..3]);
         assert_eq!(ondemand.as_bytes(), &result[3..])
     }
+
+    #[test]
+    fn round_trip_metadata() {
+        let mut keyframes = Properties::default();
+        let mut times = StrictArray::default();
+        times.push(Number::new(0.0));
+        times.push(Number::new(2.0));
+        keyframes.insert("times", times);
+        keyframes.insert("filepositions", StrictArray::default());
+        let mut properties = Properties::default();
+        properties.insert("duration", Number::new(0.0));
+        properties.insert("creationdate", Date::from(1700000000000f64));
+        properties.insert("description", LongString::from("a".repeat(1 + u16::MAX as usize).as_str()));
+        properties.insert("xmp", XmlDocument::from("<x:xmpmeta/>"));
+        properties.insert("author", Undefined);
+        properties.insert("previous", Reference::new(0));
+        properties.insert("keyframes", TypedObject::new("Keyframes", keyframes));
+        let expected = EcmaArray::new(properties);
+
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&expected);
+        let bytes: Vec<u8> = buffer.into();
+        let mut buffer: ByteBuffer = bytes.into();
+        let result: IOResult<AmfValue> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        assert_eq!(AmfValue::from(expected), actual);
+        assert_eq!(0, buffer.remained())
+    }
+
+    fn assert_round_trip(expected: &[u8]) {
+        let mut buffer: ByteBuffer = expected.to_vec().into();
+        let mut values: Vec<AmfValue> = Vec::new();
+        while buffer.remained() > 0 {
+            values.push(buffer.decode().unwrap());
+        }
+        let mut buffer = ByteBuffer::default();
+        for value in &values {
+            buffer.encode(value);
+        }
+        let actual: Vec<u8> = buffer.into();
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn round_trip_connect_command_in_ffmpeg() {
+        // NOTE: These are the connect commands which FFmpeg (Lavf60.16.100) sends as a publisher and as a subscriber respectively.
+        assert_round_trip(
+            b"\x02\x00\x07connect\
+            \x00?\xf0\x00\x00\x00\x00\x00\x00\
+            \x03\
+            \x00\x03app\x02\x00\x08ondemand\
+            \x00\x04type\x02\x00\x0anonprivate\
+            \x00\x08flashVer\x02\x00$FMLE/3.0 (compatible; Lavf60.16.100)\
+            \x00\x05tcUrl\x02\x00\x1ertmp://localhost:1935/ondemand\
+            \x00\x00\x09"
+        );
+        assert_round_trip(
+            b"\x02\x00\x07connect\
+            \x00?\xf0\x00\x00\x00\x00\x00\x00\
+            \x03\
+            \x00\x03app\x02\x00\x08ondemand\
+            \x00\x08flashVer\x02\x00\x0dLNX 9,0,124,2\
+            \x00\x05tcUrl\x02\x00\x1ertmp://localhost:1935/ondemand\
+            \x00\x04fpad\x01\x00\
+            \x00\x0ccapabilities\x00@.\x00\x00\x00\x00\x00\x00\
+            \x00\x0baudioCodecs\x00@\xaf\xce\x00\x00\x00\x00\x00\
+            \x00\x0bvideoCodecs\x00@o\x80\x00\x00\x00\x00\x00\
+            \x00\x0dvideoFunction\x00?\xf0\x00\x00\x00\x00\x00\x00\
+            \x00\x00\x09"
+        )
+    }
+
+    #[test]
+    fn round_trip_on_metadata_in_ffmpeg() {
+        // NOTE: This is the onMetaData which FFmpeg (Lavf60.16.100) has written into the test resource.
+        let mut resources_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        resources_path.pop();
+        resources_path.push("resources");
+        resources_path.push("test.flv");
+        let flv = read(resources_path).unwrap();
+        // NOTE: The first tag follows the FLV header (9 bytes) and the first PreviousTagSize (4 bytes), and its data follows its header (11 bytes).
+        assert_eq!(TagType::ScriptData as u8, flv[13]);
+        let data_size = u32::from_be_bytes([0, flv[14], flv[15], flv[16]]) as usize;
+        assert_round_trip(&flv[24..(24 + data_size)])
+    }
+
+    #[test]
+    fn keep_properties_order() {
+        let mut properties = Properties::default();
+        properties.insert("b", Number::from(0));
+        properties.insert("a", Number::from(1));
+        properties.insert("b", Number::from(2));
+        let keys: Vec<&str> = properties.iter().map(|(key, _)| key).collect();
+        assert_eq!(vec!["b", "a"], keys);
+        assert_eq!(Some(AmfValue::from(Number::from(2))), properties.remove("b"));
+        assert!(properties.get("b").is_none());
+
+        let mut reordered = Properties::default();
+        reordered.insert("a", Number::from(1));
+        let mut properties = properties.clone();
+        properties.insert("c", Null);
+        reordered.insert("c", Null);
+        assert_eq!(properties, reordered)
+    }
+
+    #[test]
+    fn act_on_last_duplicate_key() {
+        let mut buffer = ByteBuffer::default();
+        for value in [0.0, 1.0] {
+            buffer.encode(&UnmarkedString::from("a"));
+            buffer.encode(&Number::new(value));
+        }
+        buffer.encode(&UnmarkedString::from(""));
+        buffer.put_u8(Marker::ObjectEnd as u8);
+        let mut properties: Properties = buffer.decode().unwrap();
+        assert_eq!(properties, properties.clone());
+        assert_eq!(Some(&AmfValue::from(Number::new(1.0))), properties.get("a"));
+
+        properties.insert("a", Number::new(2.0));
+        assert_eq!(Some(&AmfValue::from(Number::new(2.0))), properties.get("a"));
+        assert_eq!(2, properties.len());
+
+        assert_eq!(Some(AmfValue::from(Number::new(2.0))), properties.remove("a"));
+        assert!(properties.get("a").is_none());
+        assert!(properties.is_empty())
+    }
+
+    #[test]
+    fn decode_avm_plus_value() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::AvmPlus as u8);
+        buffer.put_u8(v3::Marker::Integer as u8);
+        buffer.put_u8(0x01);
+        let result: IOResult<AmfValue> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        assert_eq!(AmfValue::from(v3::Value::Integer(1)), actual)
+    }
+
+    #[test]
+    fn encode_avm_plus_value() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&AmfValue::from(v3::Value::Integer(1)));
+        let result: Vec<u8> = buffer.into();
+        assert_eq!([Marker::AvmPlus as u8, v3::Marker::Integer as u8, 0x01], result[..])
+    }
 }
@@ -0,0 +1,145 @@
+use std::io::Result as IOResult;
+use super::{
+    Marker,
+    super::ensure_marker
+};
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer
+};
+
+/// The date type of AMF data types.
+///
+/// This consists of:
+///
+/// * Milliseconds from the UNIX epoch (UTC) as the IEEE 754 double precision floating point number.
+/// * Time zone (the signed 16 bits integer)
+///
+/// Note the time zone is reserved by the specification, that is, this should be 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Date {
+    milliseconds: f64,
+    time_zone: i16
+}
+
+impl Date {
+    /// Constructs an AMF's Date.
+    pub fn new(milliseconds: f64, time_zone: i16) -> Self {
+        Self {
+            milliseconds,
+            time_zone
+        }
+    }
+
+    /// Gets milliseconds from the UNIX epoch.
+    pub fn get_milliseconds(&self) -> f64 {
+        self.milliseconds
+    }
+
+    /// Gets the time zone.
+    pub fn get_time_zone(&self) -> i16 {
+        self.time_zone
+    }
+}
+
+impl From<f64> for Date {
+    fn from(milliseconds: f64) -> Self {
+        Self {
+            milliseconds,
+            time_zone: 0
+        }
+    }
+}
+
+impl Decoder<Date> for ByteBuffer {
+    /// Decodes bytes into an AMF's Date.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least 11 bytes.
+    ///
+    /// * [`InconsistentMarker`]
+    ///
+    /// When a marker byte doesn't indicate the AMF Date.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         Date,
+    ///         Marker
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Date as u8);
+    /// buffer.put_f64(0.0);
+    /// buffer.put_u16_be(0);
+    /// assert!(Decoder::<Date>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Number as u8);
+    /// buffer.put_f64(0.0);
+    /// buffer.put_u16_be(0);
+    /// assert!(Decoder::<Date>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Date as u8);
+    /// buffer.put_f64(0.0);
+    /// assert!(Decoder::<Date>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+    fn decode(&mut self) -> IOResult<Date> {
+        self.get_u8().and_then(
+            |marker| ensure_marker(Marker::Date as u8, marker)
+        )?;
+
+        let milliseconds = self.get_f64()?;
+        let time_zone = self.get_u16_be()? as i16;
+        Ok(Date::new(milliseconds, time_zone))
+    }
+}
+
+impl Encoder<Date> for ByteBuffer {
+    /// Encodes an AMF's Date into bytes.
+    fn encode(&mut self, date: &Date) {
+        self.put_u8(Marker::Date as u8);
+        self.put_f64(date.milliseconds);
+        self.put_u16_be(date.time_zone as u16);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_date() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::Date as u8);
+        buffer.put_f64(1700000000000f64);
+        buffer.put_u16_be(0);
+        let result: IOResult<Date> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        assert_eq!(Date::from(1700000000000f64), actual)
+    }
+
+    #[test]
+    fn encode_date() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&Date::new(1700000000000f64, -540));
+        let result: Vec<u8> = buffer.into();
+        assert_eq!(Marker::Date as u8, result[0]);
+        assert_eq!(&1700000000000f64.to_be_bytes(), &result[1..9]);
+        assert_eq!(&(-540i16).to_be_bytes(), &result[9..])
+    }
+}
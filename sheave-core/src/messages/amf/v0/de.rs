@@ -0,0 +1,377 @@
+use std::io::Result as IOResult;
+use serde::{
+    Deserialize,
+    de::{
+        self,
+        DeserializeOwned,
+        DeserializeSeed,
+        EnumAccess,
+        Error as _,
+        IntoDeserializer,
+        MapAccess,
+        SeqAccess,
+        Unexpected,
+        VariantAccess,
+        Visitor
+    },
+    forward_to_deserialize_any
+};
+use crate::{
+    ByteBuffer,
+    Decoder,
+    messages::amf::{
+        SerdeError,
+        serde_error,
+        v3
+    }
+};
+use super::AmfValue;
+
+/// The deserializer of AMF values into Rust values.
+///
+/// AMF values are mapped into Rust values in the reverse way of the [`Serializer`].
+/// In addition to it:
+///
+/// * Numbers can be read as integers only if they are integral and fit in the range of its type.
+/// * The Undefined is treated as same as the Null.
+/// * The ECMA Array and the Typed Object are read as same as the Object.
+/// * The Date is read as milliseconds from the UNIX epoch.
+/// * Primitive values of AMF3 are read as same as corresponding AMF0 values.
+///
+/// [`Serializer`]: super::Serializer
+#[derive(Debug, Clone, Copy)]
+pub struct Deserializer<'de>(&'de AmfValue);
+
+impl<'de> Deserializer<'de> {
+    /// Constructs a deserializer which reads specified value.
+    pub fn new(value: &'de AmfValue) -> Self {
+        Self(value)
+    }
+
+    fn unexpected(&self) -> Unexpected<'de> {
+        match self.0 {
+            AmfValue::Number(number) => Unexpected::Float(number.as_f64()),
+            AmfValue::Boolean(boolean) => Unexpected::Bool(boolean.as_boolean()),
+            AmfValue::String(string) => Unexpected::Str(string),
+            AmfValue::LongString(string) => Unexpected::Str(string),
+            AmfValue::XmlDocument(document) => Unexpected::Str(document),
+            AmfValue::Null | AmfValue::Undefined => Unexpected::Unit,
+            AmfValue::Object(_) | AmfValue::EcmaArray(_) | AmfValue::TypedObject(_) => Unexpected::Map,
+            AmfValue::StrictArray(_) => Unexpected::Seq,
+            AmfValue::Date(date) => Unexpected::Float(date.get_milliseconds()),
+            AmfValue::Reference(_) => Unexpected::Other("AMF reference"),
+            AmfValue::AvmPlus(_) => Unexpected::Other("AMF3 value")
+        }
+    }
+
+    fn deserialize_integer<V: Visitor<'de>>(self, visitor: V, min: f64, max: f64) -> Result<V::Value, SerdeError> {
+        let Some(number) = self.0.as_f64() else {
+            return Err(SerdeError::invalid_type(self.unexpected(), &visitor))
+        };
+
+        if number.fract() != 0.0 || number < min || number > max {
+            return Err(SerdeError::invalid_value(Unexpected::Float(number), &visitor))
+        }
+
+        if number < 0.0 {
+            visitor.visit_i64(number as i64)
+        } else {
+            visitor.visit_u64(number as u64)
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.0 {
+            AmfValue::Number(number) => visitor.visit_f64(number.as_f64()),
+            AmfValue::Boolean(boolean) => visitor.visit_bool(boolean.as_boolean()),
+            AmfValue::String(string) => visitor.visit_borrowed_str(string),
+            AmfValue::LongString(string) => visitor.visit_borrowed_str(string),
+            AmfValue::XmlDocument(document) => visitor.visit_borrowed_str(document),
+            AmfValue::Null | AmfValue::Undefined => visitor.visit_unit(),
+            AmfValue::Date(date) => visitor.visit_f64(date.get_milliseconds()),
+            AmfValue::StrictArray(strict_array) => visitor.visit_seq(StrictArrayAccess(strict_array.iter())),
+            AmfValue::AvmPlus(v3::Value::Undefined | v3::Value::Null) => visitor.visit_unit(),
+            AmfValue::AvmPlus(v3::Value::Boolean(boolean)) => visitor.visit_bool(*boolean),
+            AmfValue::AvmPlus(v3::Value::Integer(integer)) => visitor.visit_i32(*integer),
+            AmfValue::AvmPlus(v3::Value::Double(double)) => visitor.visit_f64(*double),
+            AmfValue::AvmPlus(v3::Value::String(string)) => visitor.visit_borrowed_str(string),
+            value => match value.get_properties() {
+                Some(properties) => visitor.visit_map(
+                    PropertiesAccess {
+                        properties: Box::new(properties.iter()),
+                        value: None
+                    }
+                ),
+                None => Err(SerdeError::invalid_type(self.unexpected(), &visitor))
+            }
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, i8::MIN as f64, i8::MAX as f64)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, i16::MIN as f64, i16::MAX as f64)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, i32::MIN as f64, i32::MAX as f64)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, i64::MIN as f64, i64::MAX as f64)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, 0.0, u8::MAX as f64)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, 0.0, u16::MAX as f64)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, 0.0, u32::MAX as f64)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_integer(visitor, 0.0, u64::MAX as f64)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.0 {
+            AmfValue::Null | AmfValue::Undefined | AmfValue::AvmPlus(v3::Value::Undefined | v3::Value::Null) => visitor.visit_none(),
+            _ => visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value, SerdeError> {
+        if let Some(variant) = self.0.as_str() {
+            return visitor.visit_enum(variant.into_deserializer())
+        }
+
+        let mut variants = self.0.get_properties().map(|properties| properties.iter());
+        match variants.as_mut().map(|variants| (variants.next(), variants.next())) {
+            Some((Some((variant, content)), None)) => visitor.visit_enum(
+                VariantDeserializer {
+                    variant,
+                    content
+                }
+            ),
+            _ => Err(SerdeError::invalid_type(self.unexpected(), &"a string or an object which has just one property"))
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct StrictArrayAccess<I>(I);
+
+impl<'de, I: Iterator<Item = &'de AmfValue>> SeqAccess<'de> for StrictArrayAccess<I> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, SerdeError> {
+        self.0.next().map(|value| seed.deserialize(Deserializer(value))).transpose()
+    }
+}
+
+struct PropertiesAccess<'de> {
+    properties: Box<dyn Iterator<Item = (&'de str, &'de AmfValue)> + 'de>,
+    value: Option<&'de AmfValue>
+}
+
+impl<'de> MapAccess<'de> for PropertiesAccess<'de> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, SerdeError> {
+        let Some((key, value)) = self.properties.next() else {
+            return Ok(None)
+        };
+
+        self.value = Some(value);
+        seed.deserialize(de::value::BorrowedStrDeserializer::new(key)).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, SerdeError> {
+        let value = self.value.take().ok_or_else(|| SerdeError::custom("A value got read before its key."))?;
+        seed.deserialize(Deserializer(value))
+    }
+}
+
+struct VariantDeserializer<'de> {
+    variant: &'de str,
+    content: &'de AmfValue
+}
+
+impl<'de> EnumAccess<'de> for VariantDeserializer<'de> {
+    type Error = SerdeError;
+    type Variant = Deserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Deserializer<'de>), SerdeError> {
+        let variant = seed.deserialize(de::value::BorrowedStrDeserializer::new(self.variant))?;
+        Ok((variant, Deserializer(self.content)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Deserializer<'de> {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, SerdeError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, SerdeError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _: &'static [&'static str], visitor: V) -> Result<V::Value, SerdeError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+/// Converts an AMF value into a Rust value.
+///
+/// # Errors
+///
+/// * [`SerdeError`]
+///
+/// When the value doesn't match the type of the Rust value.
+///
+/// [`SerdeError`]: crate::messages::amf::SerdeError
+pub fn from_value<'de, T: Deserialize<'de>>(value: &'de AmfValue) -> IOResult<T> {
+    T::deserialize(Deserializer::new(value)).map_err(serde_error)
+}
+
+/// Converts AMF bytes into a Rust value.
+///
+/// # Errors
+///
+/// Same as the decoder of the [`AmfValue`] and the [`from_value`].
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use sheave_core::{
+///     ByteBuffer,
+///     Encoder,
+///     messages::amf::v0::{
+///         AmfString,
+///         Number,
+///         from_bytes
+///     },
+///     object
+/// };
+///
+/// #[derive(Deserialize)]
+/// struct CommandObject {
+///     app: String,
+///     #[serde(rename = "objectEncoding")]
+///     object_encoding: Option<u8>
+/// }
+///
+/// let mut buffer = ByteBuffer::default();
+/// buffer.encode(&object!("app" => AmfString::from("ondemand"), "objectEncoding" => Number::from(3)));
+/// let command_object: CommandObject = from_bytes(&Vec::<u8>::from(buffer)).unwrap();
+/// assert_eq!("ondemand", command_object.app);
+/// assert_eq!(Some(3), command_object.object_encoding)
+/// ```
+///
+/// [`AmfValue`]: super::AmfValue
+/// [`from_value`]: from_value
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> IOResult<T> {
+    let mut buffer: ByteBuffer = bytes.to_vec().into();
+    let value: AmfValue = buffer.decode()?;
+    from_value(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde::Serialize;
+    use crate::messages::amf::v0::{
+        AmfString,
+        Boolean,
+        EcmaArray,
+        Number,
+        Object,
+        Properties,
+        StrictArray,
+        Undefined,
+        to_bytes
+    };
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Mode {
+        Live,
+        Record(String),
+        Append { offset: u32 }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Metadata {
+        duration: f64,
+        width: u32,
+        stereo: bool,
+        encoder: String,
+        title: Option<String>,
+        modes: Vec<Mode>,
+        extra: HashMap<String, i16>
+    }
+
+    #[test]
+    fn round_trip_struct() {
+        let expected = Metadata {
+            duration: 1.5,
+            width: 1920,
+            stereo: true,
+            encoder: "Lavf60.10.100".into(),
+            title: Some("sheave".into()),
+            modes: vec![Mode::Live, Mode::Record("append".into()), Mode::Append { offset: 3 }],
+            extra: HashMap::from([("a".into(), -1)])
+        };
+        let bytes = to_bytes(&expected).unwrap();
+        let actual: Metadata = from_bytes(&bytes).unwrap();
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn deserialize_ecma_array() {
+        let mut properties = Properties::default();
+        properties.insert("duration", Number::new(0.0));
+        properties.insert("width", Number::from(640));
+        properties.insert("stereo", Boolean::new(0));
+        properties.insert("encoder", AmfString::from("obs-output module"));
+        properties.insert("title", Undefined);
+        properties.insert("modes", StrictArray::default());
+        properties.insert("extra", Object::default());
+        let value = AmfValue::from(EcmaArray::new(properties));
+        let actual: Metadata = from_value(&value).unwrap();
+        assert_eq!(640, actual.width);
+        assert!(actual.title.is_none())
+    }
+
+    #[test]
+    fn deserialize_inconsistent_types() {
+        assert!(from_value::<u8>(&AmfValue::from(Number::new(1.5))).is_err());
+        assert!(from_value::<u8>(&AmfValue::from(Number::new(256.0))).is_err());
+        assert!(from_value::<String>(&AmfValue::from(Number::new(0.0))).is_err());
+        assert!(from_value::<bool>(&AmfValue::Null).is_err())
+    }
+}
@@ -29,12 +29,13 @@ use super::Properties;
 ///
 /// * Value
 ///
-/// The pointer for AMF data types, which is wrapped into `Arc`.
-/// This is because of avoiding to be deallocated its value unexpectedly.
+/// Any AMF data types as the [`AmfValue`].
 ///
 /// This is expected its size is same as the above count.
+/// However some encoder (e.g. OBS) writes a count which differs from actual size.
+/// A decoded count is kept as it is, in order to be encoded into the same bytes as the original, until properties are modified.
 ///
-/// You can access to properties which this contains, as the `HashMap`.
+/// You can access to properties which this contains in the order they were inserted, as the map.
 ///
 /// # Examples
 ///
@@ -53,23 +54,45 @@ use super::Properties;
 /// ecma_array.get_properties().get("videocodecid");
 /// &ecma_array.get_properties()["videocodecid"];
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct EcmaArray(Properties);
+///
+/// [`AmfValue`]: super::AmfValue
+#[derive(Debug, Clone, Default)]
+pub struct EcmaArray {
+    properties: Properties,
+    count: Option<u32>
+}
 
 impl EcmaArray {
     /// Constructs a new ECMA array.
     pub fn new(properties: Properties) -> Self {
-        Self(properties)
+        Self { properties, count: None }
     }
 
     /// Gets immutable properties from this array.
     pub fn get_properties(&self) -> &Properties {
-        &self.0
+        &self.properties
     }
 
     /// Gets mutable properties from this array.
+    ///
+    /// Note the decoded count is discarded at this point, that is, the actual size is encoded after this.
     pub fn get_properties_mut(&mut self) -> &mut Properties {
-        &mut self.0
+        self.count = None;
+        &mut self.properties
+    }
+
+    /// Gets the count field which was decoded.
+    ///
+    /// This returns `None` if this wasn't decoded, or its properties have been modified after decoding.
+    pub fn get_count(&self) -> Option<u32> {
+        self.count
+    }
+}
+
+impl PartialEq for EcmaArray {
+    /// Compares only properties, because the count field is just a hint.
+    fn eq(&self, other: &Self) -> bool {
+        self.properties == other.properties
     }
 }
 
@@ -152,23 +175,25 @@ impl Decoder<EcmaArray> for ByteBuffer {
             warn!("Properties length doesn't match previous field: previous field: {length}, actual length: {}", properties.len());
         }
 
-        Ok(EcmaArray(properties))
+        Ok(EcmaArray { properties, count: Some(length) })
     }
 }
 
 impl Encoder<EcmaArray> for ByteBuffer {
     /// Encodes an ECMA array into bytes.
     ///
+    /// The decoded count is written back if it is kept, otherwise the actual length is written.
+    ///
     /// # Panics
     ///
     /// Its length must be the range of 32 bits.
     /// If it exceeds, a panic is occured.
     fn encode(&mut self, ecma_array: &EcmaArray) {
-        assert!(ecma_array.0.len() <= u32::MAX as usize);
+        assert!(ecma_array.properties.len() <= u32::MAX as usize);
 
         self.put_u8(Marker::EcmaArray as u8);
-        self.put_u32_be(ecma_array.0.len() as u32);
-        self.encode(&ecma_array.0);
+        self.put_u32_be(ecma_array.count.unwrap_or(ecma_array.properties.len() as u32));
+        self.encode(&ecma_array.properties);
     }
 }
 
@@ -240,4 +265,32 @@ mod tests {
         assert_eq!(&0u16.to_be_bytes(), &result[5..7]);
         assert_eq!(Marker::ObjectEnd as u8, result[7])
     }
+
+    #[test]
+    fn keep_inconsistent_count() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::EcmaArray as u8);
+        buffer.put_u32_be(20);
+        buffer.encode(&UnmarkedString::from("width"));
+        buffer.put_u8(Marker::Number as u8);
+        buffer.put_f64(1920.0);
+        buffer.encode(&UnmarkedString::from(""));
+        buffer.put_u8(Marker::ObjectEnd as u8);
+        let expected: Vec<u8> = buffer.into();
+
+        let mut buffer: ByteBuffer = expected.clone().into();
+        let mut ecma_array: EcmaArray = buffer.decode().unwrap();
+        assert_eq!(Some(20), ecma_array.get_count());
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&ecma_array);
+        let actual: Vec<u8> = buffer.into();
+        assert_eq!(expected, actual);
+
+        ecma_array.get_properties_mut().remove("width");
+        assert!(ecma_array.get_count().is_none());
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&ecma_array);
+        let actual: Vec<u8> = buffer.into();
+        assert_eq!(&0u32.to_be_bytes(), &actual[1..5])
+    }
 }
@@ -0,0 +1,203 @@
+use std::{
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::Result as IOResult,
+    ops::{
+        Deref,
+        DerefMut
+    },
+    string::String as StdString
+};
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer
+};
+use super::{
+    Marker,
+    super::{
+        ensure_marker,
+        invalid_string
+    }
+};
+
+/// The UTF-8 string of AMF data types, which its length is the range of 4 bytes.
+///
+/// This is used for strings which are longer than the range of 2 bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LongString(StdString);
+
+impl LongString {
+    /// Constructs an AMF's Long String.
+    pub fn new(string: StdString) -> Self {
+        Self(string)
+    }
+}
+
+impl Deref for LongString {
+    type Target = StdString;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for LongString {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl PartialEq<str> for LongString {
+    fn eq(&self, other: &str) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for LongString {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl PartialEq<StdString> for LongString {
+    fn eq(&self, other: &StdString) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl From<&str> for LongString {
+    fn from(s: &str) -> Self {
+        Self(StdString::from(s))
+    }
+}
+
+impl From<StdString> for LongString {
+    fn from(s: StdString) -> Self {
+        Self(s)
+    }
+}
+
+impl Display for LongString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Decoder<LongString> for ByteBuffer {
+    /// Decodes bytes into an AMF's Long String.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least 5 bytes.
+    ///
+    /// * [`InconsistentMarker`]
+    ///
+    /// When a marker byte doesn't indicate the AMF Long String.
+    ///
+    /// * [`InvalidString`]
+    ///
+    /// When bytes are invalid for a UTF-8 string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         Marker,
+    ///         LongString
+    ///     }
+    /// };
+    ///
+    /// let s = "hello world!".as_bytes();
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::LongString as u8);
+    /// buffer.put_u32_be(s.len() as u32);
+    /// buffer.put_bytes(s);
+    /// assert!(Decoder::<LongString>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::AmfString as u8);
+    /// buffer.put_u32_be(s.len() as u32);
+    /// buffer.put_bytes(s);
+    /// assert!(Decoder::<LongString>::decode(&mut buffer).is_err());
+    ///
+    /// // This is a missing sequence of the "sparkle heart(💖)".
+    /// let bytes = vec![0, 159, 146, 150];
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::LongString as u8);
+    /// buffer.put_u32_be(bytes.len() as u32);
+    /// buffer.put_bytes(&bytes);
+    /// assert!(Decoder::<LongString>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<LongString>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+    /// [`InvalidString`]: crate::messages::amf::InvalidString
+    fn decode(&mut self) -> IOResult<LongString> {
+        self.get_u8().and_then(
+            |marker| ensure_marker(Marker::LongString as u8, marker)
+        )?;
+
+        let len = self.get_u32_be()? as usize;
+        if len == 0 {
+            return Ok("".into())
+        }
+        let bytes = self.get_bytes(len)?;
+        StdString::from_utf8(bytes.to_vec()).map(LongString::new).map_err(invalid_string)
+    }
+}
+
+impl Encoder<LongString> for ByteBuffer {
+    /// Encodes an AMF's Long String into bytes.
+    ///
+    /// # Panics
+    ///
+    /// Its length must be the range of 32 bits.
+    /// If it exceeds, a panic is occured.
+    fn encode(&mut self, string: &LongString) {
+        assert!(string.len() <= u32::MAX as usize);
+        self.put_u8(Marker::LongString as u8);
+        self.put_u32_be(string.len() as u32);
+        self.put_bytes(string.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_long_string() {
+        let string = "a".repeat(1 + u16::MAX as usize);
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::LongString as u8);
+        buffer.put_u32_be(string.len() as u32);
+        buffer.put_bytes(string.as_bytes());
+        let result: IOResult<LongString> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        assert_eq!(actual, string)
+    }
+
+    #[test]
+    fn encode_long_string() {
+        let string = LongString::from("a".repeat(1 + u16::MAX as usize));
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&string);
+        let result: Vec<u8> = buffer.into();
+        assert_eq!(Marker::LongString as u8, result[0]);
+        assert_eq!(&(string.len() as u32).to_be_bytes(), &result[1..5]);
+        assert_eq!(string.as_bytes(), &result[5..])
+    }
+}
@@ -32,6 +32,11 @@ impl Number {
         Self(number)
     }
 
+    /// Gets an inner value as it is.
+    pub fn as_f64(&self) -> f64 {
+        self.0
+    }
+
     /// Gets an inner value as an integer.
     ///
     /// This is prepared for converting any message ID to an integer from an AMF's number.
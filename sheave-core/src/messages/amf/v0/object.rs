@@ -21,10 +21,9 @@ use super::Properties;
 ///
 /// * Value
 ///
-/// The pointer for AMF data types, which is wrapped into `Arc`.
-/// This is because of avoiding to be deallocated its value unexpectedly.
+/// Any AMF data types as the [`AmfValue`].
 ///
-/// You can access to properties which this constains, as the `HashMap`.
+/// You can access to properties which this contains in the order they were inserted, as the map.
 ///
 /// # Example
 ///
@@ -40,7 +39,9 @@ use super::Properties;
 /// object.get_properties().get("app");
 /// &object.get_properties()["app"];
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+///
+/// [`AmfValue`]: super::AmfValue
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Object(Properties);
 
 impl Object {
@@ -0,0 +1,117 @@
+use std::io::Result as IOResult;
+use super::{
+    Marker,
+    super::ensure_marker
+};
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer
+};
+
+/// The index to refer a complex value which has been sent previously.
+///
+/// Complex values are the [`Object`], the [`TypedObject`], the [`EcmaArray`] and the [`StrictArray`], which are counted from 0 in the order they appeared.
+/// Note this is kept as an index, that is, this isn't resolved into its referent.
+///
+/// [`Object`]: super::Object
+/// [`TypedObject`]: super::TypedObject
+/// [`EcmaArray`]: super::EcmaArray
+/// [`StrictArray`]: super::StrictArray
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Reference(u16);
+
+impl Reference {
+    /// Constructs an AMF's Reference.
+    pub fn new(index: u16) -> Self {
+        Self(index)
+    }
+
+    /// Gets the index of the referent.
+    pub fn get_index(&self) -> u16 {
+        self.0
+    }
+}
+
+impl Decoder<Reference> for ByteBuffer {
+    /// Decodes bytes into an AMF's Reference.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least 3 bytes.
+    ///
+    /// * [`InconsistentMarker`]
+    ///
+    /// When a marker byte doesn't indicate the AMF Reference.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         Marker,
+    ///         Reference
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Reference as u8);
+    /// buffer.put_u16_be(0);
+    /// assert!(Decoder::<Reference>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Number as u8);
+    /// buffer.put_u16_be(0);
+    /// assert!(Decoder::<Reference>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<Reference>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+    fn decode(&mut self) -> IOResult<Reference> {
+        self.get_u8().and_then(
+            |marker| ensure_marker(Marker::Reference as u8, marker)
+        )?;
+
+        self.get_u16_be().map(Reference::new)
+    }
+}
+
+impl Encoder<Reference> for ByteBuffer {
+    /// Encodes an AMF's Reference into bytes.
+    fn encode(&mut self, reference: &Reference) {
+        self.put_u8(Marker::Reference as u8);
+        self.put_u16_be(reference.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_reference() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::Reference as u8);
+        buffer.put_u16_be(1);
+        let result: IOResult<Reference> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        assert_eq!(Reference::new(1), actual)
+    }
+
+    #[test]
+    fn encode_reference() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&Reference::new(1));
+        let result: Vec<u8> = buffer.into();
+        assert_eq!(Marker::Reference as u8, result[0]);
+        assert_eq!(&1u16.to_be_bytes(), &result[1..])
+    }
+}
@@ -0,0 +1,447 @@
+use std::io::Result as IOResult;
+use serde::{
+    Serialize,
+    ser
+};
+use crate::{
+    ByteBuffer,
+    Encoder,
+    messages::amf::{
+        SerdeError,
+        serde_error
+    }
+};
+use super::{
+    AmfString,
+    AmfValue,
+    Boolean,
+    EcmaArray,
+    LongString,
+    Number,
+    Object,
+    Properties,
+    StrictArray
+};
+
+/// The serializer of Rust values into AMF values.
+///
+/// Rust values are mapped as following:
+///
+/// |Rust|AMF|
+/// | :- | :- |
+/// |`bool`|[`Boolean`]|
+/// |Any number|[`Number`]|
+/// |`char`, `str`|[`AmfString`] (or [`LongString`] if it exceeds the range of 16 bits)|
+/// |`None`, `()`, unit structs|[`Null`](super::Null)|
+/// |Sequences, tuples, bytes|[`StrictArray`]|
+/// |Maps|[`EcmaArray`]|
+/// |Structs|[`Object`]|
+/// |Unit variants|[`AmfString`] of its name|
+/// |Other variants|[`Object`] which associates its name with its content|
+///
+/// Note keys of maps must be either strings, numbers or booleans.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+    type SerializeSeq = SerializeStrictArray;
+    type SerializeTuple = SerializeStrictArray;
+    type SerializeTupleStruct = SerializeStrictArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeStrictArray>;
+    type SerializeMap = SerializeProperties;
+    type SerializeStruct = SerializeProperties;
+    type SerializeStructVariant = SerializeVariant<SerializeProperties>;
+
+    fn serialize_bool(self, v: bool) -> Result<AmfValue, SerdeError> {
+        Ok(Boolean::new(v as u8).into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<AmfValue, SerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<AmfValue, SerdeError> {
+        Ok(Number::new(v).into())
+    }
+
+    fn serialize_char(self, v: char) -> Result<AmfValue, SerdeError> {
+        Ok(AmfString::from(v).into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<AmfValue, SerdeError> {
+        if v.len() <= u16::MAX as usize {
+            Ok(AmfString::from(v).into())
+        } else {
+            Ok(LongString::from(v).into())
+        }
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<AmfValue, SerdeError> {
+        let mut strict_array = StrictArray::default();
+        for byte in v {
+            strict_array.push(Number::from(*byte));
+        }
+        Ok(strict_array.into())
+    }
+
+    fn serialize_none(self) -> Result<AmfValue, SerdeError> {
+        Ok(AmfValue::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<AmfValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<AmfValue, SerdeError> {
+        Ok(AmfValue::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<AmfValue, SerdeError> {
+        Ok(AmfValue::Null)
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<AmfValue, SerdeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<AmfValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _: &'static str, _: u32, variant: &'static str, value: &T) -> Result<AmfValue, SerdeError> {
+        let mut properties = Properties::default();
+        properties.insert(variant, value.serialize(self)?);
+        Ok(Object::new(properties).into())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Ok(SerializeStrictArray::default())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Ok(SerializeStrictArray::default())
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Ok(SerializeStrictArray::default())
+    }
+
+    fn serialize_tuple_variant(self, _: &'static str, _: u32, variant: &'static str, _: usize) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Ok(SerializeVariant::new(variant, SerializeStrictArray::default()))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Ok(SerializeProperties::new(false))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Ok(SerializeProperties::new(true))
+    }
+
+    fn serialize_struct_variant(self, _: &'static str, _: u32, variant: &'static str, _: usize) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Ok(SerializeVariant::new(variant, SerializeProperties::new(true)))
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct SerializeStrictArray(StrictArray);
+
+impl ser::SerializeSeq for SerializeStrictArray {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.0.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<AmfValue, SerdeError> {
+        Ok(self.0.into())
+    }
+}
+
+impl ser::SerializeTuple for SerializeStrictArray {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<AmfValue, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeStrictArray {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<AmfValue, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeProperties {
+    properties: Properties,
+    key: Option<String>,
+    is_struct: bool
+}
+
+impl SerializeProperties {
+    fn new(is_struct: bool) -> Self {
+        Self {
+            properties: Properties::default(),
+            key: None,
+            is_struct
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeProperties {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        let key = match key.serialize(Serializer)? {
+            AmfValue::String(key) => key.to_string(),
+            AmfValue::LongString(key) => key.to_string(),
+            AmfValue::Number(key) => key.to_string(),
+            AmfValue::Boolean(key) => key.as_boolean().to_string(),
+            key => return Err(<SerdeError as ser::Error>::custom(format!("Keys must be strings, numbers or booleans. actual: {key:?}")))
+        };
+
+        if key.len() > u16::MAX as usize {
+            return Err(<SerdeError as ser::Error>::custom("Keys must be within the range of 16 bits."))
+        }
+
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self.key.take().ok_or_else(|| <SerdeError as ser::Error>::custom("A value got serialized before its key."))?;
+        self.properties.insert(&key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<AmfValue, SerdeError> {
+        if self.is_struct {
+            Ok(Object::new(self.properties).into())
+        } else {
+            Ok(EcmaArray::new(self.properties).into())
+        }
+    }
+}
+
+impl ser::SerializeStruct for SerializeProperties {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.properties.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<AmfValue, SerdeError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    content: S
+}
+
+impl<S> SerializeVariant<S> {
+    fn new(variant: &'static str, content: S) -> Self {
+        Self {
+            variant,
+            content
+        }
+    }
+
+    fn wrap(variant: &'static str, content: AmfValue) -> AmfValue {
+        let mut properties = Properties::default();
+        properties.insert(variant, content);
+        Object::new(properties).into()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeStrictArray> {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(&mut self.content, value)
+    }
+
+    fn end(self) -> Result<AmfValue, SerdeError> {
+        let content = ser::SerializeSeq::end(self.content)?;
+        Ok(Self::wrap(self.variant, content))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeProperties> {
+    type Ok = AmfValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeStruct::serialize_field(&mut self.content, key, value)
+    }
+
+    fn end(self) -> Result<AmfValue, SerdeError> {
+        let content = ser::SerializeStruct::end(self.content)?;
+        Ok(Self::wrap(self.variant, content))
+    }
+}
+
+/// Converts a Rust value into an AMF value.
+///
+/// # Errors
+///
+/// * [`SerdeError`]
+///
+/// When some key of maps is neither a string, a number nor a boolean.
+///
+/// [`SerdeError`]: crate::messages::amf::SerdeError
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> IOResult<AmfValue> {
+    value.serialize(Serializer).map_err(serde_error)
+}
+
+/// Converts a Rust value into AMF bytes.
+///
+/// # Errors
+///
+/// Same as the [`to_value`].
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::Serialize;
+/// use sheave_core::messages::amf::v0::{
+///     Marker,
+///     to_bytes
+/// };
+///
+/// #[derive(Serialize)]
+/// struct CommandObject {
+///     app: String,
+///     #[serde(rename = "objectEncoding")]
+///     object_encoding: f64
+/// }
+///
+/// let bytes = to_bytes(&CommandObject { app: "ondemand".into(), object_encoding: 0.0 }).unwrap();
+/// assert_eq!(Marker::Object as u8, bytes[0])
+/// ```
+///
+/// [`to_value`]: to_value
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> IOResult<Vec<u8>> {
+    let value = to_value(value)?;
+    let mut buffer = ByteBuffer::default();
+    buffer.encode(&value);
+    Ok(buffer.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use serde::Serialize;
+    use crate::ecma_array;
+    use super::*;
+
+    #[derive(Serialize)]
+    enum Mode {
+        Live,
+        Record(String)
+    }
+
+    #[derive(Serialize)]
+    struct Metadata {
+        duration: f64,
+        width: u32,
+        stereo: bool,
+        encoder: &'static str,
+        title: Option<String>,
+        mode: Mode,
+        extra: BTreeMap<String, u8>
+    }
+
+    #[test]
+    fn serialize_struct() {
+        let metadata = Metadata {
+            duration: 1.5,
+            width: 1920,
+            stereo: true,
+            encoder: "Lavf60.10.100",
+            title: None,
+            mode: Mode::Live,
+            extra: BTreeMap::from([("a".into(), 1)])
+        };
+        let actual = to_value(&metadata).unwrap();
+        let mut properties = Properties::default();
+        properties.insert("duration", Number::new(1.5));
+        properties.insert("width", Number::from(1920));
+        properties.insert("stereo", Boolean::new(1));
+        properties.insert("encoder", AmfString::from("Lavf60.10.100"));
+        properties.insert("title", AmfValue::Null);
+        properties.insert("mode", AmfString::from("Live"));
+        properties.insert("extra", ecma_array!("a" => Number::from(1)));
+        assert_eq!(AmfValue::from(Object::new(properties)), actual)
+    }
+
+    #[test]
+    fn serialize_variant() {
+        let actual = to_value(&Mode::Record("append".into())).unwrap();
+        assert_eq!(Some("append"), actual.get_path("Record").and_then(AmfValue::as_str))
+    }
+
+    #[test]
+    fn serialize_invalid_key() {
+        let map = BTreeMap::from([(vec![0u8], 0u8)]);
+        assert!(to_value(&map).is_err())
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    io::Result as IOResult,
+    ops::Index
+};
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer,
+    messages::amf::{
+        ensure_marker,
+        v0::Marker
+    }
+};
+use super::AmfValue;
+
+/// The **ordinal** array type of AMF.
+///
+/// This consists of:
+///
+/// * Count
+///
+/// The unsigned 32 bits integer.
+///
+/// * Values
+///
+/// Any AMF data types as the [`AmfValue`].
+///
+/// You can access to values which this contains, as the `Vec`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::{
+///     messages::amf::v0::Number,
+///     strict_array
+/// };
+///
+/// let strict_array = strict_array!(
+///     Number::from(0),
+///     Number::from(1)
+/// );
+/// strict_array.get(0);
+/// &strict_array[1];
+/// ```
+///
+/// [`AmfValue`]: super::AmfValue
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrictArray(Vec<AmfValue>);
+
+impl StrictArray {
+    /// Appends a value to the back of this array.
+    pub fn push<V: Into<AmfValue>>(&mut self, value: V) {
+        self.0.push(value.into());
+    }
+
+    /// Gets a value at specified index.
+    pub fn get(&self, index: usize) -> Option<&AmfValue> {
+        self.0.get(index)
+    }
+
+    /// Gets the number of values in this array.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks whether this array contains no value.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over values in this array.
+    pub fn iter(&self) -> impl Iterator<Item = &AmfValue> {
+        self.0.iter()
+    }
+}
+
+impl Index<usize> for StrictArray {
+    type Output = AmfValue;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl Decoder<StrictArray> for ByteBuffer {
+    /// Decodes bytes into an AMF's Strict Array.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least 5 bytes, or values are fewer than its count.
+    ///
+    /// * [`InconsistentMarker`]
+    ///
+    /// When a marker byte doesn't indicate the AMF Strict Array.
+    ///
+    /// * [`InvalidString`]
+    ///
+    /// When some string value is invalid for a UTF-8 string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         Marker,
+    ///         StrictArray
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::StrictArray as u8);
+    /// buffer.put_u32_be(1);
+    /// buffer.put_u8(Marker::Number as u8);
+    /// buffer.put_f64(0.0);
+    /// assert!(Decoder::<StrictArray>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::EcmaArray as u8);
+    /// buffer.put_u32_be(0);
+    /// assert!(Decoder::<StrictArray>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::StrictArray as u8);
+    /// buffer.put_u32_be(2);
+    /// buffer.put_u8(Marker::Number as u8);
+    /// buffer.put_f64(0.0);
+    /// assert!(Decoder::<StrictArray>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<StrictArray>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+    /// [`InvalidString`]: crate::messages::amf::InvalidString
+    fn decode(&mut self) -> IOResult<StrictArray> {
+        self.get_u8().and_then(
+            |marker| ensure_marker(Marker::StrictArray as u8, marker)
+        )?;
+
+        let count = self.get_u32_be()?;
+        let mut values: Vec<AmfValue> = Vec::new();
+        for _ in 0..count {
+            let value: AmfValue = self.decode()?;
+            values.push(value);
+        }
+        Ok(StrictArray(values))
+    }
+}
+
+impl Encoder<StrictArray> for ByteBuffer {
+    /// Encodes an AMF's Strict Array into bytes.
+    ///
+    /// # Panics
+    ///
+    /// Its length must be the range of 32 bits.
+    /// If it exceeds, a panic is occured.
+    fn encode(&mut self, strict_array: &StrictArray) {
+        assert!(strict_array.len() <= u32::MAX as usize);
+
+        self.put_u8(Marker::StrictArray as u8);
+        self.put_u32_be(strict_array.len() as u32);
+        for value in strict_array.iter() {
+            self.encode(value);
+        }
+    }
+}
+
+/// Constructs an AMF's Strict Array.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::{
+///     // Note the macro is exported from the top of crate.
+///     strict_array,
+///     messages::amf::v0::{
+///         AmfString,
+///         Number,
+///         StrictArray
+///     }
+/// };
+///
+/// let mut keyframes = StrictArray::default();
+/// keyframes.push(Number::from(0));
+/// keyframes.push(AmfString::from("1"));
+/// assert_eq!(
+///     keyframes,
+///     strict_array!(
+///         Number::from(0),
+///         AmfString::from("1")
+///     )
+/// )
+/// ```
+#[macro_export]
+macro_rules! strict_array {
+    ($($value:expr),*) => {
+        {
+            use $crate::messages::amf::v0::StrictArray;
+
+            let mut strict_array = StrictArray::default();
+            $(strict_array.push($value);)*
+            strict_array
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::messages::amf::v0::Number;
+    use super::*;
+
+    #[test]
+    fn decode_strict_array() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::StrictArray as u8);
+        buffer.put_u32_be(2);
+        buffer.put_u8(Marker::Number as u8);
+        buffer.put_f64(0.0);
+        buffer.put_u8(Marker::Number as u8);
+        buffer.put_f64(1.0);
+        let result: IOResult<StrictArray> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let mut expected = StrictArray::default();
+        expected.push(Number::new(0.0));
+        expected.push(Number::new(1.0));
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_strict_array() {
+        let mut strict_array = StrictArray::default();
+        strict_array.push(Number::new(1.0));
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&strict_array);
+        let result: Vec<u8> = buffer.into();
+        assert_eq!(Marker::StrictArray as u8, result[0]);
+        assert_eq!(&1u32.to_be_bytes(), &result[1..5]);
+        assert_eq!(Marker::Number as u8, result[5]);
+        assert_eq!(&1f64.to_be_bytes(), &result[6..])
+    }
+}
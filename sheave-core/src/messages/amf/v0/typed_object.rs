@@ -0,0 +1,208 @@
+use std::io::Result as IOResult;
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer,
+    messages::amf::{
+        ensure_marker,
+        v0::Marker
+    }
+};
+use super::{
+    Properties,
+    UnmarkedString
+};
+
+/// The object type of AMF, which is associated with its class name.
+///
+/// This consists of:
+///
+/// * Class Name
+///
+/// The string which doesn't have its marker.
+/// Also this occurs the panic if its length exceeds the range of 16 bits.
+///
+/// * Properties
+///
+/// Same as the [`Object`].
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::{
+///     messages::amf::v0::{
+///         AmfString,
+///         TypedObject
+///     },
+///     object
+/// };
+///
+/// let typed_object = TypedObject::new(
+///     "flex.messaging.io.ObjectProxy",
+///     object!(
+///         "app" => AmfString::from("ondemand")
+///     ).get_properties().clone()
+/// );
+/// assert_eq!("flex.messaging.io.ObjectProxy", typed_object.get_class_name());
+/// typed_object.get_properties().get("app");
+/// ```
+///
+/// [`Object`]: super::Object
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypedObject {
+    class_name: UnmarkedString,
+    properties: Properties
+}
+
+impl TypedObject {
+    /// Constructs a new typed object.
+    pub fn new(class_name: &str, properties: Properties) -> Self {
+        Self {
+            class_name: class_name.into(),
+            properties
+        }
+    }
+
+    /// Gets the class name of this object.
+    pub fn get_class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Gets immutable properties from this object.
+    pub fn get_properties(&self) -> &Properties {
+        &self.properties
+    }
+
+    /// Gets mutable properties from this object.
+    pub fn get_properties_mut(&mut self) -> &mut Properties {
+        &mut self.properties
+    }
+}
+
+impl Decoder<TypedObject> for ByteBuffer {
+    /// Decodes bytes into an AMF's Typed Object.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least 6 bytes.
+    ///
+    /// * [`InconsistentMarker`]
+    ///
+    /// When a marker byte doesn't indicate the AMF Typed Object.
+    ///
+    /// * [`InvalidString`]
+    ///
+    /// When the class name or key bytes are invalid for a UTF-8 string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         Marker,
+    ///         TypedObject
+    ///     }
+    /// };
+    ///
+    /// let class_name = "Foo".as_bytes();
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::TypedObject as u8);
+    /// buffer.put_u16_be(class_name.len() as u16);
+    /// buffer.put_bytes(class_name);
+    /// buffer.put_u16_be(0);
+    /// buffer.put_u8(Marker::ObjectEnd as u8);
+    /// assert!(Decoder::<TypedObject>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Object as u8);
+    /// buffer.put_u16_be(class_name.len() as u16);
+    /// buffer.put_bytes(class_name);
+    /// buffer.put_u16_be(0);
+    /// buffer.put_u8(Marker::ObjectEnd as u8);
+    /// assert!(Decoder::<TypedObject>::decode(&mut buffer).is_err());
+    ///
+    /// // This is a missing sequence of the "sparkle heart(💖)".
+    /// let bytes = vec![0, 159, 146, 150];
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::TypedObject as u8);
+    /// buffer.put_u16_be(bytes.len() as u16);
+    /// buffer.put_bytes(&bytes);
+    /// buffer.put_u16_be(0);
+    /// buffer.put_u8(Marker::ObjectEnd as u8);
+    /// assert!(Decoder::<TypedObject>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<TypedObject>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+    /// [`InvalidString`]: crate::messages::amf::InvalidString
+    fn decode(&mut self) -> IOResult<TypedObject> {
+        self.get_u8().and_then(
+            |marker| ensure_marker(Marker::TypedObject as u8, marker)
+        )?;
+
+        let class_name: UnmarkedString = self.decode()?;
+        let properties: Properties = self.decode()?;
+        Ok(
+            TypedObject {
+                class_name,
+                properties
+            }
+        )
+    }
+}
+
+impl Encoder<TypedObject> for ByteBuffer {
+    /// Encodes an AMF's Typed Object into bytes.
+    ///
+    /// # Panics
+    ///
+    /// The length of its class name must be the range of 16 bits.
+    /// If it exceeds, a panic is occured.
+    fn encode(&mut self, typed_object: &TypedObject) {
+        self.put_u8(Marker::TypedObject as u8);
+        self.encode(&typed_object.class_name);
+        self.encode(&typed_object.properties);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::messages::amf::v0::AmfString;
+    use super::*;
+
+    #[test]
+    fn decode_typed_object() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::TypedObject as u8);
+        buffer.encode(&UnmarkedString::from("Foo"));
+        buffer.encode(&UnmarkedString::from("bar"));
+        buffer.encode(&AmfString::from("baz"));
+        buffer.encode(&UnmarkedString::from(""));
+        buffer.put_u8(Marker::ObjectEnd as u8);
+        let result: IOResult<TypedObject> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let mut properties = Properties::default();
+        properties.insert("bar", AmfString::from("baz"));
+        assert_eq!(TypedObject::new("Foo", properties), actual)
+    }
+
+    #[test]
+    fn encode_typed_object() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&TypedObject::new("Foo", Properties::default()));
+        let result: Vec<u8> = buffer.into();
+        assert_eq!(Marker::TypedObject as u8, result[0]);
+        assert_eq!(&3u16.to_be_bytes(), &result[1..3]);
+        assert_eq!("Foo".as_bytes(), &result[3..6]);
+        assert_eq!(&0u16.to_be_bytes(), &result[6..8]);
+        assert_eq!(Marker::ObjectEnd as u8, result[8])
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::Result as IOResult;
+use super::{
+    Marker,
+    super::ensure_marker
+};
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer
+};
+
+/// The value to mean that isn't defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undefined;
+
+impl Decoder<Undefined> for ByteBuffer {
+    /// Decodes bytes into an AMF's Undefined.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least 1 byte.
+    ///
+    /// * [`InconsistentMarker`]
+    ///
+    /// When a marker byte doesn't indicate the AMF Undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         Marker,
+    ///         Undefined
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Undefined as u8);
+    /// assert!(Decoder::<Undefined>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Null as u8);
+    /// assert!(Decoder::<Undefined>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<Undefined>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+    fn decode(&mut self) -> IOResult<Undefined> {
+        self.get_u8().and_then(
+            |marker| ensure_marker(Marker::Undefined as u8, marker)
+        )?;
+
+        Ok(Undefined)
+    }
+}
+
+impl Encoder<Undefined> for ByteBuffer {
+    /// Encodes an AMF's Undefined into bytes.
+    fn encode(&mut self, _: &Undefined) {
+        self.put_u8(Marker::Undefined as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_undefined() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::Undefined as u8);
+        assert!(Decoder::<Undefined>::decode(&mut buffer).is_ok())
+    }
+
+    #[test]
+    fn encode_undefined() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&Undefined);
+        let result: Vec<u8> = buffer.into();
+        assert_eq!(Marker::Undefined as u8, result[0])
+    }
+}
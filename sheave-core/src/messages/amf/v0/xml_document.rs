@@ -0,0 +1,159 @@
+use std::{
+    io::Result as IOResult,
+    ops::Deref,
+    string::String as StdString
+};
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer
+};
+use super::{
+    Marker,
+    super::{
+        ensure_marker,
+        invalid_string
+    }
+};
+
+/// The XML document type of AMF data types.
+///
+/// This is encoded same as the [`LongString`] except its marker.
+/// Note this is kept as a string, that is, this isn't parsed as the XML.
+///
+/// [`LongString`]: super::LongString
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct XmlDocument(StdString);
+
+impl XmlDocument {
+    /// Constructs an AMF's XML Document.
+    pub fn new(document: StdString) -> Self {
+        Self(document)
+    }
+}
+
+impl Deref for XmlDocument {
+    type Target = StdString;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> PartialEq<&'a str> for XmlDocument {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl From<&str> for XmlDocument {
+    fn from(s: &str) -> Self {
+        Self(StdString::from(s))
+    }
+}
+
+impl Decoder<XmlDocument> for ByteBuffer {
+    /// Decodes bytes into an AMF's XML Document.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When buffer isn't remained at least 5 bytes.
+    ///
+    /// * [`InconsistentMarker`]
+    ///
+    /// When a marker byte doesn't indicate the AMF XML Document.
+    ///
+    /// * [`InvalidString`]
+    ///
+    /// When bytes are invalid for a UTF-8 string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v0::{
+    ///         Marker,
+    ///         XmlDocument
+    ///     }
+    /// };
+    ///
+    /// let s = "<a>b</a>".as_bytes();
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::XmlDocument as u8);
+    /// buffer.put_u32_be(s.len() as u32);
+    /// buffer.put_bytes(s);
+    /// assert!(Decoder::<XmlDocument>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::LongString as u8);
+    /// buffer.put_u32_be(s.len() as u32);
+    /// buffer.put_bytes(s);
+    /// assert!(Decoder::<XmlDocument>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<XmlDocument>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InconsistentMarker`]: crate::messages::amf::InconsistentMarker
+    /// [`InvalidString`]: crate::messages::amf::InvalidString
+    fn decode(&mut self) -> IOResult<XmlDocument> {
+        self.get_u8().and_then(
+            |marker| ensure_marker(Marker::XmlDocument as u8, marker)
+        )?;
+
+        let len = self.get_u32_be()? as usize;
+        if len == 0 {
+            return Ok("".into())
+        }
+        let bytes = self.get_bytes(len)?;
+        StdString::from_utf8(bytes.to_vec()).map(XmlDocument::new).map_err(invalid_string)
+    }
+}
+
+impl Encoder<XmlDocument> for ByteBuffer {
+    /// Encodes an AMF's XML Document into bytes.
+    ///
+    /// # Panics
+    ///
+    /// Its length must be the range of 32 bits.
+    /// If it exceeds, a panic is occured.
+    fn encode(&mut self, document: &XmlDocument) {
+        assert!(document.len() <= u32::MAX as usize);
+        self.put_u8(Marker::XmlDocument as u8);
+        self.put_u32_be(document.len() as u32);
+        self.put_bytes(document.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_xml_document() {
+        let document = "<a>b</a>".as_bytes();
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::XmlDocument as u8);
+        buffer.put_u32_be(document.len() as u32);
+        buffer.put_bytes(document);
+        let result: IOResult<XmlDocument> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        assert_eq!(actual, "<a>b</a>")
+    }
+
+    #[test]
+    fn encode_xml_document() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&XmlDocument::from("<a>b</a>"));
+        let result: Vec<u8> = buffer.into();
+        assert_eq!(Marker::XmlDocument as u8, result[0]);
+        assert_eq!(&8u32.to_be_bytes(), &result[1..5]);
+        assert_eq!("<a>b</a>".as_bytes(), &result[5..])
+    }
+}
@@ -0,0 +1,993 @@
+//! # The AMF Data Types (version 3).
+//!
+//! These are data types which are defined in the Action Message Format version 3 specification.
+//! In the RTMP, AMF3 values are embedded into AMF0 streams via the AVM+ marker (`0x11`) of AMF0.
+//! Following types are defined:
+//!
+//! |Marker|AMF Data Type|Description|
+//! | -: | :- | :- |
+//! |`0`|Undefined|Only the marker.|
+//! |`1`|Null|Only the marker.|
+//! |`2`|False|Only the marker.|
+//! |`3`|True|Only the marker.|
+//! |`4`|Integer|The signed 29 bits integer. (See [U29](#u29))|
+//! |`5`|Double|The IEEE 754 double precision floating point number.|
+//! |`6`|String|The UTF-8 string. This can be referred by the string reference table.|
+//! |`7`|XML Document|The legacy XML document as a string.|
+//! |`8`|Date|Milliseconds from the UNIX epoch as a double.|
+//! |`9`|[`Array`]|The array which has both of associative part and dense part.|
+//! |`10`|[`Object`]|The object which is defined by its [`Traits`].|
+//! |`11`|XML|The E4X XML as a string.|
+//! |`12`|ByteArray|The raw bytes.|
+//! |`13`|[`Vector`]&lt;int&gt;|The typed array of the signed 32 bits integers.|
+//! |`14`|[`Vector`]&lt;uint&gt;|The typed array of the unsigned 32 bits integers.|
+//! |`15`|[`Vector`]&lt;Number&gt;|The typed array of the doubles.|
+//! |`16`|[`Vector`]&lt;Object&gt;|The typed array of any AMF3 data type.|
+//! |`17`|[`Dictionary`]|The key/value-paired object that its key type is flexible.|
+//!
+//! ## U29
+//!
+//! AMF3 encodes integers and lengths into 1 to 4 bytes variably.
+//! The most significant bit of first 3 bytes indicates whether next byte continues, and the 4th byte is used all 8 bits.
+//! Therefore the range of U29 is 0 to 2<sup>29</sup> - 1.
+//!
+//! ## References
+//!
+//! Strings, complex values and [`Traits`] are stored into respective reference tables when they have been sent once.
+//! After that, the same value can be sent as an index of its table.
+//! The lowest bit of the U29 header indicates whether its value is sent inline (1) or as a reference (0).
+//!
+//! Note reference tables are reset every time the AVM+ marker appears.
+//! Circular references are resolved to the value at the time of referencing.
+//!
+//! Because references are resolved into copies, a few bytes can expand into a huge value.
+//! Therefore decoding is limited by [`MAX_DEPTH`] and [`DECODING_BUDGET`].
+
+mod traits;
+mod object;
+mod array;
+mod vector;
+mod dictionary;
+
+use std::{
+    collections::HashMap,
+    io::Result as IOResult
+};
+use crate::{
+    Decoder,
+    Encoder,
+    ByteBuffer,
+    messages::amf::{
+        invalid_reference,
+        invalid_string,
+        too_deep_value,
+        too_large_value,
+        unknown_marker,
+        unsupported_externalizable
+    }
+};
+pub use self::{
+    traits::Traits,
+    object::Object,
+    array::Array,
+    vector::Vector,
+    dictionary::Dictionary
+};
+
+/// The minimum value of AMF3's Integer type.
+pub const INTEGER_MIN: i32 = -(1 << 28);
+/// The maximum value of AMF3's Integer type.
+pub const INTEGER_MAX: i32 = (1 << 28) - 1;
+const U29_MAX: u32 = (1 << 29) - 1;
+/// The maximum depth of nested values to decode.
+pub const MAX_DEPTH: usize = 64;
+/// The maximum amount to decode from one AMF3 value.
+///
+/// Every value counts 1, and every string and every byte array count their lengths.
+/// Values which are referred count again every time they are referred, because they are copied.
+pub const DECODING_BUDGET: usize = 1 << 26;
+
+/// Representation of markers of the AMF3 data types.
+///
+/// Variants correspond to respectively following numbers:
+///
+/// |Pattern|Number|
+/// | :- | :- |
+/// |`Undefined`|`0`|
+/// |`Null`|`1`|
+/// |`False`|`2`|
+/// |`True`|`3`|
+/// |`Integer`|`4`|
+/// |`Double`|`5`|
+/// |`String`|`6`|
+/// |`XmlDocument`|`7`|
+/// |`Date`|`8`|
+/// |`Array`|`9`|
+/// |`Object`|`10`|
+/// |`Xml`|`11`|
+/// |`ByteArray`|`12`|
+/// |`VectorInt`|`13`|
+/// |`VectorUint`|`14`|
+/// |`VectorDouble`|`15`|
+/// |`VectorObject`|`16`|
+/// |`Dictionary`|`17`|
+/// |`Other`|other numbers|
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    Undefined,
+    Null,
+    False,
+    True,
+    Integer,
+    Double,
+    String,
+    XmlDocument,
+    Date,
+    Array,
+    Object,
+    Xml,
+    ByteArray,
+    VectorInt,
+    VectorUint,
+    VectorDouble,
+    VectorObject,
+    Dictionary,
+    Other = 0xff
+}
+
+impl From<u8> for Marker {
+    fn from(marker: u8) -> Self {
+        use Marker::*;
+
+        match marker {
+            0 => Undefined,
+            1 => Null,
+            2 => False,
+            3 => True,
+            4 => Integer,
+            5 => Double,
+            6 => String,
+            7 => XmlDocument,
+            8 => Date,
+            9 => Array,
+            10 => Object,
+            11 => Xml,
+            12 => ByteArray,
+            13 => VectorInt,
+            14 => VectorUint,
+            15 => VectorDouble,
+            16 => VectorObject,
+            17 => Dictionary,
+            _ => Other
+        }
+    }
+}
+
+impl From<Marker> for u8 {
+    fn from(marker: Marker) -> Self {
+        marker as u8
+    }
+}
+
+/// Any value of the AMF3 data types.
+///
+/// Note the `Integer` which exceeds the range of 29 bits is encoded as the `Double`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::messages::amf::v3::{
+///     Marker,
+///     Value
+/// };
+///
+/// let value = Value::from("sheave");
+/// assert_eq!(Marker::String, value.get_marker());
+///
+/// if let Value::String(s) = value {
+///     assert_eq!("sheave", s)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Integer(i32),
+    Double(f64),
+    String(String),
+    XmlDocument(String),
+    Date(f64),
+    Array(Array),
+    Object(Object),
+    Xml(String),
+    ByteArray(Vec<u8>),
+    VectorInt(Vector<i32>),
+    VectorUint(Vector<u32>),
+    VectorDouble(Vector<f64>),
+    VectorObject(String, Vector<Value>),
+    Dictionary(Dictionary)
+}
+
+impl Value {
+    /// Gets the marker which corresponds to this value.
+    pub fn get_marker(&self) -> Marker {
+        match self {
+            Value::Undefined => Marker::Undefined,
+            Value::Null => Marker::Null,
+            Value::Boolean(false) => Marker::False,
+            Value::Boolean(true) => Marker::True,
+            Value::Integer(n) => if (INTEGER_MIN..=INTEGER_MAX).contains(n) {
+                Marker::Integer
+            } else {
+                Marker::Double
+            },
+            Value::Double(_) => Marker::Double,
+            Value::String(_) => Marker::String,
+            Value::XmlDocument(_) => Marker::XmlDocument,
+            Value::Date(_) => Marker::Date,
+            Value::Array(_) => Marker::Array,
+            Value::Object(_) => Marker::Object,
+            Value::Xml(_) => Marker::Xml,
+            Value::ByteArray(_) => Marker::ByteArray,
+            Value::VectorInt(_) => Marker::VectorInt,
+            Value::VectorUint(_) => Marker::VectorUint,
+            Value::VectorDouble(_) => Marker::VectorDouble,
+            Value::VectorObject(..) => Marker::VectorObject,
+            Value::Dictionary(_) => Marker::Dictionary
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Self::Boolean(b)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(n: i32) -> Self {
+        Self::Integer(n)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Self::Double(n)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Self::String(s.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<Array> for Value {
+    fn from(array: Array) -> Self {
+        Self::Array(array)
+    }
+}
+
+impl From<Object> for Value {
+    fn from(object: Object) -> Self {
+        Self::Object(object)
+    }
+}
+
+impl From<Dictionary> for Value {
+    fn from(dictionary: Dictionary) -> Self {
+        Self::Dictionary(dictionary)
+    }
+}
+
+#[doc(hidden)]
+fn get_u29(buffer: &mut ByteBuffer) -> IOResult<u32> {
+    let mut n: u32 = 0;
+    for _ in 0..3 {
+        let byte = buffer.get_u8()?;
+        n = (n << 7) | (byte & 0x7f) as u32;
+
+        if byte & 0x80 == 0 {
+            return Ok(n)
+        }
+    }
+
+    let byte = buffer.get_u8()?;
+    Ok((n << 8) | byte as u32)
+}
+
+#[doc(hidden)]
+fn put_u29(buffer: &mut ByteBuffer, n: u32) {
+    assert!(n <= U29_MAX);
+
+    if n < 0x80 {
+        buffer.put_u8(n as u8);
+    } else if n < 0x4000 {
+        buffer.put_u8((n >> 7) as u8 | 0x80);
+        buffer.put_u8(n as u8 & 0x7f);
+    } else if n < 0x200000 {
+        buffer.put_u8((n >> 14) as u8 | 0x80);
+        buffer.put_u8((n >> 7) as u8 | 0x80);
+        buffer.put_u8(n as u8 & 0x7f);
+    } else {
+        buffer.put_u8((n >> 22) as u8 | 0x80);
+        buffer.put_u8((n >> 15) as u8 | 0x80);
+        buffer.put_u8((n >> 8) as u8 | 0x80);
+        buffer.put_u8(n as u8);
+    }
+}
+
+#[doc(hidden)]
+fn put_inline_length(buffer: &mut ByteBuffer, len: usize) {
+    put_u29(buffer, ((len as u32) << 1) | 1);
+}
+
+#[doc(hidden)]
+#[derive(Debug, Default)]
+struct ReadingTables {
+    strings: Vec<String>,
+    /* NOTE: Every object is stored with the amount which has been spent to decode it. */
+    objects: Vec<(Value, usize)>,
+    traits: Vec<Traits>,
+    depth: usize,
+    spent: usize
+}
+
+#[doc(hidden)]
+impl ReadingTables {
+    fn spend(&mut self, amount: usize) -> IOResult<()> {
+        self.spent = self.spent.saturating_add(amount);
+        if self.spent > DECODING_BUDGET {
+            Err(too_large_value(DECODING_BUDGET))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn get_string(&mut self, buffer: &mut ByteBuffer) -> IOResult<String> {
+        let header = get_u29(buffer)?;
+
+        if header & 1 == 0 {
+            let index = (header >> 1) as usize;
+            let len = self.strings.get(index).map(String::len).ok_or(invalid_reference(index, self.strings.len()))?;
+            self.spend(len)?;
+            return Ok(self.strings[index].clone())
+        }
+
+        let len = (header >> 1) as usize;
+        if len == 0 {
+            /* NOTE: Empty strings are never stored into the reference table. */
+            return Ok(String::new())
+        }
+
+        self.spend(len)?;
+        let s = String::from_utf8(buffer.get_bytes(len)?.to_vec()).map_err(invalid_string)?;
+        self.strings.push(s.clone());
+        Ok(s)
+    }
+
+    fn get_object(&mut self, header: u32) -> IOResult<Value> {
+        let index = (header >> 1) as usize;
+        let amount = self.objects.get(index).map(|(_, amount)| *amount).ok_or(invalid_reference(index, self.objects.len()))?;
+        self.spend(amount)?;
+        Ok(self.objects[index].0.clone())
+    }
+
+    fn push_object(&mut self, value: Value, amount: usize) -> IOResult<Value> {
+        self.spend(amount)?;
+        self.objects.push((value.clone(), amount + 1));
+        Ok(value)
+    }
+
+    fn reserve_object(&mut self) -> (usize, usize) {
+        /* NOTE: Referring an object which is still being decoded costs nothing, because it is resolved to Null. */
+        self.objects.push((Value::Null, 0));
+        (self.objects.len() - 1, self.spent)
+    }
+
+    fn fill_object(&mut self, (index, spent): (usize, usize), value: Value) -> Value {
+        self.objects[index] = (value.clone(), self.spent - spent + 1);
+        value
+    }
+
+    fn get_traits(&mut self, buffer: &mut ByteBuffer, header: u32) -> IOResult<Traits> {
+        if header & 0x02 == 0 {
+            let index = (header >> 2) as usize;
+            let traits = self.traits.get(index).ok_or(invalid_reference(index, self.traits.len()))?;
+            let amount = traits.get_class_name().len() + traits.get_sealed_names().iter().map(String::len).sum::<usize>();
+            self.spend(amount)?;
+            return Ok(self.traits[index].clone())
+        }
+
+        let externalizable = header & 0x04 != 0;
+        let dynamic = header & 0x08 != 0;
+        let class_name = self.get_string(buffer)?;
+        let mut sealed_names: Vec<String> = Vec::new();
+        for _ in 0..(header >> 4) {
+            sealed_names.push(self.get_string(buffer)?);
+        }
+
+        let traits = Traits::new(class_name, externalizable, dynamic, sealed_names);
+        self.traits.push(traits.clone());
+        Ok(traits)
+    }
+
+    fn decode_value(&mut self, buffer: &mut ByteBuffer) -> IOResult<Value> {
+        if self.depth >= MAX_DEPTH {
+            return Err(too_deep_value(MAX_DEPTH))
+        }
+
+        self.spend(1)?;
+        self.depth += 1;
+        let value = self.read_value(buffer);
+        self.depth -= 1;
+        value
+    }
+
+    fn read_value(&mut self, buffer: &mut ByteBuffer) -> IOResult<Value> {
+        let marker_byte = buffer.get_u8()?;
+        let marker: Marker = marker_byte.into();
+
+        match marker {
+            Marker::Undefined => Ok(Value::Undefined),
+            Marker::Null => Ok(Value::Null),
+            Marker::False => Ok(Value::Boolean(false)),
+            Marker::True => Ok(Value::Boolean(true)),
+            Marker::Integer => {
+                /* NOTE: Extends the sign bit of 29 bits. */
+                let n = get_u29(buffer)?;
+                Ok(Value::Integer(((n << 3) as i32) >> 3))
+            },
+            Marker::Double => buffer.get_f64().map(Value::Double),
+            Marker::String => self.get_string(buffer).map(Value::String),
+            Marker::XmlDocument | Marker::Xml => {
+                let header = get_u29(buffer)?;
+                if header & 1 == 0 {
+                    return self.get_object(header)
+                }
+
+                let len = (header >> 1) as usize;
+                let s = String::from_utf8(buffer.get_bytes(len)?.to_vec()).map_err(invalid_string)?;
+                let value = if let Marker::Xml = marker {
+                    Value::Xml(s)
+                } else {
+                    Value::XmlDocument(s)
+                };
+                self.push_object(value, len)
+            },
+            Marker::Date => {
+                let header = get_u29(buffer)?;
+                if header & 1 == 0 {
+                    return self.get_object(header)
+                }
+
+                let value = Value::Date(buffer.get_f64()?);
+                self.push_object(value, 0)
+            },
+            Marker::Array => {
+                let header = get_u29(buffer)?;
+                if header & 1 == 0 {
+                    return self.get_object(header)
+                }
+
+                let reserved = self.reserve_object();
+                let mut associative: Vec<(String, Value)> = Vec::new();
+                loop {
+                    let key = self.get_string(buffer)?;
+                    if key.is_empty() {
+                        break
+                    }
+                    associative.push((key, self.decode_value(buffer)?));
+                }
+                let mut dense: Vec<Value> = Vec::new();
+                for _ in 0..(header >> 1) {
+                    dense.push(self.decode_value(buffer)?);
+                }
+
+                Ok(self.fill_object(reserved, Value::Array(Array::new(associative, dense))))
+            },
+            Marker::Object => {
+                let header = get_u29(buffer)?;
+                if header & 1 == 0 {
+                    return self.get_object(header)
+                }
+
+                let traits = self.get_traits(buffer, header)?;
+                let reserved = self.reserve_object();
+                let object = if traits.is_externalizable() {
+                    match traits.get_class_name() {
+                        "flex.messaging.io.ArrayCollection" | "flex.messaging.io.ObjectProxy" => {
+                            let external_data = self.decode_value(buffer)?;
+                            Object::externalized(traits, external_data)
+                        },
+                        other => return Err(unsupported_externalizable(other.into()))
+                    }
+                } else {
+                    let mut object = Object::new(traits);
+                    for _ in 0..object.get_traits().get_sealed_names().len() {
+                        let value = self.decode_value(buffer)?;
+                        object.get_sealed_members_mut().push(value);
+                    }
+                    if object.get_traits().is_dynamic() {
+                        loop {
+                            let key = self.get_string(buffer)?;
+                            if key.is_empty() {
+                                break
+                            }
+                            let value = self.decode_value(buffer)?;
+                            object.get_dynamic_members_mut().push((key, value));
+                        }
+                    }
+                    object
+                };
+
+                Ok(self.fill_object(reserved, Value::Object(object)))
+            },
+            Marker::ByteArray => {
+                let header = get_u29(buffer)?;
+                if header & 1 == 0 {
+                    return self.get_object(header)
+                }
+
+                let len = (header >> 1) as usize;
+                let value = Value::ByteArray(buffer.get_bytes(len)?.to_vec());
+                self.push_object(value, len)
+            },
+            Marker::VectorInt | Marker::VectorUint | Marker::VectorDouble | Marker::VectorObject => {
+                let header = get_u29(buffer)?;
+                if header & 1 == 0 {
+                    return self.get_object(header)
+                }
+
+                let reserved = self.reserve_object();
+                let len = header >> 1;
+                let fixed = buffer.get_u8()? != 0;
+                let value = match marker {
+                    Marker::VectorInt => {
+                        self.spend(len as usize)?;
+                        let mut items: Vec<i32> = Vec::new();
+                        for _ in 0..len {
+                            items.push(buffer.get_u32_be()? as i32);
+                        }
+                        Value::VectorInt(Vector::new(fixed, items))
+                    },
+                    Marker::VectorUint => {
+                        self.spend(len as usize)?;
+                        let mut items: Vec<u32> = Vec::new();
+                        for _ in 0..len {
+                            items.push(buffer.get_u32_be()?);
+                        }
+                        Value::VectorUint(Vector::new(fixed, items))
+                    },
+                    Marker::VectorDouble => {
+                        self.spend(len as usize)?;
+                        let mut items: Vec<f64> = Vec::new();
+                        for _ in 0..len {
+                            items.push(buffer.get_f64()?);
+                        }
+                        Value::VectorDouble(Vector::new(fixed, items))
+                    },
+                    _ => {
+                        let type_name = self.get_string(buffer)?;
+                        let mut items: Vec<Value> = Vec::new();
+                        for _ in 0..len {
+                            items.push(self.decode_value(buffer)?);
+                        }
+                        Value::VectorObject(type_name, Vector::new(fixed, items))
+                    }
+                };
+
+                Ok(self.fill_object(reserved, value))
+            },
+            Marker::Dictionary => {
+                let header = get_u29(buffer)?;
+                if header & 1 == 0 {
+                    return self.get_object(header)
+                }
+
+                let reserved = self.reserve_object();
+                let weak_keys = buffer.get_u8()? != 0;
+                let mut entries: Vec<(Value, Value)> = Vec::new();
+                for _ in 0..(header >> 1) {
+                    let key = self.decode_value(buffer)?;
+                    let value = self.decode_value(buffer)?;
+                    entries.push((key, value));
+                }
+
+                Ok(self.fill_object(reserved, Value::Dictionary(Dictionary::new(weak_keys, entries))))
+            },
+            Marker::Other => Err(unknown_marker(marker_byte))
+        }
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug, Default)]
+struct WritingTables {
+    strings: HashMap<String, usize>,
+    traits: Vec<Traits>
+}
+
+#[doc(hidden)]
+impl WritingTables {
+    fn put_string(&mut self, buffer: &mut ByteBuffer, s: &str) {
+        if s.is_empty() {
+            put_inline_length(buffer, 0);
+            return
+        }
+
+        if let Some(index) = self.strings.get(s) {
+            put_u29(buffer, (*index as u32) << 1);
+            return
+        }
+
+        self.strings.insert(s.into(), self.strings.len());
+        put_inline_length(buffer, s.len());
+        buffer.put_bytes(s.as_bytes());
+    }
+
+    fn put_traits(&mut self, buffer: &mut ByteBuffer, traits: &Traits) {
+        if let Some(index) = self.traits.iter().position(|sent| sent == traits) {
+            put_u29(buffer, ((index as u32) << 2) | 0x01);
+            return
+        }
+
+        let header = ((traits.get_sealed_names().len() as u32) << 4)
+            | ((traits.is_dynamic() as u32) << 3)
+            | ((traits.is_externalizable() as u32) << 2)
+            | 0x03;
+        put_u29(buffer, header);
+        self.put_string(buffer, traits.get_class_name());
+        for sealed_name in traits.get_sealed_names() {
+            self.put_string(buffer, sealed_name);
+        }
+        self.traits.push(traits.clone());
+    }
+
+    fn encode_value(&mut self, buffer: &mut ByteBuffer, value: &Value) {
+        let marker = value.get_marker();
+        buffer.put_u8(marker.into());
+
+        match value {
+            Value::Undefined | Value::Null | Value::Boolean(_) => {},
+            Value::Integer(n) => if let Marker::Integer = marker {
+                put_u29(buffer, (*n as u32) & U29_MAX);
+            } else {
+                buffer.put_f64(*n as f64);
+            },
+            Value::Double(n) => buffer.put_f64(*n),
+            Value::String(s) => self.put_string(buffer, s),
+            Value::XmlDocument(s) | Value::Xml(s) => {
+                put_inline_length(buffer, s.len());
+                buffer.put_bytes(s.as_bytes());
+            },
+            Value::Date(milliseconds) => {
+                put_inline_length(buffer, 0);
+                buffer.put_f64(*milliseconds);
+            },
+            Value::Array(array) => {
+                put_inline_length(buffer, array.get_dense().len());
+                for (key, value) in array.get_associative() {
+                    self.put_string(buffer, key);
+                    self.encode_value(buffer, value);
+                }
+                self.put_string(buffer, "");
+                for value in array.get_dense() {
+                    self.encode_value(buffer, value);
+                }
+            },
+            Value::Object(object) => {
+                self.put_traits(buffer, object.get_traits());
+
+                if let Some(external_data) = object.get_external_data() {
+                    self.encode_value(buffer, external_data);
+                } else {
+                    for value in object.get_sealed_members() {
+                        self.encode_value(buffer, value);
+                    }
+                    if object.get_traits().is_dynamic() {
+                        for (key, value) in object.get_dynamic_members() {
+                            self.put_string(buffer, key);
+                            self.encode_value(buffer, value);
+                        }
+                        self.put_string(buffer, "");
+                    }
+                }
+            },
+            Value::ByteArray(bytes) => {
+                put_inline_length(buffer, bytes.len());
+                buffer.put_bytes(bytes);
+            },
+            Value::VectorInt(vector) => {
+                put_inline_length(buffer, vector.get_items().len());
+                buffer.put_u8(vector.is_fixed() as u8);
+                for n in vector.get_items() {
+                    buffer.put_u32_be(*n as u32);
+                }
+            },
+            Value::VectorUint(vector) => {
+                put_inline_length(buffer, vector.get_items().len());
+                buffer.put_u8(vector.is_fixed() as u8);
+                for n in vector.get_items() {
+                    buffer.put_u32_be(*n);
+                }
+            },
+            Value::VectorDouble(vector) => {
+                put_inline_length(buffer, vector.get_items().len());
+                buffer.put_u8(vector.is_fixed() as u8);
+                for n in vector.get_items() {
+                    buffer.put_f64(*n);
+                }
+            },
+            Value::VectorObject(type_name, vector) => {
+                put_inline_length(buffer, vector.get_items().len());
+                buffer.put_u8(vector.is_fixed() as u8);
+                self.put_string(buffer, type_name);
+                for value in vector.get_items() {
+                    self.encode_value(buffer, value);
+                }
+            },
+            Value::Dictionary(dictionary) => {
+                put_inline_length(buffer, dictionary.get_entries().len());
+                buffer.put_u8(dictionary.has_weak_keys() as u8);
+                for (key, value) in dictionary.get_entries() {
+                    self.encode_value(buffer, key);
+                    self.encode_value(buffer, value);
+                }
+            }
+        }
+    }
+}
+
+impl Decoder<Value> for ByteBuffer {
+    /// Decodes bytes into an AMF3 value.
+    ///
+    /// Reference tables are created newly every time this is called.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some value is shorter than its length.
+    ///
+    /// * [`InvalidString`]
+    ///
+    /// When some value is invalid for UTF-8 string.
+    ///
+    /// * [`InvalidReference`]
+    ///
+    /// When some reference points out of its table.
+    ///
+    /// * [`UnknownMarker`]
+    ///
+    /// When a marker byte is undefined in AMF3.
+    ///
+    /// * [`UnsupportedExternalizable`]
+    ///
+    /// When an externalizable object of unknown class is contained.
+    ///
+    /// * [`TooDeepValue`]
+    ///
+    /// When values nest deeper than [`MAX_DEPTH`].
+    ///
+    /// * [`TooLargeValue`]
+    ///
+    /// When the value expands beyond [`DECODING_BUDGET`], including values which are copied by references.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::amf::v3::{
+    ///         Marker,
+    ///         Value
+    ///     }
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::Integer as u8);
+    /// buffer.put_u8(0x7f);
+    /// assert_eq!(Value::Integer(127), Decoder::<Value>::decode(&mut buffer).unwrap());
+    ///
+    /// // A reference which points nothing.
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(Marker::String as u8);
+    /// buffer.put_u8(0x00);
+    /// assert!(Decoder::<Value>::decode(&mut buffer).is_err());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(0xff);
+    /// assert!(Decoder::<Value>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    /// [`InvalidString`]: crate::messages::amf::InvalidString
+    /// [`InvalidReference`]: crate::messages::amf::InvalidReference
+    /// [`UnknownMarker`]: crate::messages::amf::UnknownMarker
+    /// [`UnsupportedExternalizable`]: crate::messages::amf::UnsupportedExternalizable
+    /// [`TooDeepValue`]: crate::messages::amf::TooDeepValue
+    /// [`TooLargeValue`]: crate::messages::amf::TooLargeValue
+    fn decode(&mut self) -> IOResult<Value> {
+        ReadingTables::default().decode_value(self)
+    }
+}
+
+impl Encoder<Value> for ByteBuffer {
+    /// Encodes an AMF3 value into bytes.
+    ///
+    /// Strings and traits which appear twice or more are encoded as references.
+    ///
+    /// # Panics
+    ///
+    /// When some length exceeds the range of U29.
+    fn encode(&mut self, value: &Value) {
+        WritingTables::default().encode_value(self, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::messages::amf::{
+        TooDeepValue,
+        TooLargeValue
+    };
+    use super::*;
+
+    fn round_trip(value: &Value) -> Value {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(value);
+        let mut buffer: ByteBuffer = Vec::<u8>::from(buffer).into();
+        let decoded: Value = buffer.decode().unwrap();
+        assert_eq!(0, buffer.remained());
+        decoded
+    }
+
+    #[test]
+    fn u29_boundaries() {
+        for (n, len) in [(0x7f, 1), (0x80, 2), (0x3fff, 2), (0x4000, 3), (0x1fffff, 3), (0x200000, 4), (U29_MAX, 4)] {
+            let mut buffer = ByteBuffer::default();
+            put_u29(&mut buffer, n);
+            let bytes: Vec<u8> = buffer.into();
+            assert_eq!(len, bytes.len());
+            let mut buffer: ByteBuffer = bytes.into();
+            assert_eq!(n, get_u29(&mut buffer).unwrap())
+        }
+    }
+
+    #[test]
+    fn integer_sign_and_overflow() {
+        assert_eq!(Value::Integer(-1), round_trip(&Value::Integer(-1)));
+        assert_eq!(Value::Integer(INTEGER_MIN), round_trip(&Value::Integer(INTEGER_MIN)));
+        assert_eq!(Value::Integer(INTEGER_MAX), round_trip(&Value::Integer(INTEGER_MAX)));
+        assert_eq!(Value::Double((INTEGER_MAX as f64) + 1f64), round_trip(&Value::Integer(INTEGER_MAX + 1)))
+    }
+
+    #[test]
+    fn scalars() {
+        for value in [Value::Undefined, Value::Null, Value::Boolean(true), Value::Boolean(false), Value::Double(1.5), Value::Date(1700000000000f64), Value::from(""), Value::Xml("<a/>".into()), Value::XmlDocument("<b/>".into()), Value::ByteArray(vec![0, 1, 2])] {
+            assert_eq!(value, round_trip(&value))
+        }
+    }
+
+    #[test]
+    fn string_references() {
+        let array = Value::from(Array::from(vec![Value::from("live"), Value::from("live")]));
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&array);
+        let bytes: Vec<u8> = buffer.into();
+        // Marker, header, empty key, (marker, header, "live"), (marker, reference 0)
+        assert_eq!(3 + 6 + 2, bytes.len());
+        assert_eq!([Marker::String as u8, 0x00], bytes[9..]);
+        let mut buffer: ByteBuffer = bytes.into();
+        assert_eq!(array, buffer.decode().unwrap())
+    }
+
+    #[test]
+    fn object_with_traits() {
+        let traits = Traits::new("Point".into(), false, true, vec!["x".into(), "y".into()]);
+        let mut first = Object::new(traits.clone());
+        first.get_sealed_members_mut().extend([Value::Integer(1), Value::Integer(2)]);
+        first.get_dynamic_members_mut().push(("label".into(), Value::from("a")));
+        let mut second = Object::new(traits);
+        second.get_sealed_members_mut().extend([Value::Integer(3), Value::Integer(4)]);
+        let value = Value::from(Array::new(vec![("first".into(), first.into())], vec![second.into()]));
+        assert_eq!(value, round_trip(&value))
+    }
+
+    #[test]
+    fn object_references() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::Array as u8);
+        put_u29(&mut buffer, (2 << 1) | 1);
+        put_u29(&mut buffer, 1);
+        buffer.put_u8(Marker::ByteArray as u8);
+        put_u29(&mut buffer, (1 << 1) | 1);
+        buffer.put_u8(0xff);
+        // Index 0 is the array itself, index 1 is the byte array.
+        buffer.put_u8(Marker::ByteArray as u8);
+        put_u29(&mut buffer, 1 << 1);
+        let result: IOResult<Value> = buffer.decode();
+        assert!(result.is_ok());
+        let expected = Value::from(Array::from(vec![Value::ByteArray(vec![0xff]), Value::ByteArray(vec![0xff])]));
+        assert_eq!(expected, result.unwrap())
+    }
+
+    #[test]
+    fn externalizable_object() {
+        let traits = Traits::new("flex.messaging.io.ArrayCollection".into(), true, false, Vec::new());
+        let value = Value::from(Object::externalized(traits, Array::from(vec![Value::Integer(1)]).into()));
+        assert_eq!(value, round_trip(&value));
+
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::Object as u8);
+        put_u29(&mut buffer, 0x07);
+        let class_name = "com.example.Unknown";
+        put_u29(&mut buffer, ((class_name.len() as u32) << 1) | 1);
+        buffer.put_bytes(class_name.as_bytes());
+        assert!(Decoder::<Value>::decode(&mut buffer).is_err())
+    }
+
+    #[test]
+    fn vectors_and_dictionary() {
+        let values = [
+            Value::VectorInt(Vector::new(true, vec![-1, 0, 1])),
+            Value::VectorUint(Vector::from(vec![u32::MAX])),
+            Value::VectorDouble(Vector::from(vec![0.5])),
+            Value::VectorObject("String".into(), Vector::from(vec![Value::from("a")])),
+            Value::from(Dictionary::new(false, vec![(Value::Integer(1), Value::from("one")), (Value::from("two"), Value::Integer(2))]))
+        ];
+        for value in values {
+            assert_eq!(value, round_trip(&value))
+        }
+    }
+
+    #[test]
+    fn err_too_deep_value() {
+        let mut buffer = ByteBuffer::default();
+        for _ in 0..=MAX_DEPTH {
+            buffer.put_u8(Marker::Array as u8);
+            put_inline_length(&mut buffer, 1);
+            put_inline_length(&mut buffer, 0);
+        }
+        buffer.put_u8(Marker::Null as u8);
+        let result: IOResult<Value> = buffer.decode();
+        assert!(result.unwrap_err().get_ref().unwrap().is::<TooDeepValue>())
+    }
+
+    #[test]
+    fn err_too_large_value() {
+        /* NOTE: Every array refers the previous one twice, so that the last one expands exponentially. */
+        let levels = 20;
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(Marker::Array as u8);
+        put_inline_length(&mut buffer, levels + 1);
+        put_inline_length(&mut buffer, 0);
+        buffer.put_u8(Marker::Array as u8);
+        put_inline_length(&mut buffer, 1);
+        put_inline_length(&mut buffer, 0);
+        buffer.put_u8(Marker::String as u8);
+        put_inline_length(&mut buffer, 1024);
+        buffer.put_bytes(&[b'a'; 1024]);
+        for level in 0..levels {
+            buffer.put_u8(Marker::Array as u8);
+            put_inline_length(&mut buffer, 2);
+            put_inline_length(&mut buffer, 0);
+            for _ in 0..2 {
+                buffer.put_u8(Marker::Array as u8);
+                put_u29(&mut buffer, (level as u32 + 1) << 1);
+            }
+        }
+        let result: IOResult<Value> = buffer.decode();
+        assert!(result.unwrap_err().get_ref().unwrap().is::<TooLargeValue>())
+    }
+}
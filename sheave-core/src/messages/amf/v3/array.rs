@@ -0,0 +1,67 @@
+use super::Value;
+
+/// The array type of AMF3.
+///
+/// This consists of two parts:
+///
+/// * Associative part
+///
+/// Pairs of string keys and values, which are kept in received order.
+///
+/// * Dense part
+///
+/// Values which are indexed by ordinal numbers starting from 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::messages::amf::v3::{
+///     Array,
+///     Value
+/// };
+///
+/// let array = Array::new(vec![("name".into(), Value::from("sheave"))], vec![Value::Integer(0)]);
+/// assert_eq!(Some(&Value::from("sheave")), array.get("name"));
+/// assert_eq!(Value::Integer(0), array.get_dense()[0])
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Array {
+    associative: Vec<(String, Value)>,
+    dense: Vec<Value>
+}
+
+impl Array {
+    /// Constructs an array.
+    pub fn new(associative: Vec<(String, Value)>, dense: Vec<Value>) -> Self {
+        Self {
+            associative,
+            dense
+        }
+    }
+
+    /// Gets the associative part.
+    pub fn get_associative(&self) -> &[(String, Value)] {
+        &self.associative
+    }
+
+    /// Gets the dense part.
+    pub fn get_dense(&self) -> &[Value] {
+        &self.dense
+    }
+
+    /// Gets a value from the associative part.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.associative.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+}
+
+impl From<Vec<Value>> for Array {
+    fn from(dense: Vec<Value>) -> Self {
+        Self {
+            associative: Vec::new(),
+            dense
+        }
+    }
+}
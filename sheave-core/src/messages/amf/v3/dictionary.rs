@@ -0,0 +1,40 @@
+use super::Value;
+
+/// The dictionary type of AMF3.
+///
+/// Unlike the [`Object`], keys of this can be any AMF3 data type.
+/// Entries are kept in received order.
+///
+/// [`Object`]: super::Object
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dictionary {
+    weak_keys: bool,
+    entries: Vec<(Value, Value)>
+}
+
+impl Dictionary {
+    /// Constructs a dictionary.
+    pub fn new(weak_keys: bool, entries: Vec<(Value, Value)>) -> Self {
+        Self {
+            weak_keys,
+            entries
+        }
+    }
+
+    /// Indicates whether keys of this dictionary are weakly referenced.
+    pub fn has_weak_keys(&self) -> bool {
+        self.weak_keys
+    }
+
+    /// Gets entries of this dictionary.
+    pub fn get_entries(&self) -> &[(Value, Value)] {
+        &self.entries
+    }
+
+    /// Gets a value which is associated with specified key.
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.entries.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+}
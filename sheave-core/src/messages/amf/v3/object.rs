@@ -0,0 +1,114 @@
+use super::{
+    Traits,
+    Value
+};
+
+/// The object type of AMF3.
+///
+/// This consists of:
+///
+/// * [`Traits`]
+///
+/// The class definition of this object.
+///
+/// * Sealed members
+///
+/// Values which correspond to sealed member names in its traits, respectively.
+///
+/// * Dynamic members
+///
+/// Pairs of names and values which are added to this object at runtime.
+/// These are kept in received order.
+///
+/// * External data
+///
+/// A value which is serialized by an externalizable class itself.
+/// Currently, following Flex classes are readable because they serialize themselves as just one value:
+///
+/// * `flex.messaging.io.ArrayCollection`
+/// * `flex.messaging.io.ObjectProxy`
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::messages::amf::v3::{
+///     Object,
+///     Traits,
+///     Value
+/// };
+///
+/// let mut object = Object::new(Traits::new("Point".into(), false, true, vec!["x".into()]));
+/// object.get_sealed_members_mut().push(Value::Integer(1));
+/// object.get_dynamic_members_mut().push(("y".into(), Value::Integer(2)));
+/// assert_eq!(Some(&Value::Integer(1)), object.get("x"));
+/// assert_eq!(Some(&Value::Integer(2)), object.get("y"));
+/// assert!(object.get("z").is_none())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Object {
+    traits: Traits,
+    sealed_members: Vec<Value>,
+    dynamic_members: Vec<(String, Value)>,
+    external_data: Option<Box<Value>>
+}
+
+impl Object {
+    /// Constructs an empty object of specified traits.
+    pub fn new(traits: Traits) -> Self {
+        Self {
+            traits,
+            ..Default::default()
+        }
+    }
+
+    /// Constructs an externalized object.
+    pub fn externalized(traits: Traits, external_data: Value) -> Self {
+        Self {
+            traits,
+            external_data: Some(Box::new(external_data)),
+            ..Default::default()
+        }
+    }
+
+    /// Gets the traits of this object.
+    pub fn get_traits(&self) -> &Traits {
+        &self.traits
+    }
+
+    /// Gets values of sealed members.
+    pub fn get_sealed_members(&self) -> &[Value] {
+        &self.sealed_members
+    }
+
+    /// Gets mutable values of sealed members.
+    pub fn get_sealed_members_mut(&mut self) -> &mut Vec<Value> {
+        &mut self.sealed_members
+    }
+
+    /// Gets pairs of dynamic members.
+    pub fn get_dynamic_members(&self) -> &[(String, Value)] {
+        &self.dynamic_members
+    }
+
+    /// Gets mutable pairs of dynamic members.
+    pub fn get_dynamic_members_mut(&mut self) -> &mut Vec<(String, Value)> {
+        &mut self.dynamic_members
+    }
+
+    /// Gets the data which is serialized by an externalizable class.
+    pub fn get_external_data(&self) -> Option<&Value> {
+        self.external_data.as_deref()
+    }
+
+    /// Gets a value of a member whether it is sealed or dynamic.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.traits.get_sealed_names().iter()
+            .position(|sealed_name| sealed_name == name)
+            .and_then(|i| self.sealed_members.get(i))
+            .or_else(
+                || self.dynamic_members.iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value)
+            )
+    }
+}
@@ -0,0 +1,59 @@
+/// The class definition of AMF3's Object type.
+///
+/// This consists of:
+///
+/// * Class name (an empty string means an anonymous object)
+/// * Whether its object is externalizable
+/// * Whether its object can have dynamic members
+/// * Names of sealed members
+///
+/// Traits are sent once per a connection and then they are referred by their indices.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Traits {
+    class_name: String,
+    externalizable: bool,
+    dynamic: bool,
+    sealed_names: Vec<String>
+}
+
+impl Traits {
+    /// Constructs traits.
+    pub fn new(class_name: String, externalizable: bool, dynamic: bool, sealed_names: Vec<String>) -> Self {
+        Self {
+            class_name,
+            externalizable,
+            dynamic,
+            sealed_names
+        }
+    }
+
+    /// Constructs traits of anonymous and dynamic objects.
+    ///
+    /// This is same as ActionScript's `Object`.
+    pub fn anonymous() -> Self {
+        Self {
+            dynamic: true,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the class name.
+    pub fn get_class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Indicates whether objects of this class serialize themselves.
+    pub fn is_externalizable(&self) -> bool {
+        self.externalizable
+    }
+
+    /// Indicates whether objects of this class can have dynamic members.
+    pub fn is_dynamic(&self) -> bool {
+        self.dynamic
+    }
+
+    /// Gets names of sealed members.
+    pub fn get_sealed_names(&self) -> &[String] {
+        &self.sealed_names
+    }
+}
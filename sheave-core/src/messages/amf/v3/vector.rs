@@ -0,0 +1,44 @@
+/// The typed array of AMF3.
+///
+/// Its element type is one of:
+///
+/// * `i32` (Vector.&lt;int&gt;)
+/// * `u32` (Vector.&lt;uint&gt;)
+/// * `f64` (Vector.&lt;Number&gt;)
+/// * [`Value`] (Vector.&lt;Object&gt;, which has its type name additionally.)
+///
+/// [`Value`]: super::Value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vector<T> {
+    fixed: bool,
+    items: Vec<T>
+}
+
+impl<T> Vector<T> {
+    /// Constructs a vector.
+    pub fn new(fixed: bool, items: Vec<T>) -> Self {
+        Self {
+            fixed,
+            items
+        }
+    }
+
+    /// Indicates whether the length of this vector is fixed.
+    pub fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
+    /// Gets items in this vector.
+    pub fn get_items(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T> From<Vec<T>> for Vector<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self {
+            fixed: false,
+            items
+        }
+    }
+}
@@ -8,14 +8,13 @@ use std::{
     io::Result as IOResult,
     ops::{
         Deref,
-        DerefMut,
-        Index,
-        IndexMut
+        Index
     },
     rc::Rc,
     slice::SliceIndex,
     sync::Arc
 };
+use bytes::Bytes;
 use crate::{
     Decoder,
     Encoder,
@@ -29,11 +28,11 @@ use super::{
 
 /// The message to handle something audio data.
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Audio(Vec<u8>);
+pub struct Audio(Bytes);
 
 impl Audio {
     /// Constructs a new audio data.
-    pub fn new(bytes: Vec<u8>) -> Self {
+    pub fn new(bytes: Bytes) -> Self {
         Self(bytes)
     }
 
@@ -45,121 +44,127 @@ impl Audio {
 
 impl<const N: usize> From<[u8; N]> for Audio {
     fn from(bytes: [u8; N]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl<const N: usize> From<&[u8; N]> for Audio {
     fn from(bytes: &[u8; N]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl<const N: usize> From<&mut [u8; N]> for Audio {
     fn from(bytes: &mut [u8; N]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<&[u8]> for Audio {
     fn from(bytes: &[u8]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<&mut [u8]> for Audio {
     fn from(bytes: &mut [u8]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<&str> for Audio {
     fn from(s: &str) -> Self {
-        Self(s.into())
+        Self(Vec::<u8>::from(s).into())
     }
 }
 
 impl From<String> for Audio {
     fn from(s: String) -> Self {
-        Self(s.into())
+        Self(Vec::<u8>::from(s).into())
     }
 }
 
 impl From<CString> for Audio {
     fn from(s: CString) -> Self {
-        Self(s.into())
+        Self(Vec::<u8>::from(s).into())
     }
 }
 
 impl From<BinaryHeap<u8>> for Audio {
     fn from(bytes: BinaryHeap<u8>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<Audio> for BinaryHeap<u8> {
     fn from(audio: Audio) -> Self {
-        audio.0.into()
+        Vec::<u8>::from(audio.0).into()
     }
 }
 
 impl From<Box<[u8]>> for Audio {
     fn from(bytes: Box<[u8]>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<Audio> for Box<[u8]> {
     fn from(audio: Audio) -> Self {
-        audio.0.into()
+        Vec::<u8>::from(audio.0).into()
     }
 }
 
 impl<'a> From<Cow<'a, [u8]>> for Audio {
     fn from(bytes: Cow<'a, [u8]>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl<'a> From<Audio> for Cow<'a, [u8]> {
     fn from(audio: Audio) -> Self {
-        audio.0.into()
+        Vec::<u8>::from(audio.0).into()
     }
 }
 
 impl<'a> From<&'a Audio> for Cow<'a, [u8]> {
     fn from(audio: &'a Audio) -> Self {
-        Cow::from(&audio.0)
+        Cow::from(&audio.0[..])
     }
 }
 
 impl From<VecDeque<u8>> for Audio {
     fn from(bytes: VecDeque<u8>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<Audio> for VecDeque<u8> {
     fn from(audio: Audio) -> Self {
-        audio.0.into()
+        Vec::<u8>::from(audio.0).into()
     }
 }
 
-impl From<Audio> for Vec<u8> {
+impl From<Audio> for Bytes {
     fn from(audio: Audio) -> Self {
         audio.0
     }
 }
 
-impl From<Audio> for Rc<[u8]> {
+impl From<Audio> for Vec<u8> {
     fn from(audio: Audio) -> Self {
         audio.0.into()
     }
 }
 
+impl From<Audio> for Rc<[u8]> {
+    fn from(audio: Audio) -> Self {
+        Vec::<u8>::from(audio.0).into()
+    }
+}
+
 impl From<Audio> for Arc<[u8]> {
     fn from(audio: Audio) -> Self {
-        audio.0.into()
+        Vec::<u8>::from(audio.0).into()
     }
 }
 
@@ -167,99 +172,87 @@ impl<I: SliceIndex<[u8]>> Index<I> for Audio {
     type Output = I::Output;
 
     fn index(&self, index: I) -> &Self::Output {
-        self.0.index(index)
-    }
-}
-
-impl<I: SliceIndex<[u8]>> IndexMut<I> for Audio {
-    fn index_mut(&mut self, index: I) -> &mut Self::Output {
-        self.0.index_mut(index)
+        self.0.as_ref().index(index)
     }
 }
 
 impl Deref for Audio {
-    type Target = Vec<u8>;
+    type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl DerefMut for Audio {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
 impl<const N: usize> PartialEq<[u8; N]> for Audio {
     fn eq(&self, other: &[u8; N]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl<const N: usize> PartialEq<&[u8; N]> for Audio {
     fn eq(&self, other: &&[u8; N]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<[u8]> for Audio {
     fn eq(&self, other: &[u8]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(other)
     }
 }
 
 impl PartialEq<Audio> for [u8] {
     fn eq(&self, other: &Audio) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<&[u8]> for Audio {
     fn eq(&self, other: &&[u8]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<Audio> for &[u8] {
     fn eq(&self, other: &Audio) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<&mut [u8]> for Audio {
     fn eq(&self, other: &&mut [u8]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<Audio> for &mut [u8] {
     fn eq(&self, other: &Audio) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Vec<u8>> for Audio {
     fn eq(&self, other: &Vec<u8>) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<Audio> for Vec<u8> {
     fn eq(&self, other: &Audio) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Audio> for Cow<'_, [u8]> {
     fn eq(&self, other: &Audio) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Audio> for VecDeque<u8> {
     fn eq(&self, other: &Audio) -> bool {
-        self.eq(&other.0)
+        self.eq(&other.0.as_ref())
     }
 }
 
@@ -271,7 +264,7 @@ impl ChunkData for Audio {
 impl Decoder<Audio> for ByteBuffer {
     fn decode(&mut self) -> IOResult<Audio> {
         let remained = self.remained();
-        self.get_bytes(remained).map(|bytes| Audio::new(bytes.to_vec()))
+        self.get_shared_bytes(remained).map(Audio::new)
     }
 }
 
@@ -0,0 +1,117 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to tell that the buffer of the stream has been empty, to a client.
+///
+/// Following format is required.
+///
+/// |Event Data|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Message ID|4|The message ID which is same as contained in [`createStream`].|
+///
+/// [`createStream`]: crate::messages::CreateStream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferEmpty(u32);
+
+impl BufferEmpty {
+    /// Constructs a BufferEmpty event.
+    pub fn new(message_id: u32) -> Self {
+        Self(message_id)
+    }
+
+    /// Gets the message id which this event has.
+    pub fn get_message_id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<BufferEmpty> for u32 {
+    fn from(buffer_empty: BufferEmpty) -> Self {
+        buffer_empty.0
+    }
+}
+
+impl ChunkData for BufferEmpty {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for BufferEmpty {
+    const EVENT_TYPE: EventType = EventType::BufferEmpty;
+}
+
+impl Decoder<BufferEmpty> for ByteBuffer {
+    /// Decodes bytes into a BufferEmpty event.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::BufferEmpty
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u32_be(u32::default());
+    /// assert!(Decoder::<BufferEmpty>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<BufferEmpty>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<BufferEmpty> {
+        self.get_u32_be().map(BufferEmpty)
+    }
+}
+
+impl Encoder<BufferEmpty> for ByteBuffer {
+    /// Encodes a BufferEmpty event into bytes.
+    fn encode(&mut self, buffer_empty: &BufferEmpty) {
+        self.put_u32_be(buffer_empty.get_message_id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_buffer_empty() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u32_be(u32::default());
+        let result: IOResult<BufferEmpty> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = BufferEmpty::new(u32::default());
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_buffer_empty() {
+        let mut buffer = ByteBuffer::default();
+        let expected_message_id = u32::default();
+        let expected = BufferEmpty::new(expected_message_id);
+        buffer.encode(&expected);
+        let actual_message_id = buffer.get_u32_be().unwrap();
+        assert_eq!(expected_message_id, actual_message_id)
+    }
+}
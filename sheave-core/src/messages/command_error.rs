@@ -13,7 +13,7 @@ use super::{
 };
 
 /// The response message that some command failed.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CommandError(Object);
 
 impl CommandError {
@@ -11,8 +11,11 @@
 /// |`PeerBandwidth`|`6`|
 /// |`Audio`|`8`|
 /// |`Video`|`9`|
+/// |`Amf3Data`|`15`|
+/// |`Amf3Command`|`17`|
 /// |`Data`|`18`|
 /// |`Command`|`20`|
+/// |`Aggregate`|`22`|
 /// |`Other`|other numbers|
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,8 +27,11 @@ pub enum MessageType {
     PeerBandwidth,
     Audio = 8,
     Video = 9,
+    Amf3Data = 15,
+    Amf3Command = 17,
     Data = 18,
     Command = 20,
+    Aggregate = 22,
     Other = 0xff
 }
 
@@ -41,8 +47,11 @@ impl From<u8> for MessageType {
             6 => PeerBandwidth,
             8 => Audio,
             9 => Video,
+            15 => Amf3Data,
+            17 => Amf3Command,
             18 => Data,
             20 => Command,
+            22 => Aggregate,
             _ => Other
         }
     }
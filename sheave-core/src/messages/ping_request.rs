@@ -0,0 +1,119 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to test whether the peer is reachable.
+///
+/// The receiver is required to return a [`PingResponse`] which contains the same timestamp.
+///
+/// Following format is required.
+///
+/// |Event Data|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Timestamp|4|The local time of the sender (in milliseconds).|
+///
+/// [`PingResponse`]: crate::messages::PingResponse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingRequest(u32);
+
+impl PingRequest {
+    /// Constructs a PingRequest event.
+    pub fn new(timestamp: u32) -> Self {
+        Self(timestamp)
+    }
+
+    /// Gets the timestamp which this event has.
+    pub fn get_timestamp(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<PingRequest> for u32 {
+    fn from(ping_request: PingRequest) -> Self {
+        ping_request.0
+    }
+}
+
+impl ChunkData for PingRequest {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for PingRequest {
+    const EVENT_TYPE: EventType = EventType::PingRequest;
+}
+
+impl Decoder<PingRequest> for ByteBuffer {
+    /// Decodes bytes into a PingRequest event.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::PingRequest
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u32_be(u32::default());
+    /// assert!(Decoder::<PingRequest>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<PingRequest>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<PingRequest> {
+        self.get_u32_be().map(PingRequest)
+    }
+}
+
+impl Encoder<PingRequest> for ByteBuffer {
+    /// Encodes a PingRequest event into bytes.
+    fn encode(&mut self, ping_request: &PingRequest) {
+        self.put_u32_be(ping_request.get_timestamp());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_ping_request() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u32_be(u32::default());
+        let result: IOResult<PingRequest> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = PingRequest::new(u32::default());
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_ping_request() {
+        let mut buffer = ByteBuffer::default();
+        let expected_timestamp = u32::default();
+        let expected = PingRequest::new(expected_timestamp);
+        buffer.encode(&expected);
+        let actual_timestamp = buffer.get_u32_be().unwrap();
+        assert_eq!(expected_timestamp, actual_timestamp)
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to answer a [`PingRequest`].
+///
+/// Following format is required.
+///
+/// |Event Data|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Timestamp|4|The timestamp which has been received in the PingRequest.|
+///
+/// [`PingRequest`]: crate::messages::PingRequest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingResponse(u32);
+
+impl PingResponse {
+    /// Constructs a PingResponse event.
+    pub fn new(timestamp: u32) -> Self {
+        Self(timestamp)
+    }
+
+    /// Gets the timestamp which this event has.
+    pub fn get_timestamp(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<PingResponse> for u32 {
+    fn from(ping_response: PingResponse) -> Self {
+        ping_response.0
+    }
+}
+
+impl ChunkData for PingResponse {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for PingResponse {
+    const EVENT_TYPE: EventType = EventType::PingResponse;
+}
+
+impl Decoder<PingResponse> for ByteBuffer {
+    /// Decodes bytes into a PingResponse event.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::PingResponse
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u32_be(u32::default());
+    /// assert!(Decoder::<PingResponse>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<PingResponse>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<PingResponse> {
+        self.get_u32_be().map(PingResponse)
+    }
+}
+
+impl Encoder<PingResponse> for ByteBuffer {
+    /// Encodes a PingResponse event into bytes.
+    fn encode(&mut self, ping_response: &PingResponse) {
+        self.put_u32_be(ping_response.get_timestamp());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_ping_response() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u32_be(u32::default());
+        let result: IOResult<PingResponse> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = PingResponse::new(u32::default());
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_ping_response() {
+        let mut buffer = ByteBuffer::default();
+        let expected_timestamp = u32::default();
+        let expected = PingResponse::new(expected_timestamp);
+        buffer.encode(&expected);
+        let actual_timestamp = buffer.get_u32_be().unwrap();
+        assert_eq!(expected_timestamp, actual_timestamp)
+    }
+}
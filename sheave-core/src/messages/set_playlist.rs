@@ -16,7 +16,7 @@ use crate::{
 };
 
 /// The command to tell the Playlist of streams.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SetPlaylist(EcmaArray);
 
 impl SetPlaylist {
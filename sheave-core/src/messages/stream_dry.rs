@@ -0,0 +1,117 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to tell that no more data is on the stream for now, to a client.
+///
+/// Following format is required.
+///
+/// |Event Data|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Message ID|4|The message ID which is same as contained in [`createStream`].|
+///
+/// [`createStream`]: crate::messages::CreateStream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamDry(u32);
+
+impl StreamDry {
+    /// Constructs a StreamDry event.
+    pub fn new(message_id: u32) -> Self {
+        Self(message_id)
+    }
+
+    /// Gets the message id which this event has.
+    pub fn get_message_id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<StreamDry> for u32 {
+    fn from(stream_dry: StreamDry) -> Self {
+        stream_dry.0
+    }
+}
+
+impl ChunkData for StreamDry {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for StreamDry {
+    const EVENT_TYPE: EventType = EventType::StreamDry;
+}
+
+impl Decoder<StreamDry> for ByteBuffer {
+    /// Decodes bytes into a StreamDry event.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::StreamDry
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u32_be(u32::default());
+    /// assert!(Decoder::<StreamDry>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<StreamDry>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<StreamDry> {
+        self.get_u32_be().map(StreamDry)
+    }
+}
+
+impl Encoder<StreamDry> for ByteBuffer {
+    /// Encodes a StreamDry event into bytes.
+    fn encode(&mut self, stream_dry: &StreamDry) {
+        self.put_u32_be(stream_dry.get_message_id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_stream_dry() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u32_be(u32::default());
+        let result: IOResult<StreamDry> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = StreamDry::new(u32::default());
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_stream_dry() {
+        let mut buffer = ByteBuffer::default();
+        let expected_message_id = u32::default();
+        let expected = StreamDry::new(expected_message_id);
+        buffer.encode(&expected);
+        let actual_message_id = buffer.get_u32_be().unwrap();
+        assert_eq!(expected_message_id, actual_message_id)
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to tell that the playback of the stream has finished, to a client.
+///
+/// Following format is required.
+///
+/// |Event Data|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Message ID|4|The message ID which is same as contained in [`createStream`].|
+///
+/// [`createStream`]: crate::messages::CreateStream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamEof(u32);
+
+impl StreamEof {
+    /// Constructs a StreamEof event.
+    pub fn new(message_id: u32) -> Self {
+        Self(message_id)
+    }
+
+    /// Gets the message id which this event has.
+    pub fn get_message_id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<StreamEof> for u32 {
+    fn from(stream_eof: StreamEof) -> Self {
+        stream_eof.0
+    }
+}
+
+impl ChunkData for StreamEof {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for StreamEof {
+    const EVENT_TYPE: EventType = EventType::StreamEof;
+}
+
+impl Decoder<StreamEof> for ByteBuffer {
+    /// Decodes bytes into a StreamEof event.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::StreamEof
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u32_be(u32::default());
+    /// assert!(Decoder::<StreamEof>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<StreamEof>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<StreamEof> {
+        self.get_u32_be().map(StreamEof)
+    }
+}
+
+impl Encoder<StreamEof> for ByteBuffer {
+    /// Encodes a StreamEof event into bytes.
+    fn encode(&mut self, stream_eof: &StreamEof) {
+        self.put_u32_be(stream_eof.get_message_id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_stream_eof() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u32_be(u32::default());
+        let result: IOResult<StreamEof> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = StreamEof::new(u32::default());
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_stream_eof() {
+        let mut buffer = ByteBuffer::default();
+        let expected_message_id = u32::default();
+        let expected = StreamEof::new(expected_message_id);
+        buffer.encode(&expected);
+        let actual_message_id = buffer.get_u32_be().unwrap();
+        assert_eq!(expected_message_id, actual_message_id)
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to tell that the stream is a recorded one, to a client.
+///
+/// Following format is required.
+///
+/// |Event Data|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Message ID|4|The message ID which is same as contained in [`createStream`].|
+///
+/// [`createStream`]: crate::messages::CreateStream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamIsRecorded(u32);
+
+impl StreamIsRecorded {
+    /// Constructs a StreamIsRecorded event.
+    pub fn new(message_id: u32) -> Self {
+        Self(message_id)
+    }
+
+    /// Gets the message id which this event has.
+    pub fn get_message_id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<StreamIsRecorded> for u32 {
+    fn from(stream_is_recorded: StreamIsRecorded) -> Self {
+        stream_is_recorded.0
+    }
+}
+
+impl ChunkData for StreamIsRecorded {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for StreamIsRecorded {
+    const EVENT_TYPE: EventType = EventType::StreamIsRecorded;
+}
+
+impl Decoder<StreamIsRecorded> for ByteBuffer {
+    /// Decodes bytes into a StreamIsRecorded event.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::StreamIsRecorded
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u32_be(u32::default());
+    /// assert!(Decoder::<StreamIsRecorded>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<StreamIsRecorded>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<StreamIsRecorded> {
+        self.get_u32_be().map(StreamIsRecorded)
+    }
+}
+
+impl Encoder<StreamIsRecorded> for ByteBuffer {
+    /// Encodes a StreamIsRecorded event into bytes.
+    fn encode(&mut self, stream_is_recorded: &StreamIsRecorded) {
+        self.put_u32_be(stream_is_recorded.get_message_id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_stream_is_recorded() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u32_be(u32::default());
+        let result: IOResult<StreamIsRecorded> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = StreamIsRecorded::new(u32::default());
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_stream_is_recorded() {
+        let mut buffer = ByteBuffer::default();
+        let expected_message_id = u32::default();
+        let expected = StreamIsRecorded::new(expected_message_id);
+        buffer.encode(&expected);
+        let actual_message_id = buffer.get_u32_be().unwrap();
+        assert_eq!(expected_message_id, actual_message_id)
+    }
+}
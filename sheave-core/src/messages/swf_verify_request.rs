@@ -0,0 +1,80 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to request the SWF verification, to a client.
+///
+/// This event has no event data.
+/// The client is required to return a [`SwfVerifyResponse`].
+///
+/// [`SwfVerifyResponse`]: crate::messages::SwfVerifyResponse
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SwfVerifyRequest;
+
+impl ChunkData for SwfVerifyRequest {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for SwfVerifyRequest {
+    const EVENT_TYPE: EventType = EventType::SwfVerifyRequest;
+}
+
+impl Decoder<SwfVerifyRequest> for ByteBuffer {
+    /// Decodes bytes into a SwfVerifyRequest event.
+    ///
+    /// Note this event has no field, therefore this always succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::SwfVerifyRequest
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<SwfVerifyRequest>::decode(&mut buffer).is_ok())
+    /// ```
+    fn decode(&mut self) -> IOResult<SwfVerifyRequest> {
+        Ok(SwfVerifyRequest)
+    }
+}
+
+impl Encoder<SwfVerifyRequest> for ByteBuffer {
+    /// Encodes a SwfVerifyRequest event into bytes.
+    ///
+    /// Note this writes nothing.
+    fn encode(&mut self, _: &SwfVerifyRequest) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_swf_verify_request() {
+        let mut buffer = ByteBuffer::default();
+        let result: IOResult<SwfVerifyRequest> = buffer.decode();
+        assert!(result.is_ok());
+        assert_eq!(SwfVerifyRequest, result.unwrap())
+    }
+
+    #[test]
+    fn encode_swf_verify_request() {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&SwfVerifyRequest);
+        assert_eq!(0, buffer.remained())
+    }
+}
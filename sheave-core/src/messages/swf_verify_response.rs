@@ -0,0 +1,146 @@
+use std::io::Result as IOResult;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    messages::{
+        Channel,
+        ChunkData,
+        EventType,
+        UserControl,
+        headers::MessageType,
+    }
+};
+
+/// The event to answer a [`SwfVerifyRequest`], to a server.
+///
+/// Following format is required.
+///
+/// |Event Data|Length (in bytes)|Description|
+/// | :- | -: | :- |
+/// |Version|1|Always `1`.|
+/// |Flags|1|Always `1`.|
+/// |SWF Size|4|The size of the decompressed SWF file.|
+/// |SWF Size|4|Same as above.|
+/// |SWF Hash|32|The HMAC-SHA256 of the decompressed SWF file.|
+///
+/// [`SwfVerifyRequest`]: crate::messages::SwfVerifyRequest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwfVerifyResponse {
+    swf_size: u32,
+    swf_hash: [u8; 32]
+}
+
+impl SwfVerifyResponse {
+    /// Constructs a SwfVerifyResponse event.
+    pub fn new(swf_size: u32, swf_hash: [u8; 32]) -> Self {
+        Self { swf_size, swf_hash }
+    }
+
+    /// Gets the size of the decompressed SWF file.
+    pub fn get_swf_size(&self) -> u32 {
+        self.swf_size
+    }
+
+    /// Gets the HMAC-SHA256 of the decompressed SWF file.
+    pub fn get_swf_hash(&self) -> &[u8; 32] {
+        &self.swf_hash
+    }
+}
+
+impl ChunkData for SwfVerifyResponse {
+    const CHANNEL: Channel = Channel::Network;
+    const MESSAGE_TYPE: MessageType = MessageType::UserControl;
+}
+
+impl UserControl for SwfVerifyResponse {
+    const EVENT_TYPE: EventType = EventType::SwfVerifyResponse;
+}
+
+impl Decoder<SwfVerifyResponse> for ByteBuffer {
+    /// Decodes bytes into a SwfVerifyResponse event.
+    ///
+    /// # Errors
+    ///
+    /// * [`InsufficientBufferLength`]
+    ///
+    /// When some field misses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sheave_core::{
+    ///     ByteBuffer,
+    ///     Decoder,
+    ///     messages::SwfVerifyResponse
+    /// };
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// buffer.put_u8(1);
+    /// buffer.put_u8(1);
+    /// buffer.put_u32_be(u32::default());
+    /// buffer.put_u32_be(u32::default());
+    /// buffer.put_bytes(&[0u8; 32]);
+    /// assert!(Decoder::<SwfVerifyResponse>::decode(&mut buffer).is_ok());
+    ///
+    /// let mut buffer = ByteBuffer::default();
+    /// assert!(Decoder::<SwfVerifyResponse>::decode(&mut buffer).is_err())
+    /// ```
+    ///
+    /// [`InsufficientBufferLength`]: crate::byte_buffer::InsufficientBufferLength
+    fn decode(&mut self) -> IOResult<SwfVerifyResponse> {
+        /* NOTE: Both version and flags are always 1. */
+        self.get_u8()?;
+        self.get_u8()?;
+        let swf_size = self.get_u32_be()?;
+        self.get_u32_be()?;
+        let mut swf_hash: [u8; 32] = [0; 32];
+        swf_hash.copy_from_slice(self.get_bytes(32)?);
+        Ok(SwfVerifyResponse { swf_size, swf_hash })
+    }
+}
+
+impl Encoder<SwfVerifyResponse> for ByteBuffer {
+    /// Encodes a SwfVerifyResponse event into bytes.
+    fn encode(&mut self, swf_verify_response: &SwfVerifyResponse) {
+        self.put_u8(1);
+        self.put_u8(1);
+        self.put_u32_be(swf_verify_response.get_swf_size());
+        self.put_u32_be(swf_verify_response.get_swf_size());
+        self.put_bytes(swf_verify_response.get_swf_hash());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_swf_verify_response() {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(1);
+        buffer.put_u8(1);
+        buffer.put_u32_be(u32::default());
+        buffer.put_u32_be(u32::default());
+        buffer.put_bytes(&[0u8; 32]);
+        let result: IOResult<SwfVerifyResponse> = buffer.decode();
+        assert!(result.is_ok());
+        let actual = result.unwrap();
+        let expected = SwfVerifyResponse::new(u32::default(), [0u8; 32]);
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn encode_swf_verify_response() {
+        let mut buffer = ByteBuffer::default();
+        let expected_swf_size = u32::default();
+        let expected_swf_hash = [0u8; 32];
+        let expected = SwfVerifyResponse::new(expected_swf_size, expected_swf_hash);
+        buffer.encode(&expected);
+        assert_eq!(1, buffer.get_u8().unwrap());
+        assert_eq!(1, buffer.get_u8().unwrap());
+        assert_eq!(expected_swf_size, buffer.get_u32_be().unwrap());
+        assert_eq!(expected_swf_size, buffer.get_u32_be().unwrap());
+        assert_eq!(&expected_swf_hash, buffer.get_bytes(32).unwrap())
+    }
+}
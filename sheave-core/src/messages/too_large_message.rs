@@ -0,0 +1,35 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some message exceeds the maximum size which is allowed to receive.
+#[derive(Debug)]
+pub struct TooLargeMessage {
+    message_length: u32,
+    max_message_size: u32
+}
+
+impl Display for TooLargeMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Message is too large. message length: {}, max message size: {}", self.message_length, self.max_message_size)
+    }
+}
+
+impl Error for TooLargeMessage {}
+
+/// A utility function of constructing a `TooLargeMessage` error.
+pub fn too_large_message(message_length: u32, max_message_size: u32) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        TooLargeMessage { message_length, max_message_size }
+    )
+}
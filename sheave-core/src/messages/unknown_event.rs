@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some user control event is unknown/undefined, or isn't supported by the receiver.
+#[derive(Debug)]
+pub struct UnknownEvent(u16);
+
+impl Display for UnknownEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown user control event: {}", self.0)
+    }
+}
+
+impl Error for UnknownEvent {}
+
+/// A utility function of constructing an `UnknownEvent` error.
+pub fn unknown_event(event_type: u16) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownEvent(event_type)
+    )
+}
@@ -0,0 +1,33 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+use super::headers::MessageType;
+
+/// An error that some message is unknown/undefined, or isn't supported by the receiver.
+#[derive(Debug)]
+pub struct UnknownMessage(MessageType);
+
+impl Display for UnknownMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Unknown message: {:?}", self.0)
+    }
+}
+
+impl Error for UnknownMessage {}
+
+/// A utility function of constructing an `UnknownMessage` error.
+pub fn unknown_message(message_type: MessageType) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownMessage(message_type)
+    )
+}
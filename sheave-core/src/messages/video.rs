@@ -8,14 +8,13 @@ use std::{
     io::Result as IOResult,
     ops::{
         Deref,
-        DerefMut,
-        Index,
-        IndexMut
+        Index
     },
     rc::Rc,
     slice::SliceIndex,
     sync::Arc
 };
+use bytes::Bytes;
 use crate::{
     Decoder,
     Encoder,
@@ -29,10 +28,10 @@ use super::{
 
 /// The message to handle something video data.
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Video(Vec<u8>);
+pub struct Video(Bytes);
 
 impl Video {
-    pub fn new(bytes: Vec<u8>) -> Self {
+    pub fn new(bytes: Bytes) -> Self {
         Self(bytes)
     }
 
@@ -43,121 +42,127 @@ impl Video {
 
 impl<const N: usize> From<[u8; N]> for Video {
     fn from(bytes: [u8; N]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl<const N: usize> From<&[u8; N]> for Video {
     fn from(bytes: &[u8; N]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl<const N: usize> From<&mut [u8; N]> for Video {
     fn from(bytes: &mut [u8; N]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<&[u8]> for Video {
     fn from(bytes: &[u8]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<&mut [u8]> for Video {
     fn from(bytes: &mut [u8]) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<&str> for Video {
     fn from(s: &str) -> Self {
-        Self(s.into())
+        Self(Vec::<u8>::from(s).into())
     }
 }
 
 impl From<String> for Video {
     fn from(s: String) -> Self {
-        Self(s.into())
+        Self(Vec::<u8>::from(s).into())
     }
 }
 
 impl From<CString> for Video {
     fn from(s: CString) -> Self {
-        Self(s.into())
+        Self(Vec::<u8>::from(s).into())
     }
 }
 
 impl<'a> From<Cow<'a, [u8]>> for Video {
     fn from(bytes: Cow<'a, [u8]>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<Box<[u8]>> for Video {
     fn from(bytes: Box<[u8]>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<VecDeque<u8>> for Video {
     fn from(bytes: VecDeque<u8>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
 impl From<BinaryHeap<u8>> for Video {
     fn from(bytes: BinaryHeap<u8>) -> Self {
-        Self(bytes.into())
+        Self(Vec::<u8>::from(bytes).into())
     }
 }
 
-impl From<Video> for Vec<u8> {
+impl From<Video> for Bytes {
     fn from(video: Video) -> Self {
         video.0
     }
 }
 
-impl<'a> From<Video> for Cow<'a, [u8]> {
+impl From<Video> for Vec<u8> {
     fn from(video: Video) -> Self {
         video.0.into()
     }
 }
 
+impl<'a> From<Video> for Cow<'a, [u8]> {
+    fn from(video: Video) -> Self {
+        Vec::<u8>::from(video.0).into()
+    }
+}
+
 impl<'a> From<&'a Video> for Cow<'a, [u8]> {
     fn from(video: &'a Video) -> Self {
-        Cow::from(&video.0)
+        Cow::from(&video.0[..])
     }
 }
 
 impl From<Video> for Box<[u8]> {
     fn from(video: Video) -> Self {
-        video.0.into()
+        Vec::<u8>::from(video.0).into()
     }
 }
 
 impl From<Video> for VecDeque<u8> {
     fn from(video: Video) -> Self {
-        video.0.into()
+        Vec::<u8>::from(video.0).into()
     }
 }
 
 impl From<Video> for BinaryHeap<u8> {
     fn from(video: Video) -> Self {
-        video.0.into()
+        Vec::<u8>::from(video.0).into()
     }
 }
 
 impl From<Video> for Rc<[u8]> {
     fn from(video: Video) -> Self {
-        video.0.into()
+        Vec::<u8>::from(video.0).into()
     }
 }
 
 impl From<Video> for Arc<[u8]> {
     fn from(video: Video) -> Self {
-        video.0.into()
+        Vec::<u8>::from(video.0).into()
     }
 }
 
@@ -165,99 +170,87 @@ impl<I: SliceIndex<[u8]>> Index<I> for Video {
     type Output = I::Output;
 
     fn index(&self, index: I) -> &Self::Output {
-        self.0.index(index)
-    }
-}
-
-impl<I: SliceIndex<[u8]>> IndexMut<I> for Video {
-    fn index_mut(&mut self, index: I) -> &mut Self::Output {
-        self.0.index_mut(index)
+        self.0.as_ref().index(index)
     }
 }
 
 impl Deref for Video {
-    type Target = Vec<u8>;
+    type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl DerefMut for Video {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
 impl<const N: usize> PartialEq<[u8; N]> for Video {
     fn eq(&self, other: &[u8; N]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl<const N: usize> PartialEq<&[u8; N]> for Video {
     fn eq(&self, other: &&[u8; N]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<[u8]> for Video {
     fn eq(&self, other: &[u8]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(other)
     }
 }
 
 impl PartialEq<&[u8]> for Video {
     fn eq(&self, other: &&[u8]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<&mut [u8]> for Video {
     fn eq(&self, other: &&mut [u8]) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<Vec<u8>> for Video {
     fn eq(&self, other: &Vec<u8>) -> bool {
-        self.0.eq(other)
+        self.0[..].eq(&other[..])
     }
 }
 
 impl PartialEq<Video> for [u8] {
     fn eq(&self, other: &Video) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Video> for &[u8] {
     fn eq(&self, other: &Video) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Video> for &mut [u8] {
     fn eq(&self, other: &Video) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Video> for Cow<'_, [u8]> {
     fn eq(&self, other: &Video) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Video> for Vec<u8> {
     fn eq(&self, other: &Video) -> bool {
-        self.eq(&other.0)
+        self[..].eq(&other.0[..])
     }
 }
 
 impl PartialEq<Video> for VecDeque<u8> {
     fn eq(&self, other: &Video) -> bool {
-        self.eq(&other.0)
+        self.eq(&other.0.as_ref())
     }
 }
 
@@ -269,7 +262,7 @@ impl ChunkData for Video {
 impl Decoder<Video> for ByteBuffer {
     fn decode(&mut self) -> IOResult<Video> {
         let remained = self.remained();
-        self.get_bytes(remained).map(|bytes| Video::new(bytes.to_vec()))
+        self.get_shared_bytes(remained).map(Video::new)
     }
 }
 
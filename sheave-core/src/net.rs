@@ -1,4 +1,7 @@
 pub mod rtmp;
+pub mod rtmpe;
+pub mod rtmps;
+pub mod rtmpt;
 mod await_until_receiving;
 mod try_read_after;
 
@@ -54,12 +57,18 @@ pub trait RtmpReadExt: AsyncRead {
         await_until_receiving(self)
     }
 
-    /// Makes a stream sleeping during specified duration.
+    /// Makes a stream awaiting some message only during specified duration.
+    ///
+    /// If nothing arrives until the duration elapses, reading fails with `ErrorKind::TimedOut`.
+    /// Once some data has arrived, this awaits the rest of it without any time limit.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use std::time::Duration;
+    /// use std::{
+    ///     pin::pin,
+    ///     time::Duration
+    /// };
     /// use tokio::io::{
     ///     AsyncRead,
     ///     AsyncReadExt,
@@ -75,7 +84,7 @@ pub trait RtmpReadExt: AsyncRead {
     /// async fn main() {
     ///     let mut stream = VecStream::default();
     ///     stream.write_u8(1).await.unwrap();
-    ///     assert!(stream.try_read_after(Duration::from_secs(1)).read_u8().await.is_ok())
+    ///     assert!(pin!(stream.try_read_after(Duration::from_secs(1))).read_u8().await.is_ok())
     /// }
     /// ```
     fn try_read_after<'a>(&'a mut self, await_duration: Duration) -> TryReadAfter<'a, Self>
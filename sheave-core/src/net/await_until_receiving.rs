@@ -22,12 +22,8 @@ pub struct AwaitUntilReceiving<'a, R: AsyncRead + Unpin> {
 #[doc(hidden)]
 impl<R: AsyncRead + Unpin> AsyncRead for AwaitUntilReceiving<'_, R> {
     fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
-        loop {
-            match Pin::new(&mut self.reader).poll_read(cx, buf) {
-                Poll::Pending => continue,
-                result => return result
-            }
-        }
+        /* NOTE: The inner reader registers the waker when it returns Pending, so this task gets woken up only when some data arrives. */
+        Pin::new(&mut self.reader).poll_read(cx, buf)
     }
 }
 
@@ -0,0 +1,257 @@
+mod rc4;
+mod rtmpe_keys;
+mod undecided_encryption;
+
+use std::{
+    fmt::{
+        Debug,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind,
+        Result as IOResult
+    },
+    net::SocketAddr,
+    pin::Pin,
+    task::{
+        Context as FutureContext,
+        Poll
+    }
+};
+use futures::ready;
+use pin_project_lite::pin_project;
+use tokio::{
+    io::{
+        AsyncRead,
+        AsyncWrite,
+        ReadBuf
+    },
+    net::ToSocketAddrs
+};
+use super::rtmp::RtmpStream;
+pub use self::{
+    rc4::Rc4,
+    rtmpe_keys::RtmpeKeys,
+    undecided_encryption::*
+};
+
+/// The length of bytes which are sent/received in plain in each direction. (the version byte and two handshake data)
+const PLAIN_LEN: usize = 1 + 1536 * 2;
+
+pin_project! {
+    /// A stream for RTMPE that wrapped [`RtmpStream`].
+    ///
+    /// The handshake is sent/received in plain, then all chunks are encrypted/decrypted with RC4 transparently.
+    /// Which RC4 keys are used is decided by handlers during the handshake, via [`RtmpeKeys`] which this shares.
+    /// Therefore pass [`get_keys`] to the context of handlers before handling.
+    ///
+    /// Note the boundary of encryption is counted by bytes, so that chunks aren't processed with keys which haven't been decided yet.
+    ///
+    /// [`get_keys`]: RtmpeStream::get_keys
+    pub struct RtmpeStream {
+        #[pin]
+        rtmp_stream: RtmpStream,
+        keys: RtmpeKeys,
+        received_plain: usize,
+        sent_plain: usize,
+        encrypted: Vec<u8>,
+        written: usize
+    }
+}
+
+impl RtmpeStream {
+    fn new(rtmp_stream: RtmpStream) -> Self {
+        Self {
+            rtmp_stream,
+            keys: RtmpeKeys::default(),
+            received_plain: usize::default(),
+            sent_plain: usize::default(),
+            encrypted: Vec::default(),
+            written: usize::default()
+        }
+    }
+
+    /// Opens a RTMPE connection to a remote host.
+    ///
+    /// Note this just opens a TCP connection, that is, the handshake is left to handlers.
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> IOResult<Self> {
+        RtmpStream::connect(addr).await.map(Self::new)
+    }
+
+    /// Gets the RC4 keys which this stream shares.
+    pub fn get_keys(&self) -> RtmpeKeys {
+        self.keys.clone()
+    }
+
+    /// Gets the underlying RTMP stream.
+    pub fn get_ref(&self) -> &RtmpStream {
+        &self.rtmp_stream
+    }
+
+    /// Returns the local address that this stream is bound to.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.local_addr)
+    pub fn local_addr(&self) -> IOResult<SocketAddr> {
+        self.rtmp_stream.local_addr()
+    }
+
+    /// Returns the remote address that this stream is connected to.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.peer_addr)
+    pub fn peer_addr(&self) -> IOResult<SocketAddr> {
+        self.rtmp_stream.peer_addr()
+    }
+
+    /* NOTE: Writes encrypted bytes which have been already accepted, before accepting new ones. */
+    fn poll_write_encrypted(self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<IOResult<()>> {
+        let mut this = self.project();
+        while *this.written < this.encrypted.len() {
+            let written = ready!(this.rtmp_stream.as_mut().poll_write(cx, &this.encrypted[*this.written..]))?;
+            if written == 0 {
+                return Poll::Ready(Err(IOError::from(ErrorKind::WriteZero)))
+            }
+            *this.written += written;
+        }
+
+        this.encrypted.clear();
+        *this.written = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl From<RtmpStream> for RtmpeStream {
+    fn from(rtmp_stream: RtmpStream) -> Self {
+        Self::new(rtmp_stream)
+    }
+}
+
+impl AsyncRead for RtmpeStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
+        let this = self.project();
+
+        if *this.received_plain < PLAIN_LEN {
+            let len = (PLAIN_LEN - *this.received_plain).min(buf.remaining());
+            let mut plain_buf = ReadBuf::new(buf.initialize_unfilled_to(len));
+            ready!(this.rtmp_stream.poll_read(cx, &mut plain_buf))?;
+            let received = plain_buf.filled().len();
+            buf.advance(received);
+            *this.received_plain += received;
+            return Poll::Ready(Ok(()))
+        }
+
+        let filled = buf.filled().len();
+        ready!(this.rtmp_stream.poll_read(cx, buf))?;
+        this.keys.decrypt(&mut buf.filled_mut()[filled..])?;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for RtmpeStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, buf: &[u8]) -> Poll<IOResult<usize>> {
+        ready!(self.as_mut().poll_write_encrypted(cx))?;
+
+        let this = self.as_mut().project();
+        if *this.sent_plain < PLAIN_LEN {
+            let len = (PLAIN_LEN - *this.sent_plain).min(buf.len());
+            let sent = ready!(this.rtmp_stream.poll_write(cx, &buf[..len]))?;
+            *this.sent_plain += sent;
+            return Poll::Ready(Ok(sent))
+        }
+
+        this.encrypted.extend_from_slice(buf);
+        if let Err(e) = this.keys.encrypt(this.encrypted) {
+            this.encrypted.clear();
+            return Poll::Ready(Err(e))
+        }
+
+        /* NOTE: Bytes which are still left are written at the next writing or flushing. */
+        if let Poll::Ready(Err(e)) = self.poll_write_encrypted(cx) {
+            return Poll::Ready(Err(e))
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<IOResult<()>> {
+        ready!(self.as_mut().poll_write_encrypted(cx))?;
+        self.project().rtmp_stream.poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<IOResult<()>> {
+        ready!(self.as_mut().poll_write_encrypted(cx))?;
+        self.project().rtmp_stream.poll_shutdown(cx)
+    }
+}
+
+impl Debug for RtmpeStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_struct("RtmpeStream").field("rtmp_stream", &self.rtmp_stream).field("keys", &self.keys).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt
+        },
+        net::TcpListener
+    };
+    use crate::handshake::DiffieHellman;
+    use super::*;
+
+    async fn connect_pair() -> (RtmpeStream, RtmpeStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = RtmpeStream::connect(listener.local_addr().unwrap()).await.unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (client, RtmpeStream::from(RtmpStream::from(server)))
+    }
+
+    #[tokio::test]
+    async fn ok_encrypted_after_handshake() {
+        let (mut client, mut server) = connect_pair().await;
+        let handshake = [3u8; PLAIN_LEN];
+        client.write_all(&handshake).await.unwrap();
+        server.write_all(&handshake).await.unwrap();
+
+        /* NOTE: Reads are stopped at the boundary, even if encrypted bytes have already arrived. */
+        let mut received: [u8; PLAIN_LEN + 16] = [0; PLAIN_LEN + 16];
+        let mut len = 0;
+        while len < PLAIN_LEN {
+            len += server.read(&mut received[len..]).await.unwrap();
+        }
+        assert_eq!(PLAIN_LEN, len);
+        client.read_exact(&mut received[..PLAIN_LEN]).await.unwrap();
+
+        let client_key = DiffieHellman::generate();
+        let server_key = DiffieHellman::generate();
+        let shared_secret = client_key.compute_shared_secret(server_key.get_public_key()).unwrap();
+        client.get_keys().install(&shared_secret, client_key.get_public_key(), server_key.get_public_key());
+        server.get_keys().install(&shared_secret, server_key.get_public_key(), client_key.get_public_key());
+
+        client.write_all(b"connect").await.unwrap();
+        client.flush().await.unwrap();
+        let mut decrypted: [u8; 7] = [0; 7];
+        server.read_exact(&mut decrypted).await.unwrap();
+        assert_eq!(b"connect", &decrypted);
+
+        server.write_all(b"_result").await.unwrap();
+        server.flush().await.unwrap();
+        client.read_exact(&mut decrypted).await.unwrap();
+        assert_eq!(b"_result", &decrypted)
+    }
+
+    #[tokio::test]
+    async fn err_undecided_encryption() {
+        let (mut client, mut server) = connect_pair().await;
+        client.write_all(&[3u8; PLAIN_LEN]).await.unwrap();
+        assert!(client.write_all(b"connect").await.is_err());
+
+        let mut received: [u8; PLAIN_LEN] = [0; PLAIN_LEN];
+        server.read_exact(&mut received).await.unwrap();
+        server.get_keys().disable();
+        assert!(!server.get_keys().is_encrypted())
+    }
+}
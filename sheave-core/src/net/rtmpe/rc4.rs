@@ -0,0 +1,79 @@
+use std::fmt::{
+    Debug,
+    Formatter,
+    Result as FormatResult
+};
+
+/// The RC4 stream cipher which RTMPE uses to encrypt chunks.
+///
+/// Encryption and decryption are the same operation, that is, XORing data with the key stream.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::net::rtmpe::Rc4;
+///
+/// let mut data = *b"Plaintext";
+/// Rc4::new(b"Key").apply_keystream(&mut data);
+/// assert_eq!([0xbb, 0xf3, 0x16, 0xe8, 0xd9, 0x40, 0xaf, 0x0a, 0xd3], data);
+/// Rc4::new(b"Key").apply_keystream(&mut data);
+/// assert_eq!(*b"Plaintext", data)
+/// ```
+#[derive(Clone)]
+pub struct Rc4 {
+    state: [u8; 256],
+    i: u8,
+    j: u8
+}
+
+impl Rc4 {
+    /// Constructs a cipher from the key.
+    ///
+    /// # Panics
+    ///
+    /// When the key is empty.
+    pub fn new(key: &[u8]) -> Self {
+        assert!(!key.is_empty(), "The key of RC4 mustn't be empty.");
+
+        let mut state: [u8; 256] = [0; 256];
+        for (n, byte) in state.iter_mut().enumerate() {
+            *byte = n as u8;
+        }
+
+        let mut j = 0u8;
+        for i in 0..256 {
+            j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+            state.swap(i, usize::from(j));
+        }
+
+        Self { state, i: 0, j: 0 }
+    }
+
+    /// Encrypts/Decrypts data in place.
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        for byte in data {
+            self.i = self.i.wrapping_add(1);
+            self.j = self.j.wrapping_add(self.state[usize::from(self.i)]);
+            self.state.swap(usize::from(self.i), usize::from(self.j));
+            let k = self.state[usize::from(self.state[usize::from(self.i)].wrapping_add(self.state[usize::from(self.j)]))];
+            *byte ^= k;
+        }
+    }
+
+    /// Discards the key stream as much as specified length.
+    pub fn skip(&mut self, len: usize) {
+        let mut discarded: [u8; 256] = [0; 256];
+        let mut remained = len;
+        while remained > 0 {
+            let n = remained.min(discarded.len());
+            self.apply_keystream(&mut discarded[..n]);
+            remained -= n;
+        }
+    }
+}
+
+impl Debug for Rc4 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_struct("Rc4").finish_non_exhaustive()
+    }
+}
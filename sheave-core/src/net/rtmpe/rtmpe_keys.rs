@@ -0,0 +1,107 @@
+use std::{
+    io::Result as IOResult,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard
+    }
+};
+use hmac::{
+    Hmac,
+    Mac
+};
+use sha2::Sha256;
+use super::{
+    Rc4,
+    undecided_encryption
+};
+
+#[derive(Debug, Default)]
+enum RtmpeState {
+    #[default]
+    Undecided,
+    NotEncrypted,
+    Encrypted {
+        decryptor: Box<Rc4>,
+        encryptor: Box<Rc4>
+    }
+}
+
+/// The RC4 keys which are shared between a [`RtmpeStream`] and handlers.
+///
+/// Handlers decide whether to encrypt chunks during the handshake, then tell it to the stream via this.
+/// That is, either [`install`] or [`disable`] is required to be called before any chunk is read/written.
+///
+/// [`RtmpeStream`]: super::RtmpeStream
+/// [`install`]: RtmpeKeys::install
+/// [`disable`]: RtmpeKeys::disable
+#[derive(Debug, Clone, Default)]
+pub struct RtmpeKeys(Arc<Mutex<RtmpeState>>);
+
+impl RtmpeKeys {
+    /// Derives RC4 keys from the shared secret and both public keys, then starts encryption.
+    ///
+    /// Keys of respective directions are the first 16 bytes of HMAC-SHA256 digests of the shared secret, as following:
+    ///
+    /// |Direction|Message|
+    /// | :- | :- |
+    /// |Sending|The partner's public key|
+    /// |Receiving|Own public key|
+    ///
+    /// Note both key streams are advanced as much as the handshake data (1536 bytes), as same as Flash Player/Flash Media Server.
+    pub fn install(&self, shared_secret: &[u8], public_key: &[u8], peer_public_key: &[u8]) {
+        let mut decryptor = Rc4::new(&derive_key(shared_secret, public_key));
+        let mut encryptor = Rc4::new(&derive_key(shared_secret, peer_public_key));
+        decryptor.skip(1536);
+        encryptor.skip(1536);
+        *self.lock() = RtmpeState::Encrypted {
+            decryptor: Box::new(decryptor),
+            encryptor: Box::new(encryptor)
+        };
+    }
+
+    /// Makes chunks not to be encrypted, e.g. when the partner has declined RTMPE.
+    pub fn disable(&self) {
+        *self.lock() = RtmpeState::NotEncrypted;
+    }
+
+    /// Indicates whether chunks are encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        matches!(*self.lock(), RtmpeState::Encrypted { .. })
+    }
+
+    pub(super) fn decrypt(&self, data: &mut [u8]) -> IOResult<()> {
+        match &mut *self.lock() {
+            RtmpeState::Undecided => Err(undecided_encryption()),
+            RtmpeState::NotEncrypted => Ok(()),
+            RtmpeState::Encrypted { decryptor, .. } => {
+                decryptor.apply_keystream(data);
+                Ok(())
+            }
+        }
+    }
+
+    pub(super) fn encrypt(&self, data: &mut [u8]) -> IOResult<()> {
+        match &mut *self.lock() {
+            RtmpeState::Undecided => Err(undecided_encryption()),
+            RtmpeState::NotEncrypted => Ok(()),
+            RtmpeState::Encrypted { encryptor, .. } => {
+                encryptor.apply_keystream(data);
+                Ok(())
+            }
+        }
+    }
+
+    /* NOTE: Nothing panics while locking, so the lock can't be poisoned. */
+    fn lock(&self) -> MutexGuard<'_, RtmpeState> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn derive_key(shared_secret: &[u8], public_key: &[u8]) -> [u8; 16] {
+    let mut hmac = Hmac::<Sha256>::new_from_slice(shared_secret).unwrap();
+    hmac.update(public_key);
+    let mut key: [u8; 16] = [0; 16];
+    key.copy_from_slice(&hmac.finalize().into_bytes()[..16]);
+    key
+}
@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that chunks are about to be read/written though the handshake hasn't decided whether to encrypt them.
+#[derive(Debug)]
+pub struct UndecidedEncryption;
+
+impl Display for UndecidedEncryption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Whether to encrypt chunks hasn't been decided yet.")
+    }
+}
+
+impl Error for UndecidedEncryption {}
+
+/// A utility function of constructing an `UndecidedEncryption` error.
+pub fn undecided_encryption() -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UndecidedEncryption
+    )
+}
@@ -0,0 +1,210 @@
+mod no_certificate;
+
+use std::{
+    fmt::{
+        Debug,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind,
+        IoSlice,
+        Result as IOResult
+    },
+    net::SocketAddr,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{
+        Context as FutureContext,
+        Poll
+    }
+};
+use pin_project_lite::pin_project;
+use tokio::{
+    io::{
+        AsyncRead,
+        AsyncWrite,
+        ReadBuf
+    },
+    net::ToSocketAddrs
+};
+use tokio_rustls::{
+    TlsConnector,
+    TlsStream,
+    client::TlsStream as ClientTlsStream,
+    rustls::{
+        ClientConfig,
+        pki_types::{
+            CertificateDer,
+            PrivateKeyDer,
+            ServerName,
+            pem::{
+                Error as PemError,
+                PemObject
+            }
+        }
+    },
+    server::TlsStream as ServerTlsStream
+};
+use super::rtmp::RtmpStream;
+pub use self::no_certificate::*;
+
+pin_project! {
+    /// A stream for RTMPS that wrapped [`RtmpStream`] into a TLS session.
+    ///
+    /// If you constructs this struct from some address, use `RtmpsStream::connect("aaa.bbb.ccc.ddd:443", server_name, client_config)`.
+    /// Or if you do it from a stream which has already finished its TLS handshake, use `RtmpsStream::from(tls_stream)`.
+    /// Note this is the same as [`RtmpStream`] after the TLS handshake, that is, RTMP chunks are just sent as the application data of TLS.
+    pub struct RtmpsStream {
+        #[pin]
+        tls_stream: TlsStream<RtmpStream>
+    }
+}
+
+impl RtmpsStream {
+    fn new(tls_stream: TlsStream<RtmpStream>) -> Self {
+        Self { tls_stream }
+    }
+
+    /// Opens a RTMPS connection to a remote host.
+    ///
+    /// When connection succeeded, this performs the TLS handshake as a client, then wraps its session into RtmpsStream.
+    /// The certificate of the remote host is verified by `config`, against `server_name`.
+    pub async fn connect<A: ToSocketAddrs>(addr: A, server_name: ServerName<'static>, config: Arc<ClientConfig>) -> IOResult<Self> {
+        let rtmp_stream = RtmpStream::connect(addr).await?;
+        TlsConnector::from(config).connect(server_name, rtmp_stream).await.map(Self::from)
+    }
+
+    /// Gets the underlying RTMP stream.
+    pub fn get_ref(&self) -> &RtmpStream {
+        self.tls_stream.get_ref().0
+    }
+
+    /// Returns the local address that this stream is bound to.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.local_addr)
+    pub fn local_addr(&self) -> IOResult<SocketAddr> {
+        self.get_ref().local_addr()
+    }
+
+    /// Returns the remote address that this stream is connected to.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.peer_addr)
+    pub fn peer_addr(&self) -> IOResult<SocketAddr> {
+        self.get_ref().peer_addr()
+    }
+
+    /// Gets the value of the TCP_NODELAY option on this socket.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.nodelay)
+    pub fn nodelay(&self) -> IOResult<bool> {
+        self.get_ref().nodelay()
+    }
+
+    /// Sets the value of the TCP_NODELAY option on this socket.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpStream.html#method.set_nodelay)
+    pub fn set_nodelay(&self, nodelay: bool) -> IOResult<()> {
+        self.get_ref().set_nodelay(nodelay)
+    }
+
+    /// Gets the negotiated protocol of ALPN if it has been done.
+    pub fn alpn_protocol(&self) -> Option<&[u8]> {
+        self.tls_stream.get_ref().1.alpn_protocol()
+    }
+
+    /// Turns this into the TLS session.
+    pub fn into_inner(self) -> TlsStream<RtmpStream> {
+        self.tls_stream
+    }
+}
+
+impl From<TlsStream<RtmpStream>> for RtmpsStream {
+    fn from(tls_stream: TlsStream<RtmpStream>) -> Self {
+        Self::new(tls_stream)
+    }
+}
+
+impl From<ClientTlsStream<RtmpStream>> for RtmpsStream {
+    fn from(tls_stream: ClientTlsStream<RtmpStream>) -> Self {
+        Self::new(tls_stream.into())
+    }
+}
+
+impl From<ServerTlsStream<RtmpStream>> for RtmpsStream {
+    fn from(tls_stream: ServerTlsStream<RtmpStream>) -> Self {
+        Self::new(tls_stream.into())
+    }
+}
+
+impl AsyncRead for RtmpsStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
+        let this = self.project();
+        this.tls_stream.poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for RtmpsStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, buf: &[u8]) -> Poll<IOResult<usize>> {
+        let this = self.project();
+        this.tls_stream.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, bufs: &[IoSlice<'_>]) -> Poll<IOResult<usize>> {
+        let this = self.project();
+        this.tls_stream.poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.tls_stream.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<IOResult<()>> {
+        let this = self.project();
+        this.tls_stream.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<IOResult<()>> {
+        let this = self.project();
+        this.tls_stream.poll_shutdown(cx)
+    }
+}
+
+impl Debug for RtmpsStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_struct("RtmpsStream").field("rtmp_stream", self.get_ref()).finish_non_exhaustive()
+    }
+}
+
+/// Loads every certificate from a PEM file.
+///
+/// This is used both for the certificate chain of servers and for the trusted CAs of clients.
+/// If the file contains no certificate, this fails with `ErrorKind::InvalidData`.
+pub fn load_certificates<P: AsRef<Path>>(path: P) -> IOResult<Vec<CertificateDer<'static>>> {
+    let certificates = CertificateDer::pem_file_iter(&path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<CertificateDer<'static>>, _>>())
+        .map_err(into_io_error)?;
+
+    if certificates.is_empty() {
+        Err(no_certificate(path.as_ref().to_path_buf()))
+    } else {
+        Ok(certificates)
+    }
+}
+
+/// Loads the first private key from a PEM file.
+///
+/// Any of PKCS#1, PKCS#8 and SEC1 keys is accepted.
+pub fn load_private_key<P: AsRef<Path>>(path: P) -> IOResult<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_file(path).map_err(into_io_error)
+}
+
+/* NOTE: Failures of opening files are kept as they are, and others are treated as invalid data. */
+fn into_io_error(e: PemError) -> IOError {
+    match e {
+        PemError::Io(e) => e,
+        e => IOError::new(ErrorKind::InvalidData, e)
+    }
+}
@@ -0,0 +1,33 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    },
+    path::PathBuf
+};
+
+/// An error that some PEM file contains no certificate.
+#[derive(Debug)]
+pub struct NoCertificate(PathBuf);
+
+impl Display for NoCertificate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "No certificate is contained. path: {}", self.0.display())
+    }
+}
+
+impl Error for NoCertificate {}
+
+/// A utility function of constructing a `NoCertificate` error.
+pub fn no_certificate(path: PathBuf) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        NoCertificate(path)
+    )
+}
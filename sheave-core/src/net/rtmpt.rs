@@ -0,0 +1,371 @@
+mod http;
+mod invalid_http_message;
+
+use std::{
+    collections::VecDeque,
+    fmt::{
+        Debug,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind,
+        Result as IOResult
+    },
+    mem::replace,
+    pin::Pin,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard
+    },
+    task::{
+        Context as FutureContext,
+        Poll,
+        Waker
+    },
+    time::Duration
+};
+use tokio::{
+    io::{
+        AsyncRead,
+        AsyncWrite,
+        BufReader,
+        ReadBuf
+    },
+    net::ToSocketAddrs,
+    select,
+    spawn,
+    sync::Notify,
+    time::sleep
+};
+use super::rtmp::RtmpStream;
+pub use self::{
+    http::*,
+    invalid_http_message::*
+};
+
+/// The content type which every RTMPT request/response has.
+pub const CONTENT_TYPE: &str = "application/x-fcs";
+/// The longest polling interval which servers can tell.
+pub const MAX_POLLING_INTERVAL: u8 = 0x21;
+/// The duration which a unit of polling intervals means.
+///
+/// That is, clients wait `interval * POLLING_UNIT` until the next `idle` request if nothing has been exchanged.
+pub const POLLING_UNIT: Duration = Duration::from_millis(10);
+/// The upper limit of bytes which a session keeps until the partner's next request. (1 MiB)
+///
+/// Writing into the session waits while this is reached, that is, until the partner takes them via `/send` or `/idle`.
+pub const MAX_QUEUED_LEN: usize = 0x100000;
+
+#[derive(Debug, Default)]
+struct Tunnel {
+    received: VecDeque<u8>,
+    sending: Vec<u8>,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+    closed: bool
+}
+
+/// Bytes which are exchanged in a RTMPT session.
+///
+/// RTMP chunks in RTMPT are carried by HTTP requests/responses, so that they can't be read/written directly from/into sockets.
+/// Instead, this keeps both received bytes which are waiting to be read, and sending bytes which are waiting for next HTTP request/response.
+/// The owner of HTTP connections feeds received bytes via [`receive`], and takes sending bytes via [`drain`].
+/// On the other hand, handlers read/write them via a [`RtmptStream`] which shares this session.
+///
+/// [`receive`]: RtmptSession::receive
+/// [`drain`]: RtmptSession::drain
+#[derive(Clone, Default)]
+pub struct RtmptSession(Arc<(Mutex<Tunnel>, Notify)>);
+
+impl RtmptSession {
+    /// Stores bytes which have been carried from the partner, then wakes the reader up.
+    pub fn receive(&self, data: &[u8]) {
+        let mut tunnel = self.lock();
+        tunnel.received.extend(data);
+        if let Some(waker) = tunnel.read_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Takes bytes which are waiting to be carried to the partner, up to `max_len`.
+    ///
+    /// Bytes over `max_len` are left for the next request/response.
+    pub fn drain(&self, max_len: usize) -> Vec<u8> {
+        let mut tunnel = self.lock();
+        let len = tunnel.sending.len().min(max_len);
+        let remained = tunnel.sending.split_off(len);
+        let sending = replace(&mut tunnel.sending, remained);
+        if let Some(waker) = tunnel.write_waker.take() {
+            waker.wake();
+        }
+        sending
+    }
+
+    /// Closes this session.
+    ///
+    /// After this, the reader gets the end of the stream once all received bytes are read, and writing fails.
+    pub fn close(&self) {
+        let mut tunnel = self.lock();
+        tunnel.closed = true;
+        if let Some(waker) = tunnel.read_waker.take() {
+            waker.wake();
+        }
+        if let Some(waker) = tunnel.write_waker.take() {
+            waker.wake();
+        }
+        drop(tunnel);
+        self.notify();
+    }
+
+    /// Indicates whether this session has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    fn notify(&self) {
+        self.0.1.notify_one();
+    }
+
+    /* NOTE: Waits until some bytes are written or this session is closed. */
+    async fn notified(&self) {
+        self.0.1.notified().await
+    }
+
+    /* NOTE: Nothing panics while locking, so the lock can't be poisoned. */
+    fn lock(&self) -> MutexGuard<'_, Tunnel> {
+        self.0.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Debug for RtmptSession {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        let tunnel = self.lock();
+        f.debug_struct("RtmptSession").field("received", &tunnel.received.len()).field("sending", &tunnel.sending.len()).field("closed", &tunnel.closed).finish()
+    }
+}
+
+/// A virtual stream for RTMPT, that is, RTMP which is tunneled over HTTP.
+///
+/// This reads/writes bytes via a [`RtmptSession`] instead of sockets, as same as [`VecStream`] stands in for sockets in testing.
+/// Servers construct this from a session which is opened by the `/open` request.
+/// Clients construct this via [`connect`], then HTTP requests are sent in background.
+///
+/// This session gets closed when this stream is either shut down or dropped.
+///
+/// [`VecStream`]: crate::handlers::VecStream
+/// [`connect`]: RtmptStream::connect
+#[derive(Debug)]
+pub struct RtmptStream(RtmptSession);
+
+impl RtmptStream {
+    /// Opens a RTMPT session to a remote host.
+    ///
+    /// When the `/open` request succeeded, this starts to send `/send` or `/idle` requests in background.
+    /// Every request is sent via one keep-alive HTTP connection.
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> IOResult<Self> {
+        let rtmp_stream = RtmpStream::connect(addr).await?;
+        let host = rtmp_stream.peer_addr()?.to_string();
+        let mut connection = BufReader::new(rtmp_stream);
+
+        let response = post(&mut connection, &host, "/open/1", vec![0]).await?;
+        let session_id = String::from_utf8(response).map_err(|e| invalid_http_message(e.to_string()))?.trim().to_string();
+        if session_id.is_empty() || !session_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid_http_message(format!("Invalid session ID: {session_id}")))
+        }
+
+        let session = RtmptSession::default();
+        spawn(poll_session(connection, host, session_id, session.clone()));
+        Ok(Self(session))
+    }
+
+    /// Gets the session which this stream shares.
+    pub fn get_session(&self) -> &RtmptSession {
+        &self.0
+    }
+}
+
+impl From<RtmptSession> for RtmptStream {
+    fn from(session: RtmptSession) -> Self {
+        Self(session)
+    }
+}
+
+impl Drop for RtmptStream {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+impl AsyncRead for RtmptStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
+        let mut tunnel = self.0.lock();
+        if tunnel.received.is_empty() {
+            if tunnel.closed {
+                return Poll::Ready(Ok(()))
+            }
+
+            tunnel.read_waker = Some(cx.waker().clone());
+            return Poll::Pending
+        }
+
+        let len = tunnel.received.len().min(buf.remaining());
+        let (front, back) = tunnel.received.as_slices();
+        let front_len = front.len().min(len);
+        buf.put_slice(&front[..front_len]);
+        buf.put_slice(&back[..(len - front_len)]);
+        tunnel.received.drain(..len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for RtmptStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, buf: &[u8]) -> Poll<IOResult<usize>> {
+        let mut tunnel = self.0.lock();
+        if tunnel.closed {
+            return Poll::Ready(Err(IOError::from(ErrorKind::BrokenPipe)))
+        } else if tunnel.sending.len() >= MAX_QUEUED_LEN {
+            tunnel.write_waker = Some(cx.waker().clone());
+            return Poll::Pending
+        }
+
+        let len = buf.len().min(MAX_QUEUED_LEN - tunnel.sending.len());
+        tunnel.sending.extend_from_slice(&buf[..len]);
+        drop(tunnel);
+        self.0.notify();
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>) -> Poll<IOResult<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>) -> Poll<IOResult<()>> {
+        self.0.close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+/* NOTE: Sends a RTMPT request, then returns the body of its response. */
+async fn post(connection: &mut BufReader<RtmpStream>, host: &str, path: &str, body: Vec<u8>) -> IOResult<Vec<u8>> {
+    let headers = vec![
+        ("Host".to_string(), host.to_string()),
+        ("Content-Type".to_string(), CONTENT_TYPE.to_string()),
+        ("Connection".to_string(), "Keep-Alive".to_string()),
+        ("Cache-Control".to_string(), "no-cache".to_string())
+    ];
+    let request = HttpMessage::new(format!("POST {path} HTTP/1.1"), headers, body);
+    write_http_message(connection, &request).await?;
+
+    let Some(response) = read_http_message(connection).await? else {
+        return Err(IOError::from(ErrorKind::UnexpectedEof))
+    };
+    if response.get_start_line().split(' ').nth(1) != Some("200") {
+        return Err(invalid_http_message(format!("Unexpected response: {}", response.get_start_line())))
+    }
+    Ok(response.into_body())
+}
+
+/* NOTE: Sends written bytes via `/send`, otherwise polls the server via `/idle` at the interval which the server has told. */
+async fn poll_session(mut connection: BufReader<RtmpStream>, host: String, session_id: String, session: RtmptSession) {
+    let mut sequence: u32 = 1;
+    loop {
+        let sending = session.drain(MAX_BODY_LEN);
+        if sending.is_empty() && session.is_closed() {
+            break
+        }
+
+        let (command, body) = if sending.is_empty() {
+            ("idle", vec![0])
+        } else {
+            ("send", sending)
+        };
+        let path = format!("/{command}/{session_id}/{sequence}");
+        sequence = sequence.wrapping_add(1);
+
+        let response = match post(&mut connection, &host, &path, body).await {
+            Ok(response) => response,
+            Err(_) => {
+                session.close();
+                return
+            }
+        };
+
+        let Some((interval, received)) = response.split_first() else {
+            session.close();
+            return
+        };
+        session.receive(received);
+
+        /* NOTE: Polls again immediately while something is exchanged. */
+        if command == "idle" && received.is_empty() {
+            select! {
+                _ = session.notified() => {},
+                _ = sleep(POLLING_UNIT * u32::from(*interval)) => {}
+            }
+        }
+    }
+
+    let path = format!("/close/{session_id}/{sequence}");
+    let _ = post(&mut connection, &host, &path, vec![0]).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+    use futures::poll;
+    use tokio::io::{
+        AsyncReadExt,
+        AsyncWriteExt
+    };
+    use super::*;
+
+    #[tokio::test]
+    async fn ok_exchanged_via_session() {
+        let session = RtmptSession::default();
+        let mut stream = RtmptStream::from(session.clone());
+
+        stream.write_all(b"connect").await.unwrap();
+        assert_eq!(b"connect", session.drain(MAX_BODY_LEN).as_slice());
+        assert!(session.drain(MAX_BODY_LEN).is_empty());
+
+        session.receive(b"_result");
+        let mut received: [u8; 7] = [0; 7];
+        stream.read_exact(&mut received).await.unwrap();
+        assert_eq!(b"_result", &received);
+
+        drop(stream);
+        assert!(session.is_closed())
+    }
+
+    #[tokio::test]
+    async fn err_written_after_closing() {
+        let session = RtmptSession::default();
+        let mut stream = RtmptStream::from(session.clone());
+        session.receive(b"_result");
+        session.close();
+
+        /* NOTE: Bytes which have been received before closing are still readable. */
+        let mut received: Vec<u8> = Vec::new();
+        stream.read_to_end(&mut received).await.unwrap();
+        assert_eq!(b"_result", received.as_slice());
+        assert!(stream.write_all(b"connect").await.is_err())
+    }
+
+    #[tokio::test]
+    async fn wait_for_draining_over_limit() {
+        let session = RtmptSession::default();
+        let mut stream = RtmptStream::from(session.clone());
+        stream.write_all(&vec![0; MAX_QUEUED_LEN]).await.unwrap();
+
+        let mut writing = pin!(stream.write_all(&[1]));
+        assert!(poll!(writing.as_mut()).is_pending());
+
+        /* NOTE: One response carries at most its limit, and the rest waits for the next. */
+        assert_eq!(MAX_BODY_LEN, session.drain(MAX_BODY_LEN).len());
+        assert!(poll!(writing.as_mut()).is_ready());
+        assert_eq!(MAX_QUEUED_LEN - MAX_BODY_LEN + 1, session.drain(MAX_QUEUED_LEN).len())
+    }
+}
@@ -0,0 +1,206 @@
+use std::io::{
+    Error as IOError,
+    ErrorKind,
+    Result as IOResult
+};
+use tokio::io::{
+    AsyncBufRead,
+    AsyncBufReadExt,
+    AsyncReadExt,
+    AsyncWrite,
+    AsyncWriteExt
+};
+use super::invalid_http_message;
+
+/// The upper limit of the length of the start line and each header line.
+const MAX_LINE_LEN: u64 = 8192;
+/// The upper limit of the number of headers.
+const MAX_HEADERS: usize = 64;
+/// The upper limit of the length of bodies. (64 KiB)
+///
+/// Every request/response which carries more chunks than this is required to be split.
+pub const MAX_BODY_LEN: usize = 0x10000;
+
+/// A HTTP/1.1 message which is exchanged in RTMPT.
+///
+/// RTMPT uses only requests and responses which have their bodies of fixed length.
+/// That is, `Transfer-Encoding` isn't supported, and the length of the body is always decided by `Content-Length`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::net::rtmpt::HttpMessage;
+///
+/// let message = HttpMessage::new("POST /open/1 HTTP/1.1".into(), vec![("Content-Type".into(), "application/x-fcs".into())], vec![0]);
+/// assert_eq!("POST /open/1 HTTP/1.1", message.get_start_line());
+/// assert_eq!(Some("application/x-fcs"), message.get_header("content-type"));
+/// assert_eq!(&[0], message.get_body())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpMessage {
+    start_line: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>
+}
+
+impl HttpMessage {
+    /// Constructs a HTTP message.
+    ///
+    /// Note `Content-Length` is appended when this is written, so it isn't required to be contained in `headers`.
+    pub fn new(start_line: String, headers: Vec<(String, String)>, body: Vec<u8>) -> Self {
+        Self { start_line, headers, body }
+    }
+
+    /// Gets the start line, that is, either the request line or the status line.
+    pub fn get_start_line(&self) -> &str {
+        &self.start_line
+    }
+
+    /// Gets the value of a header.
+    ///
+    /// Header names are compared case-insensitively.
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(header_name, _)| header_name.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    /// Gets the body.
+    pub fn get_body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Turns this into the body.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/* NOTE: Reads a line without its line break, and fails if the line is too long or the stream ends in the middle of it. */
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> IOResult<Option<String>> {
+    let mut line = String::new();
+    let len = reader.take(MAX_LINE_LEN).read_line(&mut line).await?;
+    if len == 0 {
+        return Ok(None)
+    } else if !line.ends_with('\n') {
+        return Err(invalid_http_message(format!("A line is too long or truncated: {line}")))
+    }
+
+    line.truncate(line.trim_end_matches(['\r', '\n']).len());
+    Ok(Some(line))
+}
+
+/// Reads a HTTP message from streams.
+///
+/// If the stream has been closed before the message starts, this returns `None`.
+/// This fails with `ErrorKind::InvalidData` if the message is malformed, or if any part of it exceeds its limit.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::net::rtmpt::read_http_message;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut reader: &[u8] = b"HTTP/1.1 200 OK\r\nContent-Length: 1\r\n\r\n\x01";
+///     let message = read_http_message(&mut reader).await.unwrap().unwrap();
+///     assert_eq!("HTTP/1.1 200 OK", message.get_start_line());
+///     assert_eq!(&[1], message.get_body());
+///     assert!(read_http_message(&mut reader).await.unwrap().is_none())
+/// }
+/// ```
+pub async fn read_http_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> IOResult<Option<HttpMessage>> {
+    let Some(start_line) = read_line(reader).await? else {
+        return Ok(None)
+    };
+
+    let mut headers: Vec<(String, String)> = Vec::new();
+    loop {
+        let Some(line) = read_line(reader).await? else {
+            return Err(invalid_http_message("The stream got closed in headers.".into()))
+        };
+
+        if line.is_empty() {
+            break
+        } else if headers.len() >= MAX_HEADERS {
+            return Err(invalid_http_message(format!("Headers are too many: {}", headers.len())))
+        }
+
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(invalid_http_message(format!("A header has no separator: {line}")))
+        };
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut message = HttpMessage::new(start_line, headers, Vec::new());
+    if message.get_header("Transfer-Encoding").is_some() {
+        return Err(invalid_http_message("Transfer-Encoding isn't supported.".into()))
+    }
+
+    let content_length = match message.get_header("Content-Length") {
+        Some(content_length) => content_length.parse::<usize>().map_err(|e| invalid_http_message(e.to_string()))?,
+        None => 0
+    };
+    if content_length > MAX_BODY_LEN {
+        return Err(invalid_http_message(format!("The body is too large: {content_length}")))
+    }
+
+    /* NOTE: The body grows as it arrives, so that a large Content-Length without any body allocates nothing. */
+    reader.take(content_length as u64).read_to_end(&mut message.body).await?;
+    if message.body.len() < content_length {
+        return Err(IOError::from(ErrorKind::UnexpectedEof))
+    }
+    Ok(Some(message))
+}
+
+/// Writes a HTTP message into streams, then flushes it.
+///
+/// # Examples
+///
+/// ```rust
+/// use sheave_core::net::rtmpt::{
+///     HttpMessage,
+///     write_http_message
+/// };
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut writer: Vec<u8> = Vec::new();
+///     let message = HttpMessage::new("HTTP/1.1 200 OK".into(), Vec::new(), vec![1]);
+///     write_http_message(&mut writer, &message).await.unwrap();
+///     assert_eq!(b"HTTP/1.1 200 OK\r\nContent-Length: 1\r\n\r\n\x01", writer.as_slice())
+/// }
+/// ```
+pub async fn write_http_message<W: AsyncWrite + Unpin>(writer: &mut W, message: &HttpMessage) -> IOResult<()> {
+    let mut head = format!("{}\r\n", message.start_line);
+    for (name, value) in message.headers.iter().filter(|(name, _)| !name.eq_ignore_ascii_case("Content-Length")) {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str(&format!("Content-Length: {}\r\n\r\n", message.body.len()));
+
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(&message.body).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn err_too_large_body() {
+        let request = format!("POST /send/0/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_LEN + 1);
+        let mut reader = request.as_bytes();
+        assert!(read_http_message(&mut reader).await.is_err())
+    }
+
+    #[tokio::test]
+    async fn err_truncated_body() {
+        let mut reader: &[u8] = b"POST /send/0/1 HTTP/1.1\r\nContent-Length: 65536\r\n\r\n\x03";
+        assert_eq!(ErrorKind::UnexpectedEof, read_http_message(&mut reader).await.unwrap_err().kind())
+    }
+
+    #[tokio::test]
+    async fn err_truncated_headers() {
+        let mut reader: &[u8] = b"POST /idle/0/1 HTTP/1.1\r\nContent-Length: 1\r\n";
+        assert!(read_http_message(&mut reader).await.is_err())
+    }
+}
@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some HTTP message in RTMPT is malformed or too large.
+#[derive(Debug)]
+pub struct InvalidHttpMessage(String);
+
+impl Display for InvalidHttpMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Invalid HTTP message: {}", self.0)
+    }
+}
+
+impl Error for InvalidHttpMessage {}
+
+/// A utility function of constructing an `InvalidHttpMessage` error.
+pub fn invalid_http_message(message: String) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        InvalidHttpMessage(message)
+    )
+}
@@ -1,38 +1,56 @@
 use std::{
     future::Future,
-    io::Result as IOResult,
-    pin::{
-        Pin,
-        pin
+    io::{
+        Error as IOError,
+        ErrorKind,
+        Result as IOResult
     },
+    pin::Pin,
     task::{
         Context,
         Poll
     },
     time::Duration
 };
-use futures::ready;
+use pin_project_lite::pin_project;
 use tokio::{
     io::{
         AsyncRead,
         ReadBuf
     },
-    time::sleep
+    time::{
+        Sleep,
+        sleep
+    }
 };
 
-#[doc(hidden)]
-#[derive(Debug)]
-pub struct TryReadAfter<'a, R: AsyncRead + Unpin> {
-    reader: &'a mut R,
-    await_duration: Duration,
+pin_project! {
+    #[doc(hidden)]
+    #[derive(Debug)]
+    pub struct TryReadAfter<'a, R> {
+        reader: &'a mut R,
+        #[pin] sleep: Sleep,
+        is_received: bool
+    }
 }
 
 #[doc(hidden)]
 impl<R: AsyncRead + Unpin> AsyncRead for TryReadAfter<'_, R> {
-    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
-        ready!(pin!(sleep(self.await_duration)).poll(cx));
-
-        Pin::new(&mut self.reader).poll_read(cx, buf)
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
+        let this = self.project();
+        match Pin::new(&mut **this.reader).poll_read(cx, buf) {
+            Poll::Pending => if *this.is_received {
+                Poll::Pending
+            } else {
+                /* NOTE: Both the reader and the timer have registered the waker, so either wakes this task up. */
+                this.sleep.poll(cx).map(|_| Err(IOError::from(ErrorKind::TimedOut)))
+            },
+            result => {
+                /* NOTE: Once some data has arrived, the rest of it must be read until the end of the chunk. */
+                *this.is_received = true;
+                result
+            }
+        }
     }
 }
 
@@ -40,6 +58,33 @@ impl<R: AsyncRead + Unpin> AsyncRead for TryReadAfter<'_, R> {
 pub fn try_read_after<'a, R: AsyncRead + Unpin>(reader: &'a mut R, await_duration: Duration) -> TryReadAfter<'a, R> {
     TryReadAfter {
         reader,
-        await_duration
+        sleep: sleep(await_duration),
+        is_received: false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+    use tokio::io::{
+        AsyncReadExt,
+        AsyncWriteExt,
+        duplex
+    };
+    use super::*;
+
+    #[tokio::test]
+    async fn time_out_without_data() {
+        let (_writer, mut reader) = duplex(16);
+        let result = pin!(try_read_after(&mut reader, Duration::from_millis(10))).read_u8().await;
+        assert_eq!(ErrorKind::TimedOut, result.unwrap_err().kind())
+    }
+
+    #[tokio::test]
+    async fn read_arrived_data() {
+        let (mut writer, mut reader) = duplex(16);
+        writer.write_u8(1).await.unwrap();
+        let result = pin!(try_read_after(&mut reader, Duration::from_millis(10))).read_u8().await;
+        assert_eq!(1, result.unwrap())
     }
 }
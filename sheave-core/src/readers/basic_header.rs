@@ -1,6 +1,10 @@
 use std::{
     future::Future,
-    io::Result as IOResult,
+    io::{
+        Error as IOError,
+        ErrorKind,
+        Result as IOResult
+    },
     pin::Pin,
     task::{
         Context as FutureContext,
@@ -17,10 +21,62 @@ use crate::messages::headers::{
     MessageFormat
 };
 
+/// The bytes of a basic header which have been read so far.
+///
+/// This is kept across polls, so that a basic header split over several reads can be resumed.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub(crate) struct BasicHeaderBuffer {
+    bytes: [u8; 3],
+    filled: usize
+}
+
+#[doc(hidden)]
+impl BasicHeaderBuffer {
+    fn get_length(&self) -> usize {
+        if self.filled == 0 {
+            1
+        } else {
+            match self.bytes[0] << 2 >> 2 {
+                1 => 3,
+                0 => 2,
+                _ => 1
+            }
+        }
+    }
+
+    pub(crate) fn poll_read<R: AsyncRead>(&mut self, mut reader: Pin<&mut R>, cx: &mut FutureContext<'_>) -> Poll<IOResult<BasicHeader>> {
+        loop {
+            let length = self.get_length();
+            if self.filled == length {
+                break
+            }
+
+            let mut buf = ReadBuf::new(&mut self.bytes[self.filled..length]);
+            ready!(reader.as_mut().poll_read(cx, &mut buf))?;
+            if buf.filled().is_empty() {
+                return Poll::Ready(Err(IOError::from(ErrorKind::UnexpectedEof)))
+            }
+            self.filled += buf.filled().len();
+        }
+
+        let message_format: MessageFormat = (self.bytes[0] >> 6).into();
+        let chunk_id = match self.bytes[0] << 2 >> 2 {
+            1 => u16::from_le_bytes([self.bytes[1], self.bytes[2]]) + 64,
+            0 => self.bytes[1] as u16 + 64,
+            chunk_id => chunk_id as u16
+        };
+        /* NOTE: Gets ready to read a next basic header. */
+        self.filled = 0;
+        Poll::Ready(Ok(BasicHeader::new(message_format, chunk_id)))
+    }
+}
+
 #[doc(hidden)]
 #[derive(Debug)]
 pub struct BasicHeaderReader<'a, R: AsyncRead> {
-    reader: Pin<&'a mut R>
+    reader: Pin<&'a mut R>,
+    buffer: BasicHeaderBuffer
 }
 
 #[doc(hidden)]
@@ -28,31 +84,19 @@ impl<R: AsyncRead> Future for BasicHeaderReader<'_, R> {
     type Output = IOResult<BasicHeader>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<Self::Output> {
-        let mut first_byte: [u8; 1] = [0];
-        let mut buf = ReadBuf::new(&mut first_byte);
-        ready!(self.reader.as_mut().poll_read(cx, &mut buf))?;
-        let message_format: MessageFormat = (first_byte[0] >> 6).into();
-        let chunk_id = match first_byte[0] << 2 >> 2 {
-            1 => {
-                let mut chunk_id_bytes: [u8; 2] = [0; 2];
-                let mut buf = ReadBuf::new(&mut chunk_id_bytes);
-                ready!(self.reader.as_mut().poll_read(cx, &mut buf))?;
-                u16::from_le_bytes(chunk_id_bytes) + 64
-            },
-            0 => {
-                let mut chunk_id_bytes: [u8; 2] = [0; 2];
-                let mut buf = ReadBuf::new(&mut chunk_id_bytes[1..]);
-                ready!(self.reader.as_mut().poll_read(cx, &mut buf))?;
-                u16::from_be_bytes(chunk_id_bytes) + 64
-            },
-            chunk_id => chunk_id as u16
-        };
-        Poll::Ready(Ok(BasicHeader::new(message_format, chunk_id)))
+        let Self { reader, buffer } = &mut *self;
+        buffer.poll_read(reader.as_mut(), cx)
     }
 }
 
 /// Reads a basic header from streams.
 ///
+/// # Errors
+///
+/// * [`UnexpectedEof`]
+///
+/// When the stream got closed before reading all of the basic header.
+///
 /// # Examples
 ///
 /// ```rust
@@ -94,8 +138,10 @@ impl<R: AsyncRead> Future for BasicHeaderReader<'_, R> {
 ///     Ok(())
 /// }
 /// ```
+///
+/// [`UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
 pub fn read_basic_header<R: AsyncRead>(reader: Pin<&mut R>) -> BasicHeaderReader<'_, R> {
-    BasicHeaderReader { reader }
+    BasicHeaderReader { reader, buffer: BasicHeaderBuffer::default() }
 }
 
 #[cfg(test)]
@@ -104,7 +150,12 @@ mod tests {
         cmp::max,
         pin::pin
     };
+    use futures::poll;
     use rand::random;
+    use tokio::io::{
+        AsyncWriteExt,
+        duplex
+    };
     use crate::messages::headers::MessageFormat;
     use super::*;
 
@@ -149,4 +200,41 @@ mod tests {
         assert_eq!(MessageFormat::from(message_format_bits >> 6), basic_header.get_message_format());
         assert_eq!(chunk_id_bytes + 64, basic_header.get_chunk_id())
     }
+
+    #[tokio::test]
+    async fn resume_two_bytes() {
+        let (mut writer, reader) = duplex(2);
+        let mut reader = pin!(reader);
+        let mut basic_header = pin!(read_basic_header(reader.as_mut()));
+
+        writer.write_u8(u8::from(MessageFormat::New) << 6).await.unwrap();
+        assert!(poll!(basic_header.as_mut()).is_pending());
+        writer.write_u8(5).await.unwrap();
+        let basic_header = basic_header.await.unwrap();
+        assert_eq!(MessageFormat::New, basic_header.get_message_format());
+        assert_eq!(69, basic_header.get_chunk_id())
+    }
+
+    #[tokio::test]
+    async fn resume_three_bytes() {
+        let (mut writer, reader) = duplex(3);
+        let mut reader = pin!(reader);
+        let mut basic_header = pin!(read_basic_header(reader.as_mut()));
+
+        writer.write_u8(u8::from(MessageFormat::New) << 6 | 1).await.unwrap();
+        assert!(poll!(basic_header.as_mut()).is_pending());
+        writer.write_u8(5).await.unwrap();
+        assert!(poll!(basic_header.as_mut()).is_pending());
+        writer.write_u8(1).await.unwrap();
+        let basic_header = basic_header.await.unwrap();
+        assert_eq!(MessageFormat::New, basic_header.get_message_format());
+        assert_eq!(0x0105 + 64, basic_header.get_chunk_id())
+    }
+
+    #[tokio::test]
+    async fn err_insufficient_basic_header() {
+        let reader: [u8; 1] = [u8::from(MessageFormat::New) << 6];
+        let result = read_basic_header(pin!(reader.as_slice())).await;
+        assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind())
+    }
 }
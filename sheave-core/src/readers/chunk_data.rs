@@ -1,55 +1,85 @@
 use std::{
     cmp::min,
     future::Future,
-    io::Result as IOResult,
-    pin::{
-        Pin,
-        pin
+    io::{
+        Error as IOError,
+        ErrorKind,
+        Result as IOResult
     },
+    mem::take,
+    pin::Pin,
     task::{
         Context as FutureContext,
         Poll
     }
 };
+use bytes::{
+    Bytes,
+    BytesMut
+};
 use futures::ready;
 use tokio::io::{
     AsyncRead,
     ReadBuf
 };
-use crate::messages::ChunkSize;
-use super::read_basic_header;
+use crate::messages::{
+    ChunkSize,
+    too_large_message
+};
+use super::basic_header::BasicHeaderBuffer;
 
 #[doc(hidden)]
 #[derive(Debug)]
 pub struct ChunkDataReader<'a, R: AsyncRead> {
     reader: Pin<&'a mut R>,
     chunk_size: ChunkSize,
-    message_length: u32
+    message_length: u32,
+    max_message_size: u32,
+    chunk_data_bytes: BytesMut,
+    filled: usize,
+    remained: u32,
+    basic_header: Option<BasicHeaderBuffer>
 }
 
 #[doc(hidden)]
 impl<R: AsyncRead> Future for ChunkDataReader<'_, R> {
-    type Output = IOResult<Vec<u8>>;
+    type Output = IOResult<Bytes>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<Self::Output> {
-        unsafe {
-            let mut chunk_data_bytes: Vec<u8> = Vec::new();
-            let mut remained = self.message_length;
-
-            loop {
-                let capacity = min(self.chunk_size.get_chunk_size(), remained);
-                let mut tmp_bytes: Vec<u8> = Vec::with_capacity(capacity as usize);
-                tmp_bytes.set_len(tmp_bytes.capacity());
-                let mut buf = ReadBuf::new(tmp_bytes.as_mut_slice());
-                ready!(self.reader.as_mut().poll_read(cx, &mut buf))?;
-                chunk_data_bytes.extend_from_slice(&tmp_bytes);
-
-                remained -= capacity;
-                if remained > 0 {
-                    ready!(pin!(read_basic_header(self.reader.as_mut())).poll(cx))?;
-                } else {
-                    return Poll::Ready(Ok(chunk_data_bytes))
+        if self.message_length > self.max_message_size {
+            return Poll::Ready(Err(too_large_message(self.message_length, self.max_message_size)))
+        }
+
+        /* NOTE: Every progress is kept in this reader, so that it can be resumed whenever the stream gets pending. */
+        let this = &mut *self;
+        loop {
+            if let Some(ref mut basic_header) = this.basic_header {
+                ready!(basic_header.poll_read(this.reader.as_mut(), cx))?;
+                this.basic_header = None;
+            }
+
+            if this.filled == this.chunk_data_bytes.len() {
+                /* NOTE: Allocates per chunk as it arrives, instead of trusting the message length in advance. */
+                let capacity = min(this.chunk_size.get_chunk_size(), this.remained);
+                this.chunk_data_bytes.resize(this.filled + capacity as usize, 0);
+            }
+
+            /* NOTE: Reads into the tail of the message directly, so that chunks aren't copied once more. */
+            while this.filled < this.chunk_data_bytes.len() {
+                let mut buf = ReadBuf::new(&mut this.chunk_data_bytes[this.filled..]);
+                ready!(this.reader.as_mut().poll_read(cx, &mut buf))?;
+                let read = buf.filled().len();
+                if read == 0 {
+                    return Poll::Ready(Err(IOError::from(ErrorKind::UnexpectedEof)))
                 }
+                this.filled += read;
+                this.remained -= read as u32;
+            }
+
+            if this.remained > 0 {
+                this.basic_header = Some(BasicHeaderBuffer::default());
+            } else {
+                return Poll::Ready(Ok(take(&mut this.chunk_data_bytes).freeze()))
             }
         }
     }
@@ -60,6 +90,17 @@ impl<R: AsyncRead> Future for ChunkDataReader<'_, R> {
 /// If a chunk data exceeds specified chunk size, to insert continue headers between chunk data per chunk size is required.
 /// Note the message length doesn't count their headers.
 ///
+/// # Errors
+///
+/// * [`TooLargeMessage`]
+///
+/// When the message length exceeds the maximum message size.
+/// This is checked before reading anything.
+///
+/// * [`UnexpectedEof`]
+///
+/// When the stream got closed before reading all of the chunk data.
+///
 /// # Examples
 ///
 /// ```rust
@@ -83,7 +124,7 @@ impl<R: AsyncRead> Future for ChunkDataReader<'_, R> {
 ///     // When it's just one chunk.
 ///     let mut reader: [u8; 128] = [0; 128];
 ///     fill(&mut reader);
-///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 128).await?;
+///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 128, u32::MAX).await?;
 ///     assert_eq!(128, result.len());
 ///
 ///     // When it has the one byte header.
@@ -93,7 +134,7 @@ impl<R: AsyncRead> Future for ChunkDataReader<'_, R> {
 ///     reader[..128].copy_from_slice(&part);
 ///     reader[128] = u8::from(MessageFormat::Continue) << 6 | 2;
 ///     reader[129..].copy_from_slice(&part);
-///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 256).await?;
+///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 256, u32::MAX).await?;
 ///     assert_eq!(256, result.len());
 ///
 ///     // When it has the two bytes header.
@@ -104,7 +145,7 @@ impl<R: AsyncRead> Future for ChunkDataReader<'_, R> {
 ///     reader[128] = u8::from(MessageFormat::Continue) << 6;
 ///     reader[129] = 2;
 ///     reader[130..].copy_from_slice(&part);
-///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 256).await?;
+///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 256, u32::MAX).await?;
 ///     assert_eq!(256, result.len());
 ///
 ///     // When it has the three bytes header.
@@ -115,20 +156,42 @@ impl<R: AsyncRead> Future for ChunkDataReader<'_, R> {
 ///     reader[128] = u8::from(MessageFormat::Continue) << 6 | 1;
 ///     reader[129..131].copy_from_slice(&2u16.to_le_bytes());
 ///     reader[131..].copy_from_slice(&part);
-///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 256).await?;
+///     let result = read_chunk_data(pin!(reader.as_slice()), chunk_size, 256, u32::MAX).await?;
 ///     assert_eq!(256, result.len());
 ///
+///     // When the message length exceeds the maximum message size.
+///     let mut reader: [u8; 128] = [0; 128];
+///     fill(&mut reader);
+///     assert!(read_chunk_data(pin!(reader.as_slice()), chunk_size, 128, 127).await.is_err());
+///
 ///     Ok(())
 /// }
 /// ```
-pub fn read_chunk_data<'a, R: AsyncRead>(reader: Pin<&'a mut R>, chunk_size: ChunkSize, message_length: u32) -> ChunkDataReader<'a, R> {
-    ChunkDataReader { reader, chunk_size, message_length }
+///
+/// [`TooLargeMessage`]: crate::messages::TooLargeMessage
+/// [`UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+pub fn read_chunk_data<'a, R: AsyncRead>(reader: Pin<&'a mut R>, chunk_size: ChunkSize, message_length: u32, max_message_size: u32) -> ChunkDataReader<'a, R> {
+    ChunkDataReader {
+        reader,
+        chunk_size,
+        message_length,
+        max_message_size,
+        chunk_data_bytes: BytesMut::new(),
+        filled: 0,
+        remained: message_length,
+        basic_header: None
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::pin::pin;
+    use futures::poll;
     use rand::fill;
+    use tokio::io::{
+        AsyncWriteExt,
+        duplex
+    };
     use crate::messages::headers::MessageFormat;
     use super::*;
 
@@ -136,7 +199,7 @@ mod tests {
     async fn read_one_chunk() {
         let mut reader: [u8; 128] = [0; 128];
         fill(&mut reader);
-        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 128).await;
+        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 128, u32::MAX).await;
         assert!(result.is_ok());
         let bytes = result.unwrap();
         assert_eq!(128, bytes.len())
@@ -150,7 +213,7 @@ mod tests {
         reader[..128].copy_from_slice(&part);
         reader[128] = u8::from(MessageFormat::Continue) << 6 | 2;
         reader[129..].copy_from_slice(&part);
-        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 256).await;
+        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 256, u32::MAX).await;
         assert!(result.is_ok());
         let bytes = result.unwrap();
         assert_eq!(256, bytes.len())
@@ -165,7 +228,7 @@ mod tests {
         reader[128] = u8::from(MessageFormat::Continue) << 6;
         reader[129] = 2;
         reader[130..].copy_from_slice(&part);
-        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 256).await;
+        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 256, u32::MAX).await;
         assert!(result.is_ok());
         let bytes = result.unwrap();
         assert_eq!(256, bytes.len())
@@ -180,9 +243,61 @@ mod tests {
         reader[128] = u8::from(MessageFormat::Continue) << 6 | 1;
         reader[129..131].copy_from_slice(&2u16.to_le_bytes());
         reader[131..].copy_from_slice(&part);
-        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 256).await;
+        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 256, u32::MAX).await;
         assert!(result.is_ok());
         let bytes = result.unwrap();
         assert_eq!(256, bytes.len())
     }
+
+    #[tokio::test]
+    async fn err_too_large_message() {
+        let mut reader: [u8; 128] = [0; 128];
+        fill(&mut reader);
+        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 128, 127).await;
+        assert!(result.is_err())
+    }
+
+    #[tokio::test]
+    async fn err_insufficient_chunk_data() {
+        let mut reader: [u8; 64] = [0; 64];
+        fill(&mut reader);
+        let result = read_chunk_data(pin!(reader.as_slice()), ChunkSize::default(), 128, u32::MAX).await;
+        assert!(result.is_err())
+    }
+
+    #[tokio::test]
+    async fn resume_split_chunk_data() {
+        let (mut writer, reader) = duplex(128);
+        let mut reader = pin!(reader);
+        let mut chunk_data = pin!(read_chunk_data(reader.as_mut(), ChunkSize::default(), 128, u32::MAX));
+        let mut part: [u8; 128] = [0; 128];
+        fill(&mut part);
+
+        writer.write_all(&part[..64]).await.unwrap();
+        assert!(poll!(chunk_data.as_mut()).is_pending());
+        writer.write_all(&part[64..]).await.unwrap();
+        assert_eq!(part.as_slice(), chunk_data.await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn resume_split_continue_header() {
+        let (mut writer, reader) = duplex(256);
+        let mut reader = pin!(reader);
+        let mut chunk_data = pin!(read_chunk_data(reader.as_mut(), ChunkSize::default(), 256, u32::MAX));
+        let mut first: [u8; 128] = [0; 128];
+        fill(&mut first);
+        let mut second: [u8; 128] = [0; 128];
+        fill(&mut second);
+
+        writer.write_all(&first).await.unwrap();
+        writer.write_u8(u8::from(MessageFormat::Continue) << 6).await.unwrap();
+        assert!(poll!(chunk_data.as_mut()).is_pending());
+        writer.write_u8(5).await.unwrap();
+        writer.write_all(&second[..64]).await.unwrap();
+        assert!(poll!(chunk_data.as_mut()).is_pending());
+        writer.write_all(&second[64..]).await.unwrap();
+        let bytes = chunk_data.await.unwrap();
+        assert_eq!(first.as_slice(), &bytes[..128]);
+        assert_eq!(second.as_slice(), &bytes[128..])
+    }
 }
@@ -0,0 +1,898 @@
+//! # The Sans-IO RTMP session
+//!
+//! [`RtmpSession`] runs the handshake and the chunk layer of RTMP without touching any stream.
+//! Instead, its driver:
+//!
+//! 1. feeds bytes which have been received from the peer,
+//! 2. takes [`SessionEvent`]s which have been decoded from them,
+//! 3. and takes bytes which should be sent to the peer.
+//!
+//! Therefore this can be driven by tokio, by blocking sockets of the standard library, by any other runtime, or by byte captures in tests.
+//!
+//! The session also takes care of what the chunk layer requires by itself:
+//!
+//! * Applies the Set Chunk Size, the Window Acknowledgement Size and the Set Peer Bandwidth messages.
+//! * Sends Acknowledgement messages whenever received bytes pass the window.
+//! * Answers Ping Requests.
+//! * Estimates the round-trip time from echoes of the handshake and from Ping Responses.
+//!
+//! Any error is fatal, that is, the driver should close the connection when either feeding or sending has failed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::{
+//!     io::Result as IOResult,
+//!     time::Duration
+//! };
+//! use sheave_core::{
+//!     handlers::RtmpContext,
+//!     messages::headers::MessageType,
+//!     session::{
+//!         RtmpSession,
+//!         SessionEvent,
+//!         SessionRole
+//!     }
+//! };
+//!
+//! fn main() -> IOResult<()> {
+//!     let mut client = RtmpSession::new(SessionRole::Client, RtmpContext::default());
+//!     let mut server = RtmpSession::new(SessionRole::Server, RtmpContext::default());
+//!
+//!     // C0 and C1, then S0, S1 and S2, then C2.
+//!     server.feed(&client.take_outgoing())?;
+//!     client.feed(&server.take_outgoing())?;
+//!     server.feed(&client.take_outgoing())?;
+//!     assert!(matches!(client.poll_event(), Some(SessionEvent::HandshakeDone)));
+//!     assert!(matches!(server.poll_event(), Some(SessionEvent::HandshakeDone)));
+//!
+//!     client.send_message(6, Duration::from_millis(40), MessageType::Video, 1, &[0x17, 0x01]);
+//!     server.feed(&client.take_outgoing())?;
+//!     match server.poll_event() {
+//!         Some(SessionEvent::Media { message_type, message_id, timestamp, data }) => {
+//!             assert_eq!(MessageType::Video, message_type);
+//!             assert_eq!(1, message_id);
+//!             assert_eq!(Duration::from_millis(40), timestamp);
+//!             assert_eq!(vec![0x17, 0x01], data)
+//!         },
+//!         _ => panic!("Video message should be received.")
+//!     }
+//!     Ok(())
+//! }
+//! ```
+mod session_role;
+mod session_event;
+mod unknown_chunk_stream;
+
+use std::{
+    cmp::min,
+    collections::{
+        HashMap,
+        VecDeque
+    },
+    io::Result as IOResult,
+    mem::take,
+    time::Duration
+};
+use bytes::Bytes;
+use crate::{
+    ByteBuffer,
+    Decoder,
+    Encoder,
+    U24_MAX,
+    handlers::{
+        FlowControl,
+        LastChunk,
+        MeasureAcknowledgement,
+        RtmpContext,
+        inconsistent_sha
+    },
+    handshake::{
+        DigestScheme,
+        EncryptionAlgorithm,
+        Handshake,
+        Version,
+        elapsed_since,
+        epoch_timestamp
+    },
+    messages::{
+        Acknowledgement,
+        ChunkData,
+        ChunkSize,
+        EventType,
+        PeerBandwidth,
+        PingRequest,
+        PingResponse,
+        UserControl,
+        WindowAcknowledgementSize,
+        amf::v0::{
+            AmfString,
+            Number
+        },
+        headers::{
+            MessageFormat,
+            MessageHeader,
+            MessageType
+        },
+        too_large_message,
+        unknown_message
+    }
+};
+pub use self::{
+    session_role::*,
+    session_event::*,
+    unknown_chunk_stream::*
+};
+
+const HANDSHAKE_LEN: usize = 1536;
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HandshakeState {
+    AwaitingClientRequest,
+    AwaitingServerRequest,
+    AwaitingClientResponse,
+    Done
+}
+
+/// The RTMP session which is independent of any I/O.
+///
+/// The session keeps its state in the [`RtmpContext`], so the same context can be used for the application layer through [`get_rtmp_context_mut`].
+///
+/// [`get_rtmp_context_mut`]: RtmpSession::get_rtmp_context_mut
+#[derive(Debug)]
+pub struct RtmpSession {
+    role: SessionRole,
+    handshake_state: HandshakeState,
+    rtmp_context: RtmpContext,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+    partial_messages: HashMap<u16, Vec<u8>>,
+    events: VecDeque<SessionEvent>,
+    sequence_number: u32,
+    unacknowledged_amount: u32
+}
+
+impl RtmpSession {
+    /// Constructs a session.
+    ///
+    /// A client session prepares C0 and C1 at this point.
+    /// Whether they are signed follows [`is_signed`] of the context.
+    ///
+    /// [`is_signed`]: RtmpContext::is_signed
+    pub fn new(role: SessionRole, rtmp_context: RtmpContext) -> Self {
+        let handshake_state = match role {
+            SessionRole::Client => HandshakeState::AwaitingServerRequest,
+            SessionRole::Server => HandshakeState::AwaitingClientRequest
+        };
+        let mut session = Self {
+            role,
+            handshake_state,
+            rtmp_context,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+            partial_messages: HashMap::new(),
+            events: VecDeque::new(),
+            sequence_number: u32::default(),
+            unacknowledged_amount: u32::default()
+        };
+
+        if let SessionRole::Client = role {
+            session.write_client_request();
+        }
+
+        session
+    }
+
+    /// Gets the role of this session.
+    pub fn get_role(&self) -> SessionRole {
+        self.role
+    }
+
+    /// Checks whether the handshake has been completed.
+    pub fn is_handshake_done(&self) -> bool {
+        self.handshake_state == HandshakeState::Done
+    }
+
+    /// Gets the context which this session keeps its state in.
+    pub fn get_rtmp_context_mut(&mut self) -> &mut RtmpContext {
+        &mut self.rtmp_context
+    }
+
+    /// Feeds bytes which have been received from the peer.
+    ///
+    /// Bytes don't need to be split at any boundary.
+    /// Incomplete handshakes or chunks are kept until their rest arrive.
+    ///
+    /// # Errors
+    ///
+    /// When:
+    ///
+    /// * Some digest or signature of the peer's handshake is invalid.
+    /// * Some chunk omits its message header though its chunk stream hasn't started yet.
+    /// * Some message exceeds [`get_max_message_size`].
+    /// * Some message can't be decoded.
+    /// * Some message type is unknown and the [`UnknownMessagePolicy`] is `Disconnect`.
+    ///
+    /// [`get_max_message_size`]: RtmpContext::get_max_message_size
+    /// [`UnknownMessagePolicy`]: crate::handlers::UnknownMessagePolicy
+    pub fn feed(&mut self, bytes: &[u8]) -> IOResult<()> {
+        self.add_amount(bytes.len() as u32);
+        self.incoming.extend_from_slice(bytes);
+
+        while self.step()? {}
+
+        if self.is_handshake_done() {
+            let window_acknowledgement_size = self.rtmp_context.get_window_acknowledgement_size().get_inner();
+            if self.pass_window(window_acknowledgement_size) {
+                let acknowledgement = self.as_acknowledgement();
+                self.send_chunk_data(u32::default(), Duration::default(), &acknowledgement);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes a next event which has been decoded from fed bytes.
+    pub fn poll_event(&mut self) -> Option<SessionEvent> {
+        self.events.pop_front()
+    }
+
+    /// Takes all bytes which should be sent to the peer.
+    pub fn take_outgoing(&mut self) -> Vec<u8> {
+        take(&mut self.outgoing)
+    }
+
+    /// Sends a message as chunks.
+    ///
+    /// Its message header is compressed against the last chunk which has been sent on the same chunk ID, as same as [`write_chunk`].
+    /// This should be called after the handshake has been done.
+    ///
+    /// [`write_chunk`]: crate::writers::write_chunk
+    pub fn send_message(&mut self, chunk_id: u16, mut timestamp: Duration, message_type: MessageType, message_id: u32, data: &[u8]) {
+        let message_format = if let Some(last_sent_chunk) = self.rtmp_context.get_last_sent_chunk(&chunk_id) {
+            if message_id != last_sent_chunk.get_message_id() {
+                MessageFormat::New
+            } else if (message_type != last_sent_chunk.get_message_type()) || (data.len() != last_sent_chunk.get_message_length() as usize) {
+                MessageFormat::SameSource
+            } else if timestamp != last_sent_chunk.get_timestamp() {
+                MessageFormat::TimerChange
+            } else {
+                MessageFormat::Continue
+            }
+        } else {
+            MessageFormat::New
+        };
+        let extended_timestamp = if timestamp.as_millis() >= U24_MAX as u128 {
+            let extended_timestamp = Some(timestamp);
+            timestamp = Duration::from_millis(U24_MAX as u64);
+            extended_timestamp
+        } else {
+            None
+        };
+        let message_header: MessageHeader = match message_format {
+            MessageFormat::New => (timestamp, data.len() as u32, message_type, message_id).into(),
+            MessageFormat::SameSource => (timestamp, data.len() as u32, message_type).into(),
+            MessageFormat::TimerChange => timestamp.into(),
+            MessageFormat::Continue => ().into()
+        };
+
+        let sent_before = self.outgoing.len();
+        let chunk_size = self.rtmp_context.get_sending_chunk_size().get_chunk_size() as usize;
+        put_basic_header(&mut self.outgoing, message_format, chunk_id);
+        put_message_header(&mut self.outgoing, &message_header);
+        for (i, chunk) in data.chunks(chunk_size).enumerate() {
+            if i > 0 {
+                put_basic_header(&mut self.outgoing, MessageFormat::Continue, chunk_id);
+            }
+            /* NOTE: Every chunk of the message repeats the extended timestamp. */
+            if let Some(extended_timestamp) = extended_timestamp {
+                self.outgoing.extend_from_slice(&(extended_timestamp.as_millis() as u32).to_be_bytes());
+            }
+            self.outgoing.extend_from_slice(chunk);
+        }
+        let sent_amount = self.outgoing.len() - sent_before;
+        self.rtmp_context.get_flow_control_mut().add_sent_amount(sent_amount as u32);
+
+        let timestamp = extended_timestamp.unwrap_or(timestamp);
+        if let Some(last_sent_chunk) = self.rtmp_context.get_last_sent_chunk_mut(&chunk_id) {
+            last_sent_chunk.set_timestamp(timestamp);
+            last_sent_chunk.set_message_length(data.len() as u32);
+            last_sent_chunk.set_message_type(message_type);
+            last_sent_chunk.set_message_id(message_id);
+        } else {
+            self.rtmp_context.insert_sent_chunk(chunk_id, LastChunk::new(timestamp, data.len() as u32, message_type, message_id));
+        }
+    }
+
+    /// Sends chunk data on its own channel and message type.
+    pub fn send_chunk_data<T: ChunkData>(&mut self, message_id: u32, timestamp: Duration, chunk_data: &T)
+    where ByteBuffer: Encoder<T>
+    {
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(chunk_data);
+        self.send_message(T::CHANNEL.into(), timestamp, T::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer));
+    }
+
+    /// Sends a user control message with its event type.
+    pub fn send_user_control<T: ChunkData + UserControl>(&mut self, user_control: &T)
+    where ByteBuffer: Encoder<T>
+    {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(T::EVENT_TYPE.into());
+        buffer.encode(user_control);
+        self.send_message(T::CHANNEL.into(), Duration::default(), T::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer));
+    }
+
+    /// Sends the Set Chunk Size message, then splits subsequent messages by that size.
+    pub fn set_chunk_size(&mut self, chunk_size: ChunkSize) {
+        self.send_chunk_data(u32::default(), Duration::default(), &chunk_size);
+        self.rtmp_context.set_sending_chunk_size(chunk_size);
+    }
+
+    fn step(&mut self) -> IOResult<bool> {
+        match self.handshake_state {
+            HandshakeState::AwaitingClientRequest => self.handle_client_request(),
+            HandshakeState::AwaitingServerRequest => self.handle_server_request(),
+            HandshakeState::AwaitingClientResponse => self.handle_client_response(),
+            HandshakeState::Done => self.handle_chunk()
+        }
+    }
+
+    fn take_encryption_algorithm(&mut self) -> EncryptionAlgorithm {
+        self.incoming.remove(0).into()
+    }
+
+    fn take_handshake(&mut self) -> Handshake {
+        let mut handshake_bytes: [u8; HANDSHAKE_LEN] = [0; HANDSHAKE_LEN];
+        handshake_bytes.copy_from_slice(&self.incoming[..HANDSHAKE_LEN]);
+        self.incoming.drain(..HANDSHAKE_LEN);
+        handshake_bytes.into()
+    }
+
+    fn put_handshake(&mut self, handshake: &Handshake) {
+        self.outgoing.extend_from_slice(handshake.get_bytes());
+    }
+
+    /* NOTE: This is `None` if the echo hasn't had the same timestamp as what we have sent. */
+    fn store_round_trip_time(&mut self, round_trip_time: Option<Duration>) {
+        if let Some(round_trip_time) = round_trip_time {
+            self.rtmp_context.set_round_trip_time(round_trip_time);
+        }
+        self.rtmp_context.seed_from_round_trip_time();
+    }
+
+    fn complete_handshake(&mut self) {
+        /* NOTE: The peer's acknowledgements count also bytes of the handshake. */
+        self.rtmp_context.get_flow_control_mut().add_sent_amount(FlowControl::HANDSHAKE_SIZE);
+        self.handshake_state = HandshakeState::Done;
+        self.events.push_back(SessionEvent::HandshakeDone);
+    }
+
+    fn write_client_request(&mut self) {
+        let encryption_algorithm = EncryptionAlgorithm::default();
+
+        let version = if self.rtmp_context.is_signed() {
+            Version::LATEST_CLIENT
+        } else {
+            Version::UNSIGNED
+        };
+        let mut client_request = Handshake::new(epoch_timestamp(), version);
+        if self.rtmp_context.is_signed() {
+            let digest_scheme = DigestScheme::from(encryption_algorithm);
+            client_request.imprint_digest(digest_scheme, Handshake::CLIENT_KEY);
+            self.rtmp_context.set_digest_scheme(digest_scheme);
+        }
+
+        self.outgoing.push(encryption_algorithm.into());
+        self.put_handshake(&client_request);
+
+        self.rtmp_context.set_encryption_algorithm(encryption_algorithm);
+        self.rtmp_context.set_client_handshake(client_request);
+    }
+
+    fn handle_client_request(&mut self) -> IOResult<bool> {
+        if self.incoming.len() < 1 + HANDSHAKE_LEN {
+            return Ok(false)
+        }
+
+        let encryption_algorithm = self.take_encryption_algorithm();
+        let mut client_request = self.take_handshake();
+
+        let server_request = if client_request.get_version() == Version::UNSIGNED {
+            Handshake::new(epoch_timestamp(), Version::UNSIGNED)
+        } else if let Some(digest_scheme) = client_request.find_digest_scheme(Handshake::CLIENT_KEY) {
+            let mut server_request = Handshake::new(epoch_timestamp(), Version::LATEST_SERVER);
+            server_request.imprint_digest(digest_scheme, Handshake::SERVER_KEY);
+            let mut server_response_key: Vec<u8> = Vec::new();
+            server_response_key.extend_from_slice(Handshake::SERVER_KEY);
+            server_response_key.extend_from_slice(Handshake::COMMON_KEY);
+            client_request.imprint_signature(digest_scheme, &server_response_key);
+            self.rtmp_context.set_signed(true);
+            self.rtmp_context.set_digest_scheme(digest_scheme);
+            server_request
+        } else {
+            return Err(inconsistent_sha(client_request.get_digest(DigestScheme::default()).to_vec()))
+        };
+
+        self.outgoing.push(encryption_algorithm.into());
+        self.put_handshake(&server_request);
+        self.put_handshake(&client_request);
+
+        self.rtmp_context.set_encryption_algorithm(encryption_algorithm);
+        self.rtmp_context.set_server_handshake(server_request);
+        self.rtmp_context.set_client_handshake(client_request);
+        self.handshake_state = HandshakeState::AwaitingClientResponse;
+        Ok(true)
+    }
+
+    fn handle_server_request(&mut self) -> IOResult<bool> {
+        if self.incoming.len() < 1 + 2 * HANDSHAKE_LEN {
+            return Ok(false)
+        }
+
+        let encryption_algorithm = self.take_encryption_algorithm();
+        let mut server_request = self.take_handshake();
+        let server_response = self.take_handshake();
+        let round_trip_time = self.rtmp_context.get_client_handshake().and_then(|client_request| client_request.measure_round_trip_time(&server_response));
+        self.store_round_trip_time(round_trip_time);
+
+        if self.rtmp_context.is_signed() {
+            let Some(server_digest_scheme) = server_request.find_digest_scheme(Handshake::SERVER_KEY) else {
+                return Err(inconsistent_sha(server_request.get_digest(DigestScheme::from(encryption_algorithm)).to_vec()))
+            };
+
+            /* NOTE: The server signs our request with the scheme which we have chosen. */
+            let digest_scheme = self.rtmp_context.get_digest_scheme().unwrap_or_default();
+            let mut server_response_key: Vec<u8> = Vec::new();
+            server_response_key.extend_from_slice(Handshake::SERVER_KEY);
+            server_response_key.extend_from_slice(Handshake::COMMON_KEY);
+            let client_request = self.rtmp_context.get_client_handshake().unwrap();
+            if !server_response.did_signature_match_request(client_request, digest_scheme, &server_response_key) {
+                return Err(inconsistent_sha(server_response.get_signature().to_vec()))
+            }
+
+            let mut client_response_key: Vec<u8> = Vec::new();
+            client_response_key.extend_from_slice(Handshake::CLIENT_KEY);
+            client_response_key.extend_from_slice(Handshake::COMMON_KEY);
+            server_request.imprint_signature(server_digest_scheme, &client_response_key);
+        }
+
+        self.put_handshake(&server_request);
+
+        self.rtmp_context.set_server_handshake(server_request);
+        self.rtmp_context.set_client_handshake(server_response);
+        self.complete_handshake();
+        Ok(true)
+    }
+
+    fn handle_client_response(&mut self) -> IOResult<bool> {
+        if self.incoming.len() < HANDSHAKE_LEN {
+            return Ok(false)
+        }
+
+        let client_response = self.take_handshake();
+        let round_trip_time = self.rtmp_context.get_server_handshake().and_then(|server_request| server_request.measure_round_trip_time(&client_response));
+        self.store_round_trip_time(round_trip_time);
+
+        if self.rtmp_context.is_signed() {
+            let digest_scheme = self.rtmp_context.get_digest_scheme().unwrap_or_default();
+            let mut client_response_key: Vec<u8> = Vec::new();
+            client_response_key.extend_from_slice(Handshake::CLIENT_KEY);
+            client_response_key.extend_from_slice(Handshake::COMMON_KEY);
+            let server_request = self.rtmp_context.get_server_handshake().unwrap();
+            // NOTE: FFmpeg acts the handshake but imprints no signature.
+            if !client_response.did_signature_match_request(server_request, digest_scheme, &client_response_key) && server_request.get_signature() != client_response.get_signature() {
+                return Err(inconsistent_sha(client_response.get_signature().to_vec()))
+            }
+        }
+
+        self.rtmp_context.set_server_handshake(client_response);
+        self.complete_handshake();
+        Ok(true)
+    }
+
+    fn handle_chunk(&mut self) -> IOResult<bool> {
+        let Some(first_byte) = self.incoming.first().copied() else {
+            return Ok(false)
+        };
+        let message_format = MessageFormat::from(first_byte >> 6);
+        let (chunk_id, basic_header_len) = match first_byte & 0x3f {
+            0 => match self.incoming.get(1) {
+                Some(&byte) => (byte as u16 + 64, 2),
+                None => return Ok(false)
+            },
+            1 => match self.incoming.get(1..3) {
+                Some(bytes) => (u16::from_le_bytes([bytes[0], bytes[1]]) + 64, 3),
+                None => return Ok(false)
+            },
+            chunk_id => (chunk_id as u16, 1)
+        };
+        let message_header_len = match message_format {
+            MessageFormat::New => 11,
+            MessageFormat::SameSource => 7,
+            MessageFormat::TimerChange => 3,
+            MessageFormat::Continue => 0
+        };
+        let Some(message_header_bytes) = self.incoming.get(basic_header_len..(basic_header_len + message_header_len)) else {
+            return Ok(false)
+        };
+        let message_header = parse_message_header(message_header_bytes, message_format);
+        let mut offset = basic_header_len + message_header_len;
+
+        let last_received_chunk = self.rtmp_context.get_last_received_chunk(&chunk_id).copied();
+        if last_received_chunk.is_none() && message_format != MessageFormat::New {
+            return Err(unknown_chunk_stream(chunk_id))
+        }
+
+        let has_extended_timestamp = match message_header.get_timestamp() {
+            Some(timestamp) => timestamp.as_millis() == U24_MAX as u128,
+            None => last_received_chunk.is_some_and(|last_received_chunk| last_received_chunk.get_timestamp().as_millis() >= U24_MAX as u128)
+        };
+        let extended_timestamp = if has_extended_timestamp {
+            let Some(extended_timestamp_bytes) = self.incoming.get(offset..(offset + 4)) else {
+                return Ok(false)
+            };
+            offset += 4;
+            Some(Duration::from_millis(u32::from_be_bytes(extended_timestamp_bytes.try_into().unwrap()) as u64))
+        } else {
+            None
+        };
+
+        let message_length = message_header.get_message_length().unwrap_or_else(|| last_received_chunk.unwrap().get_message_length());
+        let max_message_size = self.rtmp_context.get_max_message_size();
+        if message_length > max_message_size {
+            return Err(too_large_message(message_length, max_message_size))
+        }
+
+        /* NOTE: Any header except the Continue one starts a new message on its chunk stream. */
+        if message_format != MessageFormat::Continue {
+            self.partial_messages.remove(&chunk_id);
+        }
+        let received_length = self.partial_messages.get(&chunk_id).map_or(0, |partial_message| partial_message.len());
+        let chunk_size = self.rtmp_context.get_receiving_chunk_size().get_chunk_size();
+        let chunk_data_len = min(chunk_size, message_length - received_length as u32) as usize;
+        if self.incoming.len() < offset + chunk_data_len {
+            return Ok(false)
+        }
+
+        if let Some(last_received_chunk) = self.rtmp_context.get_last_received_chunk_mut(&chunk_id) {
+            if let Some(timestamp) = extended_timestamp.or(message_header.get_timestamp()) {
+                last_received_chunk.set_timestamp(timestamp);
+            }
+            if let Some(message_length) = message_header.get_message_length() {
+                last_received_chunk.set_message_length(message_length);
+            }
+            if let Some(message_type) = message_header.get_message_type() {
+                last_received_chunk.set_message_type(message_type);
+            }
+            if let Some(message_id) = message_header.get_message_id() {
+                last_received_chunk.set_message_id(message_id);
+            }
+        } else {
+            self.rtmp_context.insert_received_chunk(
+                chunk_id,
+                LastChunk::new(
+                    extended_timestamp.or(message_header.get_timestamp()).unwrap(),
+                    message_header.get_message_length().unwrap(),
+                    message_header.get_message_type().unwrap(),
+                    message_header.get_message_id().unwrap()
+                )
+            );
+        }
+
+        let chunk_data: Vec<u8> = self.incoming.drain(..(offset + chunk_data_len)).skip(offset).collect();
+        let partial_message = self.partial_messages.entry(chunk_id).or_default();
+        partial_message.extend_from_slice(&chunk_data);
+        if partial_message.len() == message_length as usize {
+            let data = self.partial_messages.remove(&chunk_id).unwrap();
+            self.handle_message(chunk_id, data.into())?;
+        }
+
+        Ok(true)
+    }
+
+    fn handle_message(&mut self, chunk_id: u16, data: Bytes) -> IOResult<()> {
+        let last_received_chunk = self.rtmp_context.get_last_received_chunk(&chunk_id).copied().unwrap();
+        let message_type = last_received_chunk.get_message_type();
+        let message_id = last_received_chunk.get_message_id();
+        let timestamp = last_received_chunk.get_timestamp();
+        let mut buffer: ByteBuffer = data.into();
+
+        let event = match message_type {
+            MessageType::ChunkSize => {
+                let chunk_size: ChunkSize = buffer.decode()?;
+                self.rtmp_context.set_receiving_chunk_size(chunk_size);
+                SessionEvent::Control(ControlMessage::ChunkSize(chunk_size))
+            },
+            MessageType::Acknowledgement => {
+                let acknowledgement: Acknowledgement = buffer.decode()?;
+                self.rtmp_context.get_flow_control_mut().acknowledge(acknowledgement);
+                SessionEvent::Control(ControlMessage::Acknowledgement(acknowledgement))
+            },
+            MessageType::WindowAcknowledgementSize => {
+                let window_acknowledgement_size: WindowAcknowledgementSize = buffer.decode()?;
+                self.rtmp_context.set_window_acknowledgement_size(window_acknowledgement_size);
+                SessionEvent::Control(ControlMessage::WindowAcknowledgementSize(window_acknowledgement_size))
+            },
+            MessageType::PeerBandwidth => {
+                let peer_bandwidth: PeerBandwidth = buffer.decode()?;
+                self.rtmp_context.set_peer_bandwidth(peer_bandwidth);
+                self.rtmp_context.get_flow_control_mut().apply_peer_bandwidth(peer_bandwidth);
+                SessionEvent::Control(ControlMessage::PeerBandwidth(peer_bandwidth))
+            },
+            MessageType::UserControl => {
+                let event_type: EventType = buffer.get_u16_be()?.into();
+                match event_type {
+                    EventType::PingRequest => {
+                        let ping_request: PingRequest = buffer.clone().decode()?;
+                        self.send_user_control(&PingResponse::new(ping_request.get_timestamp()));
+                    },
+                    EventType::PingResponse => {
+                        let ping_response: PingResponse = buffer.clone().decode()?;
+                        self.rtmp_context.set_round_trip_time(elapsed_since(Duration::from_millis(ping_response.get_timestamp() as u64)));
+                    },
+                    _ => {}
+                }
+                SessionEvent::Control(ControlMessage::UserControl(event_type, buffer))
+            },
+            MessageType::Audio | MessageType::Video | MessageType::Data | MessageType::Aggregate => SessionEvent::Media { message_type, message_id, timestamp, data: buffer.into() },
+            MessageType::Amf3Data => {
+                /* NOTE: AMF3 messages start with the format selector byte (always 0), then same as AMF0 ones. */
+                buffer.get_u8()?;
+                let data = buffer.get_shared_bytes(buffer.remained())?;
+                SessionEvent::Media { message_type: MessageType::Data, message_id, timestamp, data }
+            },
+            MessageType::Command | MessageType::Amf3Command => {
+                if let MessageType::Amf3Command = message_type {
+                    buffer.get_u8()?;
+                }
+                let command_name: AmfString = buffer.decode()?;
+                let transaction_id: Number = buffer.decode()?;
+                SessionEvent::Command { message_id, timestamp, command_name, transaction_id, arguments: buffer }
+            },
+            other => return self.rtmp_context.get_unknown_message_policy().apply(unknown_message(other))
+        };
+        self.events.push_back(event);
+        Ok(())
+    }
+}
+
+impl MeasureAcknowledgement for RtmpSession {
+    fn add_amount(&mut self, amount: u32) {
+        self.sequence_number = self.sequence_number.wrapping_add(amount);
+        self.unacknowledged_amount = self.unacknowledged_amount.saturating_add(amount);
+    }
+
+    fn get_sequence_number(&mut self) -> u32 {
+        self.sequence_number
+    }
+
+    fn get_unacknowledged_amount(&mut self) -> u32 {
+        self.unacknowledged_amount
+    }
+
+    fn set_unacknowledged_amount(&mut self, unacknowledged_amount: u32) {
+        self.unacknowledged_amount = unacknowledged_amount;
+    }
+}
+
+fn put_basic_header(bytes: &mut Vec<u8>, message_format: MessageFormat, chunk_id: u16) {
+    let message_format_bits = u8::from(message_format) << 6;
+    if chunk_id >= 320 {
+        bytes.push(message_format_bits | 1);
+        bytes.extend_from_slice(&(chunk_id - 64).to_le_bytes());
+    } else if chunk_id >= 64 {
+        bytes.push(message_format_bits);
+        bytes.push((chunk_id - 64) as u8);
+    } else {
+        bytes.push(message_format_bits | chunk_id as u8);
+    }
+}
+
+fn put_message_header(bytes: &mut Vec<u8>, message_header: &MessageHeader) {
+    if let Some(timestamp) = message_header.get_timestamp() {
+        bytes.extend_from_slice(&(timestamp.as_millis() as u32).to_be_bytes()[1..]);
+    }
+    if let Some(message_length) = message_header.get_message_length() {
+        bytes.extend_from_slice(&message_length.to_be_bytes()[1..]);
+    }
+    if let Some(message_type) = message_header.get_message_type() {
+        bytes.push(message_type.into());
+    }
+    if let Some(message_id) = message_header.get_message_id() {
+        bytes.extend_from_slice(&message_id.to_le_bytes());
+    }
+}
+
+fn parse_message_header(bytes: &[u8], message_format: MessageFormat) -> MessageHeader {
+    let timestamp = || Duration::from_millis(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as u64);
+    let message_length = || u32::from_be_bytes([0, bytes[3], bytes[4], bytes[5]]);
+    let message_type = || MessageType::from(bytes[6]);
+    let message_id = || u32::from_le_bytes([bytes[7], bytes[8], bytes[9], bytes[10]]);
+
+    match message_format {
+        MessageFormat::New => (timestamp(), message_length(), message_type(), message_id()).into(),
+        MessageFormat::SameSource => (timestamp(), message_length(), message_type()).into(),
+        MessageFormat::TimerChange => timestamp().into(),
+        MessageFormat::Continue => ().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn establish(signed: bool) -> (RtmpSession, RtmpSession) {
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_signed(signed);
+        let mut client = RtmpSession::new(SessionRole::Client, rtmp_context);
+        let mut server = RtmpSession::new(SessionRole::Server, RtmpContext::default());
+        server.feed(&client.take_outgoing()).unwrap();
+        client.feed(&server.take_outgoing()).unwrap();
+        server.feed(&client.take_outgoing()).unwrap();
+        assert!(matches!(client.poll_event(), Some(SessionEvent::HandshakeDone)));
+        assert!(matches!(server.poll_event(), Some(SessionEvent::HandshakeDone)));
+        (client, server)
+    }
+
+    #[test]
+    fn signed_handshake() {
+        let (mut client, mut server) = establish(true);
+        assert!(server.get_rtmp_context_mut().is_signed());
+        assert!(client.take_outgoing().is_empty());
+        assert!(server.take_outgoing().is_empty())
+    }
+
+    #[test]
+    fn err_tampered_digest() {
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_signed(true);
+        let mut client = RtmpSession::new(SessionRole::Client, rtmp_context);
+        let mut server = RtmpSession::new(SessionRole::Server, RtmpContext::default());
+        let mut client_request = client.take_outgoing();
+        let digest_offset = 1 + 8 + 4;
+        client_request[digest_offset] = !client_request[digest_offset];
+        assert!(server.feed(&client_request).is_err())
+    }
+
+    #[test]
+    fn digest_scheme_got_detected() {
+        let mut client_request = Handshake::new(Duration::default(), Version::LATEST_CLIENT);
+        client_request.imprint_digest(DigestScheme::Scheme1, Handshake::CLIENT_KEY);
+        let mut bytes: Vec<u8> = vec![EncryptionAlgorithm::NotEncrypted.into()];
+        bytes.extend_from_slice(client_request.get_bytes());
+
+        let mut server = RtmpSession::new(SessionRole::Server, RtmpContext::default());
+        server.feed(&bytes).unwrap();
+        assert_eq!(Some(DigestScheme::Scheme1), server.get_rtmp_context_mut().get_digest_scheme());
+
+        let outgoing = server.take_outgoing();
+        let mut server_request_bytes: [u8; HANDSHAKE_LEN] = [0; HANDSHAKE_LEN];
+        server_request_bytes.copy_from_slice(&outgoing[1..(1 + HANDSHAKE_LEN)]);
+        let server_request = Handshake::from(server_request_bytes);
+        assert_eq!(Some(DigestScheme::Scheme1), server_request.find_digest_scheme(Handshake::SERVER_KEY))
+    }
+
+    #[test]
+    fn round_trip_time_got_estimated() {
+        let (mut client, mut server) = establish(true);
+        assert!(client.get_rtmp_context_mut().get_round_trip_time().is_some());
+        assert!(server.get_rtmp_context_mut().get_round_trip_time().is_some());
+        assert!(client.get_rtmp_context_mut().get_await_duration().is_some());
+
+        /* NOTE: A Ping Response which echoes a timestamp of 1 second ago. */
+        let timestamp = epoch_timestamp().as_millis() as u32;
+        client.send_user_control(&PingResponse::new(timestamp.wrapping_sub(1000)));
+        server.feed(&client.take_outgoing()).unwrap();
+        assert!(server.get_rtmp_context_mut().get_round_trip_time().unwrap() >= Duration::from_millis(1000 / 8))
+    }
+
+    #[test]
+    fn feed_byte_by_byte() {
+        let mut client = RtmpSession::new(SessionRole::Client, RtmpContext::default());
+        let mut server = RtmpSession::new(SessionRole::Server, RtmpContext::default());
+        for byte in client.take_outgoing() {
+            server.feed(&[byte]).unwrap();
+        }
+        for byte in server.take_outgoing() {
+            client.feed(&[byte]).unwrap();
+        }
+        for byte in client.take_outgoing() {
+            server.feed(&[byte]).unwrap();
+        }
+        assert!(client.is_handshake_done());
+        assert!(server.is_handshake_done());
+        server.poll_event();
+
+        client.set_chunk_size(ChunkSize::new(4096));
+        let data: Vec<u8> = (0..=u8::MAX).cycle().take(1000).collect();
+        client.send_message(6, Duration::default(), MessageType::Video, 1, &data);
+        for byte in client.take_outgoing() {
+            server.feed(&[byte]).unwrap();
+        }
+        assert!(matches!(server.poll_event(), Some(SessionEvent::Control(ControlMessage::ChunkSize(chunk_size))) if chunk_size.get_chunk_size() == 4096));
+        assert!(matches!(server.poll_event(), Some(SessionEvent::Media { data: received, .. }) if received == data))
+    }
+
+    #[test]
+    fn reassemble_interleaved_chunks() {
+        let (mut client, mut server) = establish(false);
+
+        let video: Vec<u8> = vec![0x17; 200];
+        let audio: Vec<u8> = vec![0xaf; 200];
+        client.send_message(6, Duration::from_millis(33), MessageType::Video, 1, &video);
+        let video_chunks = client.take_outgoing();
+        client.send_message(4, Duration::from_millis(21), MessageType::Audio, 1, &audio);
+        let audio_chunks = client.take_outgoing();
+
+        /* Both messages are split at 128 bytes after 12 bytes of headers. */
+        let (video_first, video_second) = video_chunks.split_at(12 + 128);
+        let (audio_first, audio_second) = audio_chunks.split_at(12 + 128);
+        server.feed(video_first).unwrap();
+        server.feed(audio_first).unwrap();
+        server.feed(audio_second).unwrap();
+        server.feed(video_second).unwrap();
+
+        assert!(matches!(server.poll_event(), Some(SessionEvent::Media { message_type: MessageType::Audio, data, .. }) if data == audio));
+        assert!(matches!(server.poll_event(), Some(SessionEvent::Media { message_type: MessageType::Video, data, .. }) if data == video))
+    }
+
+    #[test]
+    fn extended_timestamp_in_every_chunk() {
+        let (mut client, mut server) = establish(false);
+
+        let timestamp = Duration::from_millis(U24_MAX as u64 + 1);
+        let data: Vec<u8> = vec![0x27; 300];
+        client.send_message(6, timestamp, MessageType::Video, 1, &data);
+        /* 12 bytes of headers, 4 bytes of the extended timestamp, and 1 + 4 bytes for each of 2 continuations. */
+        let chunks = client.take_outgoing();
+        assert_eq!(12 + 4 + 300 + 2 * (1 + 4), chunks.len());
+
+        server.feed(&chunks).unwrap();
+        assert!(matches!(server.poll_event(), Some(SessionEvent::Media { timestamp: received, .. }) if received == timestamp))
+    }
+
+    #[test]
+    fn answer_ping_and_acknowledge() {
+        let (mut client, mut server) = establish(false);
+
+        server.send_user_control(&PingRequest::new(1000));
+        client.feed(&server.take_outgoing()).unwrap();
+        assert!(matches!(client.poll_event(), Some(SessionEvent::Control(ControlMessage::UserControl(EventType::PingRequest, _)))));
+        server.feed(&client.take_outgoing()).unwrap();
+        assert!(matches!(server.poll_event(), Some(SessionEvent::Control(ControlMessage::UserControl(EventType::PingResponse, _)))));
+
+        server.send_chunk_data(u32::default(), Duration::default(), &WindowAcknowledgementSize::new(4096));
+        client.feed(&server.take_outgoing()).unwrap();
+        client.poll_event();
+        server.send_message(6, Duration::default(), MessageType::Video, 1, &[0x17; 4096]);
+        client.feed(&server.take_outgoing()).unwrap();
+        server.feed(&client.take_outgoing()).unwrap();
+        match server.poll_event() {
+            Some(SessionEvent::Control(ControlMessage::Acknowledgement(acknowledgement))) => assert!(acknowledgement.get_inner() > 4096),
+            _ => panic!("Acknowledgement should be received.")
+        }
+    }
+
+    #[test]
+    fn acknowledgement_counts_handshake() {
+        let (mut client, mut server) = establish(false);
+
+        server.get_rtmp_context_mut().set_window_acknowledgement_size(WindowAcknowledgementSize::new(1));
+        client.send_message(6, Duration::default(), MessageType::Video, 1, &[0x17; 64]);
+        server.feed(&client.take_outgoing()).unwrap();
+        client.feed(&server.take_outgoing()).unwrap();
+        match client.poll_event() {
+            Some(SessionEvent::Control(ControlMessage::Acknowledgement(acknowledgement))) => assert_eq!(client.get_rtmp_context_mut().get_flow_control().get_sent_amount(), acknowledgement.get_inner()),
+            _ => panic!("Acknowledgement should be received.")
+        }
+        assert_eq!(0, client.get_rtmp_context_mut().get_flow_control().get_unacknowledged_amount())
+    }
+
+    #[test]
+    fn err_unknown_chunk_stream() {
+        let (_, mut server) = establish(false);
+        assert!(server.feed(&[u8::from(MessageFormat::Continue) << 6 | 6]).is_err())
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+use bytes::Bytes;
+use crate::{
+    ByteBuffer,
+    messages::{
+        Acknowledgement,
+        ChunkSize,
+        EventType,
+        PeerBandwidth,
+        WindowAcknowledgementSize,
+        amf::v0::{
+            AmfString,
+            Number
+        },
+        headers::MessageType
+    }
+};
+
+/// The protocol control messages which a session has received.
+///
+/// These have been already applied to the session when they are reported.
+///
+/// |Pattern|Message|
+/// | :- | :- |
+/// |`ChunkSize`|[`ChunkSize`]|
+/// |`Acknowledgement`|[`Acknowledgement`]|
+/// |`WindowAcknowledgementSize`|[`WindowAcknowledgementSize`]|
+/// |`PeerBandwidth`|[`PeerBandwidth`]|
+/// |`UserControl`|An event type and its remained data.|
+#[derive(Debug, Clone)]
+pub enum ControlMessage {
+    ChunkSize(ChunkSize),
+    Acknowledgement(Acknowledgement),
+    WindowAcknowledgementSize(WindowAcknowledgementSize),
+    PeerBandwidth(PeerBandwidth),
+    UserControl(EventType, ByteBuffer)
+}
+
+/// The events which a session emits while reading incoming bytes.
+///
+/// |Pattern|Event|
+/// | :- | :- |
+/// |`HandshakeDone`|The handshake has been completed, so chunks can be sent after this.|
+/// |`Control`|Some protocol control message has been received. See [`ControlMessage`].|
+/// |`Command`|Some command message has been received. Its arguments are left in the buffer.|
+/// |`Media`|Some audio, video, data or aggregate message has been received.|
+///
+/// Note AMF3 messages are reported as same as AMF0 ones, that is, their format selector bytes are skipped.
+#[derive(Debug, Clone)]
+pub enum SessionEvent {
+    HandshakeDone,
+    Control(ControlMessage),
+    Command {
+        message_id: u32,
+        timestamp: Duration,
+        command_name: AmfString,
+        transaction_id: Number,
+        arguments: ByteBuffer
+    },
+    Media {
+        message_type: MessageType,
+        message_id: u32,
+        timestamp: Duration,
+        data: Bytes
+    }
+}
@@ -0,0 +1,11 @@
+/// Which side of the handshake a session takes.
+///
+/// |Pattern|Behavior|
+/// | :- | :- |
+/// |`Client`|Sends C0 and C1 first, then answers S1 as C2.|
+/// |`Server`|Awaits C0 and C1, then sends S0, S1 and S2.|
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionRole {
+    Client,
+    Server
+}
@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some chunk omits its message header though its chunk stream hasn't started yet.
+#[derive(Debug)]
+pub struct UnknownChunkStream(u16);
+
+impl Display for UnknownChunkStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Chunk stream hasn't started yet. chunk id: {}", self.0)
+    }
+}
+
+impl Error for UnknownChunkStream {}
+
+/// A utility function of constructing an `UnknownChunkStream` error.
+pub fn unknown_chunk_stream(chunk_id: u16) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnknownChunkStream(chunk_id)
+    )
+}
@@ -10,7 +10,10 @@ use std::{
     pin::Pin,
     time::Duration
 };
-use tokio::io::AsyncWrite;
+use tokio::io::{
+    AsyncWrite,
+    AsyncWriteExt
+};
 use crate::{
     U24_MAX,
     handlers::{
@@ -83,7 +86,7 @@ pub use self::{
 ///     let message_header = read_message_header(stream.as_mut(), basic_header.get_message_format()).await?;
 ///     let receiving_chunk_size = rtmp_context.get_receiving_chunk_size();
 ///     let message_length = rtmp_context.get_last_sent_chunk(&basic_header.get_chunk_id()).unwrap().get_message_length();
-///     let actual = read_chunk_data(stream.as_mut(), receiving_chunk_size, message_length).await?;
+///     let actual = read_chunk_data(stream.as_mut(), receiving_chunk_size, message_length, rtmp_context.get_max_message_size()).await?;
 ///     assert_eq!(expected, actual);
 ///
 ///     Ok(())
@@ -152,5 +155,53 @@ pub async fn write_chunk<'a, W: AsyncWrite>(mut writer: Pin<&'a mut W>, rtmp_con
         );
     }
 
-    write_chunk_data(writer.as_mut(), chunk_id, rtmp_context.get_sending_chunk_size(), data).await
+    let chunk_size = rtmp_context.get_sending_chunk_size();
+    write_chunk_data(writer.as_mut(), chunk_id, chunk_size, data).await?;
+
+    /* NOTE: The peer acknowledges also bytes of headers, so the flow control counts them too. */
+    let basic_header_len = if chunk_id >= 320 {
+        3
+    } else if chunk_id >= 64 {
+        2
+    } else {
+        1
+    };
+    let message_header_len = match message_format {
+        MessageFormat::New => 11,
+        MessageFormat::SameSource => 7,
+        MessageFormat::TimerChange => 3,
+        MessageFormat::Continue => 0
+    };
+    let extended_timestamp_len = if extended_timestamp.is_some() { 4 } else { 0 };
+    let continue_headers_len = data.len().div_ceil(chunk_size.get_chunk_size() as usize).saturating_sub(1) * basic_header_len;
+    let sent_amount = basic_header_len + message_header_len + extended_timestamp_len + data.len() + continue_headers_len;
+    rtmp_context.get_flow_control_mut().add_sent_amount(sent_amount as u32);
+
+    /* NOTE: Awaits until the peer accepts whole chunks, so that a slow peer throttles us instead of piling bytes up. */
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+    use tokio::io::AsyncReadExt;
+    use crate::{
+        handlers::VecStream,
+        messages::ChunkSize
+    };
+    use super::*;
+
+    #[tokio::test]
+    async fn count_sent_amount() {
+        let mut stream = pin!(VecStream::default());
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_sending_chunk_size(ChunkSize::new(128));
+        let data = vec![0u8; 300];
+        write_chunk(stream.as_mut(), &mut rtmp_context, 64, Duration::from_millis(U24_MAX as u64), MessageType::Video, 1, &data).await.unwrap();
+        write_chunk(stream.as_mut(), &mut rtmp_context, 64, Duration::from_millis(U24_MAX as u64), MessageType::Video, 1, &data).await.unwrap();
+
+        let mut written = [0u8; 1024];
+        let written_len = stream.read(&mut written).await.unwrap();
+        assert_eq!(written_len as u32, rtmp_context.get_flow_control().get_sent_amount())
+    }
 }
@@ -1,10 +1,12 @@
 use std::{
     future::Future,
-    io::Result as IOResult,
-    pin::{
-        Pin,
-        pin
+    io::{
+        Error as IOError,
+        ErrorKind,
+        IoSlice,
+        Result as IOResult
     },
+    pin::Pin,
     task::{
         Context as FutureContext,
         Poll
@@ -12,15 +14,9 @@ use std::{
 };
 use futures::ready;
 use tokio::io::AsyncWrite;
-use crate::{
-    messages::{
-        ChunkSize,
-        headers::{
-            BasicHeader,
-            MessageFormat
-        }
-    },
-    writers::write_basic_header
+use crate::messages::{
+    ChunkSize,
+    headers::MessageFormat
 };
 
 #[doc(hidden)]
@@ -30,6 +26,27 @@ pub struct ChunkDataWriter<'a, W: AsyncWrite> {
     chunk_id: u16,
     chunk_size: ChunkSize,
     chunk_data: &'a [u8],
+    written: usize
+}
+
+#[doc(hidden)]
+impl<W: AsyncWrite> ChunkDataWriter<'_, W> {
+    fn continue_header(&self) -> ([u8; 3], usize) {
+        let message_format_bits = u8::from(MessageFormat::Continue) << 6;
+        let mut basic_header_bytes: [u8; 3] = [0; 3];
+        if self.chunk_id >= 320 {
+            basic_header_bytes[0] = message_format_bits | 1;
+            basic_header_bytes[1..].copy_from_slice(&(self.chunk_id - 64).to_le_bytes());
+            (basic_header_bytes, 3)
+        } else if self.chunk_id >= 64 {
+            basic_header_bytes[0] = message_format_bits;
+            basic_header_bytes[1] = (self.chunk_id - 64) as u8;
+            (basic_header_bytes, 2)
+        } else {
+            basic_header_bytes[0] = message_format_bits | self.chunk_id as u8;
+            (basic_header_bytes, 1)
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -37,15 +54,39 @@ impl<W: AsyncWrite> Future for ChunkDataWriter<'_, W> {
     type Output = IOResult<()>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<Self::Output> {
-        let chunk_size = self.chunk_size.get_chunk_size();
-        let mut chunks = self.chunk_data.chunks(chunk_size as usize);
-        while let Some(chunk) = chunks.next() {
-            ready!(self.writer.as_mut().poll_write(cx, chunk))?;
+        let (continue_header, continue_header_len) = self.continue_header();
+        let continue_header = &continue_header[..continue_header_len];
+        let chunk_data = self.chunk_data;
+        let chunk_size = self.chunk_size.get_chunk_size() as usize;
+
+        /* NOTE: Interleaves chunks and continue headers as slices, so that whole chunks are handed over at once without copying them. */
+        let mut segments: Vec<&[u8]> = Vec::new();
+        for (i, chunk) in chunk_data.chunks(chunk_size).enumerate() {
+            if i > 0 {
+                segments.push(continue_header);
+            }
+            segments.push(chunk);
+        }
+        let total_len: usize = segments.iter().map(|segment| segment.len()).sum();
+
+        while self.written < total_len {
+            let mut skipped = self.written;
+            let slices: Vec<IoSlice<'_>> = segments.iter().filter_map(
+                |segment| if skipped >= segment.len() {
+                    skipped -= segment.len();
+                    None
+                } else {
+                    let slice = IoSlice::new(&segment[skipped..]);
+                    skipped = 0;
+                    Some(slice)
+                }
+            ).collect();
 
-            if chunks.size_hint().0 >= 1 {
-                let basic_header = BasicHeader::new(MessageFormat::Continue, self.chunk_id);
-                ready!(pin!(write_basic_header(self.writer.as_mut(), &basic_header)).poll(cx))?;
+            let written = ready!(self.writer.as_mut().poll_write_vectored(cx, &slices))?;
+            if written == 0 {
+                return Poll::Ready(Err(IOError::from(ErrorKind::WriteZero)))
             }
+            self.written += written;
         }
 
         Poll::Ready(Ok(()))
@@ -56,6 +97,7 @@ impl<W: AsyncWrite> Future for ChunkDataWriter<'_, W> {
 ///
 /// If a chunk data exceeds specified chunk size, continue headers is inserted between chunk data per chunk size.
 /// Note the message length doesn't count their headers.
+/// Chunks and continue headers are handed over to the stream as one vectored write, as far as the stream accepts.
 ///
 /// # Examples
 ///
@@ -126,7 +168,7 @@ impl<W: AsyncWrite> Future for ChunkDataWriter<'_, W> {
 /// }
 /// ```
 pub fn write_chunk_data<'a, W: AsyncWrite>(writer: Pin<&'a mut W>, chunk_id: u16, chunk_size: ChunkSize, chunk_data: &'a [u8]) -> ChunkDataWriter<'a, W> {
-    ChunkDataWriter { writer, chunk_id, chunk_size, chunk_data }
+    ChunkDataWriter { writer, chunk_id, chunk_size, chunk_data, written: 0 }
 }
 
 #[cfg(test)]
@@ -9,6 +9,8 @@ mod empty_topic_id;
 mod metadata_not_found;
 mod stream_is_unpublished;
 mod inconsistent_topic_id;
+mod swf_is_unverified;
+mod unpublished_media;
 
 pub use self::rtmp::RtmpHandler;
 use self::{
@@ -19,5 +21,7 @@ use self::{
     empty_topic_id::*,
     metadata_not_found::*,
     stream_is_unpublished::*,
-    inconsistent_topic_id::*
+    inconsistent_topic_id::*,
+    swf_is_unverified::*,
+    unpublished_media::*
 };
@@ -1,3 +1,7 @@
 mod write_acknowledgement;
+mod write_ping_request;
 
-pub use self::write_acknowledgement::*;
+pub use self::{
+    write_acknowledgement::*,
+    write_ping_request::*
+};
@@ -0,0 +1,65 @@
+use std::{
+    io::Result as IOResult,
+    pin::Pin,
+    time::{
+        Duration,
+        Instant
+    }
+};
+use log::info;
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite
+};
+use sheave_core::{
+    ByteBuffer,
+    Encoder,
+    handlers::{
+        AsyncHandler,
+        MeasureAcknowledgement,
+        RtmpContext,
+        Middleware
+    },
+    handshake::epoch_timestamp,
+    messages::{
+        ChunkData,
+        PingRequest,
+        UserControl
+    },
+    writers::write_chunk
+};
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct PingRequestWriter(Instant);
+
+#[doc(hidden)]
+impl Middleware for PingRequestWriter {
+    async fn handle_wrapped<R, W, H>(&mut self, mut reader: Pin<&mut R>, mut writer: Pin<&mut W>, rtmp_context: &mut RtmpContext, handler: &mut H) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        H: AsyncHandler + Send
+    {
+        handler.handle(reader.as_mut(), writer.as_mut(), rtmp_context).await?;
+
+        /* NOTE: The round-trip time is measured only from responses which echo the timestamp stored here. */
+        if self.0.elapsed() >= RtmpContext::PING_INTERVAL {
+            let timestamp = epoch_timestamp().as_millis() as u32;
+            let mut buffer = ByteBuffer::default();
+            buffer.put_u16_be(PingRequest::EVENT_TYPE.into());
+            buffer.encode(&PingRequest::new(timestamp));
+            write_chunk(writer, rtmp_context, PingRequest::CHANNEL.into(), Duration::default(), PingRequest::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+            rtmp_context.set_ping_timestamp(timestamp);
+            self.0 = Instant::now();
+            info!("Ping Request got sent.");
+        }
+
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub fn write_ping_request() -> PingRequestWriter {
+    PingRequestWriter(Instant::now())
+}
@@ -1,5 +1,4 @@
 use std::{
-    future::Future,
     io::{
         Error as IOError,
         ErrorKind,
@@ -9,25 +8,19 @@ use std::{
         Pin,
         pin
     },
-    sync::Arc,
-    task::{
-        Context as FutureContext,
-        Poll
-    },
-    time::{
-        Duration,
-        Instant
-    }
+    time::Duration
 };
+use bytes::Bytes;
 use log::{
     debug,
     error,
-    info
+    info,
+    warn
 };
-use futures::ready;
 use tokio::io::{
     AsyncRead,
-    AsyncWrite
+    AsyncWrite,
+    AsyncWriteExt
 };
 use sheave_core::{
     ByteBuffer,
@@ -38,20 +31,28 @@ use sheave_core::{
     handlers::{
         AsyncHandler,
         AsyncHandlerExt,
+        Capabilities,
         ClientType,
         ErrorHandler,
+        FlowControl,
         HandlerConstructor,
         LastChunk,
+        MeasureAcknowledgement,
+        Pacing,
         PublisherStatus,
         RtmpContext,
-        StreamWrapper,
         SubscriberStatus,
         inconsistent_sha,
         stream_got_exhausted
     },
     handshake::{
+        DiffieHellman,
+        DigestScheme,
+        EncryptionAlgorithm,
         Handshake,
-        Version
+        Version,
+        elapsed_since,
+        epoch_timestamp
     },
     messages::{
         /* Used in common */
@@ -68,13 +69,17 @@ use sheave_core::{
         Audio,
         Video,
         SetDataFrame,
+        Aggregate,
         Acknowledgement,
         amf::v0::{
             AmfString,
+            AmfValue,
             Number,
             Object
         },
         headers::MessageType,
+        unknown_message,
+        unknown_event,
 
         /* Publisher-side */
         ReleaseStream,
@@ -95,10 +100,20 @@ use sheave_core::{
         PlaylistReady,
         Play,
         SetBufferLength,
+        StreamIsRecorded,
+        StreamEof,
+
+        /* Either side */
+        PeerBandwidth,
+        PingRequest,
+        PingResponse,
+        SwfVerifyRequest,
+        SwfVerifyResponse,
     },
     net::RtmpReadExt,
     object,
     readers::*,
+    session::unknown_chunk_stream,
     writers::*
 };
 use super::{
@@ -107,13 +122,18 @@ use super::{
     undistinguishable_client,
     empty_topic_id,
     inconsistent_topic_id,
-    middlewares::write_acknowledgement,
+    swf_is_unverified,
+    middlewares::{
+        write_acknowledgement,
+        write_ping_request
+    },
 
     /* Publisher-side */
     publish_topic,
     provide_message_id,
     unpublish_topic,
     return_message_id,
+    unpublished_media,
 
     /* Subscriver-side */
     subscribe_topic,
@@ -122,43 +142,71 @@ use super::{
 
 #[doc(hidden)]
 #[derive(Debug)]
-struct HandshakeHandler<'a, RW: AsyncRead + AsyncWrite + Unpin>(Pin<&'a mut RW>);
+struct HandshakeHandler<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(Pin<&'a mut R>, Pin<&'a mut W>);
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> HandshakeHandler<'_, RW> {
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> HandshakeHandler<'_, R, W> {
     async fn handle_first_handshake(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         let encryption_algorithm = read_encryption_algorithm(pin!(self.0.await_until_receiving())).await?;
         let mut client_request = read_handshake(pin!(self.0.await_until_receiving())).await?;
+        let rtmpe_keys = rtmp_context.get_rtmpe_keys().cloned();
 
         if client_request.get_version() == Version::UNSIGNED {
-            let server_request = Handshake::new(Instant::now().elapsed(), Version::UNSIGNED);
-            write_encryption_algorithm(self.0.as_mut(), encryption_algorithm).await?;
-            write_handshake(self.0.as_mut(), &server_request).await?;
-            write_handshake(self.0.as_mut(), &client_request).await?;
+            let server_request = Handshake::new(epoch_timestamp(), Version::UNSIGNED);
+            write_encryption_algorithm(self.1.as_mut(), encryption_algorithm).await?;
+            write_handshake(self.1.as_mut(), &server_request).await?;
+            write_handshake(self.1.as_mut(), &client_request).await?;
 
+            if let Some(rtmpe_keys) = rtmpe_keys {
+                rtmpe_keys.disable();
+            }
             rtmp_context.set_encryption_algorithm(encryption_algorithm);
             rtmp_context.set_server_handshake(server_request);
             rtmp_context.set_client_handshake(client_request);
         } else {
-            if !client_request.did_digest_match(encryption_algorithm, Handshake::CLIENT_KEY) {
-                error!("Invalid SHA digest/signature: {:x?}", client_request.get_digest(encryption_algorithm));
-                return Err(inconsistent_sha(client_request.get_digest(encryption_algorithm).to_vec()))
-            } else {
-                let mut server_request = Handshake::new(Instant::now().elapsed(), Version::LATEST_SERVER);
-                server_request.imprint_digest(encryption_algorithm, Handshake::SERVER_KEY);
-                let mut server_response_key: Vec<u8> = Vec::new();
-                server_response_key.extend_from_slice(Handshake::SERVER_KEY);
-                server_response_key.extend_from_slice(Handshake::COMMON_KEY);
-                client_request.imprint_signature(encryption_algorithm, &server_response_key);
-                write_encryption_algorithm(self.0.as_mut(), encryption_algorithm).await?;
-                write_handshake(self.0.as_mut(), &server_request).await?;
-                write_handshake(self.0.as_mut(), &client_request).await?;
-
-                rtmp_context.set_signed(true);
-                rtmp_context.set_encryption_algorithm(encryption_algorithm);
-                rtmp_context.set_server_handshake(server_request);
-                rtmp_context.set_client_handshake(client_request);
+            let Some(digest_scheme) = client_request.find_digest_scheme(Handshake::CLIENT_KEY) else {
+                error!("Invalid SHA digest/signature: {:x?}", client_request.get_digest(DigestScheme::default()));
+                return Err(inconsistent_sha(client_request.get_digest(DigestScheme::default()).to_vec()))
+            };
+            debug!("Digest scheme: {digest_scheme:?}");
+
+            /* NOTE: Responds 3 if the client requires RTMPE but this server doesn't serve it. */
+            let server_encryption_algorithm = match (encryption_algorithm, &rtmpe_keys) {
+                (EncryptionAlgorithm::DiffieHellman, None) => EncryptionAlgorithm::NotEncrypted,
+                _ => encryption_algorithm
+            };
+            let mut server_request = Handshake::new(epoch_timestamp(), Version::LATEST_SERVER);
+            if let Some(rtmpe_keys) = rtmpe_keys {
+                if let EncryptionAlgorithm::DiffieHellman = encryption_algorithm {
+                    let diffie_hellman = DiffieHellman::generate();
+                    let client_public_key = client_request.get_public_key(digest_scheme);
+                    let shared_secret = diffie_hellman.compute_shared_secret(client_public_key)?;
+                    rtmpe_keys.install(&shared_secret, diffie_hellman.get_public_key(), client_public_key);
+                    server_request.imprint_public_key(digest_scheme, diffie_hellman.get_public_key());
+                } else {
+                    rtmpe_keys.disable();
+                }
             }
+            server_request.imprint_digest(digest_scheme, Handshake::SERVER_KEY);
+            let mut server_response_key: Vec<u8> = Vec::new();
+            server_response_key.extend_from_slice(Handshake::SERVER_KEY);
+            server_response_key.extend_from_slice(Handshake::COMMON_KEY);
+            client_request.imprint_signature(digest_scheme, &server_response_key);
+            write_encryption_algorithm(self.1.as_mut(), server_encryption_algorithm).await?;
+            write_handshake(self.1.as_mut(), &server_request).await?;
+            write_handshake(self.1.as_mut(), &client_request).await?;
+
+            rtmp_context.set_signed(true);
+            rtmp_context.set_encryption_algorithm(server_encryption_algorithm);
+            rtmp_context.set_digest_scheme(digest_scheme);
+            rtmp_context.set_server_handshake(server_request);
+            rtmp_context.set_client_handshake(client_request);
+        }
+
+        /* NOTE: The response depends on S1 as it has been sent, so it is computed before C2 overwrites it. */
+        if let Some(swf_verification) = rtmp_context.get_swf_verification() {
+            let swf_verify_response = swf_verification.respond(rtmp_context.get_server_handshake().unwrap());
+            rtmp_context.set_swf_verify_response(swf_verify_response);
         }
 
         info!("First handshake got handled.");
@@ -168,16 +216,26 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> HandshakeHandler<'_, RW> {
     async fn handle_second_handshake(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         let client_response = read_handshake(pin!(self.0.await_until_receiving())).await?;
 
+        /* NOTE: C2 is expected to echo the timestamp of S1, otherwise the round-trip time can't be measured. */
+        match rtmp_context.get_server_handshake().unwrap().measure_round_trip_time(&client_response) {
+            Some(round_trip_time) => {
+                debug!("Round-trip time: {round_trip_time:?}");
+                rtmp_context.set_round_trip_time(round_trip_time);
+            },
+            None => warn!("C2 didn't echo the timestamp of S1: {:?}", client_response.get_timestamp())
+        }
+        rtmp_context.seed_from_round_trip_time();
+
         if !rtmp_context.is_signed() {
             rtmp_context.set_server_handshake(client_response);
         } else {
-            let encryption_algorithm = rtmp_context.get_encryption_algorithm().unwrap();
+            let digest_scheme = rtmp_context.get_digest_scheme().unwrap_or_default();
             let mut client_response_key: Vec<u8> = Vec::new();
             client_response_key.extend_from_slice(Handshake::CLIENT_KEY);
             client_response_key.extend_from_slice(Handshake::COMMON_KEY);
             let server_request = rtmp_context.get_server_handshake().unwrap();
             // NOTE: FFmpeg acts the handshake but imprints no signature.
-            if !client_response.did_signature_match(encryption_algorithm, &client_response_key) && server_request.get_signature() != client_response.get_signature() {
+            if !client_response.did_signature_match_request(server_request, digest_scheme, &client_response_key) && server_request.get_signature() != client_response.get_signature() {
                 error!("Invalid SHA digest/signature: {:x?}", client_response.get_signature());
                 return Err(inconsistent_sha(client_response.get_signature().to_vec()))
             } else {
@@ -193,31 +251,60 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> HandshakeHandler<'_, RW> {
 }
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for HandshakeHandler<'_, RW> {
-    fn poll_handle(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        ready!(pin!(self.handle_first_handshake(rtmp_context)).poll(cx))?;
-        pin!(self.handle_second_handshake(rtmp_context)).poll(cx)
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> HandshakeHandler<'_, R, W> {
+    async fn handle(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
+        self.handle_first_handshake(rtmp_context).await?;
+        self.1.as_mut().flush().await?;
+        self.handle_second_handshake(rtmp_context).await?;
+
+        /* NOTE: The client's acknowledgements count also S0-S2. */
+        rtmp_context.get_flow_control_mut().add_sent_amount(FlowControl::HANDSHAKE_SIZE);
+        Ok(())
     }
 }
 
 #[doc(hidden)]
-fn handle_handshake<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>) -> HandshakeHandler<'a, RW> {
-    HandshakeHandler(stream)
+fn handle_handshake<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(reader: Pin<&'a mut R>, writer: Pin<&'a mut W>) -> HandshakeHandler<'a, R, W> {
+    HandshakeHandler(reader, writer)
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+struct HandshakeStep;
+
+#[doc(hidden)]
+impl AsyncHandler for HandshakeStep {
+    async fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        handle_handshake(reader, writer).handle(rtmp_context).await
+    }
 }
 
 #[doc(hidden)]
 #[derive(Debug)]
-struct MessageHandler<'a, RW: AsyncRead + AsyncWrite + Unpin>(Pin<&'a mut RW>);
+struct MessageHandler<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(Pin<&'a mut R>, Pin<&'a mut W>);
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
-    async fn handle_acknowledgement(&mut self, _: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
-        Decoder::<Acknowledgement>::decode(&mut buffer)?;
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> MessageHandler<'_, R, W> {
+    async fn handle_acknowledgement(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let acknowledgement: Acknowledgement = buffer.decode()?;
+        rtmp_context.get_flow_control_mut().acknowledge(acknowledgement);
 
         info!("Acknowledgement got handled.");
         Ok(())
     }
 
+    async fn handle_peer_bandwidth(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let peer_bandwidth: PeerBandwidth = buffer.decode()?;
+        rtmp_context.get_flow_control_mut().apply_peer_bandwidth(peer_bandwidth);
+
+        info!("Peer Bandwidth got handled.");
+        Ok(())
+    }
+
     async fn handle_connect_request(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
         let connect_request: Connect = buffer.decode()?;
         rtmp_context.set_command_object(connect_request.into());
@@ -275,9 +362,13 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
          *      Makes topic to subscribe during request handling.
          *      Because onFCSubscribe command is undefined about its specification.
          */
-        let topic = subscribe_topic(&database_url, &storage_path, &app, fc_subscribe_request.get_topic_id(), client_addr).await?;
+        /* NOTE: Some client can request same name as the play command, that is, including the track selection. */
+        let (topic_id, _) = TrackSelection::from_stream_name(fc_subscribe_request.get_topic_id());
+        let topic_id = AmfString::from(topic_id);
+        let mut topic = subscribe_topic(&database_url, &storage_path, &app, &topic_id, client_addr).await?;
+        topic.set_max_tag_size(rtmp_context.get_max_tag_size());
         rtmp_context.set_topic(topic);
-        rtmp_context.set_topic_id(fc_subscribe_request.into());
+        rtmp_context.set_topic_id(topic_id);
 
         rtmp_context.set_subscriber_status(SubscriberStatus::FcSubscribed);
 
@@ -314,7 +405,12 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
     async fn handle_play_request(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
         let play_request: Play = buffer.decode()?;
         let (stream_name, start_time, play_mode) = play_request.into();
-        rtmp_context.set_stream_name(stream_name);
+        let (stream_name, track_selection) = TrackSelection::from_stream_name(&stream_name);
+        if !track_selection.is_empty() {
+            debug!("Track selection: {track_selection:?}");
+            rtmp_context.set_track_selection(track_selection);
+        }
+        rtmp_context.set_stream_name(AmfString::from(stream_name));
         rtmp_context.set_start_time(start_time);
         rtmp_context.set_play_mode(play_mode);
 
@@ -339,13 +435,63 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         Ok(())
     }
 
+    async fn handle_ping_request(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let ping_request: PingRequest = buffer.decode()?;
+
+        info!("Ping Request got handled.");
+        self.write_ping_response(rtmp_context, ping_request.get_timestamp()).await
+    }
+
+    async fn handle_ping_response(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let ping_response: PingResponse = buffer.decode()?;
+
+        /* NOTE: Only the response to our own request is measured, otherwise the partner could set any round-trip time. */
+        if rtmp_context.get_ping_timestamp() != Some(ping_response.get_timestamp()) {
+            warn!("Ping Response didn't echo the timestamp of our request: {}", ping_response.get_timestamp());
+            return Ok(())
+        }
+        rtmp_context.reset_ping_timestamp();
+
+        let round_trip_time = elapsed_since(Duration::from_millis(ping_response.get_timestamp() as u64));
+        debug!("Round-trip time: {round_trip_time:?}");
+        rtmp_context.set_round_trip_time(round_trip_time);
+
+        info!("Ping Response got handled.");
+        Ok(())
+    }
+
+    async fn handle_swf_verify_response(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
+        let swf_verify_response: SwfVerifyResponse = buffer.decode()?;
+
+        if rtmp_context.get_swf_verify_response().is_some_and(|expected| expected == swf_verify_response) {
+            rtmp_context.set_swf_verified(true);
+        } else {
+            warn!("SWF Verify Response didn't match: {swf_verify_response:x?}");
+        }
+
+        info!("SWF Verify Response got handled.");
+
+        /* NOTE: onStatus has been deferred until the client answers, then it results in an error unless the client has passed. */
+        match (rtmp_context.get_publisher_status(), rtmp_context.get_subscriber_status()) {
+            (Some(PublisherStatus::Began), _) => self.write_publish_response(rtmp_context).await,
+            (_, Some(SubscriberStatus::Began)) => {
+                self.write_stream_is_recorded(rtmp_context).await?;
+                self.write_play_response(rtmp_context).await
+            },
+            _ => Ok(())
+        }
+    }
+
     async fn handle_user_control(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer) -> IOResult<()> {
         use EventType::*;
 
-        let event_type: EventType = buffer.get_u16_be()?.into();
-        match event_type {
+        let event_type = buffer.get_u16_be()?;
+        match EventType::from(event_type) {
             SetBufferLength => self.handle_buffer_length(rtmp_context, buffer).await,
-            _ => unimplemented!("Undefined event type: {event_type:?}")
+            PingRequest => self.handle_ping_request(rtmp_context, buffer).await,
+            PingResponse => self.handle_ping_response(rtmp_context, buffer).await,
+            SwfVerifyResponse => self.handle_swf_verify_response(rtmp_context, buffer).await,
+            _ => rtmp_context.get_unknown_message_policy().apply(unknown_event(event_type))
         }
     }
 
@@ -441,7 +587,10 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
     }
 
     async fn handle_flv(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer, message_type: MessageType, timestamp: Duration) -> IOResult<()> {
-        let topic = rtmp_context.get_topic().unwrap();
+        /* NOTE: Media data which arrives before publishing has nowhere to be stored. */
+        let Some(topic) = rtmp_context.get_topic() else {
+            return rtmp_context.get_unknown_message_policy().apply(unpublished_media(message_type))
+        };
 
         let tag_type = match message_type {
             MessageType::Audio => TagType::Audio,
@@ -455,7 +604,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
             Decoder::<AmfString>::decode(&mut buffer)?;
         }
 
-        let data: Vec<u8> = buffer.into();
+        let data: Bytes = buffer.into();
         let flv_tag = FlvTag::new(tag_type, timestamp, data);
         topic.append_flv_tag(flv_tag)?;
 
@@ -463,12 +612,27 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         Ok(())
     }
 
+    async fn handle_aggregate(&mut self, rtmp_context: &mut RtmpContext, mut buffer: ByteBuffer, timestamp: Duration) -> IOResult<()> {
+        let Some(topic) = rtmp_context.get_topic() else {
+            return rtmp_context.get_unknown_message_policy().apply(unpublished_media(MessageType::Aggregate))
+        };
+
+        let mut aggregate: Aggregate = buffer.decode()?;
+        aggregate.rebase(timestamp);
+        for flv_tag in Vec::<FlvTag>::from(aggregate) {
+            topic.append_flv_tag(flv_tag)?;
+        }
+
+        info!("Aggregate chunk got handled.");
+        Ok(())
+    }
+
     async fn write_error_response(&mut self, rtmp_context: &mut RtmpContext, information: Object, error: IOError) -> IOResult<()> {
         let mut buffer = ByteBuffer::default();
         buffer.encode(&AmfString::from("_error"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&CommandError::new(information.clone()));
-        write_chunk(self.0.as_mut(), rtmp_context, CommandError::CHANNEL.into(), Duration::default(), CommandError::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, CommandError::CHANNEL.into(), Duration::default(), CommandError::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_information(information);
 
@@ -495,31 +659,42 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         };
 
         let app = rtmp_context.get_app().unwrap().clone();
-        let requested_app: &AmfString = (&command_object.get_properties()["app"]).into();
-        if *requested_app != app {
+        let requested_app = command_object.get_properties().get("app").and_then(AmfValue::as_string).cloned().unwrap_or_default();
+        if requested_app != app {
             let information = object!(
                 "level" => AmfString::from("error"),
                 "code" => AmfString::from("NetConnection.Connect.InconsistentAppPath"),
                 "description" => AmfString::new(format!("Requested app path is inconsistent. expected: {}, actual: {}", app, requested_app))
             );
-            return self.write_error_response(rtmp_context, information, inconsistent_app_path(app, requested_app.clone())).await
+            return self.write_error_response(rtmp_context, information, inconsistent_app_path(app, requested_app)).await
         }
 
-        let properties = object!(
+        /* NOTE: Echoes the AMF version which the client requested. (AMF0 if it's absent) */
+        let object_encoding = command_object.get_properties().get("objectEncoding").and_then(AmfValue::as_number).copied().unwrap_or_default();
+        let mut properties = object!(
             "fmsVer" => AmfString::from("FMS/5,0,17"),
             "capabilities" => Number::from(31)
         );
+        /* NOTE: Echoes Enhanced RTMP capabilities only to clients which advertised them. */
+        if let Some(client_capabilities) = Capabilities::from_properties(command_object.get_properties()) {
+            let server_capabilities = Capabilities::supported(Capabilities::CAN_FORWARD);
+            server_capabilities.negotiate(&client_capabilities).write_into(properties.get_properties_mut());
+
+            let negotiated = client_capabilities.negotiate(&server_capabilities);
+            debug!("Negotiated capabilities: {negotiated:?}");
+            rtmp_context.set_capabilities(negotiated);
+        }
         let information = object!(
             "level" => AmfString::from("status"),
             "code" => AmfString::from("NetConnection.Connect.Success"),
             "description" => AmfString::from("Connection succeeded."),
-            "objectEncoding" => Number::from(0)
+            "objectEncoding" => object_encoding
         );
         let mut buffer = ByteBuffer::default();
         buffer.encode(&AmfString::from("_result"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&ConnectResult::new(properties.clone(), information.clone()));
-        write_chunk(self.0.as_mut(), rtmp_context, ConnectResult::CHANNEL.into(), Duration::default(), ConnectResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, ConnectResult::CHANNEL.into(), Duration::default(), ConnectResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_client_type(client_type);
         rtmp_context.set_properties(properties);
@@ -567,7 +742,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("_result"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&ReleaseStreamResult);
-        write_chunk(self.0.as_mut(), rtmp_context, ReleaseStreamResult::CHANNEL.into(), Duration::default(), ReleaseStreamResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, ReleaseStreamResult::CHANNEL.into(), Duration::default(), ReleaseStreamResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_topic(topic);
 
@@ -581,7 +756,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         let mut buffer = ByteBuffer::default();
         buffer.encode(&AmfString::from("onFCPublish"));
         buffer.encode(&OnFcPublish);
-        write_chunk(self.0.as_mut(), rtmp_context, OnFcPublish::CHANNEL.into(), Duration::default(), OnFcPublish::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, OnFcPublish::CHANNEL.into(), Duration::default(), OnFcPublish::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_publisher_status(PublisherStatus::FcPublished);
 
@@ -599,7 +774,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("_result"));
         buffer.encode(&rtmp_context.get_transaction_id());
         buffer.encode(&CreateStreamResult::new(message_id.into()));
-        write_chunk(self.0.as_mut(), rtmp_context, CreateStreamResult::CHANNEL.into(), Duration::default(), CreateStreamResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, CreateStreamResult::CHANNEL.into(), Duration::default(), CreateStreamResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_message_id(message_id);
 
@@ -625,12 +800,12 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
                 let script_data: ScriptDataTag = buffer.decode()?;
 
                 if *script_data.get_name() == "onMetaData" {
-                    let duration: &Number = (&script_data.get_value().get_properties()["duration"]).into();
+                    let duration = script_data.get_value().get_properties().get("duration").and_then(AmfValue::as_number).copied().unwrap_or_default();
                     let mut buffer = ByteBuffer::default();
                     buffer.encode(&AmfString::from("_result"));
                     buffer.encode(&transaction_id);
-                    buffer.encode(&GetStreamLengthResult::new(*duration));
-                    write_chunk(self.0.as_mut(), rtmp_context, GetStreamLengthResult::CHANNEL.into(), Duration::default(), GetStreamLengthResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+                    buffer.encode(&GetStreamLengthResult::new(duration));
+                    write_chunk(self.1.as_mut(), rtmp_context, GetStreamLengthResult::CHANNEL.into(), Duration::default(), GetStreamLengthResult::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
                     rtmp_context.set_subscriber_status(SubscriberStatus::AdditionalCommandGotSent);
 
@@ -653,7 +828,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         let mut buffer = ByteBuffer::default();
         buffer.encode(&AmfString::from("playlist_ready"));
         buffer.encode(&PlaylistReady);
-        write_chunk(self.0.as_mut(), rtmp_context, PlaylistReady::CHANNEL.into(), Duration::default(), PlaylistReady::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, PlaylistReady::CHANNEL.into(), Duration::default(), PlaylistReady::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_subscriber_status(SubscriberStatus::AdditionalCommandGotSent);
 
@@ -668,7 +843,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         let mut buffer = ByteBuffer::default();
         buffer.put_u16_be(StreamBegin::EVENT_TYPE.into());
         buffer.encode(&StreamBegin::new(message_id));
-        write_chunk(self.0.as_mut(), rtmp_context, StreamBegin::CHANNEL.into(), Duration::default(), StreamBegin::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, StreamBegin::CHANNEL.into(), Duration::default(), StreamBegin::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
 
         match rtmp_context.get_client_type().unwrap() {
             Publisher => rtmp_context.set_publisher_status(PublisherStatus::Began),
@@ -679,6 +854,57 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         Ok(())
     }
 
+    async fn write_stream_is_recorded(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
+        let message_id = rtmp_context.get_message_id().unwrap();
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(StreamIsRecorded::EVENT_TYPE.into());
+        buffer.encode(&StreamIsRecorded::new(message_id));
+        write_chunk(self.1.as_mut(), rtmp_context, StreamIsRecorded::CHANNEL.into(), Duration::default(), StreamIsRecorded::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+
+        info!("Stream Is Recorded got sent.");
+        Ok(())
+    }
+
+    async fn write_stream_eof(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
+        let message_id = rtmp_context.get_message_id().unwrap();
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(StreamEof::EVENT_TYPE.into());
+        buffer.encode(&StreamEof::new(message_id));
+        write_chunk(self.1.as_mut(), rtmp_context, StreamEof::CHANNEL.into(), Duration::default(), StreamEof::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+
+        info!("Stream EOF got sent.");
+        Ok(())
+    }
+
+    async fn write_ping_response(&mut self, rtmp_context: &mut RtmpContext, timestamp: u32) -> IOResult<()> {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingResponse::EVENT_TYPE.into());
+        buffer.encode(&PingResponse::new(timestamp));
+        write_chunk(self.1.as_mut(), rtmp_context, PingResponse::CHANNEL.into(), Duration::default(), PingResponse::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+
+        info!("Ping Response got sent.");
+        Ok(())
+    }
+
+    async fn write_swf_verify_request(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(SwfVerifyRequest::EVENT_TYPE.into());
+        buffer.encode(&SwfVerifyRequest);
+        write_chunk(self.1.as_mut(), rtmp_context, SwfVerifyRequest::CHANNEL.into(), Duration::default(), SwfVerifyRequest::MESSAGE_TYPE, u32::default(), &Vec::<u8>::from(buffer)).await?;
+
+        info!("SWF Verify Request got sent.");
+        Ok(())
+    }
+
+    async fn write_flv_exhausted(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
+        info!("FLV data became empty.");
+
+        /* NOTE: Every subscription is served from the stored file, so its end is told regardless of the play mode. */
+        self.write_stream_eof(rtmp_context).await?;
+
+        Err(stream_got_exhausted())
+    }
+
     async fn write_error_status(&mut self, rtmp_context: &mut RtmpContext, information: Object, error: IOError) -> IOResult<()> {
         let message_id = rtmp_context.get_message_id().unwrap();
 
@@ -686,7 +912,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("onStatus"));
         buffer.encode(&Number::from(0));
         buffer.encode(&OnStatus::new(information.clone()));
-        write_chunk(self.0.as_mut(), rtmp_context, OnStatus::CHANNEL.into(), Duration::default(), OnStatus::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, OnStatus::CHANNEL.into(), Duration::default(), OnStatus::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_information(information);
 
@@ -708,6 +934,15 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
             return self.write_error_status(rtmp_context, information, inconsistent_topic_id(topic_id, publishing_name)).await
         }
 
+        if rtmp_context.get_swf_verification().is_some() && !rtmp_context.is_swf_verified() {
+            let information = object!(
+                "level" => AmfString::from("error"),
+                "code" => AmfString::from("NetStream.Publish.SwfUnverified"),
+                "description" => AmfString::from("This app requires the SWF verification, but you didn't pass it.")
+            );
+            return self.write_error_status(rtmp_context, information, swf_is_unverified()).await
+        }
+
         let information = object!(
             "level" => AmfString::from("status"),
             "code" => AmfString::from("NetStream.Publish.Start"),
@@ -718,7 +953,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("onStatus"));
         buffer.encode(&Number::from(0));
         buffer.encode(&OnStatus::new(information.clone()));
-        write_chunk(self.0.as_mut(), rtmp_context, OnStatus::CHANNEL.into(), Duration::default(), OnStatus::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, OnStatus::CHANNEL.into(), Duration::default(), OnStatus::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_information(information);
 
@@ -742,6 +977,15 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
             return self.write_error_status(rtmp_context, information, inconsistent_topic_id(topic_id, stream_name)).await
         }
 
+        if rtmp_context.get_swf_verification().is_some() && !rtmp_context.is_swf_verified() {
+            let information = object!(
+                "level" => AmfString::from("error"),
+                "code" => AmfString::from("NetStream.Play.SwfUnverified"),
+                "description" => AmfString::from("This app requires the SWF verification, but you didn't pass it.")
+            );
+            return self.write_error_status(rtmp_context, information, swf_is_unverified()).await
+        }
+
         let information = object!(
             "level" => AmfString::from("status"),
             "code" => AmfString::from("NetStream.Play.Start"),
@@ -751,7 +995,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         buffer.encode(&AmfString::from("onStatus"));
         buffer.encode(&Number::from(0));
         buffer.encode(&OnStatus::new(information.clone()));
-        write_chunk(self.0.as_mut(), rtmp_context, OnStatus::CHANNEL.into(), Duration::default(), OnStatus::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
+        write_chunk(self.1.as_mut(), rtmp_context, OnStatus::CHANNEL.into(), Duration::default(), OnStatus::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
 
         rtmp_context.set_information(information);
 
@@ -761,61 +1005,182 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> MessageHandler<'_, RW> {
         Ok(())
     }
 
-    async fn write_flv(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
-        for next in rtmp_context.get_topic_mut().unwrap() {
-            let flv_tag = next?;
-            let message_id = rtmp_context.get_message_id().unwrap();
-
-            let channel;
-            let message_type;
-            match flv_tag.get_tag_type() {
-                TagType::Audio => {
-                    channel = Audio::CHANNEL;
-                    message_type = Audio::MESSAGE_TYPE;
-                },
-                TagType::Video => {
-                    channel = Video::CHANNEL;
-                    message_type = Video::MESSAGE_TYPE;
-                },
-                TagType::ScriptData => {
-                    channel = SetDataFrame::CHANNEL;
-                    message_type = SetDataFrame::MESSAGE_TYPE;
+    async fn write_flv_tag(&mut self, rtmp_context: &mut RtmpContext, message_id: u32, flv_tag: FlvTag) -> IOResult<()> {
+        let channel;
+        let message_type;
+        match flv_tag.get_tag_type() {
+            TagType::Audio => {
+                channel = Audio::CHANNEL;
+                message_type = Audio::MESSAGE_TYPE;
+            },
+            TagType::Video => {
+                channel = Video::CHANNEL;
+                message_type = Video::MESSAGE_TYPE;
+            },
+            TagType::ScriptData => {
+                channel = SetDataFrame::CHANNEL;
+                message_type = SetDataFrame::MESSAGE_TYPE;
+            },
+            TagType::Other => {
+                channel = Channel::Other;
+                message_type = MessageType::Other;
+            }
+        }
+        let timestamp = flv_tag.get_timestamp();
+        let data: Bytes = if let MessageType::Data = message_type {
+            let mut buffer = ByteBuffer::default();
+            buffer.encode(&AmfString::from("@setDataFrame"));
+            buffer.put_bytes(flv_tag.get_data());
+            buffer.into()
+        } else {
+            flv_tag.get_data().clone()
+        };
+        write_chunk(self.1.as_mut(), rtmp_context, channel.into(), timestamp, message_type, message_id, &data).await?;
+
+        info!("FLV chunk got sent.");
+        Ok(())
+    }
+
+    fn filter_flv_tag(rtmp_context: &mut RtmpContext, flv_tag: FlvTag) -> Option<FlvTag> {
+        let flv_tag = match rtmp_context.get_track_selection_mut() {
+            /* NOTE: A tag which can't be decoded is skipped, so that it doesn't stop the playback. */
+            Some(track_selection) => track_selection.select(flv_tag).unwrap_or_else(
+                |e| {
+                    warn!("Undecodable FLV tag got skipped: {e}");
+                    None
+                }
+            ),
+            None => Some(flv_tag)
+        };
+
+        match (flv_tag, rtmp_context.get_capabilities()) {
+            (Some(flv_tag), Some(capabilities)) => capabilities.filter(flv_tag),
+            (flv_tag, _) => flv_tag
+        }
+    }
+
+    async fn write_aggregate(&mut self, rtmp_context: &mut RtmpContext, aggregation_size: u32) -> IOResult<()> {
+        let message_id = rtmp_context.get_message_id().unwrap();
+        let mut flv_tags: Vec<FlvTag> = Vec::new();
+        let mut total_size: usize = 0;
+        let mut script_data: Option<FlvTag> = None;
+        let mut paused = false;
+
+        loop {
+            let mut topic = rtmp_context.get_topic_mut().unwrap().clone();
+            let Some(next) = topic.next() else {
+                break
+            };
+            /* NOTE: Tracks/codecs which the subscriber doesn't want are consumed without sending. */
+            let Some(flv_tag) = Self::filter_flv_tag(rtmp_context, next?) else {
+                rtmp_context.set_topic(topic);
+                continue
+            };
+
+            /* NOTE: An Aggregate message can't exceed 24 bits, so the tag which would overflow it is left in the topic for the next one. */
+            let tag_size = METADATA_LEN + flv_tag.get_data().len() + 4;
+            if !flv_tags.is_empty() && total_size + tag_size > U24_MAX as usize {
+                break
+            }
+
+            match rtmp_context.get_flow_control_mut().pace(&flv_tag) {
+                Pacing::Send => rtmp_context.set_topic(topic),
+                Pacing::Drop => {
+                    rtmp_context.set_topic(topic);
+                    info!("Non-keyframe video got dropped.");
+                    continue
                 },
-                TagType::Other => {
-                    channel = Channel::Other;
-                    message_type = MessageType::Other;
+                Pacing::Pause => {
+                    paused = true;
+                    break
                 }
             }
-            let timestamp = flv_tag.get_timestamp();
-            let data: Vec<u8> = if let MessageType::Data = message_type {
-                let mut buffer = ByteBuffer::default();
-                buffer.encode(&AmfString::from("@setDataFrame"));
-                buffer.put_bytes(flv_tag.get_data());
-                buffer.into()
-            } else {
-                flv_tag.get_data().to_vec()
-            };
-            write_chunk(self.0.as_mut(), rtmp_context, channel.into(), timestamp, message_type, message_id, &data).await?;
 
-            info!("FLV chunk got sent.");
-            return Ok(())
+            /* NOTE: Metadata is sent as a Data message in itself. */
+            if let TagType::ScriptData = flv_tag.get_tag_type() {
+                script_data = Some(flv_tag);
+                break
+            }
+
+            total_size += tag_size;
+            flv_tags.push(flv_tag);
+            if total_size >= aggregation_size as usize {
+                break
+            }
         }
 
-        info!("FLV data became empty.");
-        Err(stream_got_exhausted())
+        if flv_tags.is_empty() && script_data.is_none() {
+            if paused {
+                info!("Sending FLV got paused until the peer acknowledges.");
+                return Ok(())
+            }
+
+            return self.write_flv_exhausted(rtmp_context).await
+        }
+
+        if !flv_tags.is_empty() {
+            let aggregate = Aggregate::new(flv_tags);
+            let timestamp = aggregate.get_timestamp().unwrap();
+            let mut buffer = ByteBuffer::default();
+            buffer.encode(&aggregate);
+            write_chunk(self.1.as_mut(), rtmp_context, Aggregate::CHANNEL.into(), timestamp, Aggregate::MESSAGE_TYPE, message_id, &Vec::<u8>::from(buffer)).await?;
+
+            info!("Aggregate chunk got sent.");
+        }
+
+        if let Some(flv_tag) = script_data {
+            self.write_flv_tag(rtmp_context, message_id, flv_tag).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn write_flv(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
+        if let Some(aggregation_size) = rtmp_context.get_aggregation_size() {
+            return self.write_aggregate(rtmp_context, aggregation_size).await
+        }
+
+        let message_id = rtmp_context.get_message_id().unwrap();
+        loop {
+            /* NOTE: A tag to be paused must remain in the topic, so this reads it from a copy. */
+            let mut topic = rtmp_context.get_topic_mut().unwrap().clone();
+            let Some(next) = topic.next() else {
+                return self.write_flv_exhausted(rtmp_context).await
+            };
+            /* NOTE: Tracks/codecs which the subscriber doesn't want are consumed without sending. */
+            let Some(flv_tag) = Self::filter_flv_tag(rtmp_context, next?) else {
+                rtmp_context.set_topic(topic);
+                continue
+            };
+
+            match rtmp_context.get_flow_control_mut().pace(&flv_tag) {
+                Pacing::Send => {
+                    rtmp_context.set_topic(topic);
+                    return self.write_flv_tag(rtmp_context, message_id, flv_tag).await
+                },
+                Pacing::Drop => {
+                    rtmp_context.set_topic(topic);
+                    info!("Non-keyframe video got dropped.");
+                },
+                Pacing::Pause => {
+                    info!("Sending FLV got paused until the peer acknowledges.");
+                    return Ok(())
+                }
+            }
+        }
     }
 }
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for MessageHandler<'_, RW> {
-    fn poll_handle(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> MessageHandler<'_, R, W> {
+    async fn handle(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         use MessageType::*;
 
-        let basic_header = ready!(pin!(read_basic_header(pin!(self.0.await_until_receiving()))).poll(cx))?;
-        let message_header = ready!(pin!(read_message_header(pin!(self.0.await_until_receiving()), basic_header.get_message_format())).poll(cx))?;
+        let basic_header = read_basic_header(pin!(self.0.await_until_receiving())).await?;
+        let message_header = read_message_header(pin!(self.0.await_until_receiving()), basic_header.get_message_format()).await?;
         let extended_timestamp = if let Some(timestamp) = message_header.get_timestamp() {
             if timestamp.as_millis() == U24_MAX as u128 {
-                let extended_timestamp = ready!(pin!(read_extended_timestamp(pin!(self.0.await_until_receiving()))).poll(cx))?;
+                let extended_timestamp = read_extended_timestamp(pin!(self.0.await_until_receiving())).await?;
                 Some(extended_timestamp)
             } else {
                 None
@@ -843,52 +1208,69 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for MessageHandler<'_, RW>
                 |message_id| last_received_chunk.set_message_id(message_id)
             );
         } else {
-            rtmp_context.insert_received_chunk(
-                chunk_id,
-                LastChunk::new(
-                    message_header.get_timestamp().unwrap(),
-                    message_header.get_message_length().unwrap(),
-                    message_header.get_message_type().unwrap(),
-                    message_header.get_message_id().unwrap()
-                )
-            );
+            /* NOTE: The first chunk of every chunk stream is required to have its full message header. */
+            let (Some(timestamp), Some(message_length), Some(message_type), Some(message_id)) = (
+                message_header.get_timestamp(),
+                message_header.get_message_length(),
+                message_header.get_message_type(),
+                message_header.get_message_id()
+            ) else {
+                return Err(unknown_chunk_stream(chunk_id))
+            };
+            rtmp_context.insert_received_chunk(chunk_id, LastChunk::new(timestamp, message_length, message_type, message_id));
         } 
-        let data = ready!(
-            pin!(
-                read_chunk_data(
-                    pin!(self.0.await_until_receiving()),
-                    rtmp_context.get_receiving_chunk_size(),
-                    rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_message_length()
-                )
-            ).poll(cx)
-        )?;
-        let buffer: ByteBuffer = data.into();
+        let data = read_chunk_data(
+            pin!(self.0.await_until_receiving()),
+            rtmp_context.get_receiving_chunk_size(),
+            rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_message_length(),
+            rtmp_context.get_max_message_size()
+        ).await?;
+        let mut buffer: ByteBuffer = data.into();
 
         let message_type = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_message_type();
         match message_type {
-            Acknowledgement => ready!(pin!(self.handle_acknowledgement(rtmp_context, buffer)).poll(cx))?,
-            UserControl => ready!(pin!(self.handle_user_control(rtmp_context, buffer)).poll(cx))?,
-            WindowAcknowledgementSize => ready!(pin!(self.handle_window_acknowledgement_size(rtmp_context, buffer)).poll(cx))?,
+            Acknowledgement => self.handle_acknowledgement(rtmp_context, buffer).await?,
+            UserControl => self.handle_user_control(rtmp_context, buffer).await?,
+            WindowAcknowledgementSize => self.handle_window_acknowledgement_size(rtmp_context, buffer).await?,
+            PeerBandwidth => self.handle_peer_bandwidth(rtmp_context, buffer).await?,
             Audio | Video | Data => {
                 let timestamp = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_timestamp();
-                ready!(pin!(self.handle_flv(rtmp_context, buffer, message_type, timestamp)).poll(cx))?
+                self.handle_flv(rtmp_context, buffer, message_type, timestamp).await?
             },
-            Command => ready!(pin!(self.handle_command_request(rtmp_context, buffer)).poll(cx))?,
-            other => unimplemented!("Undefined Message: {other:?}")
+            Aggregate => {
+                let timestamp = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_timestamp();
+                self.handle_aggregate(rtmp_context, buffer, timestamp).await?
+            },
+            Command => self.handle_command_request(rtmp_context, buffer).await?,
+            Amf3Data => {
+                /* NOTE: AMF3 messages start with the format selector byte (always 0), then same as AMF0 ones. */
+                buffer.get_u8()?;
+                let timestamp = rtmp_context.get_last_received_chunk(&chunk_id).unwrap().get_timestamp();
+                self.handle_flv(rtmp_context, buffer, Data, timestamp).await?
+            },
+            Amf3Command => {
+                buffer.get_u8()?;
+                self.handle_command_request(rtmp_context, buffer).await?
+            },
+            other => rtmp_context.get_unknown_message_policy().apply(unknown_message(other))?
         }
 
         if let Some(publisher_status) = rtmp_context.get_publisher_status() {
             match publisher_status {
-                PublisherStatus::Connected => pin!(self.write_release_stream_response(rtmp_context)).poll(cx),
-                PublisherStatus::Released => pin!(self.write_fc_publish_response(rtmp_context)).poll(cx),
-                PublisherStatus::FcPublished => pin!(self.write_create_stream_response(rtmp_context)).poll(cx),
+                PublisherStatus::Connected => self.write_release_stream_response(rtmp_context).await,
+                PublisherStatus::Released => self.write_fc_publish_response(rtmp_context).await,
+                PublisherStatus::FcPublished => self.write_create_stream_response(rtmp_context).await,
                 PublisherStatus::Created => {
-                    ready!(pin!(self.write_stream_begin(rtmp_context)).poll(cx))?;
-                    pin!(self.write_publish_response(rtmp_context)).poll(cx)
+                    self.write_stream_begin(rtmp_context).await?;
+                    if rtmp_context.get_swf_verification().is_some() {
+                        self.write_swf_verify_request(rtmp_context).await
+                    } else {
+                        self.write_publish_response(rtmp_context).await
+                    }
                 },
                 _ => {
                     /* Just receiving flv after publishing. */
-                    Poll::Ready(Ok(()))
+                    Ok(())
                 }
             }
         } else if let Some(mut subscriber_status) = rtmp_context.get_subscriber_status() {
@@ -896,43 +1278,62 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for MessageHandler<'_, RW>
                 let command = rtmp_context.get_command_name().unwrap().clone();
 
                 if command == "getStreamLength" {
-                    return pin!(self.write_stream_length_response(rtmp_context)).poll(cx)
+                    return self.write_stream_length_response(rtmp_context).await
                 } else if command == "set_playlist" {
-                    return pin!(self.write_playlist_response(rtmp_context)).poll(cx)
+                    return self.write_playlist_response(rtmp_context).await
                 } else {
                     subscriber_status = SubscriberStatus::AdditionalCommandGotSent;
                 }
             }
 
             match subscriber_status {
-                SubscriberStatus::WindowAcknowledgementSizeGotSent => pin!(self.write_create_stream_response(rtmp_context)).poll(cx),
+                SubscriberStatus::WindowAcknowledgementSizeGotSent => self.write_create_stream_response(rtmp_context).await,
                 SubscriberStatus::AdditionalCommandGotSent => {
-                    ready!(pin!(self.write_stream_begin(rtmp_context)).poll(cx))?;
-                    pin!(self.write_play_response(rtmp_context)).poll(cx)
+                    self.write_stream_begin(rtmp_context).await?;
+                    if rtmp_context.get_swf_verification().is_some() {
+                        return self.write_swf_verify_request(rtmp_context).await
+                    }
+                    self.write_stream_is_recorded(rtmp_context).await?;
+                    self.write_play_response(rtmp_context).await
                 },
-                SubscriberStatus::Played => pin!(self.write_flv(rtmp_context)).poll(cx),
+                SubscriberStatus::Played => self.write_flv(rtmp_context).await,
                 _ => {
                     /* NOTE: There are plural chunks just to receive. */
-                    Poll::Ready(Ok(()))
+                    Ok(())
                 }
             }
         } else {
-            pin!(self.write_connect_response(rtmp_context)).poll(cx)
+            self.write_connect_response(rtmp_context).await
         }
     }
 }
 
 #[doc(hidden)]
-fn handle_message<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>) -> MessageHandler<'a, RW> {
-    MessageHandler(stream)
+fn handle_message<'a, R: AsyncRead + Unpin, W: AsyncWrite + Unpin>(reader: Pin<&'a mut R>, writer: Pin<&'a mut W>) -> MessageHandler<'a, R, W> {
+    MessageHandler(reader, writer)
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+struct MessageStep;
+
+#[doc(hidden)]
+impl AsyncHandler for MessageStep {
+    async fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        handle_message(reader, writer).handle(rtmp_context).await
+    }
 }
 
 #[doc(hidden)]
 #[derive(Debug)]
-struct CloseHandler<'a, RW: AsyncRead + AsyncWrite + Unpin>(Pin<&'a mut RW>);
+struct CloseHandler<'a, W: AsyncWrite + Unpin>(Pin<&'a mut W>);
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> CloseHandler<'_, RW> {
+impl<W: AsyncWrite + Unpin> CloseHandler<'_, W> {
     async fn write_fc_unpublish_request(&mut self, rtmp_context: &mut RtmpContext) -> IOResult<()> {
         let topic_id = rtmp_context.get_topic_id().unwrap().clone();
         rtmp_context.increase_transaction_id();
@@ -963,26 +1364,41 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> CloseHandler<'_, RW> {
 }
 
 #[doc(hidden)]
-fn handle_close<'a, RW: AsyncRead + AsyncWrite + Unpin>(stream: Pin<&'a mut RW>) -> CloseHandler<'a, RW> {
-    CloseHandler(stream)
+fn handle_close<'a, W: AsyncWrite + Unpin>(writer: Pin<&'a mut W>) -> CloseHandler<'a, W> {
+    CloseHandler(writer)
 }
 
 #[doc(hidden)]
-impl<RW: AsyncRead + AsyncWrite + Unpin> ErrorHandler for CloseHandler<'_, RW> {
-    fn poll_handle_error(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext, error: IOError) -> Poll<IOResult<()>> {
+#[derive(Debug)]
+struct CloseStep;
+
+#[doc(hidden)]
+impl ErrorHandler for CloseStep {
+    async fn handle_error<R, W>(&mut self, _: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext, error: IOError) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        handle_close(writer).handle_error(rtmp_context, error).await
+    }
+}
+
+#[doc(hidden)]
+impl<W: AsyncWrite + Unpin> CloseHandler<'_, W> {
+    async fn handle_error(&mut self, rtmp_context: &mut RtmpContext, error: IOError) -> IOResult<()> {
         if error.kind() != ErrorKind::Other {
             if let Some(publisher_status) = rtmp_context.get_publisher_status() {
                 if publisher_status >= PublisherStatus::FcPublished {
-                    ready!(pin!(self.write_fc_unpublish_request(rtmp_context)).poll(cx))?;
+                    self.write_fc_unpublish_request(rtmp_context).await?;
                 }
 
                 if publisher_status >= PublisherStatus::Created {
-                    ready!(pin!(self.write_delete_stream_request(rtmp_context)).poll(cx))?;
+                    self.write_delete_stream_request(rtmp_context).await?;
                 }
             }
         }
 
-        self.0.as_mut().poll_shutdown(cx)
+        self.0.as_mut().shutdown().await
     }
 }
 
@@ -1012,7 +1428,17 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> ErrorHandler for CloseHandler<'_, RW> {
 /// 6. Checkes subscription informaitons from [`Play`] command/
 /// 7. The sends FLV media data.
 ///
+/// Because every stream is served from the stored file, this also tells it via the [`StreamIsRecorded`] event before starting, and via the [`StreamEof`] event after sending all of it, whichever the play mode is.
+///
+/// # SWF verification
+///
+/// If the app requires the SWF verification (see [`RtmpContext::set_swf_verification`]), this sends the [`SwfVerifyRequest`] event after the [`StreamBegin`] event.
+/// Then the onStatus command of publishing/playing is deferred until the client answers with the [`SwfVerifyResponse`] event.
+/// If the answer doesn't match, that onStatus command results in an error.
+///
 /// In Both sides, if receiving data size exceeds server's bandwidth, this reports its thing via the [`Acknowledgement`] message to its client.
+/// Furthermore, this answers every [`PingRequest`] event with the [`PingResponse`] event.
+/// It also sends own [`PingRequest`] event every [`PING_INTERVAL`], then measures the round-trip time only from the response which echoes its timestamp.
 ///
 /// # Examples
 ///
@@ -1029,7 +1455,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> ErrorHandler for CloseHandler<'_, RW> {
 ///
 /// let stream = VecStream::default();
 /// let rtmp_context = RtmpContext::default();
-/// let server = Server::new(stream, rtmp_context, PhantomData::<RtmpHandler<VecStream>>);
+/// let server = Server::new(stream, rtmp_context, PhantomData::<RtmpHandler>);
 /// ```
 ///
 /// [`Connect`]: sheave_core::messages::Connect
@@ -1045,22 +1471,35 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> ErrorHandler for CloseHandler<'_, RW> {
 /// [`GetStreamLength`]: sheave_core::messages::GetStreamLength
 /// [`SetPlaylist`]: sheave_core::messages::SetPlaylist
 /// [`Play`]: sheave_core::messages::Play
-#[derive(Debug)]
-pub struct RtmpHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<StreamWrapper<RW>>);
-
-impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for RtmpHandler<RW> {
-    fn poll_handle(self: Pin<&mut Self>, cx: &mut FutureContext<'_>, rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-        pin!(
-            handle_handshake(self.0.make_weak_pin())
-                .while_ok(handle_message(self.0.make_weak_pin()).wrap(write_acknowledgement(self.0.make_weak_pin())))
-                .map_err(handle_close(self.0.make_weak_pin()))
-        ).poll_handle(cx, rtmp_context)
+/// [`StreamIsRecorded`]: sheave_core::messages::StreamIsRecorded
+/// [`StreamEof`]: sheave_core::messages::StreamEof
+/// [`PingRequest`]: sheave_core::messages::PingRequest
+/// [`PingResponse`]: sheave_core::messages::PingResponse
+/// [`PING_INTERVAL`]: sheave_core::handlers::RtmpContext::PING_INTERVAL
+/// [`RtmpContext::set_swf_verification`]: sheave_core::handlers::RtmpContext::set_swf_verification
+/// [`StreamBegin`]: sheave_core::messages::StreamBegin
+/// [`SwfVerifyRequest`]: sheave_core::messages::SwfVerifyRequest
+/// [`SwfVerifyResponse`]: sheave_core::messages::SwfVerifyResponse
+#[derive(Debug, Default)]
+pub struct RtmpHandler;
+
+impl AsyncHandler for RtmpHandler {
+    async fn handle<R, W>(&mut self, reader: Pin<&mut R>, writer: Pin<&mut W>, rtmp_context: &mut RtmpContext) -> IOResult<()>
+    where
+        R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+        W: AsyncWrite + Unpin + Send
+    {
+        HandshakeStep
+            .while_ok(MessageStep.wrap(write_acknowledgement()).wrap(write_ping_request()))
+            .map_err(CloseStep)
+            .handle(reader, writer, rtmp_context)
+            .await
     }
 }
 
-impl<RW: AsyncRead + AsyncWrite + Unpin> HandlerConstructor<StreamWrapper<RW>> for RtmpHandler<RW> {
-    fn new(stream: Arc<StreamWrapper<RW>>) -> Self {
-        Self(stream)
+impl HandlerConstructor for RtmpHandler {
+    fn new() -> Self {
+        Self
     }
 }
 
@@ -1082,7 +1521,7 @@ mod tests {
             MAIN_SEPARATOR,
             PathBuf,
         },
-        str::FromStr,
+        str::FromStr
     };
     use dotenvy::{
         from_filename,
@@ -1096,19 +1535,27 @@ mod tests {
         migrate::Migrator,
         query
     };
-    use tokio::sync::OnceCell;
+    use tokio::{
+        io::split,
+        sync::OnceCell
+    };
     use uuid::Uuid;
     use sheave_core::{
         ecma_array,
         flv::Flv,
-        handlers::VecStream,
-        handshake::EncryptionAlgorithm,
+        handlers::{
+            UnknownMessagePolicy,
+            VecStream
+        },
         messages::{
             ChunkSize,
             SetPlaylist,
             amf::v0::Boolean
-        }
+        },
+        net::rtmpe::RtmpeKeys,
+        session::UnknownChunkStream
     };
+    use crate::handlers::UnpublishedMedia;
     use super::*;
 
     const CLIENT_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 1935);
@@ -1132,126 +1579,278 @@ mod tests {
 
     #[tokio::test]
     async fn ok_unsigned_handshake_got_handled() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
 
         let sent_encryption_algorithm = EncryptionAlgorithm::NotEncrypted;
-        write_encryption_algorithm(stream.as_mut(), sent_encryption_algorithm).await.unwrap();
-        let mut sent_client_handshake = Handshake::new(Instant::now().elapsed(), Version::UNSIGNED);
-        sent_client_handshake.imprint_digest(sent_encryption_algorithm, Handshake::CLIENT_KEY);
-        write_handshake(stream.as_mut(), &sent_client_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let mut sent_client_handshake = Handshake::new(epoch_timestamp(), Version::UNSIGNED);
+        sent_client_handshake.imprint_digest(DigestScheme::from(sent_encryption_algorithm), Handshake::CLIENT_KEY);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
         assert!(result.is_ok());
 
-        let received_encryption_algorithm = read_encryption_algorithm(stream.as_mut()).await.unwrap();
-        let received_server_handshake = read_handshake(stream.as_mut()).await.unwrap();
-        let received_client_handshake = read_handshake(stream.as_mut()).await.unwrap();
+        let received_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        let received_client_handshake = read_handshake(reader.as_mut()).await.unwrap();
         assert_eq!(sent_encryption_algorithm, received_encryption_algorithm);
         assert_eq!(sent_client_handshake.get_bytes(), received_client_handshake.get_bytes());
 
-        write_handshake(stream.as_mut(), &received_server_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_second_handshake(&mut rtmp_context).await;
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await;
         assert!(result.is_ok());
         let sent_server_handshake = rtmp_context.get_server_handshake().unwrap();
         assert_eq!(received_server_handshake.get_bytes(), sent_server_handshake.get_bytes())
     }
 
+    #[tokio::test]
+    async fn ok_handshake_got_counted_as_sent() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        write_encryption_algorithm(writer.as_mut(), EncryptionAlgorithm::NotEncrypted).await.unwrap();
+        write_handshake(writer.as_mut(), &Handshake::new(epoch_timestamp(), Version::UNSIGNED)).await.unwrap();
+        write_handshake(writer.as_mut(), &Handshake::new(epoch_timestamp(), Version::UNSIGNED)).await.unwrap();
+        assert!(handle_handshake(reader.as_mut(), writer.as_mut()).handle(&mut rtmp_context).await.is_ok());
+        assert_eq!(FlowControl::HANDSHAKE_SIZE, rtmp_context.get_flow_control().get_sent_amount())
+    }
+
+    #[tokio::test]
+    async fn ok_round_trip_time_got_measured() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        write_encryption_algorithm(writer.as_mut(), EncryptionAlgorithm::NotEncrypted).await.unwrap();
+        write_handshake(writer.as_mut(), &Handshake::new(epoch_timestamp(), Version::UNSIGNED)).await.unwrap();
+        handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await.unwrap();
+        read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        read_handshake(reader.as_mut()).await.unwrap();
+
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await.unwrap();
+        assert!(rtmp_context.get_round_trip_time().unwrap() < Duration::from_secs(1));
+        assert!(rtmp_context.get_await_duration().is_some())
+    }
+
+    #[tokio::test]
+    async fn ok_timestamp_did_not_get_echoed() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        write_encryption_algorithm(writer.as_mut(), EncryptionAlgorithm::NotEncrypted).await.unwrap();
+        write_handshake(writer.as_mut(), &Handshake::new(epoch_timestamp(), Version::UNSIGNED)).await.unwrap();
+        handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await.unwrap();
+        read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        read_handshake(reader.as_mut()).await.unwrap();
+
+        /* NOTE: The handshake itself succeeds even if the timestamp isn't echoed, but the round-trip time isn't measured. */
+        let not_echoed = Duration::from_millis(received_server_handshake.get_timestamp().as_millis() as u64 + 1);
+        write_handshake(writer.as_mut(), &Handshake::new(not_echoed, Version::UNSIGNED)).await.unwrap();
+        handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await.unwrap();
+        assert!(rtmp_context.get_round_trip_time().is_none());
+        assert_eq!(Some(RtmpContext::DEFAULT_AWAIT_DURATION), rtmp_context.get_await_duration())
+    }
+
     #[tokio::test]
     async fn err_digest_did_not_match() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
 
         let sent_encryption_algorithm = EncryptionAlgorithm::NotEncrypted;
-        write_encryption_algorithm(stream.as_mut(), sent_encryption_algorithm).await.unwrap();
-        let sent_client_handshake = Handshake::new(Instant::now().elapsed(), Version::LATEST_CLIENT);
-        write_handshake(stream.as_mut(), &sent_client_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let sent_client_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_CLIENT);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
         assert!(result.is_err())
     }
 
     #[tokio::test]
     async fn err_signature_did_not_match() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
 
         let sent_encryption_algorithm = EncryptionAlgorithm::NotEncrypted;
-        write_encryption_algorithm(stream.as_mut(), sent_encryption_algorithm).await.unwrap();
-        let mut sent_client_handshake = Handshake::new(Instant::now().elapsed(), Version::LATEST_CLIENT);
-        sent_client_handshake.imprint_digest(sent_encryption_algorithm, Handshake::CLIENT_KEY);
-        write_handshake(stream.as_mut(), &sent_client_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let mut sent_client_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_CLIENT);
+        sent_client_handshake.imprint_digest(DigestScheme::from(sent_encryption_algorithm), Handshake::CLIENT_KEY);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
         assert!(result.is_ok());
 
-        read_encryption_algorithm(stream.as_mut()).await.unwrap();
-        let mut received_server_handshake = read_handshake(stream.as_mut()).await.unwrap();
-        read_handshake(stream.as_mut()).await.unwrap();
+        read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let mut received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        read_handshake(reader.as_mut()).await.unwrap();
         let mut invalid_signature_key: [u8; Handshake::CLIENT_KEY.len() + Handshake::COMMON_KEY.len()] = [0; Handshake::CLIENT_KEY.len() + Handshake::COMMON_KEY.len()];
         fill(&mut invalid_signature_key);
-        received_server_handshake.imprint_signature(sent_encryption_algorithm, &invalid_signature_key);
-        write_handshake(stream.as_mut(), &received_server_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_second_handshake(&mut rtmp_context).await;
+        received_server_handshake.imprint_signature(DigestScheme::from(sent_encryption_algorithm), &invalid_signature_key);
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await;
         assert!(result.is_err())
     }
 
     #[tokio::test]
     async fn ok_singed_handshake_got_handled() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
 
         let sent_encryption_algorithm = EncryptionAlgorithm::NotEncrypted;
-        write_encryption_algorithm(stream.as_mut(), sent_encryption_algorithm).await.unwrap();
-        let mut sent_client_handshake = Handshake::new(Instant::now().elapsed(), Version::LATEST_CLIENT);
-        sent_client_handshake.imprint_digest(sent_encryption_algorithm, Handshake::CLIENT_KEY);
-        write_handshake(stream.as_mut(), &sent_client_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let mut sent_client_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_CLIENT);
+        sent_client_handshake.imprint_digest(DigestScheme::from(sent_encryption_algorithm), Handshake::CLIENT_KEY);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
         assert!(result.is_ok());
 
-        let received_encryption_algorithm = read_encryption_algorithm(stream.as_mut()).await.unwrap();
-        let mut received_server_handshake = read_handshake(stream.as_mut()).await.unwrap();
-        let received_client_handshake = read_handshake(stream.as_mut()).await.unwrap();
+        let received_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let mut received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        let received_client_handshake = read_handshake(reader.as_mut()).await.unwrap();
         let mut server_signature_key: Vec<u8> = Vec::new();
         server_signature_key.extend_from_slice(Handshake::SERVER_KEY);
         server_signature_key.extend_from_slice(Handshake::COMMON_KEY);
-        sent_client_handshake.imprint_signature(sent_encryption_algorithm, &server_signature_key);
+        sent_client_handshake.imprint_signature(DigestScheme::from(sent_encryption_algorithm), &server_signature_key);
         assert_eq!(sent_encryption_algorithm, received_encryption_algorithm);
         assert_eq!(sent_client_handshake.get_bytes(), received_client_handshake.get_bytes());
 
         let mut client_signature_key: Vec<u8> = Vec::new();
         client_signature_key.extend_from_slice(Handshake::CLIENT_KEY);
         client_signature_key.extend_from_slice(Handshake::COMMON_KEY);
-        received_server_handshake.imprint_signature(sent_encryption_algorithm, &client_signature_key);
-        write_handshake(stream.as_mut(), &received_server_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_second_handshake(&mut rtmp_context).await;
+        received_server_handshake.imprint_signature(DigestScheme::from(sent_encryption_algorithm), &client_signature_key);
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await;
         assert!(result.is_ok());
         let sent_server_handshake = rtmp_context.get_server_handshake().unwrap();
         assert_eq!(received_server_handshake.get_bytes(), sent_server_handshake.get_bytes())
     }
 
+    #[tokio::test]
+    async fn ok_digest_scheme_got_detected() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        /* NOTE: Some encoders use the second scheme without the encryption. */
+        let sent_encryption_algorithm = EncryptionAlgorithm::NotEncrypted;
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let mut sent_client_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_CLIENT);
+        sent_client_handshake.imprint_digest(DigestScheme::Scheme1, Handshake::CLIENT_KEY);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        assert!(result.is_ok());
+        assert_eq!(Some(DigestScheme::Scheme1), rtmp_context.get_digest_scheme());
+
+        let received_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let mut received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        let received_client_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        assert_eq!(sent_encryption_algorithm, received_encryption_algorithm);
+        assert_eq!(Some(DigestScheme::Scheme1), received_server_handshake.find_digest_scheme(Handshake::SERVER_KEY));
+        let mut server_signature_key: Vec<u8> = Vec::new();
+        server_signature_key.extend_from_slice(Handshake::SERVER_KEY);
+        server_signature_key.extend_from_slice(Handshake::COMMON_KEY);
+        assert!(received_client_handshake.did_signature_match_request(&sent_client_handshake, DigestScheme::Scheme1, &server_signature_key));
+
+        let mut client_signature_key: Vec<u8> = Vec::new();
+        client_signature_key.extend_from_slice(Handshake::CLIENT_KEY);
+        client_signature_key.extend_from_slice(Handshake::COMMON_KEY);
+        received_server_handshake.imprint_signature(DigestScheme::Scheme1, &client_signature_key);
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await;
+        assert!(result.is_ok())
+    }
+
+    #[tokio::test]
+    async fn ok_encrypted_handshake_got_handled() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        let rtmpe_keys = RtmpeKeys::default();
+        rtmp_context.set_rtmpe_keys(rtmpe_keys.clone());
+
+        let sent_encryption_algorithm = EncryptionAlgorithm::DiffieHellman;
+        let client_diffie_hellman = DiffieHellman::generate();
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let mut sent_client_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_CLIENT);
+        sent_client_handshake.imprint_public_key(DigestScheme::from(sent_encryption_algorithm), client_diffie_hellman.get_public_key());
+        sent_client_handshake.imprint_digest(DigestScheme::from(sent_encryption_algorithm), Handshake::CLIENT_KEY);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        assert!(result.is_ok());
+        assert!(rtmpe_keys.is_encrypted());
+
+        let received_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        assert_eq!(sent_encryption_algorithm, received_encryption_algorithm);
+        assert!(received_server_handshake.did_digest_match(DigestScheme::from(received_encryption_algorithm), Handshake::SERVER_KEY));
+        let server_public_key = received_server_handshake.get_public_key(DigestScheme::from(received_encryption_algorithm));
+        assert!(client_diffie_hellman.compute_shared_secret(server_public_key).is_ok())
+    }
+
+    #[tokio::test]
+    async fn ok_encryption_got_declined() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        let sent_encryption_algorithm = EncryptionAlgorithm::DiffieHellman;
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let mut sent_client_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_CLIENT);
+        sent_client_handshake.imprint_public_key(DigestScheme::from(sent_encryption_algorithm), DiffieHellman::generate().get_public_key());
+        sent_client_handshake.imprint_digest(DigestScheme::from(sent_encryption_algorithm), Handshake::CLIENT_KEY);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        assert!(result.is_ok());
+
+        let received_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        assert_eq!(EncryptionAlgorithm::NotEncrypted, received_encryption_algorithm);
+        assert!(received_server_handshake.did_digest_match(DigestScheme::from(sent_encryption_algorithm), Handshake::SERVER_KEY))
+    }
+
     #[tokio::test]
     async fn ok_signed_handshake_as_ffmpeg_got_handled() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
 
         let sent_encryption_algorithm = EncryptionAlgorithm::NotEncrypted;
-        write_encryption_algorithm(stream.as_mut(), sent_encryption_algorithm).await.unwrap();
-        let mut sent_client_handshake = Handshake::new(Instant::now().elapsed(), Version::LATEST_CLIENT);
-        sent_client_handshake.imprint_digest(sent_encryption_algorithm, Handshake::CLIENT_KEY);
-        write_handshake(stream.as_mut(), &sent_client_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_first_handshake(&mut rtmp_context).await;
+        write_encryption_algorithm(writer.as_mut(), sent_encryption_algorithm).await.unwrap();
+        let mut sent_client_handshake = Handshake::new(epoch_timestamp(), Version::LATEST_CLIENT);
+        sent_client_handshake.imprint_digest(DigestScheme::from(sent_encryption_algorithm), Handshake::CLIENT_KEY);
+        write_handshake(writer.as_mut(), &sent_client_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_first_handshake(&mut rtmp_context).await;
         assert!(result.is_ok());
 
-        let received_encryption_algorithm = read_encryption_algorithm(stream.as_mut()).await.unwrap();
-        let received_server_handshake = read_handshake(stream.as_mut()).await.unwrap();
-        let received_client_handshake = read_handshake(stream.as_mut()).await.unwrap();
+        let received_encryption_algorithm = read_encryption_algorithm(reader.as_mut()).await.unwrap();
+        let received_server_handshake = read_handshake(reader.as_mut()).await.unwrap();
+        let received_client_handshake = read_handshake(reader.as_mut()).await.unwrap();
         let mut server_signature_key: Vec<u8> = Vec::new();
         server_signature_key.extend_from_slice(Handshake::SERVER_KEY);
         server_signature_key.extend_from_slice(Handshake::COMMON_KEY);
-        sent_client_handshake.imprint_signature(sent_encryption_algorithm, &server_signature_key);
+        sent_client_handshake.imprint_signature(DigestScheme::from(sent_encryption_algorithm), &server_signature_key);
         assert_eq!(sent_encryption_algorithm, received_encryption_algorithm);
         assert_eq!(sent_client_handshake.get_bytes(), received_client_handshake.get_bytes());
 
-        write_handshake(stream.as_mut(), &received_server_handshake).await.unwrap();
-        let result = handle_handshake(stream.as_mut()).handle_second_handshake(&mut rtmp_context).await;
+        write_handshake(writer.as_mut(), &received_server_handshake).await.unwrap();
+        let result = handle_handshake(reader.as_mut(), writer.as_mut()).handle_second_handshake(&mut rtmp_context).await;
         assert!(result.is_ok());
         let sent_server_handshake = rtmp_context.get_server_handshake().unwrap();
         assert_eq!(received_server_handshake.get_bytes(), sent_server_handshake.get_bytes());
@@ -1259,16 +1858,18 @@ mod tests {
 
     #[tokio::test]
     async fn err_undistinguishable_client() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&Connect::default());
-        handle_message(stream.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
-        let result = handle_message(stream.as_mut()).write_connect_response(&mut rtmp_context).await;
-        let basic_header = read_basic_header(stream.as_mut()).await.unwrap();
-        let message_header = read_message_header(stream.as_mut(), basic_header.get_message_format()).await.unwrap();
-        let chunk = read_chunk_data(stream.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap()).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_connect_response(&mut rtmp_context).await;
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        let chunk = read_chunk_data(reader.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
         let mut buffer: ByteBuffer = chunk.into();
         let command: AmfString = buffer.decode().unwrap();
         assert!(result.is_err());
@@ -1278,17 +1879,19 @@ mod tests {
 
     #[tokio::test]
     async fn err_inconsistent_app_path() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_app("ondemand");
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&Connect::new(object!("app" => AmfString::default())));
-        handle_message(stream.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
-        let result = handle_message(stream.as_mut()).write_connect_response(&mut rtmp_context).await;
-        let basic_header = read_basic_header(stream.as_mut()).await.unwrap();
-        let message_header = read_message_header(stream.as_mut(), basic_header.get_message_format()).await.unwrap();
-        let chunk = read_chunk_data(stream.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap()).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_connect_response(&mut rtmp_context).await;
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        let chunk = read_chunk_data(reader.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
         let mut buffer: ByteBuffer = chunk.into();
         let command: AmfString = buffer.decode().unwrap();
         assert!(result.is_err());
@@ -1296,17 +1899,142 @@ mod tests {
         assert!(rtmp_context.get_information().is_some())
     }
 
+    #[tokio::test]
+    async fn ok_amf3_connect_got_handled() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_app("ondemand");
+
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u8(0);
+        buffer.encode(
+            &Connect::new(
+                object!(
+                    "app" => AmfString::from("ondemand"),
+                    "type" => AmfString::from("nonprivate"),
+                    "objectEncoding" => Number::from(3)
+                )
+            )
+        );
+        let bytes: Vec<u8> = buffer.into();
+        write_chunk(writer.as_mut(), &mut RtmpContext::default(), Connect::CHANNEL.into(), Duration::default(), MessageType::Amf3Command, u32::default(), &bytes).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).handle(&mut rtmp_context).await;
+        assert!(result.is_ok());
+        assert_eq!(Some(PublisherStatus::Connected), rtmp_context.get_publisher_status());
+        let object_encoding = rtmp_context.get_information().unwrap().get_properties().get("objectEncoding").and_then(AmfValue::as_f64);
+        assert_eq!(Some(3f64), object_encoding)
+    }
+
+    #[tokio::test]
+    async fn ok_ping_got_answered() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingRequest::EVENT_TYPE.into());
+        buffer.encode(&PingRequest::new(1000));
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok());
+
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        assert_eq!(Some(MessageType::UserControl), message_header.get_message_type());
+        let data = read_chunk_data(reader.as_mut(), rtmp_context.get_receiving_chunk_size(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
+        let mut buffer: ByteBuffer = data.into();
+        assert_eq!(EventType::PingResponse, buffer.get_u16_be().unwrap().into());
+        let ping_response: PingResponse = buffer.decode().unwrap();
+        assert_eq!(1000, ping_response.get_timestamp())
+    }
+
+    #[tokio::test]
+    async fn ok_ping_response_got_measured() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        let timestamp = epoch_timestamp().as_millis() as u32;
+        rtmp_context.set_ping_timestamp(timestamp.wrapping_sub(500));
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingResponse::EVENT_TYPE.into());
+        buffer.encode(&PingResponse::new(timestamp.wrapping_sub(500)));
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok());
+        assert!(rtmp_context.get_round_trip_time().unwrap() >= Duration::from_millis(500));
+        assert!(rtmp_context.get_ping_timestamp().is_none())
+    }
+
+    #[tokio::test]
+    async fn ok_unsolicited_ping_response_got_ignored() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        /* NOTE: Neither a response without any request nor one to another request is measured. */
+        let timestamp = epoch_timestamp().as_millis() as u32;
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingResponse::EVENT_TYPE.into());
+        buffer.encode(&PingResponse::new(timestamp.wrapping_sub(60000)));
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok());
+        assert!(rtmp_context.get_round_trip_time().is_none());
+
+        rtmp_context.set_ping_timestamp(timestamp);
+        let mut buffer = ByteBuffer::default();
+        buffer.put_u16_be(PingResponse::EVENT_TYPE.into());
+        buffer.encode(&PingResponse::new(timestamp.wrapping_sub(60000)));
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).handle_user_control(&mut rtmp_context, buffer).await.is_ok());
+        assert!(rtmp_context.get_round_trip_time().is_none());
+        assert_eq!(Some(timestamp), rtmp_context.get_ping_timestamp())
+    }
+
+    #[tokio::test]
+    async fn err_unknown_chunk_stream() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        /* NOTE: A chunk which continues a chunk stream that hasn't started yet. */
+        writer.write_all(&[0xc3, 0x00]).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).handle(&mut rtmp_context).await;
+        assert!(result.unwrap_err().get_ref().unwrap().is::<UnknownChunkStream>())
+    }
+
+    #[tokio::test]
+    async fn err_unpublished_media() {
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+
+        let result = handle_message(reader.as_mut(), writer.as_mut()).handle_flv(&mut rtmp_context, ByteBuffer::default(), MessageType::Audio, Duration::default()).await;
+        assert!(result.is_ok());
+        let result = handle_message(reader.as_mut(), writer.as_mut()).handle_aggregate(&mut rtmp_context, ByteBuffer::default(), Duration::default()).await;
+        assert!(result.is_ok());
+
+        rtmp_context.set_unknown_message_policy(UnknownMessagePolicy::Disconnect);
+        let result = handle_message(reader.as_mut(), writer.as_mut()).handle_flv(&mut rtmp_context, ByteBuffer::default(), MessageType::Video, Duration::default()).await;
+        assert!(result.unwrap_err().get_ref().unwrap().is::<UnpublishedMedia>());
+        let result = handle_message(reader.as_mut(), writer.as_mut()).handle_aggregate(&mut rtmp_context, ByteBuffer::default(), Duration::default()).await;
+        assert!(result.unwrap_err().get_ref().unwrap().is::<UnpublishedMedia>())
+    }
+
     #[tokio::test]
     async fn err_empty_topic_id() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         let mut buffer = ByteBuffer::default();
         buffer.encode(&ReleaseStream::new(AmfString::from("")));
-        handle_message(stream.as_mut()).handle_release_stream_request(&mut rtmp_context, buffer).await.unwrap();
-        let result = handle_message(stream.as_mut()).write_release_stream_response(&mut rtmp_context).await;
-        let basic_header = read_basic_header(stream.as_mut()).await.unwrap();
-        let message_header = read_message_header(stream.as_mut(), basic_header.get_message_format()).await.unwrap();
-        let chunk = read_chunk_data(stream.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap()).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_release_stream_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_release_stream_response(&mut rtmp_context).await;
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        let chunk = read_chunk_data(reader.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
         let mut buffer: ByteBuffer = chunk.into();
         let command: AmfString = buffer.decode().unwrap();
         assert!(result.is_err());
@@ -1329,7 +2057,9 @@ mod tests {
 
         let app = "ondemand";
 
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_storage_path(&storage_path);
         rtmp_context.set_database_url(&database_url);
@@ -1340,11 +2070,11 @@ mod tests {
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&ReleaseStream::new(AmfString::new(topic_id)));
-        handle_message(stream.as_mut()).handle_release_stream_request(&mut rtmp_context, buffer).await.unwrap();
-        let result = handle_message(stream.as_mut()).write_release_stream_response(&mut rtmp_context).await;
-        let basic_header = read_basic_header(stream.as_mut()).await.unwrap();
-        let message_header = read_message_header(stream.as_mut(), basic_header.get_message_format()).await.unwrap();
-        let chunk = read_chunk_data(stream.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap()).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_release_stream_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_release_stream_response(&mut rtmp_context).await;
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        let chunk = read_chunk_data(reader.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
         let mut buffer: ByteBuffer = chunk.into();
         let command: AmfString = buffer.decode().unwrap();
         assert!(result.is_err());
@@ -1354,15 +2084,17 @@ mod tests {
 
     #[tokio::test]
     async fn err_inconsistent_topic_id_in_publish() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_message_id(0);
         rtmp_context.set_topic_id(AmfString::new(Uuid::now_v7().to_string()));
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&Publish::new(AmfString::default(), "live".into()));
-        handle_message(stream.as_mut()).handle_publish_request(&mut rtmp_context, buffer).await.unwrap();
-        let result = handle_message(stream.as_mut()).write_publish_response(&mut rtmp_context).await;
+        handle_message(reader.as_mut(), writer.as_mut()).handle_publish_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_publish_response(&mut rtmp_context).await;
         assert!(result.is_err());
         assert!(rtmp_context.get_information().is_some())
     }
@@ -1381,17 +2113,19 @@ mod tests {
             Flv::create(&format!("{topic_storage_path}{MAIN_SEPARATOR}{topic_id}.flv")).unwrap()
         };
 
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_topic(topic);
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&GetStreamLength::new(AmfString::new(topic_id)));
-        handle_message(stream.as_mut()).handle_stream_length_request(&mut rtmp_context, buffer).await.unwrap();
-        let result = handle_message(stream.as_mut()).write_stream_length_response(&mut rtmp_context).await;
-        let basic_header = read_basic_header(stream.as_mut()).await.unwrap();
-        let message_header = read_message_header(stream.as_mut(), basic_header.get_message_format()).await.unwrap();
-        let chunk = read_chunk_data(stream.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap()).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_stream_length_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_stream_length_response(&mut rtmp_context).await;
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        let chunk = read_chunk_data(reader.as_mut(), ChunkSize::default(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
         let mut buffer: ByteBuffer = chunk.into();
         let command: AmfString = buffer.decode().unwrap();
         assert!(result.is_err());
@@ -1401,19 +2135,52 @@ mod tests {
 
     #[tokio::test]
     async fn err_inconsistent_topic_id_in_play() {
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut rtmp_context = RtmpContext::default();
         rtmp_context.set_topic_id(AmfString::new(Uuid::now_v7().to_string()));
         rtmp_context.set_message_id(0);
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&Play::new(AmfString::new(Uuid::now_v7().to_string()), Number::from(-2i8)));
-        handle_message(stream.as_mut()).handle_play_request(&mut rtmp_context, buffer).await.unwrap();
-        let result = handle_message(stream.as_mut()).write_play_response(&mut rtmp_context).await;
+        handle_message(reader.as_mut(), writer.as_mut()).handle_play_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_play_response(&mut rtmp_context).await;
         assert!(result.is_err());
         assert!(rtmp_context.get_information().is_some())
     }
 
+    #[tokio::test]
+    async fn ok_stream_eof_got_sent_in_live_mode() {
+        let temp_dir = temp_dir();
+        let topic_storage_path = format!("{}{MAIN_SEPARATOR}sheave", temp_dir.display());
+        let topic_id = Uuid::now_v7().to_string();
+        let topic = {
+            create_dir_all(&topic_storage_path).unwrap();
+            Flv::create(format!("{topic_storage_path}{MAIN_SEPARATOR}{topic_id}.flv")).unwrap()
+        };
+
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        let mut rtmp_context = RtmpContext::default();
+        rtmp_context.set_topic(topic);
+        rtmp_context.set_message_id(0);
+
+        let mut buffer = ByteBuffer::default();
+        buffer.encode(&Play::new(AmfString::new(topic_id), Number::from(-1i8)));
+        handle_message(reader.as_mut(), writer.as_mut()).handle_play_request(&mut rtmp_context, buffer).await.unwrap();
+        let result = handle_message(reader.as_mut(), writer.as_mut()).write_flv(&mut rtmp_context).await;
+        assert!(result.is_err());
+
+        let basic_header = read_basic_header(reader.as_mut()).await.unwrap();
+        let message_header = read_message_header(reader.as_mut(), basic_header.get_message_format()).await.unwrap();
+        assert_eq!(Some(MessageType::UserControl), message_header.get_message_type());
+        let data = read_chunk_data(reader.as_mut(), rtmp_context.get_receiving_chunk_size(), message_header.get_message_length().unwrap(), u32::MAX).await.unwrap();
+        let mut buffer: ByteBuffer = data.into();
+        assert_eq!(EventType::StreamEof, buffer.get_u16_be().unwrap().into())
+    }
+
     #[tokio::test]
     async fn ok_valid_publisher_sequence() {
         if exists(format!("{}{MAIN_SEPARATOR}.env.test", env!("CARGO_MANIFEST_DIR"))).unwrap() {
@@ -1449,7 +2216,9 @@ mod tests {
             .await
             .unwrap();
 
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(
             &Connect::new(
@@ -1459,38 +2228,46 @@ mod tests {
                 )
             )
         );
-        handle_message(stream.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
-        assert!(handle_message(stream.as_mut()).write_connect_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_connect_response(&mut rtmp_context).await.is_ok());
         assert_eq!(PublisherStatus::Connected, rtmp_context.get_publisher_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&ReleaseStream::new(AmfString::new(topic_id.clone())));
-        handle_message(stream.as_mut()).handle_release_stream_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_release_stream_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_release_stream_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_release_stream_response(&mut rtmp_context).await.is_ok());
         assert_eq!(PublisherStatus::Released, rtmp_context.get_publisher_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&FcPublish::new(AmfString::new(topic_id.clone())));
-        handle_message(stream.as_mut()).handle_fc_publish_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_fc_publish_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_fc_publish_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_fc_publish_response(&mut rtmp_context).await.is_ok());
         assert_eq!(PublisherStatus::FcPublished, rtmp_context.get_publisher_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&CreateStream);
-        handle_message(stream.as_mut()).handle_create_stream_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_create_stream_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_create_stream_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_create_stream_response(&mut rtmp_context).await.is_ok());
         assert_eq!(PublisherStatus::Created, rtmp_context.get_publisher_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&Publish::new(AmfString::new(topic_id), "live".into()));
-        handle_message(stream.as_mut()).handle_publish_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_stream_begin(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_publish_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_stream_begin(&mut rtmp_context).await.is_ok());
         assert_eq!(PublisherStatus::Began, rtmp_context.get_publisher_status().unwrap());
-        assert!(handle_message(stream).write_publish_response(&mut rtmp_context).await.is_ok());
+        assert!(handle_message(reader, writer).write_publish_response(&mut rtmp_context).await.is_ok());
         assert_eq!(PublisherStatus::Published, rtmp_context.get_publisher_status().unwrap())
     }
 
@@ -1530,7 +2307,9 @@ mod tests {
             .await
             .unwrap();
 
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(
             &Connect::new(
@@ -1540,33 +2319,39 @@ mod tests {
                 )
             )
         );
-        handle_message(stream.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
-        assert!(handle_message(stream.as_mut()).write_connect_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_connect_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Connected, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&WindowAcknowledgementSize::default());
-        handle_message(stream.as_mut()).handle_window_acknowledgement_size(&mut rtmp_context, buffer).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_window_acknowledgement_size(&mut rtmp_context, buffer).await.unwrap();
         assert_eq!(SubscriberStatus::WindowAcknowledgementSizeGotSent, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&CreateStream);
-        handle_message(stream.as_mut()).handle_create_stream_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_create_stream_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_create_stream_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_create_stream_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Created, rtmp_context.get_subscriber_status().unwrap());
 
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(&FcSubscribe::new(AmfString::new(topic_id.clone())));
-        handle_message(stream.as_mut()).handle_fc_subscribe_request(&mut rtmp_context, buffer).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_fc_subscribe_request(&mut rtmp_context, buffer).await.unwrap();
         assert_eq!(SubscriberStatus::FcSubscribed, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&GetStreamLength::new(AmfString::new(topic_id.clone())));
-        handle_message(stream.as_mut()).handle_stream_length_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_stream_length_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_stream_length_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_stream_length_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::AdditionalCommandGotSent, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
@@ -1576,16 +2361,18 @@ mod tests {
                 Number::from(-2i8)
             )
         );
-        handle_message(stream.as_mut()).handle_play_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_stream_begin(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_play_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_stream_begin(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Began, rtmp_context.get_subscriber_status().unwrap());
-        assert!(handle_message(stream.as_mut()).write_play_response(&mut rtmp_context).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_play_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Played, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&SetBufferLength::new(rtmp_context.get_message_id().unwrap(), 0));
-        handle_message(stream.as_mut()).handle_buffer_length(&mut rtmp_context, buffer).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_buffer_length(&mut rtmp_context, buffer).await.unwrap();
         assert_eq!(SubscriberStatus::BufferLengthGotSent, rtmp_context.get_subscriber_status().unwrap())
     }
 
@@ -1626,7 +2413,9 @@ mod tests {
             .await
             .unwrap();
 
-        let mut stream = pin!(VecStream::default());
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
         let mut buffer = ByteBuffer::default();
         buffer.encode(
             &Connect::new(
@@ -1636,25 +2425,27 @@ mod tests {
                 )
             )
         );
-        handle_message(stream.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
-        assert!(handle_message(stream.as_mut()).write_connect_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_connect_request(&mut rtmp_context, buffer).await.unwrap();
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_connect_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Connected, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&WindowAcknowledgementSize::default());
-        handle_message(stream.as_mut()).handle_window_acknowledgement_size(&mut rtmp_context, buffer).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_window_acknowledgement_size(&mut rtmp_context, buffer).await.unwrap();
         assert_eq!(SubscriberStatus::WindowAcknowledgementSizeGotSent, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&CreateStream);
-        handle_message(stream.as_mut()).handle_create_stream_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_create_stream_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_create_stream_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_create_stream_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Created, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&FcSubscribe::new(AmfString::new(topic_id.clone())));
-        handle_message(stream.as_mut()).handle_fc_subscribe_request(&mut rtmp_context, buffer).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_fc_subscribe_request(&mut rtmp_context, buffer).await.unwrap();
         assert_eq!(SubscriberStatus::FcSubscribed, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
@@ -1665,9 +2456,11 @@ mod tests {
                 )
             )
         );
-        handle_message(stream.as_mut()).handle_playlist_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_playlist_response(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_playlist_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_playlist_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::AdditionalCommandGotSent, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
@@ -1677,16 +2470,18 @@ mod tests {
                 Number::from(-2i8)
             )
         );
-        handle_message(stream.as_mut()).handle_play_request(&mut rtmp_context, buffer).await.unwrap();
-        let mut stream = pin!(VecStream::default());
-        assert!(handle_message(stream.as_mut()).write_stream_begin(&mut rtmp_context).await.is_ok());
+        handle_message(reader.as_mut(), writer.as_mut()).handle_play_request(&mut rtmp_context, buffer).await.unwrap();
+        let (reader, writer) = split(VecStream::default());
+        let mut reader = pin!(reader);
+        let mut writer = pin!(writer);
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_stream_begin(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Began, rtmp_context.get_subscriber_status().unwrap());
-        assert!(handle_message(stream.as_mut()).write_play_response(&mut rtmp_context).await.is_ok());
+        assert!(handle_message(reader.as_mut(), writer.as_mut()).write_play_response(&mut rtmp_context).await.is_ok());
         assert_eq!(SubscriberStatus::Played, rtmp_context.get_subscriber_status().unwrap());
 
         let mut buffer = ByteBuffer::default();
         buffer.encode(&SetBufferLength::new(rtmp_context.get_message_id().unwrap(), 0));
-        handle_message(stream.as_mut()).handle_buffer_length(&mut rtmp_context, buffer).await.unwrap();
+        handle_message(reader.as_mut(), writer.as_mut()).handle_buffer_length(&mut rtmp_context, buffer).await.unwrap();
         assert_eq!(SubscriberStatus::BufferLengthGotSent, rtmp_context.get_subscriber_status().unwrap())
     }
 }
@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that the client is about to publish/play without passing the SWF verification which the app requires.
+#[derive(Debug)]
+pub struct SwfIsUnverified;
+
+impl Display for SwfIsUnverified {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Client didn't pass the SWF verification.")
+    }
+}
+
+impl Error for SwfIsUnverified {}
+
+/// A utility function of constructing a `SwfIsUnverified` error.
+pub fn swf_is_unverified() -> IOError {
+    IOError::new(
+        ErrorKind::PermissionDenied,
+        SwfIsUnverified
+    )
+}
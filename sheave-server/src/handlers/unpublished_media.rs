@@ -0,0 +1,33 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+use sheave_core::messages::headers::MessageType;
+
+/// An error that some media message has arrived before its stream gets published.
+#[derive(Debug)]
+pub struct UnpublishedMedia(MessageType);
+
+impl Display for UnpublishedMedia {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Media message has arrived before publishing: {:?}", self.0)
+    }
+}
+
+impl Error for UnpublishedMedia {}
+
+/// A utility function of constructing an `UnpublishedMedia` error.
+pub fn unpublished_media(message_type: MessageType) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        UnpublishedMedia(message_type)
+    )
+}
@@ -4,8 +4,12 @@ pub mod net;
 pub mod handlers;
 mod server;
 mod invalid_uri;
+mod missing_certificate;
+mod unsupported_protocol;
 
 pub use self::{
     server::Server,
-    invalid_uri::*
+    invalid_uri::*,
+    missing_certificate::*,
+    unsupported_protocol::*
 };
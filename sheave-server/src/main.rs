@@ -4,16 +4,23 @@ pub mod net;
 pub mod handlers;
 mod server;
 mod invalid_uri;
+mod missing_certificate;
+mod unsupported_protocol;
 
 use std::{
     env::temp_dir,
-    fs::exists,
+    fs::{
+        exists,
+        read
+    },
     io::{
         Error as IOError,
         Result as IOResult
     },
     marker::PhantomData,
-    path::Path
+    net::SocketAddr,
+    path::Path,
+    sync::Arc
 };
 use log::{
     LevelFilter,
@@ -24,10 +31,17 @@ use env_logger::builder;
 use clap::{
     ArgAction,
     Parser,
-    ValueEnum
+    ValueEnum,
+    value_parser
 };
 use dotenvy::dotenv;
-use tokio::spawn;
+use tokio::{
+    io::{
+        AsyncRead,
+        AsyncWrite
+    },
+    spawn
+};
 
 #[cfg(feature = "sqlite")]
 use sqlx::SqliteConnection as Connector;
@@ -42,16 +56,32 @@ use sqlx::{
 };
 
 use sheave_core::{
-    handlers::RtmpContext,
-    net::rtmp::RtmpStream
+    U24_MAX,
+    flv::Flv,
+    handshake::SwfVerification,
+    handlers::{
+        RtmpContext,
+        UnknownMessagePolicy
+    },
+    net::rtmpe::RtmpeStream
 };
 use self::{
     handlers::RtmpHandler,
-    net::rtmp::RtmpListener
+    net::{
+        rtmp::RtmpListener,
+        rtmps::{
+            RtmpsListener,
+            handshake,
+            server_config
+        },
+        rtmpt::RtmptListener
+    }
 };
 pub use self::{
     server::Server,
-    invalid_uri::*
+    invalid_uri::*,
+    missing_certificate::*,
+    unsupported_protocol::*
 };
 
 #[repr(usize)]
@@ -119,10 +149,13 @@ struct ServerOptions {
 
     /// Listening URIs which starts with protocol schemas of the RTMP.
     ///
-    /// Currently only `rtmp` schema is available.
+    /// Currently `rtmp`, `rtmps`, `rtmpe` and `rtmpt` schemas are available.
     /// Following URI format is required.
     ///
     /// `rtmp://{address}[:port]/[app_name]`
+    /// `rtmps://{address}[:port]/[app_name]`
+    /// `rtmpe://{address}[:port]/[app_name]`
+    /// `rtmpt://{address}[:port]/[app_name]`
     ///
     /// For example:
     ///
@@ -130,11 +163,25 @@ struct ServerOptions {
     /// * `rtmp://127.0.0.1:1935`
     /// * `rtmp://127.0.0.1/live`
     /// * `rtmp://127.0.0.1:1935/live`
+    /// * `rtmps://127.0.0.1/live`
+    /// * `rtmpe://127.0.0.1/live`
+    /// * `rtmpt://127.0.0.1/live`
     ///
-    /// Note that URIs are appended the port of `1935` as defaults if they are without ports.
+    /// Note that URIs are appended the port of `1935` (`443` in `rtmps`, `80` in `rtmpt`) as defaults if they are without ports.
+    /// `rtmpe` listeners also accept clients which don't require the encryption.
     #[arg(long, value_name = "URIs", num_args = 1.., value_delimiter = ',', action = ArgAction::Append, required = true, env = "LISTENERS")]
     listeners: Vec<String>,
 
+    /// The path to a PEM file of the certificate chain for `rtmps` listeners.
+    ///
+    /// This must start with the server's own certificate.
+    #[arg(long, value_name = "Path", requires = "tls_private_key", env = "TLS_CERTIFICATE")]
+    tls_certificate: Option<String>,
+
+    /// The path to a PEM file of the private key for `rtmps` listeners.
+    #[arg(long, value_name = "Path", requires = "tls_certificate", env = "TLS_PRIVATE_KEY")]
+    tls_private_key: Option<String>,
+
     /// The database URL to keep the topic path to handle topics.
     ///
     /// This must start with one of database URL schemas. (e.g. mysql:, postgres:, sqlite:, etc.)
@@ -148,9 +195,72 @@ struct ServerOptions {
     /// If this isn't present, the server set this to TEMP(windows)/TMPDIR(linux) environment variable.
     #[arg(long, value_name = "Path", env = "STORAGE_PATH")]
     storage_path: Option<String>,
+
+    /// The total size of FLV tags to bundle into one Aggregate message in bytes.
+    ///
+    /// If present, the server sends audio/video data to subscribers as Aggregate messages.
+    /// Otherwise every FLV tag is sent as an Audio/Video message in itself.
+    /// This must be within `1` to `16777215`, because the length of messages is 3 bytes.
+    #[arg(long, value_name = "Bytes", value_parser = value_parser!(u32).range(1..=i64::from(U24_MAX)), env = "AGGREGATION_SIZE")]
+    aggregation_size: Option<u32>,
+
+    /// Specifies how to treat messages/events which the server doesn't know or doesn't support.
+    ///
+    /// * ignore: Discards them silently.
+    /// * log: Discards them after logging as warnings.
+    /// * disconnect: Closes the connection.
+    ///
+    /// The default is `log`.
+    #[arg(long, value_enum, value_name = "ignore / log / disconnect", default_value_t)]
+    unknown_message_policy: UnknownMessagePolicy,
+
+    /// The maximum size of a message which the server receives, in bytes.
+    ///
+    /// Every message which exceeds it makes the connection close, instead of being allocated as is.
+    /// The default is `8388608` (8 MiB).
+    #[arg(long, value_name = "Bytes", default_value_t = RtmpContext::DEFAULT_MAX_MESSAGE_SIZE)]
+    max_message_size: u32,
+
+    /// The maximum size of a FLV tag which the server reads from topics, in bytes.
+    ///
+    /// The default is `8388608` (8 MiB).
+    #[arg(long, value_name = "Bytes", default_value_t = Flv::DEFAULT_MAX_TAG_SIZE)]
+    max_tag_size: u32,
+
+    /// Apps which require the SWF verification, and SWF files which clients are required to have been loaded from.
+    ///
+    /// Following format is required.
+    ///
+    /// `{app_name}={path to the SWF file}`
+    ///
+    /// For example:
+    ///
+    /// * `live=/var/www/player.swf`
+    ///
+    /// Clients of these apps are refused to publish/play until they answer the SWF Verify Request correctly.
+    #[arg(long, value_name = "App=Path", num_args = 1.., value_delimiter = ',', value_parser = parse_swf_verification, action = ArgAction::Append, env = "SWF_VERIFICATIONS")]
+    swf_verifications: Vec<(String, String)>,
     // TODO: Makes other options if they are required.
 }
 
+fn parse_swf_verification(swf_verification: &str) -> Result<(String, String), String> {
+    match swf_verification.split_once('=') {
+        Some((app, path)) if !path.is_empty() => Ok((app.to_string(), path.to_string())),
+        _ => Err(format!("This isn't the pair of an app and a SWF file: {swf_verification}"))
+    }
+}
+
+/* NOTE: Computes the hash of the SWF file once per listener, rather than per connection. */
+fn load_swf_verification(app: Option<&str>, options: &ServerOptions) -> IOResult<Option<SwfVerification>> {
+    let Some((_, path)) = options.swf_verifications.iter().find(|(swf_app, _)| swf_app == app.unwrap_or_default()) else {
+        return Ok(None)
+    };
+
+    let swf_verification = SwfVerification::from_swf(&read(path)?)?;
+    info!("SWF verification got required. app: {}, SWF file: {path}", app.unwrap_or_default());
+    Ok(Some(swf_verification))
+}
+
 fn split_uri(uri: &str) -> IOResult<(&str, &str, Option<&str>)> {
     let protocol_len = match uri.find(':') {
         Some(protocol_len) => protocol_len,
@@ -188,26 +298,115 @@ fn split_uri(uri: &str) -> IOResult<(&str, &str, Option<&str>)> {
     Ok((protocol, server_addr, app))
 }
 
-async fn run_as_rtmp(server_addr: &str, app: Option<&str>, options: ServerOptions) -> IOResult<()> {
-    /* NOTE: Addresses can be specified without ports. */
-    let server_addr = if let Some(_) = server_addr.rfind(':') {
+fn build_rtmp_context(app: Option<&str>, client_addr: SocketAddr, swf_verification: Option<SwfVerification>, options: &ServerOptions) -> RtmpContext {
+    let mut rtmp_context = RtmpContext::default();
+    rtmp_context.set_database_url(&options.database_url);
+    let temp_dir = temp_dir().to_str().map(String::from);
+    rtmp_context.set_storage_path(&options.storage_path.clone().or(temp_dir).unwrap());
+    rtmp_context.set_app(app.unwrap_or_default());
+    rtmp_context.set_client_addr(client_addr);
+    if let Some(aggregation_size) = options.aggregation_size {
+        rtmp_context.set_aggregation_size(aggregation_size);
+    }
+    if let Some(swf_verification) = swf_verification {
+        rtmp_context.set_swf_verification(swf_verification);
+    }
+    rtmp_context.set_unknown_message_policy(options.unknown_message_policy);
+    rtmp_context.set_max_message_size(options.max_message_size);
+    rtmp_context.set_max_tag_size(options.max_tag_size);
+    rtmp_context
+}
+
+/* NOTE: Addresses can be specified without ports. */
+fn append_default_port(server_addr: &str, default_port: u16) -> String {
+    if let Some(_) = server_addr.rfind(':') {
         server_addr.to_string()
     } else {
-        format!("{server_addr}:1935")
+        format!("{server_addr}:{default_port}")
+    }
+}
+
+/* NOTE: Every connection is served in its own task, so that the listener keeps accepting others meanwhile. */
+fn serve<RW>(stream: RW, rtmp_context: RtmpContext, client_addr: SocketAddr)
+where RW: AsyncRead + AsyncWrite + Unpin + Send + 'static
+{
+    let server = Server::new(stream, rtmp_context, PhantomData::<RtmpHandler>);
+    spawn(
+        async move {
+            if let Err(e) = server.await {
+                error!("Connection got closed by an error. client: {client_addr}, error: {e}");
+            }
+        }
+    );
+}
+
+async fn run_as_rtmp(server_addr: &str, app: Option<&str>, options: ServerOptions) -> IOResult<()> {
+    let server_addr = append_default_port(server_addr, 1935);
+    let listener = RtmpListener::bind(&server_addr).await?;
+    let swf_verification = load_swf_verification(app, &options)?;
+
+    loop {
+        let (stream, client_addr) = listener.accept().await?;
+        let rtmp_context = build_rtmp_context(app, client_addr, swf_verification, &options);
+        serve(stream, rtmp_context, client_addr);
+    }
+}
+
+async fn run_as_rtmps(server_addr: &str, app: Option<&str>, options: ServerOptions) -> IOResult<()> {
+    let (Some(tls_certificate), Some(tls_private_key)) = (options.tls_certificate.as_ref(), options.tls_private_key.as_ref()) else {
+        error!("RTMPS requires both a certificate and its private key. listener: {server_addr}");
+        return Err(missing_certificate(server_addr.into()))
     };
+    let config = server_config(tls_certificate, tls_private_key)?;
+
+    let server_addr = append_default_port(server_addr, 443);
+    let listener = RtmpsListener::bind(&server_addr, Arc::new(config)).await?;
+    let swf_verification = load_swf_verification(app, &options)?;
+
+    /* NOTE: The TLS handshake is performed in each task, so that neither a stalled client nor a broken one stops the listener. */
+    loop {
+        let (stream, tls_acceptor, client_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                error!("Connection couldn't get accepted. listener: {server_addr}, error: {e}");
+                continue
+            }
+        };
+        let rtmp_context = build_rtmp_context(app, client_addr, swf_verification, &options);
+        spawn(
+            async move {
+                match handshake(stream, tls_acceptor).await {
+                    Ok(stream) => serve(stream, rtmp_context, client_addr),
+                    Err(e) => error!("TLS handshake got failed. client: {client_addr}, error: {e}")
+                }
+            }
+        );
+    }
+}
+
+async fn run_as_rtmpe(server_addr: &str, app: Option<&str>, options: ServerOptions) -> IOResult<()> {
+    let server_addr = append_default_port(server_addr, 1935);
     let listener = RtmpListener::bind(&server_addr).await?;
+    let swf_verification = load_swf_verification(app, &options)?;
+
+    loop {
+        let (stream, client_addr) = listener.accept().await?;
+        let stream = RtmpeStream::from(stream);
+        let mut rtmp_context = build_rtmp_context(app, client_addr, swf_verification, &options);
+        rtmp_context.set_rtmpe_keys(stream.get_keys());
+        serve(stream, rtmp_context, client_addr);
+    }
+}
+
+async fn run_as_rtmpt(server_addr: &str, app: Option<&str>, options: ServerOptions) -> IOResult<()> {
+    let server_addr = append_default_port(server_addr, 80);
+    let listener = RtmptListener::bind(&server_addr).await?;
+    let swf_verification = load_swf_verification(app, &options)?;
 
     loop {
         let (stream, client_addr) = listener.accept().await?;
-        let mut rtmp_context = RtmpContext::default();
-        rtmp_context.set_database_url(&options.database_url);
-        let temp_dir = temp_dir().to_str().map(String::from);
-        rtmp_context.set_storage_path(&options.storage_path.or(temp_dir).unwrap());
-        rtmp_context.set_app(app.unwrap_or_default());
-        rtmp_context.set_client_addr(client_addr);
-
-        let server = Server::new(stream, rtmp_context, PhantomData::<RtmpHandler<RtmpStream>>);
-        return spawn(server).await?;
+        let rtmp_context = build_rtmp_context(app, client_addr, swf_verification, &options);
+        serve(stream, rtmp_context, client_addr);
     }
 }
 
@@ -237,7 +436,22 @@ async fn main() -> IOResult<()> {
             error!("Some error got occurred: {e}");
             return Err(e)
         },
-        _ => unimplemented!("Other protocols.")
+        "rtmps" => if let Err(e) = run_as_rtmps(server_addr, app, options).await {
+            error!("Some error got occurred: {e}");
+            return Err(e)
+        },
+        "rtmpe" => if let Err(e) = run_as_rtmpe(server_addr, app, options).await {
+            error!("Some error got occurred: {e}");
+            return Err(e)
+        },
+        "rtmpt" => if let Err(e) = run_as_rtmpt(server_addr, app, options).await {
+            error!("Some error got occurred: {e}");
+            return Err(e)
+        },
+        _ => {
+            error!("Unsupported protocol got specified: {protocol}");
+            return Err(unsupported_protocol(protocol.into()))
+        }
     }
 
     info!("RTMP communication got completed");
@@ -282,4 +496,41 @@ mod tests {
             .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmp://127.0.0.1:1935,rtmp://0.0.0.0:1935", "--database-url", "sqlite::memory:"]);
         assert!(plural_listeners.is_ok())
     }
+
+    #[test]
+    fn aggregation_size_within_u24() {
+        let result = ServerOptions::command()
+            .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmp://127.0.0.1", "--database-url", "sqlite::memory:", "--aggregation-size", "16777215"]);
+        assert!(result.is_ok());
+        let result = ServerOptions::command()
+            .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmp://127.0.0.1", "--database-url", "sqlite::memory:", "--aggregation-size", "16777216"]);
+        assert!(result.is_err());
+        let result = ServerOptions::command()
+            .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmp://127.0.0.1", "--database-url", "sqlite::memory:", "--aggregation-size", "0"]);
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn err_certificate_without_private_key() {
+        let result = ServerOptions::command()
+            .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmps://127.0.0.1", "--database-url", "sqlite::memory:", "--tls-certificate", "server.crt"]);
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn ok_passing_tls_parameters() {
+        let result = ServerOptions::command()
+            .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmps://127.0.0.1", "--database-url", "sqlite::memory:", "--tls-certificate", "server.crt", "--tls-private-key", "server.key"]);
+        assert!(result.is_ok())
+    }
+
+    #[test]
+    fn err_swf_verification_without_path() {
+        let result = ServerOptions::command()
+            .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmp://127.0.0.1/live", "--database-url", "sqlite::memory:", "--swf-verifications", "live"]);
+        assert!(result.is_err());
+        let result = ServerOptions::command()
+            .try_get_matches_from(vec!["sheave-server", "--listeners", "rtmp://127.0.0.1/live", "--database-url", "sqlite::memory:", "--swf-verifications", "live=player.swf"]);
+        assert!(result.is_ok())
+    }
 }
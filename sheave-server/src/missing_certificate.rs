@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that some `rtmps` listener is specified without either a certificate or its private key.
+#[derive(Debug)]
+pub struct MissingCertificate(String);
+
+impl Display for MissingCertificate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "RTMPS requires both a certificate and its private key. listener: {}", self.0)
+    }
+}
+
+impl Error for MissingCertificate {}
+
+/// A utility function of constructing a `MissingCertificate` error.
+pub fn missing_certificate(listener: String) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidInput,
+        MissingCertificate(listener)
+    )
+}
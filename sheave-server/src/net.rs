@@ -1 +1,3 @@
 pub mod rtmp;
+pub mod rtmps;
+pub mod rtmpt;
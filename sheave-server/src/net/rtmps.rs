@@ -0,0 +1,249 @@
+use std::{
+    fmt::{
+        Debug,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        Result as IOResult
+    },
+    net::SocketAddr,
+    path::Path,
+    sync::Arc,
+    time::Duration
+};
+use tokio::{
+    net::ToSocketAddrs,
+    time::timeout
+};
+use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{
+        ServerConfig,
+        crypto::ring::default_provider
+    }
+};
+use sheave_core::net::{
+    rtmp::RtmpStream,
+    rtmps::*
+};
+use super::rtmp::RtmpListener;
+
+/// The duration which the TLS handshake is required to complete within.
+///
+/// A client which exceeds this is regarded as stalled, then its connection is dropped.
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The RTMPS listener.
+///
+/// This accepts TCP connections as same as [`RtmpListener`].
+/// The TLS handshake is left to [`handshake`], so that a stalled/broken client doesn't block accepting others.
+#[derive(Clone)]
+pub struct RtmpsListener {
+    rtmp_listener: Arc<RtmpListener>,
+    tls_acceptor: TlsAcceptor
+}
+
+impl RtmpsListener {
+    /// Opens a RTMPS socket for remote host.
+    ///
+    /// Every connection which is accepted via this listener is authenticated by `config`.
+    pub async fn bind<A: ToSocketAddrs>(addr: A, config: Arc<ServerConfig>) -> IOResult<Self> {
+        let rtmp_listener = RtmpListener::bind(addr).await?;
+        Ok(
+            Self {
+                rtmp_listener: Arc::new(rtmp_listener),
+                tls_acceptor: TlsAcceptor::from(config)
+            }
+        )
+    }
+
+    /// Accepts a new incoming connection from this listener.
+    ///
+    /// This returns the connection before the TLS handshake, along with the acceptor to perform it.
+    /// Pass both to [`handshake`] in the task which serves the connection.
+    pub async fn accept(&self) -> IOResult<(RtmpStream, TlsAcceptor, SocketAddr)> {
+        let (rtmp_stream, addr) = self.rtmp_listener.accept().await?;
+        Ok((rtmp_stream, self.tls_acceptor.clone(), addr))
+    }
+
+    /// Returns the local address that this listener is bound to.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpListener.html#method.local_addr)
+    pub fn local_addr(&self) -> IOResult<SocketAddr> {
+        self.rtmp_listener.local_addr()
+    }
+}
+
+impl Debug for RtmpsListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_struct("RtmpsListener").field("rtmp_listener", &self.rtmp_listener).finish_non_exhaustive()
+    }
+}
+
+/// Performs the TLS handshake as a server on an accepted connection, then wraps its session into RtmpsStream.
+///
+/// # Errors
+///
+/// When the handshake fails, or doesn't complete within [`HANDSHAKE_TIMEOUT`].
+pub async fn handshake(rtmp_stream: RtmpStream, tls_acceptor: TlsAcceptor) -> IOResult<RtmpsStream> {
+    let tls_stream = timeout(HANDSHAKE_TIMEOUT, tls_acceptor.accept(rtmp_stream)).await??;
+    Ok(RtmpsStream::from(tls_stream))
+}
+
+/// Builds the TLS configuration of servers from PEM files of a certificate chain and its private key.
+///
+/// The certificate chain is required to start with the server's own certificate.
+pub fn server_config<P: AsRef<Path>>(certificate_path: P, private_key_path: P) -> IOResult<ServerConfig> {
+    let certificates = load_certificates(certificate_path)?;
+    let private_key = load_private_key(private_key_path)?;
+    ServerConfig::builder_with_provider(Arc::new(default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(IOError::other)?
+        .with_no_client_auth()
+        .with_single_cert(certificates, private_key)
+        .map_err(IOError::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::write
+    };
+    use rcgen::generate_simple_self_signed;
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt
+        },
+        net::TcpStream,
+        spawn
+    };
+    use tokio_rustls::rustls::{
+        ClientConfig,
+        RootCertStore
+    };
+    use super::*;
+
+    fn generate_config(name: &str) -> (Arc<ServerConfig>, RootCertStore) {
+        let certified_key = generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let certificate_path = temp_dir().join(format!("sheave-server-{name}.crt"));
+        let private_key_path = temp_dir().join(format!("sheave-server-{name}.key"));
+        write(&certificate_path, certified_key.cert.pem()).unwrap();
+        write(&private_key_path, certified_key.signing_key.serialize_pem()).unwrap();
+        let config = server_config(&certificate_path, &private_key_path).unwrap();
+
+        let mut root_cert_store = RootCertStore::empty();
+        root_cert_store.add(certified_key.cert.der().clone()).unwrap();
+        (Arc::new(config), root_cert_store)
+    }
+
+    fn client_config(root_cert_store: RootCertStore) -> Arc<ClientConfig> {
+        let config = ClientConfig::builder_with_provider(Arc::new(default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(root_cert_store)
+            .with_no_client_auth();
+        Arc::new(config)
+    }
+
+    #[tokio::test]
+    async fn ok_loopback() {
+        let (server_config, root_cert_store) = generate_config("ok-loopback");
+        let listener = RtmpsListener::bind("127.0.0.1:0", server_config).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepting = spawn(
+            async move {
+                let (rtmp_stream, tls_acceptor, _) = listener.accept().await.unwrap();
+                let mut stream = handshake(rtmp_stream, tls_acceptor).await.unwrap();
+                let mut received = [0u8; 4];
+                stream.read_exact(&mut received).await.unwrap();
+                stream.write_all(&received).await.unwrap();
+                stream.flush().await.unwrap();
+            }
+        );
+
+        let server_name = "localhost".try_into().unwrap();
+        let mut stream = RtmpsStream::connect(addr, server_name, client_config(root_cert_store)).await.unwrap();
+        stream.write_all(&[0x03, 0x00, 0x00, 0x00]).await.unwrap();
+        stream.flush().await.unwrap();
+        let mut echoed = [0u8; 4];
+        stream.read_exact(&mut echoed).await.unwrap();
+        assert_eq!([0x03, 0x00, 0x00, 0x00], echoed);
+        accepting.await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn err_untrusted_certificate() {
+        let (server_config, _) = generate_config("err-untrusted-certificate");
+        let listener = RtmpsListener::bind("127.0.0.1:0", server_config).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepting = spawn(
+            async move {
+                let (rtmp_stream, tls_acceptor, _) = listener.accept().await?;
+                handshake(rtmp_stream, tls_acceptor).await.map(|_| ())
+            }
+        );
+
+        let server_name = "localhost".try_into().unwrap();
+        let result = RtmpsStream::connect(addr, server_name, client_config(RootCertStore::empty())).await;
+        assert!(result.is_err());
+        assert!(accepting.await.unwrap().is_err())
+    }
+
+    #[tokio::test]
+    async fn ok_served_after_garbage_clients() {
+        let (server_config, root_cert_store) = generate_config("ok-served-after-garbage-clients");
+        let listener = RtmpsListener::bind("127.0.0.1:0", server_config).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepting = spawn(
+            async move {
+                loop {
+                    let (rtmp_stream, tls_acceptor, _) = listener.accept().await.unwrap();
+                    spawn(
+                        async move {
+                            let Ok(mut stream) = handshake(rtmp_stream, tls_acceptor).await else {
+                                return
+                            };
+                            let mut received = [0u8; 4];
+                            stream.read_exact(&mut received).await.unwrap();
+                            stream.write_all(&received).await.unwrap();
+                            stream.flush().await.unwrap();
+                        }
+                    );
+                }
+            }
+        );
+
+        /* NOTE: One stalls without sending anything, another sends what isn't the ClientHello. */
+        let _stalled = TcpStream::connect(addr).await.unwrap();
+        let mut garbage = TcpStream::connect(addr).await.unwrap();
+        garbage.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        garbage.flush().await.unwrap();
+
+        let server_name = "localhost".try_into().unwrap();
+        let echoed = timeout(
+            Duration::from_secs(5),
+            async {
+                let mut stream = RtmpsStream::connect(addr, server_name, client_config(root_cert_store)).await.unwrap();
+                stream.write_all(&[0x03, 0x00, 0x00, 0x00]).await.unwrap();
+                stream.flush().await.unwrap();
+                let mut echoed = [0u8; 4];
+                stream.read_exact(&mut echoed).await.unwrap();
+                echoed
+            }
+        ).await.unwrap();
+        assert_eq!([0x03, 0x00, 0x00, 0x00], echoed);
+        accepting.abort()
+    }
+
+    #[test]
+    fn err_missing_certificate() {
+        let private_key_path = temp_dir().join("sheave-server-err-missing-certificate.key");
+        let certified_key = generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        write(&private_key_path, certified_key.signing_key.serialize_pem()).unwrap();
+        assert!(server_config(&private_key_path, &private_key_path).is_err())
+    }
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::HashMap,
+    fmt::{
+        Debug,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind,
+        Result as IOResult
+    },
+    net::SocketAddr,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard
+    },
+    time::{
+        Duration,
+        Instant
+    }
+};
+use log::{
+    debug,
+    error,
+    info,
+    warn
+};
+use tokio::{
+    io::BufReader,
+    net::ToSocketAddrs,
+    spawn,
+    sync::{
+        Mutex as AsyncMutex,
+        mpsc::{
+            Receiver,
+            Sender,
+            channel,
+            error::TrySendError
+        }
+    },
+    task::JoinHandle,
+    time::interval
+};
+use uuid::Uuid;
+use sheave_core::net::{
+    rtmp::RtmpStream,
+    rtmpt::*
+};
+use super::rtmp::RtmpListener;
+
+/// The duration which a session is kept for without any request.
+///
+/// Clients poll at most every [`MAX_POLLING_INTERVAL`] units, so a session which exceeds this is regarded as abandoned.
+pub const SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+/// The number of sessions which can wait to be accepted.
+///
+/// The `open` request is refused while this is full.
+pub const OPENING_BACKLOG: usize = 128;
+
+#[derive(Debug)]
+struct Session {
+    session: RtmptSession,
+    client_addr: SocketAddr,
+    polling_interval: u8,
+    last_requested: Instant
+}
+
+#[derive(Debug, Default)]
+struct Sessions(Mutex<HashMap<String, Session>>);
+
+impl Sessions {
+    /* NOTE: Nothing panics while locking, so the lock can't be poisoned. */
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Session>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /* NOTE: Closing makes the handler of the session get the end of the stream, so its task also terminates. */
+    fn expire(&self, timeout: Duration) {
+        self.lock().retain(
+            |session_id, session| {
+                if session.last_requested.elapsed() < timeout {
+                    return true
+                }
+
+                warn!("RTMPT session got expired. session ID: {session_id}, client address: {}", session.client_addr);
+                session.session.close();
+                false
+            }
+        );
+    }
+}
+
+/// The RTMPT listener.
+///
+/// This serves HTTP connections, and handles following POST requests on them.
+///
+/// |Path|Body of the request|Body of the response|
+/// | :- | :- | :- |
+/// |`/open/{sequence}`|A byte of `0`.|The session ID and a line break.|
+/// |`/send/{session ID}/{sequence}`|RTMP chunks which the client has sent.|A polling interval, then RTMP chunks which the server has sent.|
+/// |`/idle/{session ID}/{sequence}`|A byte of `0`.|Same as `/send`.|
+/// |`/close/{session ID}/{sequence}`|A byte of `0`.|A byte of `0`.|
+///
+/// Every session which is opened is accepted as a [`RtmptStream`], so that the same handlers as RTMP can handle it.
+/// The polling interval is reset to `1` while some chunk is exchanged, otherwise gets doubled until [`MAX_POLLING_INTERVAL`].
+/// Every session which hasn't been requested for [`SESSION_TIMEOUT`] gets closed.
+pub struct RtmptListener {
+    rtmp_listener: Arc<RtmpListener>,
+    opened: AsyncMutex<Receiver<(RtmptStream, SocketAddr)>>,
+    serving: JoinHandle<()>,
+    expiring: JoinHandle<()>
+}
+
+impl RtmptListener {
+    /// Opens a RTMPT socket for remote host.
+    ///
+    /// HTTP connections are served in background until this listener is dropped.
+    pub async fn bind<A: ToSocketAddrs>(addr: A) -> IOResult<Self> {
+        let rtmp_listener = Arc::new(RtmpListener::bind(addr).await?);
+        let sessions: Arc<Sessions> = Arc::default();
+        let (sender, receiver) = channel(OPENING_BACKLOG);
+        let serving = spawn(serve(Arc::clone(&rtmp_listener), Arc::clone(&sessions), sender));
+        let expiring = spawn(expire(sessions, SESSION_TIMEOUT));
+        Ok(
+            Self {
+                rtmp_listener,
+                opened: AsyncMutex::new(receiver),
+                serving,
+                expiring
+            }
+        )
+    }
+
+    /// Accepts a new session which has been opened via this listener.
+    pub async fn accept(&self) -> IOResult<(RtmptStream, SocketAddr)> {
+        self.opened.lock().await.recv().await.ok_or_else(|| IOError::from(ErrorKind::ConnectionAborted))
+    }
+
+    /// Returns the local address that this listener is bound to.
+    ///
+    /// [Read more](https://docs.rs/tokio/latest/tokio/net/struct.TcpListener.html#method.local_addr)
+    pub fn local_addr(&self) -> IOResult<SocketAddr> {
+        self.rtmp_listener.local_addr()
+    }
+}
+
+impl Drop for RtmptListener {
+    fn drop(&mut self) {
+        self.serving.abort();
+        self.expiring.abort();
+    }
+}
+
+impl Debug for RtmptListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_struct("RtmptListener").field("rtmp_listener", &self.rtmp_listener).finish_non_exhaustive()
+    }
+}
+
+async fn serve(rtmp_listener: Arc<RtmpListener>, sessions: Arc<Sessions>, opened: Sender<(RtmptStream, SocketAddr)>) {
+    loop {
+        match rtmp_listener.accept().await {
+            Ok((rtmp_stream, client_addr)) => {
+                spawn(serve_connection(rtmp_stream, client_addr, Arc::clone(&sessions), opened.clone()));
+            },
+            Err(e) => error!("Some HTTP connection couldn't be accepted: {e}")
+        }
+    }
+}
+
+async fn expire(sessions: Arc<Sessions>, timeout: Duration) {
+    let mut ticks = interval(timeout / 2);
+    loop {
+        ticks.tick().await;
+        sessions.expire(timeout);
+    }
+}
+
+async fn serve_connection(rtmp_stream: RtmpStream, client_addr: SocketAddr, sessions: Arc<Sessions>, opened: Sender<(RtmptStream, SocketAddr)>) {
+    let mut connection = BufReader::new(rtmp_stream);
+    loop {
+        let request = match read_http_message(&mut connection).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return,
+            Err(e) => {
+                error!("Invalid HTTP request from {client_addr}: {e}");
+                let _ = write_http_message(&mut connection, &respond("400 Bad Request", Vec::new())).await;
+                return
+            }
+        };
+
+        let response = handle_request(&request, client_addr, &sessions, &opened);
+        if let Err(e) = write_http_message(&mut connection, &response).await {
+            error!("HTTP response couldn't be sent to {client_addr}: {e}");
+            return
+        }
+    }
+}
+
+fn handle_request(request: &HttpMessage, client_addr: SocketAddr, sessions: &Sessions, opened: &Sender<(RtmptStream, SocketAddr)>) -> HttpMessage {
+    let mut request_line = request.get_start_line().split(' ');
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return respond("400 Bad Request", Vec::new())
+    };
+    if method != "POST" {
+        return respond("405 Method Not Allowed", Vec::new())
+    }
+
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["open", _] => {
+            let session_id = Uuid::now_v7().simple().to_string();
+            let session = RtmptSession::default();
+            match opened.try_send((RtmptStream::from(session.clone()), client_addr)) {
+                Ok(()) => {},
+                Err(TrySendError::Full(_)) => {
+                    warn!("RTMPT session couldn't be opened because too many sessions are waiting. client address: {client_addr}");
+                    return respond("503 Service Unavailable", Vec::new())
+                },
+                Err(TrySendError::Closed(_)) => return respond("503 Service Unavailable", Vec::new())
+            }
+
+            info!("RTMPT session got opened. session ID: {session_id}, client address: {client_addr}");
+            sessions.lock().insert(session_id.clone(), Session { session, client_addr, polling_interval: 1, last_requested: Instant::now() });
+            respond("200 OK", format!("{session_id}\n").into_bytes())
+        },
+        [command @ ("send" | "idle"), session_id, _] => {
+            let mut sessions = sessions.lock();
+            let Some(session) = sessions.get_mut(*session_id) else {
+                return respond("404 Not Found", Vec::new())
+            };
+            if session.client_addr.ip() != client_addr.ip() {
+                return respond("404 Not Found", Vec::new())
+            }
+            session.last_requested = Instant::now();
+
+            if *command == "send" {
+                session.session.receive(request.get_body());
+            }
+            /* NOTE: The first byte of the body is the polling interval. */
+            let sending = session.session.drain(MAX_BODY_LEN - 1);
+            if sending.is_empty() && session.session.is_closed() {
+                debug!("RTMPT session got closed by the server. session ID: {session_id}");
+                sessions.remove(*session_id);
+                return respond("404 Not Found", Vec::new())
+            }
+
+            session.polling_interval = if *command == "send" || !sending.is_empty() {
+                1
+            } else {
+                session.polling_interval.saturating_mul(2).min(MAX_POLLING_INTERVAL)
+            };
+            let mut body = vec![session.polling_interval];
+            body.extend_from_slice(&sending);
+            respond("200 OK", body)
+        },
+        ["close", session_id, _] => {
+            let mut sessions = sessions.lock();
+            match sessions.get(*session_id) {
+                Some(session) if session.client_addr.ip() == client_addr.ip() => {
+                    session.session.close();
+                    sessions.remove(*session_id);
+                    info!("RTMPT session got closed. session ID: {session_id}");
+                    respond("200 OK", vec![0])
+                },
+                _ => respond("404 Not Found", Vec::new())
+            }
+        },
+        _ => respond("404 Not Found", Vec::new())
+    }
+}
+
+fn respond(status: &str, body: Vec<u8>) -> HttpMessage {
+    let headers = vec![
+        ("Server".to_string(), "sheave".to_string()),
+        ("Content-Type".to_string(), CONTENT_TYPE.to_string()),
+        ("Connection".to_string(), "Keep-Alive".to_string()),
+        ("Cache-Control".to_string(), "no-cache".to_string())
+    ];
+    HttpMessage::new(format!("HTTP/1.1 {status}"), headers, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt
+        },
+        net::TcpStream
+    };
+    use super::*;
+
+    #[tokio::test]
+    async fn ok_loopback() {
+        let listener = RtmptListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut client = RtmptStream::connect(addr).await.unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        client.write_all(&[0x03, 0x00, 0x00, 0x00]).await.unwrap();
+        let mut received = [0u8; 4];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!([0x03, 0x00, 0x00, 0x00], received);
+
+        server.write_all(&received).await.unwrap();
+        let mut echoed = [0u8; 4];
+        client.read_exact(&mut echoed).await.unwrap();
+        assert_eq!([0x03, 0x00, 0x00, 0x00], echoed);
+
+        /* NOTE: The client gets the end of the stream after the server has closed the session. */
+        drop(server);
+        let mut rest: Vec<u8> = Vec::new();
+        client.read_to_end(&mut rest).await.unwrap();
+        assert!(rest.is_empty())
+    }
+
+    #[test]
+    fn expire_idle_session() {
+        let sessions = Sessions::default();
+        let session = RtmptSession::default();
+        let client_addr: SocketAddr = "127.0.0.1:1935".parse().unwrap();
+        let expired = Instant::now().checked_sub(SESSION_TIMEOUT * 2).unwrap();
+        sessions.lock().insert("idle".into(), Session { session: session.clone(), client_addr, polling_interval: 1, last_requested: expired });
+        sessions.lock().insert("active".into(), Session { session: RtmptSession::default(), client_addr, polling_interval: 1, last_requested: Instant::now() });
+
+        sessions.expire(SESSION_TIMEOUT);
+        assert!(!sessions.lock().contains_key("idle"));
+        assert!(session.is_closed());
+        assert!(sessions.lock().contains_key("active"))
+    }
+
+    #[test]
+    fn err_too_many_opening_sessions() {
+        let sessions = Sessions::default();
+        let (opened, _receiver) = channel(1);
+        let client_addr: SocketAddr = "127.0.0.1:1935".parse().unwrap();
+        let request = HttpMessage::new("POST /open/1 HTTP/1.1".into(), Vec::new(), vec![0]);
+
+        assert_eq!("HTTP/1.1 200 OK", handle_request(&request, client_addr, &sessions, &opened).get_start_line());
+        assert_eq!("HTTP/1.1 503 Service Unavailable", handle_request(&request, client_addr, &sessions, &opened).get_start_line());
+        assert_eq!(1, sessions.lock().len())
+    }
+
+    #[tokio::test]
+    async fn err_unknown_session() {
+        let listener = RtmptListener::bind("127.0.0.1:0").await.unwrap();
+        let tcp_stream = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+        let mut connection = BufReader::new(tcp_stream);
+
+        let request = HttpMessage::new("POST /idle/unknown/1 HTTP/1.1".into(), Vec::new(), vec![0]);
+        write_http_message(&mut connection, &request).await.unwrap();
+        let response = read_http_message(&mut connection).await.unwrap().unwrap();
+        assert_eq!("HTTP/1.1 404 Not Found", response.get_start_line())
+    }
+}
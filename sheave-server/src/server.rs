@@ -1,14 +1,13 @@
 use std::{
-    future::Future,
-    io::{
-        Result as IOResult
+    fmt::{
+        Debug,
+        Formatter,
+        Result as FormatResult
     },
+    future::Future,
+    io::Result as IOResult,
     marker::PhantomData,
-    pin::{
-        Pin,
-        pin
-    },
-    sync::Arc,
+    pin::Pin,
     task::{
         Context as FutureContext,
         Poll
@@ -16,12 +15,14 @@ use std::{
 };
 use tokio::io::{
     AsyncRead,
-    AsyncWrite
+    AsyncWrite,
+    split
 };
 use sheave_core::handlers::{
+    HandlerConstructor,
     RtmpContext,
-    StreamWrapper,
-    HandlerConstructor
+    StreamReader,
+    StreamWriter
 };
 
 /// The server instance of the Sheave
@@ -32,8 +33,9 @@ use sheave_core::handlers::{
 /// * Context data in the server.
 /// * Some type parameter which implemented the [`HandlerConstructor`] trait.
 ///
-/// The server wraps streams into [`Arc`] as a way of sharing streams among communication steps.
-/// And also wraps contexts because of the same purpose.
+/// The server splits streams into the read half and the write half, then owns both of them and its context.
+/// These are lent to the handler while handling, so that the handler holds no reference to them.
+/// Note each half can be used independently of the other, that is, reading and writing can be performed at the same time.
 ///
 /// The server makes any foreign handler to be able to construct via the [`PhantomData`], where a type parameter of [`PhantomData`] requires to implement the [`HandlerConstructor`] trait.
 /// That is, its type parameter behaves as the constructor injection.
@@ -44,12 +46,7 @@ use sheave_core::handlers::{
 /// use std::{
 ///     io::Result as IOResult,
 ///     marker::PhantomData,
-///     pin::Pin,
-///     sync::Arc,
-///     task::{
-///         Context as FutureContext,
-///         Poll
-///     }
+///     pin::Pin
 /// };
 /// use tokio::io::{
 ///     AsyncRead,
@@ -58,23 +55,27 @@ use sheave_core::handlers::{
 /// use sheave_core::handlers::{
 ///     AsyncHandler,
 ///     HandlerConstructor,
+///     MeasureAcknowledgement,
 ///     RtmpContext,
-///     StreamWrapper,
 ///     VecStream
 /// };
 /// use sheave_server::Server;
 ///
-/// struct SomethingHandler<RW: AsyncRead + AsyncWrite + Unpin>(Arc<StreamWrapper<RW>>);
+/// struct SomethingHandler;
 ///
-/// impl<RW: AsyncRead + AsyncWrite + Unpin> AsyncHandler for SomethingHandler<RW> {
-///     fn poll_handle(self: Pin<&mut Self>, _cx: &mut FutureContext<'_>, _rtmp_context: &mut RtmpContext) -> Poll<IOResult<()>> {
-///         Poll::Ready(Ok(()))
+/// impl AsyncHandler for SomethingHandler {
+///     async fn handle<R, W>(&mut self, _reader: Pin<&mut R>, _writer: Pin<&mut W>, _rtmp_context: &mut RtmpContext) -> IOResult<()>
+///     where
+///         R: AsyncRead + MeasureAcknowledgement + Unpin + Send,
+///         W: AsyncWrite + Unpin + Send
+///     {
+///         Ok(())
 ///     }
 /// }
 ///
-/// impl<RW: AsyncRead + AsyncWrite + Unpin> HandlerConstructor<StreamWrapper<RW>> for SomethingHandler<RW> {
-///     fn new(stream: Arc<StreamWrapper<RW>>) -> Self {
-///         Self(stream)
+/// impl HandlerConstructor for SomethingHandler {
+///     fn new() -> Self {
+///         Self
 ///     }
 /// }
 ///
@@ -82,49 +83,63 @@ use sheave_core::handlers::{
 /// async fn main() {
 ///     let stream = VecStream::default();
 ///     let rtmp_context = RtmpContext::default();
-///     let mut server = Server::new(stream, rtmp_context, PhantomData::<SomethingHandler<VecStream>>);
+///     let mut server = Server::new(stream, rtmp_context, PhantomData::<SomethingHandler>);
 ///     let result = server.await;
 ///     assert!(result.is_ok())
 /// }
 /// ```
 ///
-/// [`Arc`]: std::sync::Arc
 /// [`PhantomData`]: std::marker::PhantomData
 /// [`HandlerConstructor`]: sheave_core::handlers::HandlerConstructor
-#[derive(Debug)]
 pub struct Server<RW, C>
 where
     RW: AsyncRead + AsyncWrite + Unpin,
-    C: HandlerConstructor<StreamWrapper<RW>>
+    C: HandlerConstructor
 {
-    stream: Arc<StreamWrapper<RW>>,
-    rtmp_context: Arc<RtmpContext>,
-    handler_constructor: PhantomData<C>
+    handling: Pin<Box<dyn Future<Output = IOResult<()>> + Send>>,
+    handler_constructor: PhantomData<fn() -> (RW, C)>
 }
 
 impl<RW, C> Server<RW, C>
 where
-    RW: AsyncRead + AsyncWrite + Unpin,
-    C: HandlerConstructor<StreamWrapper<RW>>
+    RW: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    C: HandlerConstructor + Send + 'static
 {
     /// Constructs a Server instance.
-    pub fn new(stream: RW, rtmp_context: RtmpContext, handler_constructor: PhantomData<C>) -> Self {
+    pub fn new(stream: RW, rtmp_context: RtmpContext, _: PhantomData<C>) -> Self {
+        let (reader, writer) = split(stream);
+        let handling = async move {
+            let mut reader = StreamReader::new(reader);
+            let mut writer = StreamWriter::new(writer);
+            let mut rtmp_context = rtmp_context;
+            C::new().handle(Pin::new(&mut reader), Pin::new(&mut writer), &mut rtmp_context).await
+        };
+
         Self {
-            stream: Arc::new(StreamWrapper::new(stream)),
-            rtmp_context: Arc::new(rtmp_context),
-            handler_constructor
+            handling: Box::pin(handling),
+            handler_constructor: PhantomData
         }
     }
 }
 
+impl<RW, C> Debug for Server<RW, C>
+where
+    RW: AsyncRead + AsyncWrite + Unpin,
+    C: HandlerConstructor
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_struct("Server").finish_non_exhaustive()
+    }
+}
+
 impl<RW, C> Future for Server<RW, C>
 where
     RW: AsyncRead + AsyncWrite + Unpin,
-    C: HandlerConstructor<StreamWrapper<RW>>
+    C: HandlerConstructor
 {
     type Output = IOResult<()>;
 
-    fn poll(self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<Self::Output> {
-        pin!(C::new(Arc::clone(&self.stream))).poll_handle(cx, self.rtmp_context.make_weak_mut())
+    fn poll(mut self: Pin<&mut Self>, cx: &mut FutureContext<'_>) -> Poll<Self::Output> {
+        self.handling.as_mut().poll(cx)
     }
 }
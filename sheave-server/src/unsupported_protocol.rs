@@ -0,0 +1,32 @@
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult
+    },
+    io::{
+        Error as IOError,
+        ErrorKind
+    }
+};
+
+/// An error that an URI in the command line option is specified with a protocol scheme which isn't supported.
+#[derive(Debug)]
+pub struct UnsupportedProtocol(String);
+
+impl Display for UnsupportedProtocol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        writeln!(f, "Protocol: Currently rtmp, rtmps, rtmpe and rtmpt only. specified: {}", self.0)
+    }
+}
+
+impl Error for UnsupportedProtocol {}
+
+/// A utility function of constructing an `UnsupportedProtocol` error.
+pub fn unsupported_protocol(protocol: String) -> IOError {
+    IOError::new(
+        ErrorKind::InvalidInput,
+        UnsupportedProtocol(protocol)
+    )
+}